@@ -0,0 +1,130 @@
+use anyhow::{anyhow, Result};
+use copypasta_ext::prelude::ClipboardProvider;
+use copypasta_ext::{wayland_bin, x11_bin, x11_fork};
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Default duration (in seconds) for clearing the sensitive
+/// content from clipboard.
+pub const DEFAULT_CLEAR_TIMEOUT: u64 = 30;
+
+/// Returns the hash of the given clipboard content.
+///
+/// It is used for checking if the clipboard still contains the
+/// copied content without keeping the content itself in memory.
+pub fn get_content_hash(content: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	content.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// Backend for accessing the clipboard.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClipboardBackend {
+	/// Detect the backend from the current session.
+	Auto,
+	/// X11 clipboard (via a forked process).
+	X11,
+	/// X11 clipboard (via `xclip`/`xsel` binaries).
+	X11Bin,
+	/// Wayland clipboard (via `wl-clipboard` binaries).
+	Wayland,
+}
+
+impl Display for ClipboardBackend {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Auto => "auto",
+				Self::X11 => "x11",
+				Self::X11Bin => "x11-bin",
+				Self::Wayland => "wayland",
+			}
+		)
+	}
+}
+
+impl FromStr for ClipboardBackend {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"auto" => Ok(Self::Auto),
+			"x11" => Ok(Self::X11),
+			"x11-bin" => Ok(Self::X11Bin),
+			"wayland" => Ok(Self::Wayland),
+			_ => Err(String::from("could not parse the clipboard backend")),
+		}
+	}
+}
+
+impl ClipboardBackend {
+	/// Returns the backend that will be used.
+	///
+	/// Wayland is selected for the sessions that have
+	/// `WAYLAND_DISPLAY` set if the backend is [`Auto`].
+	///
+	/// [`Auto`]: ClipboardBackend::Auto
+	pub fn resolve(self) -> Self {
+		match self {
+			Self::Auto => {
+				if env::var_os("WAYLAND_DISPLAY").is_some() {
+					Self::Wayland
+				} else {
+					Self::X11
+				}
+			}
+			backend => backend,
+		}
+	}
+
+	/// Constructs a new clipboard context for the backend.
+	pub fn get_context(self) -> Result<Box<dyn ClipboardProvider>> {
+		Ok(match self.resolve() {
+			Self::Wayland => Box::new(
+				wayland_bin::ClipboardContext::new()
+					.map_err(|e| anyhow!("{}", e))?,
+			),
+			Self::X11Bin => Box::new(
+				x11_bin::ClipboardContext::new()
+					.map_err(|e| anyhow!("{}", e))?,
+			),
+			_ => Box::new(
+				x11_fork::ClipboardContext::new()
+					.map_err(|e| anyhow!("{}", e))?,
+			),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_clipboard_backend() {
+		for backend in &[
+			ClipboardBackend::Auto,
+			ClipboardBackend::X11,
+			ClipboardBackend::X11Bin,
+			ClipboardBackend::Wayland,
+		] {
+			assert_eq!(
+				Ok(*backend),
+				ClipboardBackend::from_str(&backend.to_string())
+			);
+		}
+		assert!(ClipboardBackend::from_str("test").is_err());
+		assert_eq!(get_content_hash("test"), get_content_hash("test"));
+		assert_ne!(get_content_hash("test"), get_content_hash("test2"));
+		assert_eq!(
+			ClipboardBackend::Wayland,
+			ClipboardBackend::Wayland.resolve()
+		);
+		assert_ne!(ClipboardBackend::Auto, ClipboardBackend::Auto.resolve());
+	}
+}
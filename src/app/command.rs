@@ -1,8 +1,17 @@
+use crate::app::export::ExportDestination;
+use crate::app::filter::Filter;
 use crate::app::mode::Mode;
 use crate::app::prompt::OutputType;
+use crate::app::rotate::RotationAction;
 use crate::app::selection::Selection;
+use crate::gpg::agent;
+use crate::gpg::card::{CardPin, CardSlot};
+use crate::gpg::edit::{self, OwnerTrust};
+use crate::gpg::handler;
 use crate::gpg::key::KeyType;
+use crate::gpg::prefs;
 use crate::widget::row::ScrollDirection;
+use gpgme::TofuPolicy;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
 
@@ -21,26 +30,142 @@ pub enum Command {
 	ShowOutput(OutputType, String),
 	/// Show popup for options menu.
 	ShowOptions,
+	/// Show popup for recent output messages.
+	ShowMessages,
+	/// Show the explanation of the key flags.
+	ShowLegend,
+	/// Show the details of the last error.
+	ShowError,
+	/// Show the captured output of the last external command.
+	ShowCommandOutput,
 	/// List the public/secret keys.
 	ListKeys(KeyType),
 	/// Import public/secret keys from files or a keyserver.
 	ImportKeys(Vec<String>, bool),
+	/// Show the changes of importing keys before importing them.
+	PreviewImport(Vec<String>),
+	/// Import keys from files with only their self-signatures.
+	ImportCleanKeys(Vec<String>),
+	/// Locate the key of an email address via the configured mechanisms.
+	LocateKey(String),
 	/// Import public/secret keys from clipboard.
 	ImportClipboard,
-	/// Export the public/secret keys.
-	ExportKeys(KeyType, Vec<String>, bool),
-	/// Delete the public/secret key.
-	DeleteKey(KeyType, String),
+	/// Browse the files to import/encrypt or to select the output directory.
+	BrowseFiles(String),
+	/// Encrypt a file for the given recipients.
+	Encrypt(String, Vec<String>),
+	/// Export the public/secret keys (or subkeys) to the destination.
+	ExportKeys(KeyType, Vec<String>, bool, ExportDestination),
+	/// Export the secret keys encrypted with a passphrase.
+	ExportSymmetric(Vec<String>),
+	/// Export the information about the keys in JSON format.
+	ExportJson(KeyType, Vec<String>),
+	/// Export the authentication subkey in OpenSSH format.
+	ExportSshKey(String),
+	/// Dump the keys table to a file or clipboard (with ANSI colors if set).
+	DumpKeys(Option<String>, bool),
+	/// Delete the public/secret keys.
+	DeleteKey(KeyType, Vec<String>),
+	/// Restore the most recently deleted keys.
+	Undo,
+	/// Show information about gpg-agent and the other components.
+	ShowAgent,
+	/// Run an action (e.g. reload) on a daemon via gpgconf.
+	ManageDaemon(String, String),
+	/// Edit a configuration file (e.g. gpg.conf).
+	EditConfig(String),
+	/// Show the keys that are exposed to the SSH agent.
+	ShowSshKeys,
+	/// Add the authentication subkeys to the SSH agent.
+	AddSshKey(String),
+	/// Show the signing key of git.
+	ShowGitKey,
+	/// Set the signing key of git (globally if set).
+	SetGitKey(String, bool),
+	/// Set the key as the default key (in `gpg.conf`).
+	SetDefaultKey(String),
+	/// Set the preferred keyserver of the key (or the selected key).
+	SetKeyserver(Option<String>, String),
+	/// Add a designated revoker to the key (or the selected key).
+	AddRevoker(Option<String>, String),
+	/// Rotate the key (or the selected key) step by step.
+	Rotate(RotationAction),
+	/// Show the algorithm preferences of the selected key.
+	ShowPrefs,
+	/// Set the algorithm preferences of the key (or the selected key).
+	SetPrefs(Option<String>, String),
+	/// Clean (or minimize if set) the keys (or all keys).
+	CleanKeys(Option<Vec<String>>, bool),
 	/// Send the key to the default keyserver.
 	SendKey(String),
-	/// Edit a key.
+	/// Show the editing options of a key.
 	EditKey(String),
-	/// Sign a key.
-	SignKey(String),
-	/// Generate a new key pair.
+	/// Add a user ID to the key.
+	AddUserId(String, String),
+	/// Add a subkey to the key with the given
+	/// algorithm, usage and expiration time.
+	AddSubkey(String, String, String, Option<String>),
+	/// Revoke the user ID of the key.
+	RevokeUserId(String, String),
+	/// Set the user ID as the primary user ID of the key.
+	SetPrimaryUserId(String, String),
+	/// Change the passphrase of the key.
+	ChangePassphrase(String),
+	/// Set the owner trust of the key (or show the trust levels).
+	SetOwnerTrust(String, Option<OwnerTrust>),
+	/// Set the expiration time of a key.
+	SetExpire(Option<String>, Option<String>),
+	/// Set the expiration time of all secret keys
+	/// (and their subkeys if set).
+	SetExpireAll(String, bool),
+	/// Show the subkeys of the selected key.
+	ShowSubkeys,
+	/// Export the given subkey (0 for the primary key) of a key.
+	ExportSubkey(String, usize),
+	/// Set the expiration time of the given subkey of a key.
+	SetSubkeyExpire(String, usize, Option<String>),
+	/// Revoke the given subkey of a key.
+	RevokeSubkey(String, usize),
+	/// Move a (sub)key to the given slot of the card.
+	KeyToCard(String, usize, CardSlot),
+	/// Change the PIN of the card.
+	ChangePin(CardPin),
+	/// Show the statistics about the keyring.
+	ShowStats,
+	/// Check the keyring for issues (e.g. duplicate user IDs).
+	CheckKeyring,
+	/// Select the key of the given issue (1-based) from the last check.
+	JumpToIssue(usize),
+	/// Show the certifications that are made by the default key.
+	ShowCertifications,
+	/// Select the key of the given certification (1-based).
+	JumpToCertification(usize),
+	/// Revoke the given certification (1-based).
+	RevokeCertification(usize),
+	/// Revoke the certifications made by the signing key on the key.
+	RevokeSig(String, String),
+	/// Show the recipient groups.
+	ShowGroups,
+	/// Set the members of a recipient group (removes it if empty).
+	SetGroup(String, Vec<String>),
+	/// Show the certification paths from the default key
+	/// to the key (or the selected key).
+	ShowTrustPaths(Option<String>),
+	/// Show the fingerprint of the selected key in verification formats.
+	ShowFingerprint,
+	/// Set the TOFU policy of the key (or the selected key).
+	SetTofuPolicy(Option<String>, TofuPolicy),
+	/// Sign the keys (with the given notations).
+	SignKey(Vec<String>, Vec<String>),
+	/// Generate a new key pair (by asking for the user ID).
 	GenerateKey,
+	/// Generate a new key pair with the given user ID,
+	/// algorithm and expiration time.
+	CreateKey(String, String, Option<String>),
 	/// Refresh the keyring.
 	RefreshKeys,
+	/// Cancel the running task.
+	Cancel,
 	/// Copy a property to clipboard.
 	Copy(Selection),
 	/// Toggle the detail level.
@@ -61,6 +186,24 @@ pub enum Command {
 	EnableInput,
 	/// Search for a value.
 	Search(Option<String>),
+	/// Filter the keys by their state or capability.
+	Filter(Option<Filter>),
+	/// Set a mark on the selected key.
+	SetMark(char),
+	/// Select the marked key.
+	JumpToMark(char),
+	/// Select the next secret key that is about to expire.
+	JumpToExpiring,
+	/// Select the first key that matches the search query.
+	Focus(String),
+	/// Start recording a macro into the given register (or stop recording).
+	RecordMacro(Option<char>),
+	/// Replay the macro of the given register (count times).
+	ReplayMacro(char, u16),
+	/// Run the script command with the given name and arguments.
+	RunScript(String, Vec<String>),
+	/// Repeat the last command.
+	Repeat,
 	/// Select the next tab.
 	NextTab,
 	/// Select the previous tab.
@@ -83,6 +226,12 @@ impl Display for Command {
 				Command::Refresh => String::from("refresh application"),
 				Command::RefreshKeys => String::from("refresh the keyring"),
 				Command::ShowHelp => String::from("show help"),
+				Command::ShowMessages => String::from("show recent messages"),
+				Command::ShowLegend => String::from("show flag legend"),
+				Command::ShowError => String::from("show error details"),
+				Command::ShowCommandOutput => {
+					String::from("show command output")
+				}
 				Command::ListKeys(key_type) => {
 					format!(
 						"list {} keys",
@@ -92,22 +241,261 @@ impl Display for Command {
 				Command::ImportClipboard => {
 					String::from("import key(s) from clipboard")
 				}
-				Command::ExportKeys(key_type, patterns, ref export_subkeys) => {
-					if patterns.is_empty() {
+				Command::PreviewImport(keys) => {
+					format!("preview the import of {} file(s)", keys.len())
+				}
+				Command::ImportCleanKeys(keys) => format!(
+					"import {} file(s) with only the self-signatures",
+					keys.len()
+				),
+				Command::LocateKey(email) => {
+					format!("locate the key of {}", email)
+				}
+				Command::BrowseFiles(target) => {
+					if target == "output" {
+						String::from("select the output directory")
+					} else {
+						format!("browse files to {}", target)
+					}
+				}
+				Command::Encrypt(file, recipients) => {
+					if recipients.is_empty() {
+						format!("encrypt {}", file)
+					} else {
+						format!(
+							"encrypt {} for {} recipient(s)",
+							file,
+							recipients.len()
+						)
+					}
+				}
+				Command::ExportKeys(
+					key_type,
+					patterns,
+					ref export_subkeys,
+					destination,
+				) => {
+					let action = if patterns.is_empty() {
 						format!("export all the keys ({})", key_type)
 					} else if *export_subkeys {
 						format!("export the selected subkeys ({})", key_type)
+					} else if patterns.len() > 1 {
+						format!(
+							"export {} selected keys ({})",
+							patterns.len(),
+							key_type
+						)
 					} else {
 						format!("export the selected key ({})", key_type)
+					};
+					if *destination == ExportDestination::File {
+						action
+					} else {
+						format!("{} to {}", action, destination)
+					}
+				}
+				Command::ExportSshKey(_) => {
+					String::from("export the selected key as SSH key")
+				}
+				Command::ExportSymmetric(patterns) => {
+					if patterns.is_empty() {
+						String::from(
+							"export all the secret keys with a passphrase",
+						)
+					} else {
+						String::from(
+							"export the selected secret key with a passphrase",
+						)
+					}
+				}
+				Command::ExportJson(key_type, patterns) => {
+					if patterns.is_empty() {
+						format!("export all the keys as JSON ({})", key_type)
+					} else {
+						format!(
+							"export the selected key as JSON ({})",
+							key_type
+						)
+					}
+				}
+				Command::DumpKeys(path, _) => match path {
+					Some(path) => format!("dump the keys table to {}", path),
+					None => String::from("copy the keys table to clipboard"),
+				},
+				Command::ShowAgent => String::from("show agent information"),
+				Command::ManageDaemon(action, daemon) => {
+					format!("{} {}", action, daemon)
+				}
+				Command::EditConfig(name) => {
+					format!("edit the {} configuration", name)
+				}
+				Command::ShowSshKeys => String::from("show the SSH agent keys"),
+				Command::AddSshKey(_) => String::from("add to the SSH agent"),
+				Command::ShowGitKey => String::from("show the git signing key"),
+				Command::SetGitKey(_, global) => format!(
+					"set as the {}git signing key",
+					if *global { "global " } else { "" }
+				),
+				Command::SetDefaultKey(_) => {
+					String::from("set as the default key")
+				}
+				Command::DeleteKey(key_type, key_ids) => {
+					if key_ids.len() > 1 {
+						format!(
+							"delete {} keys ({}): {}",
+							key_ids.len(),
+							key_type,
+							key_ids.join(", ")
+						)
+					} else {
+						format!("delete the selected key ({})", key_type)
+					}
+				}
+				Command::SetKeyserver(_, url) => {
+					format!("set the preferred keyserver ({})", url)
+				}
+				Command::AddRevoker(_, revoker) => {
+					format!("add {} as a designated revoker", revoker)
+				}
+				Command::SetExpireAll(expiry, subkeys) => format!(
+					"set the expiration time of all secret keys{} to {}",
+					if *subkeys { " and subkeys" } else { "" },
+					expiry
+				),
+				Command::CleanKeys(key_ids, minimize) => format!(
+					"{} {}",
+					if *minimize { "minimize" } else { "clean" },
+					match key_ids {
+						Some(key_ids) if key_ids.len() > 1 => {
+							format!("{} keys", key_ids.len())
+						}
+						Some(_) => String::from("the selected key"),
+						None => String::from("all keys"),
+					}
+				),
+				Command::Rotate(action) => action.to_string(),
+				Command::ShowPrefs => String::from("show the preferences"),
+				Command::SetPrefs(_, preferences) => {
+					match prefs::PREFERENCE_PRESETS
+						.iter()
+						.find(|(_, v)| v == preferences)
+					{
+						Some((name, _)) => {
+							format!("set the {} preferences", name)
+						}
+						None => String::from("set the preferences"),
 					}
 				}
-				Command::DeleteKey(key_type, _) =>
-					format!("delete the selected key ({})", key_type),
 				Command::SendKey(_) =>
 					String::from("send key to the keyserver"),
 				Command::EditKey(_) => String::from("edit the selected key"),
-				Command::SignKey(_) => String::from("sign the selected key"),
+				Command::AddUserId(_, user_id) => {
+					format!("add the user ID: {}", user_id)
+				}
+				Command::AddSubkey(_, algo, usage, _) => {
+					format!("add a subkey ({}, {})", algo, usage)
+				}
+				Command::RevokeUserId(_, user_id) => {
+					format!("revoke the user ID: {}", user_id)
+				}
+				Command::SetPrimaryUserId(_, user_id) => {
+					format!("set as the primary user ID: {}", user_id)
+				}
+				Command::ChangePassphrase(_) => {
+					String::from("change the passphrase")
+				}
+				Command::SetOwnerTrust(_, Some(trust)) => {
+					format!("set the owner trust to {}", trust)
+				}
+				Command::SetOwnerTrust(_, None) => {
+					String::from("set the owner trust")
+				}
+				Command::SetExpire(_, expiry) => match expiry {
+					Some(expiry) =>
+						format!("set the expiration time to {}", expiry),
+					None => String::from("change the expiration time"),
+				},
+				Command::ShowSubkeys => String::from("show the subkeys"),
+				Command::ExportSubkey(_, subkey) => {
+					format!("export subkey {}", subkey)
+				}
+				Command::SetSubkeyExpire(_, subkey, expiry) => match expiry {
+					Some(expiry) => format!(
+						"set the expiration time of subkey {} to {}",
+						subkey, expiry
+					),
+					None => {
+						format!(
+							"change the expiration time of subkey {}",
+							subkey
+						)
+					}
+				},
+				Command::RevokeSubkey(_, subkey) => {
+					format!("revoke subkey {}", subkey)
+				}
+				Command::KeyToCard(_, subkey, slot) => format!(
+					"move {} to the card ({}) and leave a stub",
+					if *subkey == 0 {
+						String::from("the primary key")
+					} else {
+						format!("subkey {}", subkey)
+					},
+					slot
+				),
+				Command::SignKey(keys, notations) => format!(
+					"{}{}",
+					if keys.len() > 1 {
+						format!("sign {} selected keys", keys.len())
+					} else {
+						String::from("sign the selected key")
+					},
+					if notations.is_empty() {
+						String::new()
+					} else {
+						format!(" ({})", notations.join(", "))
+					}
+				),
+				Command::ChangePin(pin) => format!("change the card {}", pin),
+				Command::ShowTrustPaths(_) => String::from("show trust paths"),
+				Command::ShowFingerprint => {
+					String::from("show the fingerprint for verification")
+				}
+				Command::ShowStats => String::from("show keyring statistics"),
+				Command::CheckKeyring => String::from("check the keyring"),
+				Command::JumpToIssue(index) => {
+					format!("jump to the keyring issue ({})", index)
+				}
+				Command::ShowCertifications => {
+					String::from("show the certifications made")
+				}
+				Command::JumpToCertification(index) => {
+					format!("jump to the certification ({})", index)
+				}
+				Command::RevokeCertification(index) => {
+					format!("revoke the certification ({})", index)
+				}
+				Command::RevokeSig(_, signing_key) => {
+					format!("revoke the certifications by {}", signing_key)
+				}
+				Command::ShowGroups =>
+					String::from("show the recipient groups"),
+				Command::SetGroup(name, members) => {
+					if members.is_empty() {
+						format!("remove the group ({})", name)
+					} else {
+						format!("set the group ({})", name)
+					}
+				}
+				Command::SetTofuPolicy(_, policy) => format!(
+					"set TOFU policy to {}",
+					handler::get_tofu_policy_name(*policy)
+				),
 				Command::GenerateKey => String::from("generate a new key pair"),
+				Command::CreateKey(user_id, _, _) =>
+					format!("generate a new key pair for {}", user_id),
+				Command::Undo => String::from("undo the last deletion"),
+				Command::Cancel => String::from("cancel the running task"),
 				Command::Copy(copy_type) =>
 					format!("copy {}", copy_type.to_string().to_lowercase()),
 				Command::Paste => String::from("paste from clipboard"),
@@ -123,12 +511,27 @@ impl Display for Command {
 						"armor" => format!("{} armored output", action),
 						"signer" => String::from("set as the signing key"),
 						"colored" => format!("{} colors", action),
+						"splitview" => format!("{} split view", action),
+						"log" => format!("{} log pane", action),
+						"notify" => format!("{} notifications", action),
+						"redact" => format!("{} redaction", action),
+						"hide-unusable" => {
+							if value == "true" {
+								String::from("hide unusable keys")
+							} else {
+								String::from("show unusable keys")
+							}
+						}
 						"margin" => String::from("toggle table margin"),
 						"prompt" => {
 							if value == ":import " {
 								String::from("import key(s) from a file")
 							} else if value == ":receive " {
 								String::from("receive key(s) from keyserver")
+							} else if value.starts_with(":adduid ") {
+								String::from("add a user ID")
+							} else if value.starts_with(":addkey ") {
+								String::from("add a subkey")
 							} else {
 								format!("set prompt text to {}", value)
 							}
@@ -136,6 +539,27 @@ impl Display for Command {
 						_ => format!("set {} to {}", option, value),
 					}
 				}
+				Command::Filter(Some(filter)) => {
+					format!("filter keys ({})", filter)
+				}
+				Command::Filter(None) => String::from("clear the filter"),
+				Command::SetMark(name) => format!("set mark ({})", name),
+				Command::JumpToMark(name) => format!("jump to mark ({})", name),
+				Command::JumpToExpiring => {
+					String::from("jump to the expiring key")
+				}
+				Command::Focus(query) => format!("select the key ({})", query),
+				Command::RecordMacro(Some(register)) => {
+					format!("record macro ({})", register)
+				}
+				Command::RecordMacro(None) => {
+					String::from("stop recording the macro")
+				}
+				Command::ReplayMacro(register, _) => {
+					format!("replay macro ({})", register)
+				}
+				Command::RunScript(name, _) => format!("run script ({})", name),
+				Command::Repeat => String::from("repeat the last command"),
 				Command::SwitchMode(mode) => format!(
 					"switch to {} mode",
 					format!("{:?}", mode).to_lowercase()
@@ -148,9 +572,165 @@ impl Display for Command {
 	}
 }
 
+impl Command {
+	/// Returns the command for a count prefix that is not followed
+	/// by a motion (`1`-`3` sets the detail level of the selected key).
+	pub fn from_count(count: u16) -> Option<Self> {
+		match count {
+			1..=3 => {
+				Some(Command::Set(String::from("detail"), count.to_string()))
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns `true` if the command can be repeated.
+	///
+	/// Navigation and interface commands (e.g. scrolling,
+	/// showing popups) are not repeatable.
+	pub fn is_repeatable(&self) -> bool {
+		!matches!(
+			self,
+			Command::ShowHelp
+				| Command::ShowOutput(_, _)
+				| Command::ShowOptions
+				| Command::ShowMessages
+				| Command::ShowLegend
+				| Command::ShowError
+				| Command::ShowCommandOutput
+				| Command::ListKeys(_)
+				| Command::ShowAgent
+				| Command::ShowSshKeys
+				| Command::ShowGitKey
+				| Command::ShowStats
+				| Command::ShowCertifications
+				| Command::ShowGroups
+				| Command::JumpToIssue(_)
+				| Command::JumpToCertification(_)
+				| Command::ShowTrustPaths(_)
+				| Command::ShowSubkeys
+				| Command::ShowPrefs
+				| Command::EditKey(_)
+				| Command::SetOwnerTrust(_, None)
+				| Command::Rotate(_)
+				| Command::ShowFingerprint
+				| Command::Cancel
+				| Command::Scroll(_, _)
+				| Command::Get(_)
+				| Command::SwitchMode(_)
+				| Command::EnableInput
+				| Command::Search(_)
+				| Command::SetMark(_)
+				| Command::JumpToMark(_)
+				| Command::JumpToExpiring
+				| Command::Focus(_)
+				| Command::RecordMacro(_)
+				| Command::ReplayMacro(_, _)
+				| Command::RunScript(_, _)
+				| Command::Repeat
+				| Command::NextTab
+				| Command::PreviousTab
+				| Command::Refresh
+				| Command::Quit
+				| Command::None
+		) && !matches!(self, Command::Set(option, _) if option == "prompt")
+	}
+
+	/// Returns the command with the given key IDs replaced by the new ones.
+	///
+	/// It is used for running the same command on a different selection.
+	pub fn replace_keys(&self, keys: &[String], new_keys: &[String]) -> Self {
+		let replace = |ids: &[String]| {
+			if ids == keys {
+				new_keys.to_vec()
+			} else {
+				ids.to_vec()
+			}
+		};
+		let replace_one = |id: &String| match (keys, new_keys) {
+			([key], [new_key]) if key == id => new_key.to_string(),
+			_ => id.to_string(),
+		};
+		match self {
+			Command::Confirm(command) => {
+				Command::Confirm(Box::new(command.replace_keys(keys, new_keys)))
+			}
+			Command::ExportKeys(key_type, ids, export_subkeys, destination) => {
+				Command::ExportKeys(
+					*key_type,
+					replace(ids),
+					*export_subkeys,
+					*destination,
+				)
+			}
+			Command::ExportSymmetric(ids) => {
+				Command::ExportSymmetric(replace(ids))
+			}
+			Command::ExportJson(key_type, ids) => {
+				Command::ExportJson(*key_type, replace(ids))
+			}
+			Command::DeleteKey(key_type, ids) => {
+				Command::DeleteKey(*key_type, replace(ids))
+			}
+			Command::SignKey(ids, notations) => {
+				Command::SignKey(replace(ids), notations.to_vec())
+			}
+			Command::CleanKeys(Some(ids), minimize) => {
+				Command::CleanKeys(Some(replace(ids)), *minimize)
+			}
+			Command::ExportSshKey(id) => Command::ExportSshKey(replace_one(id)),
+			Command::AddSshKey(id) => Command::AddSshKey(replace_one(id)),
+			Command::SendKey(id) => Command::SendKey(replace_one(id)),
+			Command::EditKey(id) => Command::EditKey(replace_one(id)),
+			Command::ChangePassphrase(id) => {
+				Command::ChangePassphrase(replace_one(id))
+			}
+			Command::SetOwnerTrust(id, trust) => {
+				Command::SetOwnerTrust(replace_one(id), *trust)
+			}
+			Command::SetDefaultKey(id) => {
+				Command::SetDefaultKey(replace_one(id))
+			}
+			Command::ExportSubkey(id, subkey) => {
+				Command::ExportSubkey(replace_one(id), *subkey)
+			}
+			Command::SetSubkeyExpire(id, subkey, time) => {
+				Command::SetSubkeyExpire(replace_one(id), *subkey, time.clone())
+			}
+			Command::RevokeSubkey(id, subkey) => {
+				Command::RevokeSubkey(replace_one(id), *subkey)
+			}
+			Command::SetExpire(Some(id), time) => {
+				Command::SetExpire(Some(replace_one(id)), time.clone())
+			}
+			Command::SetKeyserver(Some(id), url) => {
+				Command::SetKeyserver(Some(replace_one(id)), url.to_string())
+			}
+			Command::AddRevoker(Some(id), revoker) => {
+				Command::AddRevoker(Some(replace_one(id)), revoker.to_string())
+			}
+			Command::SetPrefs(Some(id), preferences) => Command::SetPrefs(
+				Some(replace_one(id)),
+				preferences.to_string(),
+			),
+			Command::SetTofuPolicy(Some(id), policy) => {
+				Command::SetTofuPolicy(Some(replace_one(id)), *policy)
+			}
+			_ => self.clone(),
+		}
+	}
+}
+
 impl FromStr for Command {
 	type Err = ();
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let input = s.strip_prefix(':').unwrap_or(s);
+		if handler::is_armored_block(input) {
+			return Ok(Command::ImportKeys(
+				vec![input.trim().to_string()],
+				false,
+			));
+		}
 		let mut values = s
 			.replacen(':', "", 1)
 			.to_lowercase()
@@ -175,13 +755,34 @@ impl FromStr for Command {
 						args[1..].join(" "),
 					))
 				} else {
-					Err(())
+					Ok(Command::ShowCommandOutput)
 				}
 			}
 			"options" | "opt" => Ok(Command::ShowOptions),
+			"messages" | "msg" => Ok(Command::ShowMessages),
+			"legend" => Ok(Command::ShowLegend),
+			"error" | "err" => Ok(Command::ShowError),
 			"list" | "ls" => Ok(Command::ListKeys(KeyType::from_str(
 				&args.first().cloned().unwrap_or_else(|| String::from("pub")),
 			)?)),
+			"import" if args.first() == Some(&String::from("--preview")) => {
+				Ok(Command::PreviewImport(
+					s.replacen(':', "", 1)
+						.split_whitespace()
+						.map(String::from)
+						.skip(2)
+						.collect(),
+				))
+			}
+			"import" if args.first() == Some(&String::from("--clean")) => {
+				Ok(Command::ImportCleanKeys(
+					s.replacen(':', "", 1)
+						.split_whitespace()
+						.map(String::from)
+						.skip(2)
+						.collect(),
+				))
+			}
 			"import" | "receive" => Ok(Command::ImportKeys(
 				s.replacen(':', "", 1)
 					.split_whitespace()
@@ -191,7 +792,134 @@ impl FromStr for Command {
 				command.as_str() == "receive",
 			)),
 			"import-clipboard" => Ok(Command::ImportClipboard),
+			"locate-auto" => {
+				Ok(Command::LocateKey(args.first().cloned().ok_or(())?))
+			}
+			"browse" => match args.as_slice() {
+				[] => Ok(Command::BrowseFiles(String::from("import"))),
+				[target]
+					if ["import", "encrypt", "output"]
+						.contains(&target.as_str()) =>
+				{
+					Ok(Command::BrowseFiles(target.to_string()))
+				}
+				_ => Err(()),
+			},
+			"encrypt" | "enc" => {
+				let mut args = s
+					.replacen(':', "", 1)
+					.split_whitespace()
+					.map(String::from)
+					.skip(1)
+					.collect::<Vec<String>>();
+				if args.is_empty() {
+					Ok(Command::BrowseFiles(String::from("encrypt")))
+				} else {
+					let file = args.remove(0);
+					Ok(Command::Encrypt(file, args))
+				}
+			}
+			"agent" => match args.as_slice() {
+				[] => Ok(Command::ShowAgent),
+				[action, daemon]
+					if agent::DAEMON_ACTIONS.contains(&action.as_str())
+						&& agent::DAEMONS.contains(&daemon.as_str()) =>
+				{
+					Ok(Command::ManageDaemon(
+						action.to_string(),
+						daemon.to_string(),
+					))
+				}
+				_ => Err(()),
+			},
+			"config" => match args.as_slice() {
+				[edit] if edit == "edit" => {
+					Ok(Command::EditConfig(String::from("gpg")))
+				}
+				[edit, name]
+					if edit == "edit"
+						&& agent::CONFIG_FILES
+							.iter()
+							.any(|(config, _, _)| config == name) =>
+				{
+					Ok(Command::EditConfig(name.to_string()))
+				}
+				_ => Err(()),
+			},
+			"ssh" => match args.first().map(String::as_str) {
+				None => Ok(Command::ShowSshKeys),
+				Some("add") => match args.get(1) {
+					Some(key_id) => Ok(Command::AddSshKey(
+						key_id.to_uppercase().replace("0X", "0x"),
+					)),
+					None => Err(()),
+				},
+				_ => Err(()),
+			},
+			"git" => match args.first().map(String::as_str) {
+				None => Ok(Command::ShowGitKey),
+				Some("set") => {
+					let global = args.contains(&String::from("--global"));
+					match args.iter().skip(1).find(|arg| *arg != "--global") {
+						Some(key_id) => Ok(Command::SetGitKey(
+							key_id.to_uppercase().replace("0X", "0x"),
+							global,
+						)),
+						None => Err(()),
+					}
+				}
+				_ => Err(()),
+			},
+			"default-key" | "defaultkey" => Ok(Command::SetDefaultKey(
+				args.first()
+					.map(|key_id| key_id.to_uppercase().replace("0X", "0x"))
+					.ok_or(())?,
+			)),
 			"export" | "exp" => {
+				let mut args = args;
+				if let Some(i) = args.iter().position(|arg| arg == "--ssh") {
+					args.remove(i);
+					let key_ids = args
+						.iter()
+						.filter(|arg| KeyType::from_str(arg).is_err())
+						.collect::<Vec<&String>>();
+					return match key_ids.as_slice() {
+						[key_id] => Ok(Command::ExportSshKey(
+							key_id.to_uppercase().replace("0X", "0x"),
+						)),
+						_ => Err(()),
+					};
+				}
+				let symmetric =
+					match args.iter().position(|arg| arg == "--symmetric") {
+						Some(i) => {
+							args.remove(i);
+							true
+						}
+						None => false,
+					};
+				let format = args.iter().position(|arg| arg == "--format");
+				let json = match format {
+					Some(i)
+						if args.get(i + 1) == Some(&String::from("json")) =>
+					{
+						args.drain(i..i + 2);
+						true
+					}
+					Some(_) => return Err(()),
+					None => false,
+				};
+				let destination =
+					match args.iter().position(|arg| arg == "--to") {
+						Some(i) => {
+							let destination = ExportDestination::from_str(
+								args.get(i + 1).ok_or(())?,
+							)?;
+							args.drain(i..i + 2);
+							destination
+						}
+						None => ExportDestination::File,
+					};
 				let mut patterns = if !args.is_empty() {
 					args[1..].to_vec()
 				} else {
@@ -202,19 +930,56 @@ impl FromStr for Command {
 				if export_subkeys {
 					patterns.truncate(patterns.len() - 1)
 				}
-				Ok(Command::ExportKeys(
-					KeyType::from_str(
-						&args
-							.first()
-							.cloned()
-							.unwrap_or_else(|| String::from("pub")),
-					)?,
-					patterns,
-					export_subkeys,
+				let key_type = KeyType::from_str(
+					&args
+						.first()
+						.cloned()
+						.unwrap_or_else(|| String::from("pub")),
+				)?;
+				if symmetric {
+					if key_type == KeyType::Secret
+						&& !json && !export_subkeys
+						&& destination == ExportDestination::File
+					{
+						Ok(Command::ExportSymmetric(patterns))
+					} else {
+						Err(())
+					}
+				} else if json && destination == ExportDestination::File {
+					Ok(Command::ExportJson(key_type, patterns))
+				} else if json {
+					Err(())
+				} else {
+					Ok(Command::ExportKeys(
+						key_type,
+						patterns,
+						export_subkeys,
+						destination,
+					))
+				}
+			}
+			"dump" => {
+				let ansi = args.iter().any(|arg| arg == "--ansi");
+				Ok(Command::DumpKeys(
+					args.into_iter().find(|arg| arg != "--ansi"),
+					ansi,
 				))
 			}
 			"delete" | "del" => {
-				let key_id = args.get(1).cloned().unwrap_or_default();
+				let key_ids = args
+					.iter()
+					.skip(1)
+					.map(|key_id| {
+						if let Some(key) = key_id.strip_prefix("0x") {
+							format!("0x{}", key.to_string().to_uppercase())
+						} else {
+							key_id.to_string()
+						}
+					})
+					.collect::<Vec<String>>();
+				if key_ids.is_empty() {
+					return Err(());
+				}
 				Ok(Command::DeleteKey(
 					KeyType::from_str(
 						&args
@@ -222,17 +987,249 @@ impl FromStr for Command {
 							.cloned()
 							.unwrap_or_else(|| String::from("pub")),
 					)?,
+					key_ids,
+				))
+			}
+			"send" => Ok(Command::SendKey(args.first().cloned().ok_or(())?)),
+			"edit" => Ok(Command::EditKey(args.first().cloned().ok_or(())?)),
+			"adduid" | "revuid" | "primary" if args.len() > 1 => {
+				let key_id = args[0].to_uppercase().replace("0X", "0x");
+				let user_id = s
+					.split_whitespace()
+					.skip(2)
+					.collect::<Vec<&str>>()
+					.join(" ");
+				Ok(match command.as_str() {
+					"adduid" => Command::AddUserId(key_id, user_id),
+					"revuid" => Command::Confirm(Box::new(
+						Command::RevokeUserId(key_id, user_id),
+					)),
+					_ => Command::SetPrimaryUserId(key_id, user_id),
+				})
+			}
+			"addkey" => {
+				let usage = args
+					.get(2)
+					.cloned()
+					.unwrap_or_else(|| String::from("default"));
+				edit::get_subkey_flags(&usage).ok_or(())?;
+				Ok(Command::AddSubkey(
+					args.first()
+						.map(|key_id| key_id.to_uppercase().replace("0X", "0x"))
+						.ok_or(())?,
+					args.get(1)
+						.cloned()
+						.unwrap_or_else(|| String::from("default")),
+					usage,
+					args.get(3).cloned(),
+				))
+			}
+			"passwd" => Ok(Command::ChangePassphrase(
+				args.first()
+					.map(|key_id| key_id.to_uppercase().replace("0X", "0x"))
+					.ok_or(())?,
+			)),
+			"trust" => Ok(Command::SetOwnerTrust(
+				args.first()
+					.map(|key_id| key_id.to_uppercase().replace("0X", "0x"))
+					.ok_or(())?,
+				match args.get(1) {
+					Some(trust) => Some(OwnerTrust::from_str(trust)?),
+					None => None,
+				},
+			)),
+			"expire" => Ok(Command::SetExpire(
+				args.get(1)
+					.map(|key_id| key_id.to_uppercase().replace("0X", "0x")),
+				args.first().cloned(),
+			)),
+			"expire-all" => {
+				Ok(Command::Confirm(Box::new(Command::SetExpireAll(
+					args.iter()
+						.find(|arg| !arg.starts_with("--"))
+						.cloned()
+						.ok_or(())?,
+					args.iter().any(|arg| arg == "--subkeys"),
+				))))
+			}
+			"clean" | "minimize" => {
+				let minimize = command == "minimize";
+				if args.iter().any(|arg| arg == "--all") {
+					Ok(Command::Confirm(Box::new(Command::CleanKeys(
+						None, minimize,
+					))))
+				} else {
+					Ok(Command::CleanKeys(Some(args), minimize))
+				}
+			}
+			"subkeys" => Ok(Command::ShowSubkeys),
+			"addrevoker" | "revoker" => {
+				Ok(Command::Confirm(Box::new(Command::AddRevoker(
+					args.get(1).map(|key_id| {
+						key_id.to_uppercase().replace("0X", "0x")
+					}),
+					args.first()
+						.map(|key_id| key_id.to_uppercase().replace("0X", "0x"))
+						.ok_or(())?,
+				))))
+			}
+			"rotate" => Ok(Command::Rotate(match args.first() {
+				Some(value) => {
+					RotationAction::from_str(value).unwrap_or_else(|_| {
+						RotationAction::Start(Some(
+							value.to_uppercase().replace("0X", "0x"),
+						))
+					})
+				}
+				None => RotationAction::Start(None),
+			})),
+			"prefs" | "setpref" => match args.first().map(String::as_str) {
+				None => Ok(Command::ShowPrefs),
+				Some("set") if args.len() > 1 => Ok(Command::SetPrefs(
+					None,
+					s.split_whitespace()
+						.skip(2)
+						.collect::<Vec<&str>>()
+						.join(" "),
+				)),
+				Some(name) => Ok(Command::SetPrefs(
+					args.get(1).map(|key_id| {
+						key_id.to_uppercase().replace("0X", "0x")
+					}),
+					prefs::PREFERENCE_PRESETS
+						.iter()
+						.find(|(preset, _)| *preset == name)
+						.map(|(_, preferences)| preferences.to_string())
+						.ok_or(())?,
+				)),
+			},
+			"subkey" => {
+				let key_id = args
+					.get(1)
+					.map(|key_id| key_id.to_uppercase().replace("0X", "0x"))
+					.ok_or(())?;
+				let subkey = args.get(2).ok_or(())?.parse().map_err(|_| ())?;
+				match args.first().map(String::as_str) {
+					Some("export") => Ok(Command::ExportSubkey(key_id, subkey)),
+					Some("expire") => Ok(Command::SetSubkeyExpire(
+						key_id,
+						subkey,
+						args.get(3).cloned(),
+					)),
+					Some("revoke") => Ok(Command::RevokeSubkey(key_id, subkey)),
+					_ => Err(()),
+				}
+			}
+			"keytocard" => {
+				let key_id = args.first().ok_or(())?;
+				Ok(Command::KeyToCard(
 					if let Some(key) = key_id.strip_prefix("0x") {
-						format!("0x{}", key.to_string().to_uppercase())
+						format!("0x{}", key.to_uppercase())
 					} else {
-						key_id
+						key_id.to_string()
 					},
+					args.get(1).ok_or(())?.parse().map_err(|_| ())?,
+					CardSlot::from_str(args.get(2).ok_or(())?)?,
 				))
 			}
-			"send" => Ok(Command::SendKey(args.first().cloned().ok_or(())?)),
-			"edit" => Ok(Command::EditKey(args.first().cloned().ok_or(())?)),
-			"sign" => Ok(Command::SignKey(args.first().cloned().ok_or(())?)),
-			"generate" | "gen" => Ok(Command::GenerateKey),
+			"sign" => {
+				let mut keys = Vec::new();
+				let mut notations = Vec::new();
+				let mut values = args.iter().zip(s.split_whitespace().skip(1));
+				while let Some((arg, _)) = values.next() {
+					if arg == "--notation" {
+						let notation = values
+							.next()
+							.map(|(_, value)| value.trim_matches('"'))
+							.filter(|v| handler::is_valid_notation(v))
+							.ok_or(())?;
+						notations.push(notation.to_string());
+					} else {
+						keys.push(arg.to_string());
+					}
+				}
+				if keys.is_empty() && notations.is_empty() {
+					Err(())
+				} else {
+					Ok(Command::SignKey(keys, notations))
+				}
+			}
+			"card" => match args.first().map(String::as_str) {
+				Some("pin") | None => Ok(Command::ChangePin(CardPin::Pin)),
+				Some(arg) => Ok(Command::ChangePin(CardPin::from_str(arg)?)),
+			},
+			"stats" => Ok(Command::ShowStats),
+			"doctor" => match args.first() {
+				Some(index) => {
+					Ok(Command::JumpToIssue(index.parse().map_err(|_| ())?))
+				}
+				None => Ok(Command::CheckKeyring),
+			},
+			"certs" => match args.first().map(String::as_str) {
+				Some("revoke") => Ok(Command::RevokeCertification(
+					args.get(1).ok_or(())?.parse().map_err(|_| ())?,
+				)),
+				Some(index) => Ok(Command::JumpToCertification(
+					index.parse().map_err(|_| ())?,
+				)),
+				None => Ok(Command::ShowCertifications),
+			},
+			"revsig" => match args.as_slice() {
+				[key_id, signing_key] => Ok(Command::RevokeSig(
+					key_id.to_uppercase().replace("0X", "0x"),
+					signing_key.to_uppercase().replace("0X", "0x"),
+				)),
+				_ => Err(()),
+			},
+			"groups" => Ok(Command::ShowGroups),
+			"group" => {
+				let mut values = s.split_whitespace().skip(1).map(String::from);
+				Ok(Command::SetGroup(
+					values.next().ok_or(())?,
+					values.collect(),
+				))
+			}
+			"wot" => Ok(Command::ShowTrustPaths(
+				args.first()
+					.map(|key_id| key_id.to_uppercase().replace("0X", "0x")),
+			)),
+			"fingerprint" | "fpr" => Ok(Command::ShowFingerprint),
+			"tofu" => match args.first().map(String::as_str) {
+				Some("policy") => Ok(Command::SetTofuPolicy(
+					args.get(2).map(|key_id| {
+						key_id.to_uppercase().replace("0X", "0x")
+					}),
+					handler::parse_tofu_policy(args.get(1).ok_or(())?)
+						.ok_or(())?,
+				)),
+				_ => Err(()),
+			},
+			"generate" | "gen" if args.is_empty() => Ok(Command::GenerateKey),
+			"generate" | "gen" => {
+				let mut algo = String::from("default");
+				let mut expiry = None;
+				let mut user_id = Vec::new();
+				let mut words = s.split_whitespace().skip(1);
+				while let Some(word) = words.next() {
+					match word {
+						"--algo" if user_id.is_empty() => {
+							algo = words.next().ok_or(())?.to_lowercase()
+						}
+						"--expire" if user_id.is_empty() => {
+							expiry =
+								Some(words.next().ok_or(())?.to_lowercase())
+						}
+						_ => user_id.push(word),
+					}
+				}
+				if user_id.is_empty() {
+					Err(())
+				} else {
+					Ok(Command::CreateKey(user_id.join(" "), algo, expiry))
+				}
+			}
+			"undo" => Ok(Command::Undo),
+			"cancel" => Ok(Command::Cancel),
 			"copy" | "c" => {
 				if let Some(arg) = args.first().cloned() {
 					Ok(Command::Copy(
@@ -263,9 +1260,25 @@ impl FromStr for Command {
 					scroll_row,
 				))
 			}
+			"set" | "s"
+				if args.first().map(String::as_str)
+					== Some("pref-keyserver") =>
+			{
+				Ok(Command::SetKeyserver(
+					args.get(2).map(|key_id| {
+						key_id.to_uppercase().replace("0X", "0x")
+					}),
+					s.split_whitespace().nth(2).ok_or(())?.to_string(),
+				))
+			}
 			"set" | "s" => Ok(Command::Set(
 				args.get(0).cloned().unwrap_or_default(),
-				args.get(1).cloned().unwrap_or_default(),
+				s.split_whitespace()
+					.skip(2)
+					.collect::<Vec<&str>>()
+					.join(" ")
+					.trim_matches('"')
+					.to_string(),
 			)),
 			"get" | "g" => {
 				Ok(Command::Get(args.get(0).cloned().unwrap_or_default()))
@@ -278,6 +1291,47 @@ impl FromStr for Command {
 			"paste" | "p" => Ok(Command::Paste),
 			"input" => Ok(Command::EnableInput),
 			"search" => Ok(Command::Search(args.first().cloned())),
+			"filter" => match args.first().map(|v| v.as_str()) {
+				Some("none") => Ok(Command::Filter(None)),
+				Some(filter) => Ok(Command::Filter(Some(
+					Filter::from_str(filter).map_err(|_| ())?,
+				))),
+				None => Err(()),
+			},
+			"mark" | "jump" => {
+				let name = s
+					.split_whitespace()
+					.nth(1)
+					.and_then(|v| v.chars().next())
+					.ok_or(())?;
+				if command.as_str() == "mark" {
+					Ok(Command::SetMark(name))
+				} else {
+					Ok(Command::JumpToMark(name))
+				}
+			}
+			"expiring" => Ok(Command::JumpToExpiring),
+			"select" | "focus" if !args.is_empty() => Ok(Command::Focus(
+				s.split_whitespace()
+					.skip(1)
+					.collect::<Vec<&str>>()
+					.join(" "),
+			)),
+			"record" => Ok(Command::RecordMacro(
+				args.first().and_then(|v| v.chars().next()),
+			)),
+			"replay" => Ok(Command::ReplayMacro(
+				args.first().and_then(|v| v.chars().next()).ok_or(())?,
+				match args.get(1) {
+					Some(count) => count.parse().map_err(|_| ())?,
+					None => 1,
+				},
+			)),
+			"run" => Ok(Command::RunScript(
+				args.first().cloned().ok_or(())?,
+				s.split_whitespace().skip(2).map(String::from).collect(),
+			)),
+			"repeat" => Ok(Command::Repeat),
 			"next" => Ok(Command::NextTab),
 			"previous" | "prev" => Ok(Command::PreviousTab),
 			"refresh" | "r" => {
@@ -316,6 +1370,18 @@ mod tests {
 			Command::ShowOptions,
 			Command::from_str(":options").unwrap()
 		);
+		for cmd in &[":messages", ":msg"] {
+			assert_eq!(Command::ShowMessages, Command::from_str(cmd).unwrap());
+		}
+		for cmd in &[":error", ":err"] {
+			assert_eq!(Command::ShowError, Command::from_str(cmd).unwrap());
+		}
+		for cmd in &[":output", ":out"] {
+			assert_eq!(
+				Command::ShowCommandOutput,
+				Command::from_str(cmd).unwrap()
+			);
+		}
 		for cmd in &[":list", ":list pub", ":ls", ":ls pub"] {
 			let command = Command::from_str(cmd).unwrap();
 			assert_eq!(Command::ListKeys(KeyType::Public), command);
@@ -343,10 +1409,64 @@ mod tests {
 			Command::ImportClipboard,
 			Command::from_str(":import-clipboard").unwrap()
 		);
+		assert_eq!(
+			Command::PreviewImport(vec![String::from("Keys.asc")]),
+			Command::from_str(":import --preview Keys.asc").unwrap()
+		);
+		assert_eq!(
+			Command::ImportCleanKeys(vec![String::from("Keys.asc")]),
+			Command::from_str(":import --clean Keys.asc").unwrap()
+		);
+		assert_eq!(
+			Command::LocateKey(String::from("alice@example.org")),
+			Command::from_str(":locate-auto Alice@example.org").unwrap()
+		);
+		assert!(Command::from_str(":locate-auto").is_err());
+		assert_eq!(
+			Command::ImportKeys(vec![String::from("-")], false),
+			Command::from_str(":import -").unwrap()
+		);
+		for cmd in &[":browse", ":browse import"] {
+			assert_eq!(
+				Command::BrowseFiles(String::from("import")),
+				Command::from_str(cmd).unwrap()
+			);
+		}
+		assert_eq!(
+			Command::BrowseFiles(String::from("output")),
+			Command::from_str(":browse output").unwrap()
+		);
+		assert!(Command::from_str(":browse xyz").is_err());
+		assert_eq!(
+			Command::BrowseFiles(String::from("encrypt")),
+			Command::from_str(":encrypt").unwrap()
+		);
+		assert_eq!(
+			Command::Encrypt(String::from("Notes.txt"), Vec::new()),
+			Command::from_str(":enc Notes.txt").unwrap()
+		);
+		assert_eq!(
+			Command::Encrypt(
+				String::from("notes.txt"),
+				vec![String::from("0xABC"), String::from("0xDEF")]
+			),
+			Command::from_str(":encrypt notes.txt 0xABC 0xDEF").unwrap()
+		);
+		let block = "-----BEGIN PGP PUBLIC KEY BLOCK-----\n\nXyZ\n\
+			-----END PGP PUBLIC KEY BLOCK-----";
+		assert_eq!(
+			Command::ImportKeys(vec![String::from(block)], false),
+			Command::from_str(&format!(":{}\n", block)).unwrap()
+		);
 		for cmd in &[":export", ":export pub", ":exp", ":exp pub"] {
 			let command = Command::from_str(cmd).unwrap();
 			assert_eq!(
-				Command::ExportKeys(KeyType::Public, Vec::new(), false),
+				Command::ExportKeys(
+					KeyType::Public,
+					Vec::new(),
+					false,
+					ExportDestination::File
+				),
 				command
 			);
 		}
@@ -354,7 +1474,8 @@ mod tests {
 			Command::ExportKeys(
 				KeyType::Public,
 				vec![String::from("test1"), String::from("test2")],
-				false
+				false,
+				ExportDestination::File
 			),
 			Command::from_str(":export pub test1 test2").unwrap()
 		);
@@ -362,52 +1483,460 @@ mod tests {
 			Command::ExportKeys(
 				KeyType::Secret,
 				vec![String::from("test3"), String::from("test4")],
-				true
+				true,
+				ExportDestination::File
 			),
 			Command::from_str(":export sec test3 test4 subkey").unwrap()
 		);
-		for cmd in &[":export sec", ":exp sec"] {
-			let command = Command::from_str(cmd).unwrap();
-			assert_eq!(
-				Command::ExportKeys(KeyType::Secret, Vec::new(), false),
-				command
-			);
-		}
+		assert_eq!(
+			Command::ExportKeys(
+				KeyType::Public,
+				vec![String::from("test1")],
+				false,
+				ExportDestination::Clipboard
+			),
+			Command::from_str(":export pub test1 --to clipboard").unwrap()
+		);
 		assert_eq!(
 			Command::ExportKeys(
 				KeyType::Secret,
-				vec![
-					String::from("test1"),
-					String::from("test2"),
-					String::from("test3")
-				],
-				false
+				Vec::new(),
+				false,
+				ExportDestination::Stdout
 			),
-			Command::from_str(":export sec test1 test2 test3").unwrap()
+			Command::from_str(":export sec --to stdout").unwrap()
 		);
-		for cmd in &[":delete pub xyz", ":del pub xyz"] {
-			let command = Command::from_str(cmd).unwrap();
-			assert_eq!(
-				Command::DeleteKey(KeyType::Public, String::from("xyz")),
-				command
-			);
-		}
 		assert_eq!(
-			Command::SendKey(String::from("test")),
-			Command::from_str(":send test").unwrap()
+			Command::ExportSymmetric(vec![String::from("test1")]),
+			Command::from_str(":export sec test1 --symmetric").unwrap()
+		);
+		assert!(Command::from_str(":export pub test1 --symmetric").is_err());
+		assert!(Command::from_str(":export pub --to").is_err());
+		assert!(Command::from_str(":export pub --to ftp").is_err());
+		assert!(
+			Command::from_str(":export pub --format json --to clipboard")
+				.is_err()
+		);
+		assert_eq!(Command::ShowAgent, Command::from_str(":agent").unwrap());
+		assert_eq!(
+			Command::ManageDaemon(
+				String::from("reload"),
+				String::from("gpg-agent")
+			),
+			Command::from_str(":agent reload gpg-agent").unwrap()
+		);
+		assert!(Command::from_str(":agent restart gpg-agent").is_err());
+		assert!(Command::from_str(":agent kill gpg").is_err());
+		assert_eq!(
+			Command::EditConfig(String::from("gpg")),
+			Command::from_str(":config edit").unwrap()
+		);
+		assert_eq!(
+			Command::EditConfig(String::from("dirmngr")),
+			Command::from_str(":config edit dirmngr").unwrap()
+		);
+		assert!(Command::from_str(":config edit xyz").is_err());
+		assert_eq!(Command::ShowSshKeys, Command::from_str(":ssh").unwrap());
+		assert_eq!(
+			Command::AddSshKey(String::from("0xABCD")),
+			Command::from_str(":ssh add 0xabcd").unwrap()
+		);
+		assert!(Command::from_str(":ssh add").is_err());
+		assert_eq!(Command::ShowGitKey, Command::from_str(":git").unwrap());
+		assert_eq!(
+			Command::SetGitKey(String::from("0xABCD1234"), true),
+			Command::from_str(":git set 0xabcd1234 --global").unwrap()
+		);
+		assert_eq!(
+			Command::SetDefaultKey(String::from("0xABCD1234")),
+			Command::from_str(":default-key 0xabcd1234").unwrap()
+		);
+		assert!(Command::from_str(":default-key").is_err());
+		assert!(Command::from_str(":git set").is_err());
+		assert_eq!(
+			Command::ExportJson(KeyType::Public, Vec::new()),
+			Command::from_str(":export --format json").unwrap()
+		);
+		assert_eq!(
+			Command::ExportJson(KeyType::Secret, vec![String::from("test1")]),
+			Command::from_str(":exp sec test1 --format json").unwrap()
+		);
+		assert!(Command::from_str(":export pub --format xml").is_err());
+		assert_eq!(
+			Command::ExportSshKey(String::from("0xABCD")),
+			Command::from_str(":export pub 0xabcd --ssh").unwrap()
+		);
+		assert!(Command::from_str(":export --ssh").is_err());
+		assert_eq!(
+			Command::DumpKeys(None, false),
+			Command::from_str(":dump").unwrap()
+		);
+		assert_eq!(
+			Command::DumpKeys(Some(String::from("keys.txt")), true),
+			Command::from_str(":dump --ansi keys.txt").unwrap()
+		);
+		for cmd in &[":export sec", ":exp sec"] {
+			let command = Command::from_str(cmd).unwrap();
+			assert_eq!(
+				Command::ExportKeys(
+					KeyType::Secret,
+					Vec::new(),
+					false,
+					ExportDestination::File
+				),
+				command
+			);
+		}
+		assert_eq!(
+			Command::ExportKeys(
+				KeyType::Secret,
+				vec![
+					String::from("test1"),
+					String::from("test2"),
+					String::from("test3")
+				],
+				false,
+				ExportDestination::File
+			),
+			Command::from_str(":export sec test1 test2 test3").unwrap()
+		);
+		for cmd in &[":delete pub xyz", ":del pub xyz"] {
+			let command = Command::from_str(cmd).unwrap();
+			assert_eq!(
+				Command::DeleteKey(KeyType::Public, vec![String::from("xyz")]),
+				command
+			);
+		}
+		assert_eq!(
+			Command::DeleteKey(
+				KeyType::Secret,
+				vec![String::from("0xABC"), String::from("xyz")]
+			),
+			Command::from_str(":delete sec 0xabc xyz").unwrap()
+		);
+		assert!(Command::from_str(":delete pub").is_err());
+		assert_eq!(
+			Command::KeyToCard(String::from("0xABC"), 2, CardSlot::Encryption),
+			Command::from_str(":keytocard 0xabc 2 enc").unwrap()
+		);
+		assert!(Command::from_str(":keytocard 0xabc 2 xyz").is_err());
+		assert!(Command::from_str(":keytocard 0xabc").is_err());
+		assert_eq!(
+			Command::ShowSubkeys,
+			Command::from_str(":subkeys").unwrap()
+		);
+		assert_eq!(
+			Command::Confirm(Box::new(Command::AddRevoker(
+				None,
+				String::from("0xABC")
+			))),
+			Command::from_str(":addrevoker 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::Confirm(Box::new(Command::AddRevoker(
+				Some(String::from("0xDEF")),
+				String::from("0xABC")
+			))),
+			Command::from_str(":addrevoker 0xabc 0xdef").unwrap()
+		);
+		assert!(Command::from_str(":addrevoker").is_err());
+		assert_eq!(
+			Command::Confirm(Box::new(Command::SetExpireAll(
+				String::from("1y"),
+				false
+			))),
+			Command::from_str(":expire-all 1y").unwrap()
+		);
+		assert_eq!(
+			Command::Confirm(Box::new(Command::SetExpireAll(
+				String::from("2023-01-01"),
+				true
+			))),
+			Command::from_str(":expire-all --subkeys 2023-01-01").unwrap()
+		);
+		assert!(Command::from_str(":expire-all").is_err());
+		assert_eq!(
+			Command::CleanKeys(Some(Vec::new()), false),
+			Command::from_str(":clean").unwrap()
+		);
+		assert_eq!(
+			Command::CleanKeys(Some(vec![String::from("0xabc")]), true),
+			Command::from_str(":minimize 0xABC").unwrap()
+		);
+		assert_eq!(
+			Command::Confirm(Box::new(Command::CleanKeys(None, false))),
+			Command::from_str(":clean --all").unwrap()
+		);
+		assert_eq!(
+			Command::Rotate(RotationAction::Start(None)),
+			Command::from_str(":rotate").unwrap()
+		);
+		assert_eq!(
+			Command::Rotate(RotationAction::Start(Some(String::from("0xABC")))),
+			Command::from_str(":rotate 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::Rotate(RotationAction::Skip),
+			Command::from_str(":rotate skip").unwrap()
+		);
+		assert_eq!(Command::ShowPrefs, Command::from_str(":prefs").unwrap());
+		assert_eq!(
+			Command::SetPrefs(None, String::from("AES256 SHA512 ZLIB")),
+			Command::from_str(":prefs set AES256 SHA512 ZLIB").unwrap()
+		);
+		assert_eq!(
+			Command::SetPrefs(
+				Some(String::from("0xABC")),
+				String::from(prefs::PREFERENCE_PRESETS[0].1)
+			),
+			Command::from_str(":prefs modern 0xabc").unwrap()
+		);
+		assert!(Command::from_str(":prefs set").is_err());
+		assert!(Command::from_str(":prefs xyz").is_err());
+		assert_eq!(
+			Command::ExportSubkey(String::from("0xABC"), 1),
+			Command::from_str(":subkey export 0xabc 1").unwrap()
+		);
+		assert_eq!(
+			Command::SetSubkeyExpire(
+				String::from("0xABC"),
+				2,
+				Some(String::from("1y"))
+			),
+			Command::from_str(":subkey expire 0xabc 2 1y").unwrap()
+		);
+		assert_eq!(
+			Command::RevokeSubkey(String::from("0xABC"), 1),
+			Command::from_str(":subkey revoke 0xabc 1").unwrap()
+		);
+		assert!(Command::from_str(":subkey revoke 0xabc").is_err());
+		assert!(Command::from_str(":subkey xyz 0xabc 1").is_err());
+		assert_eq!(
+			Command::ChangePin(CardPin::Pin),
+			Command::from_str(":card pin").unwrap()
+		);
+		assert_eq!(
+			Command::ChangePin(CardPin::AdminPin),
+			Command::from_str(":card admin-pin").unwrap()
+		);
+		assert!(Command::from_str(":card xyz").is_err());
+		assert_eq!(
+			Command::SetTofuPolicy(None, TofuPolicy::Good),
+			Command::from_str(":tofu policy good").unwrap()
+		);
+		assert_eq!(
+			Command::SetTofuPolicy(
+				Some(String::from("0xABC")),
+				TofuPolicy::Bad
+			),
+			Command::from_str(":tofu policy bad 0xabc").unwrap()
+		);
+		assert!(Command::from_str(":tofu policy xyz").is_err());
+		assert_eq!(
+			Command::SetKeyserver(
+				None,
+				String::from("hkps://Keys.example.org")
+			),
+			Command::from_str(":set pref-keyserver hkps://Keys.example.org")
+				.unwrap()
+		);
+		assert_eq!(
+			Command::SetKeyserver(
+				Some(String::from("0xABC")),
+				String::from("none")
+			),
+			Command::from_str(":set pref-keyserver none 0xabc").unwrap()
+		);
+		assert!(Command::from_str(":set pref-keyserver").is_err());
+		assert!(Command::from_str(":tofu").is_err());
+		assert_eq!(
+			Command::ShowTrustPaths(None),
+			Command::from_str(":wot").unwrap()
+		);
+		for cmd in &[":fingerprint", ":fpr"] {
+			assert_eq!(
+				Command::ShowFingerprint,
+				Command::from_str(cmd).unwrap()
+			);
+		}
+		assert_eq!(Command::ShowStats, Command::from_str(":stats").unwrap());
+		assert_eq!(
+			Command::CheckKeyring,
+			Command::from_str(":doctor").unwrap()
+		);
+		assert_eq!(
+			Command::JumpToIssue(2),
+			Command::from_str(":doctor 2").unwrap()
+		);
+		assert!(Command::from_str(":doctor x").is_err());
+		assert_eq!(
+			Command::ShowCertifications,
+			Command::from_str(":certs").unwrap()
+		);
+		assert_eq!(
+			Command::JumpToCertification(3),
+			Command::from_str(":certs 3").unwrap()
+		);
+		assert_eq!(
+			Command::RevokeCertification(1),
+			Command::from_str(":certs revoke 1").unwrap()
+		);
+		assert_eq!(Command::ShowGroups, Command::from_str(":groups").unwrap());
+		assert_eq!(
+			Command::SetGroup(
+				String::from("Team"),
+				vec![String::from("0xABC"), String::from("bob@example.org")]
+			),
+			Command::from_str(":group Team 0xABC bob@example.org").unwrap()
+		);
+		assert_eq!(
+			Command::SetGroup(String::from("team"), Vec::new()),
+			Command::from_str(":group team").unwrap()
+		);
+		assert!(Command::from_str(":group").is_err());
+		assert!(Command::from_str(":certs revoke").is_err());
+		assert_eq!(
+			Command::RevokeSig(String::from("0xABC"), String::from("0xDEF")),
+			Command::from_str(":revsig 0xabc 0xdef").unwrap()
+		);
+		assert!(Command::from_str(":revsig 0xabc").is_err());
+		assert_eq!(
+			Command::ShowTrustPaths(Some(String::from("0xABC"))),
+			Command::from_str(":wot 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::SendKey(String::from("test")),
+			Command::from_str(":send test").unwrap()
 		);
 		assert_eq!(
 			Command::EditKey(String::from("test")),
 			Command::from_str(":edit test").unwrap()
 		);
 		assert_eq!(
-			Command::SignKey(String::from("test")),
+			Command::AddUserId(
+				String::from("0xABC"),
+				String::from("Alice <Alice@example.org>")
+			),
+			Command::from_str(":adduid 0xabc Alice <Alice@example.org>")
+				.unwrap()
+		);
+		assert_eq!(
+			Command::Confirm(Box::new(Command::RevokeUserId(
+				String::from("0xABC"),
+				String::from("Alice")
+			))),
+			Command::from_str(":revuid 0xabc Alice").unwrap()
+		);
+		assert_eq!(
+			Command::SetPrimaryUserId(
+				String::from("0xABC"),
+				String::from("Alice")
+			),
+			Command::from_str(":primary 0xabc Alice").unwrap()
+		);
+		assert!(Command::from_str(":adduid 0xabc").is_err());
+		assert_eq!(
+			Command::AddSubkey(
+				String::from("0xABC"),
+				String::from("default"),
+				String::from("default"),
+				None
+			),
+			Command::from_str(":addkey 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::AddSubkey(
+				String::from("0xABC"),
+				String::from("ed25519"),
+				String::from("sign"),
+				Some(String::from("1y"))
+			),
+			Command::from_str(":addkey 0xabc ed25519 sign 1y").unwrap()
+		);
+		assert!(Command::from_str(":addkey 0xabc rsa4096 xyz").is_err());
+		assert_eq!(
+			Command::ChangePassphrase(String::from("0xABC")),
+			Command::from_str(":passwd 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::SetOwnerTrust(String::from("0xABC"), None),
+			Command::from_str(":trust 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::SetOwnerTrust(
+				String::from("0xABC"),
+				Some(OwnerTrust::Marginal)
+			),
+			Command::from_str(":trust 0xabc marginal").unwrap()
+		);
+		assert!(Command::from_str(":trust 0xabc xyz").is_err());
+		assert_eq!(
+			Command::SetExpire(None, None),
+			Command::from_str(":expire").unwrap()
+		);
+		assert_eq!(
+			Command::SetExpire(
+				Some(String::from("0xABC")),
+				Some(String::from("1y"))
+			),
+			Command::from_str(":expire 1y 0xabc").unwrap()
+		);
+		assert_eq!(
+			Command::SignKey(vec![String::from("test")], Vec::new()),
 			Command::from_str(":sign test").unwrap()
 		);
+		assert_eq!(
+			Command::SignKey(
+				vec![String::from("test1"), String::from("test2")],
+				Vec::new()
+			),
+			Command::from_str(":sign test1 test2").unwrap()
+		);
+		assert_eq!(
+			Command::SignKey(
+				vec![String::from("0x1")],
+				vec![String::from("policy@example.org=https://Example.org")]
+			),
+			Command::from_str(
+				":sign --notation \"policy@example.org=https://Example.org\" 0x1"
+			)
+			.unwrap()
+		);
+		assert_eq!(
+			Command::SignKey(
+				Vec::new(),
+				vec![String::from("a@b=1"), String::from("!c@d=2")]
+			),
+			Command::from_str(":sign --notation a@b=1 --notation !c@d=2")
+				.unwrap()
+		);
+		assert!(Command::from_str(":sign --notation x=1").is_err());
+		assert!(Command::from_str(":sign --notation").is_err());
+		assert!(Command::from_str(":sign").is_err());
 		assert_eq!(
 			Command::GenerateKey,
 			Command::from_str(":generate").unwrap()
 		);
+		assert_eq!(
+			Command::CreateKey(
+				String::from("Alice <alice@example.org>"),
+				String::from("default"),
+				None
+			),
+			Command::from_str(":generate Alice <alice@example.org>").unwrap()
+		);
+		assert_eq!(
+			Command::CreateKey(
+				String::from("Bob"),
+				String::from("rsa4096"),
+				Some(String::from("1y"))
+			),
+			Command::from_str(":gen --algo RSA4096 --expire 1y Bob").unwrap()
+		);
+		assert!(Command::from_str(":generate --algo rsa4096").is_err());
+		assert!(Command::from_str(":generate --expire").is_err());
+		assert_eq!(Command::Undo, Command::from_str(":undo").unwrap());
+		assert_eq!(Command::Cancel, Command::from_str(":cancel").unwrap());
 		assert_eq!(
 			Command::RefreshKeys,
 			Command::from_str(":refresh keys").unwrap()
@@ -439,6 +1968,10 @@ mod tests {
 			Command::Set(String::from("test"), String::from("_")),
 			Command::from_str(":set test _").unwrap()
 		);
+		assert_eq!(
+			Command::Set(String::from("datefmt"), String::from("%d %b %Y")),
+			Command::from_str(":set datefmt \"%d %b %Y\"").unwrap()
+		);
 		for cmd in &[":normal", ":n"] {
 			let command = Command::from_str(cmd).unwrap();
 			assert_eq!(Command::SwitchMode(Mode::Normal), command);
@@ -459,7 +1992,65 @@ mod tests {
 			Command::Search(Some(String::from("q"))),
 			Command::from_str(":search q").unwrap()
 		);
+		assert_eq!(
+			Command::Filter(Some(Filter::CanEncrypt)),
+			Command::from_str(":filter can=encrypt").unwrap()
+		);
+		assert_eq!(
+			Command::Filter(None),
+			Command::from_str(":filter none").unwrap()
+		);
+		assert!(Command::from_str(":filter").is_err());
+		assert_eq!(
+			Command::SetMark('a'),
+			Command::from_str(":mark a").unwrap()
+		);
+		assert_eq!(
+			Command::JumpToMark('a'),
+			Command::from_str(":jump a").unwrap()
+		);
+		assert_eq!(
+			Command::SetMark('B'),
+			Command::from_str(":mark B").unwrap()
+		);
+		assert!(Command::from_str(":mark").is_err());
+		assert_eq!(
+			Command::JumpToExpiring,
+			Command::from_str(":expiring").unwrap()
+		);
+		assert_eq!(
+			Command::RecordMacro(Some('a')),
+			Command::from_str(":record a").unwrap()
+		);
+		assert_eq!(
+			Command::RecordMacro(None),
+			Command::from_str(":record").unwrap()
+		);
+		assert_eq!(
+			Command::ReplayMacro('a', 1),
+			Command::from_str(":replay a").unwrap()
+		);
+		assert_eq!(
+			Command::ReplayMacro('a', 5),
+			Command::from_str(":replay a 5").unwrap()
+		);
+		assert!(Command::from_str(":replay").is_err());
+		assert!(Command::from_str(":replay a x").is_err());
+		assert_eq!(
+			Command::RunScript(
+				String::from("backup"),
+				vec![String::from("0xABC"), String::from("Key")]
+			),
+			Command::from_str(":run Backup 0xABC Key").unwrap()
+		);
+		assert!(Command::from_str(":run").is_err());
+		assert_eq!(
+			Command::Focus(String::from("email:Alice@example.org")),
+			Command::from_str("select email:Alice@example.org").unwrap()
+		);
+		assert!(Command::from_str(":focus").is_err());
 		assert_eq!(Command::EnableInput, Command::from_str(":input").unwrap());
+		assert_eq!(Command::Repeat, Command::from_str(":repeat").unwrap());
 		assert_eq!(Command::NextTab, Command::from_str(":next").unwrap());
 		assert_eq!(Command::PreviousTab, Command::from_str(":prev").unwrap());
 		assert_eq!(Command::Refresh, Command::from_str(":refresh").unwrap());
@@ -471,6 +2062,13 @@ mod tests {
 		assert!(Command::from_str("test").is_err());
 		assert_eq!("close menu", Command::None.to_string());
 		assert_eq!("show help", Command::ShowHelp.to_string());
+		assert_eq!("show recent messages", Command::ShowMessages.to_string());
+		assert_eq!("show flag legend", Command::ShowLegend.to_string());
+		assert_eq!("show error details", Command::ShowError.to_string());
+		assert_eq!(
+			"show command output",
+			Command::ShowCommandOutput.to_string()
+		);
 		assert_eq!("refresh application", Command::Refresh.to_string());
 		assert_eq!("refresh the keyring", Command::RefreshKeys.to_string());
 		assert_eq!(
@@ -479,21 +2077,151 @@ mod tests {
 		);
 		assert_eq!(
 			"export all the keys (sec)",
-			Command::ExportKeys(KeyType::Secret, Vec::new(), false).to_string()
+			Command::ExportKeys(
+				KeyType::Secret,
+				Vec::new(),
+				false,
+				ExportDestination::File
+			)
+			.to_string()
+		);
+		assert_eq!(
+			"export all the secret keys with a passphrase",
+			Command::ExportSymmetric(Vec::new()).to_string()
+		);
+		assert_eq!(
+			"export all the keys (pub) to clipboard",
+			Command::ExportKeys(
+				KeyType::Public,
+				Vec::new(),
+				false,
+				ExportDestination::Clipboard
+			)
+			.to_string()
 		);
 		assert_eq!(
 			"export the selected subkeys (sec)",
-			Command::ExportKeys(KeyType::Secret, vec![String::new()], true)
-				.to_string()
+			Command::ExportKeys(
+				KeyType::Secret,
+				vec![String::new()],
+				true,
+				ExportDestination::File
+			)
+			.to_string()
 		);
 		assert_eq!(
 			"export the selected key (pub)",
-			Command::ExportKeys(KeyType::Public, vec![String::new()], false)
+			Command::ExportKeys(
+				KeyType::Public,
+				vec![String::new()],
+				false,
+				ExportDestination::File
+			)
+			.to_string()
+		);
+		assert_eq!(
+			"set as the global git signing key",
+			Command::SetGitKey(String::new(), true).to_string()
+		);
+		assert_eq!(
+			"export all the keys as JSON (pub)",
+			Command::ExportJson(KeyType::Public, Vec::new()).to_string()
+		);
+		assert_eq!(
+			"dump the keys table to keys.txt",
+			Command::DumpKeys(Some(String::from("keys.txt")), false)
 				.to_string()
 		);
 		assert_eq!(
 			"delete the selected key (pub)",
-			Command::DeleteKey(KeyType::Public, String::new()).to_string()
+			Command::DeleteKey(KeyType::Public, vec![String::new()])
+				.to_string()
+		);
+		assert_eq!("show keyring statistics", Command::ShowStats.to_string());
+		assert_eq!(
+			"jump to the keyring issue (1)",
+			Command::JumpToIssue(1).to_string()
+		);
+		assert_eq!(
+			"revoke the certification (2)",
+			Command::RevokeCertification(2).to_string()
+		);
+		assert_eq!(
+			"revoke the certifications by 0xDEF",
+			Command::RevokeSig(String::from("0xABC"), String::from("0xDEF"))
+				.to_string()
+		);
+		assert_eq!(
+			"remove the group (team)",
+			Command::SetGroup(String::from("team"), Vec::new()).to_string()
+		);
+		assert_eq!(
+			"show trust paths",
+			Command::ShowTrustPaths(None).to_string()
+		);
+		assert_eq!(
+			"show the fingerprint for verification",
+			Command::ShowFingerprint.to_string()
+		);
+		assert_eq!(
+			"add 0xABC as a designated revoker",
+			Command::AddRevoker(None, String::from("0xABC")).to_string()
+		);
+		assert_eq!(
+			"set the default preferences",
+			Command::SetPrefs(None, String::new()).to_string()
+		);
+		assert_eq!(
+			"set the preferences",
+			Command::SetPrefs(None, String::from("AES256")).to_string()
+		);
+		assert_eq!(
+			"set the preferred keyserver (none)",
+			Command::SetKeyserver(None, String::from("none")).to_string()
+		);
+		assert_eq!(
+			"set TOFU policy to ask",
+			Command::SetTofuPolicy(None, TofuPolicy::Ask).to_string()
+		);
+		assert_eq!(
+			"change the card Admin PIN",
+			Command::ChangePin(CardPin::AdminPin).to_string()
+		);
+		assert_eq!(
+			"set the expiration time of subkey 1 to 1y",
+			Command::SetSubkeyExpire(
+				String::new(),
+				1,
+				Some(String::from("1y"))
+			)
+			.to_string()
+		);
+		assert_eq!(
+			"revoke subkey 2",
+			Command::RevokeSubkey(String::new(), 2).to_string()
+		);
+		assert_eq!(
+			"move subkey 1 to the card (signature) and leave a stub",
+			Command::KeyToCard(String::new(), 1, CardSlot::Signature)
+				.to_string()
+		);
+		assert_eq!(
+			"delete 2 keys (sec): 0x1, 0x2",
+			Command::DeleteKey(
+				KeyType::Secret,
+				vec![String::from("0x1"), String::from("0x2")]
+			)
+			.to_string()
+		);
+		assert_eq!(
+			"export 2 selected keys (pub)",
+			Command::ExportKeys(
+				KeyType::Public,
+				vec![String::from("0x1"), String::from("0x2")],
+				false,
+				ExportDestination::File
+			)
+			.to_string()
 		);
 		assert_eq!(
 			"send key to the keyserver",
@@ -503,11 +2231,44 @@ mod tests {
 			"edit the selected key",
 			Command::EditKey(String::new()).to_string()
 		);
+		assert_eq!(
+			"add a user ID",
+			Command::Set(String::from("prompt"), String::from(":adduid 0xA "))
+				.to_string()
+		);
+		assert_eq!(
+			"set the owner trust to full",
+			Command::SetOwnerTrust(String::new(), Some(OwnerTrust::Full))
+				.to_string()
+		);
+		assert_eq!(
+			"change the expiration time",
+			Command::SetExpire(None, None).to_string()
+		);
+		assert_eq!(
+			"set the expiration time to never",
+			Command::SetExpire(None, Some(String::from("never"))).to_string()
+		);
 		assert_eq!(
 			"sign the selected key",
-			Command::SignKey(String::new()).to_string()
+			Command::SignKey(vec![String::new()], Vec::new()).to_string()
+		);
+		assert_eq!(
+			"sign 2 selected keys (a@b=1)",
+			Command::SignKey(
+				vec![String::from("0x1"), String::from("0x2")],
+				vec![String::from("a@b=1")]
+			)
+			.to_string()
 		);
 		assert_eq!("generate a new key pair", Command::GenerateKey.to_string());
+		assert_eq!(
+			"generate a new key pair for Bob",
+			Command::CreateKey(String::from("Bob"), String::new(), None)
+				.to_string()
+		);
+		assert_eq!("undo the last deletion", Command::Undo.to_string());
+		assert_eq!("cancel the running task", Command::Cancel.to_string());
 		assert_eq!(
 			"copy exported key",
 			Command::Copy(Selection::Key).to_string()
@@ -532,6 +2293,30 @@ mod tests {
 			Command::Set(String::from("colored"), String::from("true"))
 				.to_string()
 		);
+		assert_eq!(
+			"show unusable keys",
+			Command::Set(String::from("hide-unusable"), String::from("false"))
+				.to_string()
+		);
+		assert_eq!(
+			"disable split view",
+			Command::Set(String::from("splitview"), String::from("false"))
+				.to_string()
+		);
+		assert_eq!(
+			"disable notifications",
+			Command::Set(String::from("notify"), String::from("false"))
+				.to_string()
+		);
+		assert_eq!(
+			"enable log pane",
+			Command::Set(String::from("log"), String::from("true")).to_string()
+		);
+		assert_eq!(
+			"enable redaction",
+			Command::Set(String::from("redact"), String::from("true"))
+				.to_string()
+		);
 		assert_eq!(
 			"toggle table margin",
 			Command::Set(String::from("margin"), String::new()).to_string()
@@ -545,6 +2330,18 @@ mod tests {
 			"import key(s) from clipboard",
 			Command::ImportClipboard.to_string()
 		);
+		assert_eq!(
+			"select the output directory",
+			Command::BrowseFiles(String::from("output")).to_string()
+		);
+		assert_eq!(
+			"encrypt x.txt for 2 recipient(s)",
+			Command::Encrypt(
+				String::from("x.txt"),
+				vec![String::new(), String::new()]
+			)
+			.to_string()
+		);
 		assert_eq!(
 			"receive key(s) from keyserver",
 			Command::Set(String::from("prompt"), String::from(":receive "))
@@ -567,7 +2364,89 @@ mod tests {
 			"refresh application",
 			Command::Confirm(Box::new(Command::Refresh)).to_string()
 		);
+		assert_eq!(
+			"filter keys (revoked)",
+			Command::Filter(Some(Filter::Revoked)).to_string()
+		);
+		assert_eq!("clear the filter", Command::Filter(None).to_string());
+		assert_eq!("set mark (a)", Command::SetMark('a').to_string());
+		assert_eq!(
+			"record macro (a)",
+			Command::RecordMacro(Some('a')).to_string()
+		);
+		assert_eq!(
+			"replay macro (a)",
+			Command::ReplayMacro('a', 2).to_string()
+		);
+		assert_eq!(
+			"run script (backup)",
+			Command::RunScript(String::from("backup"), Vec::new()).to_string()
+		);
+		assert_eq!("jump to mark (b)", Command::JumpToMark('b').to_string());
+		assert_eq!(
+			"jump to the expiring key",
+			Command::JumpToExpiring.to_string()
+		);
 		assert_eq!("quit application", Command::Quit.to_string());
 		assert_eq!("NextTab", Command::NextTab.to_string());
+		assert!(Command::SignKey(Vec::new(), Vec::new()).is_repeatable());
+		assert!(Command::Set(String::from("armor"), String::from("true"))
+			.is_repeatable());
+		assert!(!Command::Set(String::from("prompt"), String::from(":"))
+			.is_repeatable());
+		assert!(!Command::Scroll(ScrollDirection::Top, false).is_repeatable());
+		assert!(!Command::Repeat.is_repeatable());
+		assert!(!Command::EditKey(String::new()).is_repeatable());
+		assert!(
+			Command::SetOwnerTrust(String::new(), Some(OwnerTrust::Full))
+				.is_repeatable()
+		);
+		assert_eq!(
+			Some(Command::Set(String::from("detail"), String::from("2"))),
+			Command::from_count(2)
+		);
+		assert_eq!(None, Command::from_count(12));
+		let keys = vec![String::from("0xA")];
+		let new_keys = vec![String::from("0xB")];
+		assert_eq!(
+			Command::Confirm(Box::new(Command::SendKey(String::from("0xB")))),
+			Command::Confirm(Box::new(Command::SendKey(String::from("0xA"))))
+				.replace_keys(&keys, &new_keys)
+		);
+		assert_eq!(
+			Command::ExportKeys(
+				KeyType::Public,
+				new_keys.to_vec(),
+				false,
+				ExportDestination::File
+			),
+			Command::ExportKeys(
+				KeyType::Public,
+				keys.to_vec(),
+				false,
+				ExportDestination::File
+			)
+			.replace_keys(&keys, &new_keys)
+		);
+		assert_eq!(
+			Command::ExportKeys(
+				KeyType::Public,
+				Vec::new(),
+				false,
+				ExportDestination::File
+			),
+			Command::ExportKeys(
+				KeyType::Public,
+				Vec::new(),
+				false,
+				ExportDestination::File
+			)
+			.replace_keys(&keys, &new_keys)
+		);
+		assert_eq!(
+			Command::EditKey(String::from("0xC")),
+			Command::EditKey(String::from("0xC"))
+				.replace_keys(&keys, &new_keys)
+		);
 	}
 }
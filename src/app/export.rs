@@ -0,0 +1,57 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+/// Destination of the exported keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExportDestination {
+	/// File in the output directory.
+	#[default]
+	File,
+	/// Standard output (printed after exiting the interface).
+	Stdout,
+	/// Clipboard.
+	Clipboard,
+}
+
+impl Display for ExportDestination {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}", format!("{:?}", self).to_lowercase())
+	}
+}
+
+impl FromStr for ExportDestination {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"file" => Ok(Self::File),
+			"stdout" | "-" => Ok(Self::Stdout),
+			"clipboard" | "clip" => Ok(Self::Clipboard),
+			_ => Err(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_export() {
+		assert_eq!(ExportDestination::File, ExportDestination::default());
+		for destination in &[
+			ExportDestination::File,
+			ExportDestination::Stdout,
+			ExportDestination::Clipboard,
+		] {
+			assert_eq!(
+				Ok(*destination),
+				ExportDestination::from_str(&destination.to_string())
+			);
+		}
+		assert_eq!(
+			Ok(ExportDestination::Stdout),
+			ExportDestination::from_str("-")
+		);
+		assert!(ExportDestination::from_str("xyz").is_err());
+	}
+}
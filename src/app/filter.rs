@@ -0,0 +1,88 @@
+use crate::gpg::key::GpgKey;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+/// Filter for narrowing down the keys table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Filter {
+	/// Expired keys.
+	Expired,
+	/// Revoked keys.
+	Revoked,
+	/// Keys that can be used for encryption.
+	CanEncrypt,
+	/// Keys that can be used for signing.
+	CanSign,
+	/// Keys that can be used for authentication.
+	CanAuthenticate,
+	/// Keys that can be used for certification.
+	CanCertify,
+}
+
+impl Display for Filter {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Expired => "expired",
+				Self::Revoked => "revoked",
+				Self::CanEncrypt => "can=encrypt",
+				Self::CanSign => "can=sign",
+				Self::CanAuthenticate => "can=auth",
+				Self::CanCertify => "can=cert",
+			}
+		)
+	}
+}
+
+impl FromStr for Filter {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"expired" | "exp" => Ok(Self::Expired),
+			"revoked" | "rev" => Ok(Self::Revoked),
+			"can=encrypt" | "can=e" => Ok(Self::CanEncrypt),
+			"can=sign" | "can=s" => Ok(Self::CanSign),
+			"can=auth" | "can=a" => Ok(Self::CanAuthenticate),
+			"can=cert" | "can=c" => Ok(Self::CanCertify),
+			_ => Err(String::from("could not parse the filter")),
+		}
+	}
+}
+
+impl Filter {
+	/// Returns `true` if the given key passes the filter.
+	pub fn matches(&self, key: &GpgKey) -> bool {
+		match self {
+			Self::Expired => key.is_expired(),
+			Self::Revoked => key.is_revoked(),
+			Self::CanEncrypt => key.can_encrypt(),
+			Self::CanSign => key.can_sign(),
+			Self::CanAuthenticate => key.can_authenticate(),
+			Self::CanCertify => key.can_certify(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_filter() {
+		for (filter, value) in &[
+			(Filter::Expired, "expired"),
+			(Filter::Revoked, "revoked"),
+			(Filter::CanEncrypt, "can=encrypt"),
+			(Filter::CanSign, "can=sign"),
+			(Filter::CanAuthenticate, "can=auth"),
+			(Filter::CanCertify, "can=cert"),
+		] {
+			assert_eq!(Ok(*filter), Filter::from_str(value));
+			assert_eq!(value, &filter.to_string());
+		}
+		assert_eq!(Ok(Filter::CanEncrypt), Filter::from_str("can=e"));
+		assert!(Filter::from_str("can=fly").is_err());
+	}
+}
@@ -1,16 +1,22 @@
 use crate::app::command::Command;
+use crate::app::export::ExportDestination;
 use crate::app::launcher::App;
 use crate::app::mode::Mode;
-use crate::app::prompt::OutputType;
+use crate::app::prompt::{OutputType, COMMAND_PREFIX};
+use crate::app::rotate::RotationAction;
 use crate::app::selection::Selection;
 use crate::app::tab::Tab;
 use crate::app::util;
+use crate::gpg::handler::{self, ARMOR_FOOTER};
 use crate::gpg::key::KeyType;
+use crate::gpg::prefs;
 use crate::term::tui::Tui;
 use crate::widget::row::ScrollDirection;
 use anyhow::Result;
 use crossterm::event::{KeyCode as Key, KeyEvent, KeyModifiers as Modifiers};
+use std::convert::TryFrom;
 use std::str::FromStr;
+use std::time::Instant;
 use tui::backend::Backend;
 
 /// Handles the key events and executes the application command.
@@ -19,47 +25,317 @@ pub fn handle_events<B: Backend>(
 	tui: &mut Tui<B>,
 	app: &mut App,
 ) -> Result<()> {
-	handle_command_execution(handle_key_event(key_event, app), tui, app)
+	let recording = app.macros.is_recording();
+	if let Some(command) = take_unused_count(key_event, app) {
+		handle_command_execution(command, tui, app)?;
+	}
+	handle_command_execution(handle_key_event(key_event, app), tui, app)?;
+	if recording && app.macros.is_recording() {
+		app.macros.record(key_event);
+	}
+	Ok(())
+}
+
+/// Replays the key events of the macro in the given register.
+fn replay_macro<B: Backend>(
+	key_events: Vec<KeyEvent>,
+	count: u16,
+	tui: &mut Tui<B>,
+	app: &mut App,
+) -> Result<()> {
+	if !app.macros.enter() {
+		return app.run_command(Command::ShowOutput(
+			OutputType::Failure,
+			String::from("macro recursion limit is reached"),
+		));
+	}
+	let result = (0..count.max(1)).try_for_each(|_| {
+		key_events
+			.iter()
+			.try_for_each(|key_event| handle_events(*key_event, tui, app))
+	});
+	app.macros.exit();
+	result
+}
+
+/// Runs the command that is registered by a script.
+///
+/// Commands that are requested by the script are run in order.
+fn run_script<B: Backend>(
+	name: &str,
+	args: &[String],
+	tui: &mut Tui<B>,
+	app: &mut App,
+) -> Result<()> {
+	match app.scripts.run(name, args, app.get_script_context()) {
+		Ok(commands) => commands.into_iter().try_for_each(|command| {
+			handle_command_execution(command, tui, app)
+		}),
+		Err(e) => app.run_command(Command::ShowOutput(
+			OutputType::Failure,
+			format!("script error ({}): {}", name, e),
+		)),
+	}
+}
+
+/// Returns the command for running the script command if
+/// the given input starts with the name of a script command.
+fn get_script_command(app: &App, input: &str) -> Option<Command> {
+	let mut values =
+		input.trim_start_matches(COMMAND_PREFIX).split_whitespace();
+	let name = values.next()?.to_lowercase();
+	if app.scripts.contains(&name) {
+		Some(Command::RunScript(name, values.map(String::from).collect()))
+	} else {
+		None
+	}
 }
 
 /// Returns the corresponding application command for a key event.
 fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 	let mut command = Command::None;
-	if app.prompt.is_enabled() {
+	if let Some(dialog) = app.dialog.as_mut() {
 		match key_event.code {
+			Key::Left | Key::Char('h') | Key::BackTab => dialog.previous(),
+			Key::Right | Key::Char('l') | Key::Tab => dialog.next(),
+			Key::Enter | Key::Char(' ') => {
+				command = dialog.selected().cloned().unwrap_or(Command::None);
+				app.dialog = None;
+			}
+			Key::Esc | Key::Char('q') => app.dialog = None,
 			Key::Char(c) => {
-				app.prompt.text.push(c);
+				if let Some(action) = dialog.get_shortcut(c) {
+					command = action.clone();
+					app.dialog = None;
+				}
+			}
+			_ => {}
+		}
+	} else if let Some((target, picker)) = app.file_picker.as_mut() {
+		let mut close = false;
+		let result = match key_event.code {
+			Key::Up | Key::Char('k') | Key::Char('K') => {
+				picker.entries.previous();
+				Ok(None)
+			}
+			Key::Down | Key::Char('j') | Key::Char('J') => {
+				picker.entries.next();
+				Ok(None)
+			}
+			Key::Enter | Key::Right | Key::Char('l') | Key::Char('L') => {
+				picker.open()
+			}
+			Key::Left | Key::Backspace | Key::Char('h') | Key::Char('H') => {
+				picker.parent().map(|_| None)
+			}
+			Key::Char('.') => picker.toggle_hidden().map(|_| None),
+			Key::Char('f') | Key::Char('F') => {
+				picker.toggle_filter().map(|_| None)
+			}
+			Key::Esc | Key::Char('q') | Key::Char('Q') => {
+				close = true;
+				Ok(None)
+			}
+			_ => Ok(None),
+		};
+		match result {
+			Ok(Some(path)) => {
+				let path = path.to_string_lossy().to_string();
+				command = match target.as_str() {
+					"output" => Command::Set(String::from("output"), path),
+					"encrypt" => Command::Encrypt(path, Vec::new()),
+					_ => Command::ImportKeys(vec![path], false),
+				};
+				close = true;
+			}
+			Ok(None) => {}
+			Err(e) => {
+				command = Command::ShowOutput(
+					OutputType::Failure,
+					format!("browse error: {}", e),
+				)
+			}
+		}
+		if close {
+			app.file_picker = None;
+		}
+	} else if let Some((file, recipients)) = app.recipient_picker.as_mut() {
+		let mut close = false;
+		match key_event.code {
+			Key::Up => recipients.previous(),
+			Key::Down => recipients.next(),
+			Key::Tab | Key::Char(' ') => recipients.toggle(),
+			Key::Char(c) => {
+				recipients.query.insert(c);
+				recipients.filter();
+			}
+			Key::Backspace => {
+				recipients.query.delete_previous();
+				recipients.filter();
+			}
+			Key::Enter => {
+				let mut checked = recipients
+					.get_checked()
+					.into_iter()
+					.cloned()
+					.collect::<Vec<String>>();
+				if checked.is_empty() {
+					checked.extend(
+						recipients.selected().map(|item| item.value.clone()),
+					);
+				}
+				if !checked.is_empty() {
+					command = Command::Encrypt(file.to_string(), checked);
+					close = true;
+				}
+			}
+			Key::Esc => close = true,
+			_ => {}
+		}
+		if close {
+			app.recipient_picker = None;
+		}
+	} else if let Some((action, picker)) = app.date_picker.as_mut() {
+		let mut close = false;
+		match key_event.code {
+			Key::Left => picker.move_days(-1),
+			Key::Right => picker.move_days(1),
+			Key::Up => picker.move_days(-7),
+			Key::Down => picker.move_days(7),
+			Key::PageUp => picker.move_months(-1),
+			Key::PageDown => picker.move_months(1),
+			Key::Tab => picker.toggle_never(),
+			Key::Char(c) => picker.input.insert(c),
+			Key::Backspace => {
+				picker.input.delete_previous();
+			}
+			Key::Enter if picker.input.is_empty() => {
+				command = match action {
+					Command::SetExpire(key_id, _) => Command::SetExpire(
+						key_id.clone(),
+						Some(picker.get_value()),
+					),
+					Command::SetSubkeyExpire(key_id, subkey, _) => {
+						Command::SetSubkeyExpire(
+							key_id.clone(),
+							*subkey,
+							Some(picker.get_value()),
+						)
+					}
+					Command::Set(option, _) => {
+						Command::Set(option.to_string(), picker.get_value())
+					}
+					_ => Command::None,
+				};
+				close = true;
+			}
+			Key::Enter => {
+				if let Err(e) = picker.apply_input() {
+					command =
+						Command::ShowOutput(OutputType::Failure, e.to_string())
+				}
+			}
+			Key::Esc => close = true,
+			_ => {}
+		}
+		if close {
+			app.date_picker = None;
+		}
+	} else if app.prompt.is_typed_confirmation() {
+		match key_event.code {
+			Key::Char(c) => app.prompt.answer.push(c),
+			Key::Backspace => {
+				app.prompt.answer.pop();
+			}
+			Key::Esc => app.prompt.clear(),
+			Key::Enter => {
+				if app.prompt.is_confirmed() {
+					command =
+						app.prompt.command.clone().unwrap_or(Command::None);
+				} else {
+					app.prompt.clear();
+					app.prompt.set_output((
+						OutputType::Failure,
+						String::from(
+							"confirmation failed, command is cancelled",
+						),
+					));
+				}
+			}
+			_ => {}
+		}
+	} else if app.prompt.is_enabled() {
+		match key_event.code {
+			Key::Char(c) => {
+				app.prompt.text.insert(c);
 				if app.prompt.is_search_enabled() {
-					app.keys_table.reset_state();
+					app.state.pending_search = Some(Instant::now());
+				}
+			}
+			Key::Left if app.prompt.text.cursor > 1 => {
+				app.prompt.text.move_left()
+			}
+			Key::Right => app.prompt.text.move_right(),
+			Key::Home => {
+				app.prompt.text.move_home();
+				if app.prompt.text.cursor == 0 {
+					app.prompt.text.move_right();
 				}
 			}
+			Key::End => app.prompt.text.move_end(),
 			Key::Up => app.prompt.previous(),
 			Key::Down => app.prompt.next(),
 			Key::Tab => {
 				if app.prompt.is_command_input_enabled() {
 					app.prompt.enable_search();
+					app.keys_table.reset_items();
 				} else if app.prompt.is_search_enabled() {
 					app.prompt.enable_command_input();
-					app.keys_table.items = app.keys_table.default_items.clone();
+					app.keys_table.reset_items();
 				}
 			}
-			Key::Backspace => {
-				app.prompt.text.pop();
+			Key::Backspace | Key::Delete => {
+				if key_event.code == Key::Delete {
+					app.prompt.text.delete_next();
+				} else if app.prompt.text.cursor > 1
+					|| app.prompt.text.value.len() == 1
+				{
+					app.prompt.text.delete_previous();
+				}
 				if app.prompt.is_search_enabled() {
-					app.keys_table.reset_state();
+					app.state.pending_search = Some(Instant::now());
 				}
 			}
 			Key::Esc => {
 				app.prompt.clear();
 				if app.prompt.is_search_enabled() {
+					app.search_query = None;
+					app.state.pending_search = None;
 					app.keys_table.reset_state();
 				}
 			}
+			Key::Enter
+				if app.prompt.is_command_input_enabled()
+					&& handler::is_armored_block(
+						&app.prompt.text.value.replacen(COMMAND_PREFIX, "", 1),
+					) && !app.prompt.text.value.contains(ARMOR_FOOTER) =>
+			{
+				app.prompt.text.insert('\n');
+			}
 			Key::Enter => {
-				if app.prompt.is_search_enabled() || app.prompt.text.len() < 2 {
+				if app.prompt.is_search_enabled()
+					|| app.prompt.text.value.len() < 2
+				{
+					let query = app.prompt.text.value.replacen('/', "", 1);
+					app.search_query =
+						if query.is_empty() { None } else { Some(query) };
+					app.apply_search();
 					app.prompt.clear();
-				} else if let Ok(cmd) = Command::from_str(&app.prompt.text) {
-					app.prompt.history.push(app.prompt.text.clone());
+				} else if let Some(cmd) =
+					Command::from_str(&app.prompt.text.value).ok().or_else(
+						|| get_script_command(app, &app.prompt.text.value),
+					) {
+					app.prompt.history.push(app.prompt.text.value.clone());
 					app.prompt.clear();
 					command = cmd;
 				} else {
@@ -67,25 +343,277 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 						OutputType::Failure,
 						format!(
 							"invalid command: {}",
-							app.prompt.text.replacen(":", "", 1)
+							app.prompt.text.value.replacen(":", "", 1)
 						),
 					));
 				}
 			}
 			_ => {}
 		}
+	} else if app.state.show_output {
+		command = match key_event.code {
+			Key::Up | Key::Char('k') | Key::Char('K') => {
+				Command::Scroll(ScrollDirection::Up(1), false)
+			}
+			Key::Down | Key::Char('j') | Key::Char('J') => {
+				Command::Scroll(ScrollDirection::Down(1), false)
+			}
+			Key::PageUp => {
+				Command::Scroll(ScrollDirection::Up(get_page_size(app)), false)
+			}
+			Key::PageDown => Command::Scroll(
+				ScrollDirection::Down(get_page_size(app)),
+				false,
+			),
+			_ => {
+				app.state.show_output = false;
+				Command::None
+			}
+		};
+	} else if app.state.show_doctor {
+		command = match key_event.code {
+			Key::Up | Key::Char('k') | Key::Char('K') => {
+				app.keyring_issues.previous();
+				Command::None
+			}
+			Key::Down | Key::Char('j') | Key::Char('J') => {
+				app.keyring_issues.next();
+				Command::None
+			}
+			Key::Enter => match app.keyring_issues.state.selected() {
+				Some(index) => Command::JumpToIssue(index + 1),
+				None => Command::None,
+			},
+			_ => {
+				app.state.show_doctor = false;
+				Command::None
+			}
+		};
+	} else if app.state.show_certs {
+		command = match key_event.code {
+			Key::Up | Key::Char('k') | Key::Char('K') => {
+				app.certifications.previous();
+				Command::None
+			}
+			Key::Down | Key::Char('j') | Key::Char('J') => {
+				app.certifications.next();
+				Command::None
+			}
+			Key::Enter => match app.certifications.state.selected() {
+				Some(index) => Command::JumpToCertification(index + 1),
+				None => Command::None,
+			},
+			Key::Char('r') => match app.certifications.state.selected() {
+				Some(index) => Command::Confirm(Box::new(
+					Command::RevokeCertification(index + 1),
+				)),
+				None => Command::None,
+			},
+			Key::Char('R') => match app.certifications.selected() {
+				Some(certification) => {
+					Command::Confirm(Box::new(Command::RevokeSig(
+						certification.key_id.to_string(),
+						certification.signer_id.to_string(),
+					)))
+				}
+				None => Command::None,
+			},
+			_ => {
+				app.state.show_certs = false;
+				Command::None
+			}
+		};
+	} else if app.state.show_groups {
+		let selected_key = app.keys_table.selected().map(|key| key.get_id());
+		command = match (key_event.code, app.groups.selected().cloned()) {
+			(Key::Up, _) | (Key::Char('k'), _) | (Key::Char('K'), _) => {
+				app.groups.previous();
+				Command::None
+			}
+			(Key::Down, _) | (Key::Char('j'), _) | (Key::Char('J'), _) => {
+				app.groups.next();
+				Command::None
+			}
+			(Key::Enter, Some(group)) | (Key::Char('e'), Some(group)) => {
+				app.state.show_groups = false;
+				Command::Set(
+					String::from("prompt"),
+					format!(
+						":group {} {}",
+						group.name,
+						group.members.join(" ")
+					),
+				)
+			}
+			(Key::Char('a'), Some(mut group)) => match selected_key {
+				Some(key_id) if !group.members.contains(&key_id) => {
+					group.members.push(key_id);
+					Command::SetGroup(group.name, group.members)
+				}
+				_ => Command::None,
+			},
+			(Key::Char('x'), Some(group)) => match selected_key {
+				Some(key_id) if group.members.contains(&key_id) => {
+					Command::SetGroup(
+						group.name,
+						group
+							.members
+							.into_iter()
+							.filter(|member| member != &key_id)
+							.collect(),
+					)
+				}
+				_ => Command::None,
+			},
+			(Key::Char('d'), Some(group)) => Command::Confirm(Box::new(
+				Command::SetGroup(group.name, Vec::new()),
+			)),
+			(Key::Char('n'), _) => {
+				app.state.show_groups = false;
+				Command::Set(String::from("prompt"), String::from(":group "))
+			}
+			_ => {
+				app.state.show_groups = false;
+				Command::None
+			}
+		};
+	} else if app.state.show_prefs {
+		let selected_key = app.keys_table.selected().map(|key| key.get_id());
+		command = match (key_event.code, app.preferences.state.selected()) {
+			(Key::Up, _) | (Key::Char('k'), _) | (Key::Char('K'), _) => {
+				app.preferences.previous();
+				Command::None
+			}
+			(Key::Down, _) | (Key::Char('j'), _) | (Key::Char('J'), _) => {
+				app.preferences.next();
+				Command::None
+			}
+			(Key::Enter, Some(i)) if i > 0 => {
+				match prefs::PREFERENCE_PRESETS.get(i - 1) {
+					Some((_, preferences)) => {
+						Command::Confirm(Box::new(Command::SetPrefs(
+							selected_key,
+							preferences.to_string(),
+						)))
+					}
+					None => Command::None,
+				}
+			}
+			(Key::Char('e'), _) => {
+				app.state.show_prefs = false;
+				Command::Set(
+					String::from("prompt"),
+					String::from(":prefs set "),
+				)
+			}
+			_ => {
+				app.state.show_prefs = false;
+				Command::None
+			}
+		};
+	} else if app.state.show_subkeys {
+		let selected = app
+			.keys_table
+			.selected()
+			.map(|key| key.get_id())
+			.zip(app.subkeys.state.selected());
+		command = match (key_event.code, selected) {
+			(Key::Up, _) | (Key::Char('k'), _) | (Key::Char('K'), _) => {
+				app.subkeys.previous();
+				Command::None
+			}
+			(Key::Down, _) | (Key::Char('j'), _) | (Key::Char('J'), _) => {
+				app.subkeys.next();
+				Command::None
+			}
+			(Key::Char('x'), Some((key_id, subkey))) => {
+				Command::ExportSubkey(key_id, subkey)
+			}
+			(Key::Char('e'), Some((key_id, subkey))) => {
+				Command::SetSubkeyExpire(key_id, subkey, None)
+			}
+			(Key::Char('r'), Some((key_id, subkey))) => Command::Confirm(
+				Box::new(Command::RevokeSubkey(key_id, subkey)),
+			),
+			(Key::Char('c'), Some((key_id, subkey))) => {
+				match app.keys_table.selected().and_then(|key| {
+					key.get_card_candidates()
+						.into_iter()
+						.find(|(i, _)| *i == subkey)
+				}) {
+					Some((_, slot)) => Command::Confirm(Box::new(
+						Command::KeyToCard(key_id, subkey, slot),
+					)),
+					None => Command::ShowOutput(
+						OutputType::Warning,
+						String::from("subkey cannot be moved to the card"),
+					),
+				}
+			}
+			_ => {
+				app.state.show_subkeys = false;
+				Command::None
+			}
+		};
+	} else if app.state.show_messages
+		|| app.state.show_legend
+		|| app.state.show_agent
+		|| app.state.show_wot
+		|| app.state.show_stats
+	{
+		app.state.show_messages = false;
+		app.state.show_legend = false;
+		app.state.show_agent = false;
+		app.state.show_wot = false;
+		app.state.show_stats = false;
+	} else if let Some((pending_key, count)) = app.state.pending_key.take() {
+		if let Key::Char(c) = key_event.code {
+			command = match pending_key {
+				'm' => Command::SetMark(c),
+				'q' => Command::RecordMacro(Some(c)),
+				'@' => Command::ReplayMacro(c, count),
+				_ => Command::JumpToMark(c),
+			};
+		}
 	} else {
+		let count = app.state.count.take().map(|(count, _)| count);
+		let amount = count.unwrap_or(1);
 		command = match key_event.code {
+			Key::Char(c)
+				if c.is_ascii_digit()
+					&& app.mode != Mode::Copy
+					&& (c != '0' || count.is_some()) =>
+			{
+				app.state.count = Some((
+					count
+						.unwrap_or_default()
+						.saturating_mul(10)
+						.saturating_add(
+							c.to_digit(10).unwrap_or_default() as u16
+						),
+					Instant::now(),
+				));
+				Command::None
+			}
 			Key::Char('?') => Command::ShowHelp,
-			Key::Char('q') | Key::Char('Q') => Command::Quit,
+			Key::Char('!') => Command::ShowError,
+			Key::Char('q') if app.macros.is_recording() => {
+				Command::RecordMacro(None)
+			}
+			Key::Char('Q') => Command::Quit,
+			Key::Esc if count.is_some() => Command::None,
+			Key::Esc | Key::Char('c') | Key::Char('C')
+				if app.worker.is_running()
+					&& (key_event.code == Key::Esc
+						|| key_event.modifiers == Modifiers::CONTROL) =>
+			{
+				Command::Cancel
+			}
 			Key::Esc => {
 				if app.mode != Mode::Normal {
 					Command::SwitchMode(Mode::Normal)
 				} else if app.state.show_options {
 					Command::None
-				} else if app.prompt.command.is_some() {
-					app.prompt.clear();
-					Command::None
 				} else {
 					Command::Quit
 				}
@@ -94,22 +622,22 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 				if key_event.modifiers == Modifiers::CONTROL
 					&& key_event.code != Key::Backspace
 				{
-					Command::Quit
+					Command::Scroll(
+						ScrollDirection::Down(get_half_page_size(app)),
+						false,
+					)
 				} else {
 					match app.keys_table.selected() {
-						Some(selected_key) => {
+						Some(_) => {
 							Command::Confirm(Box::new(Command::DeleteKey(
 								match app.tab {
 									Tab::Keys(key_type) => key_type,
 									_ => KeyType::Public,
 								},
-								selected_key.get_id(),
+								app.get_selected_keys(),
 							)))
 						}
-						None => Command::ShowOutput(
-							OutputType::Failure,
-							String::from("invalid selection"),
-						),
+						None => get_selection_warning(app),
 					}
 				}
 			}
@@ -128,6 +656,7 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 				}
 			}
 			Key::Char('p') | Key::Char('P') => Command::Paste,
+			Key::Char('w') | Key::Char('W') => Command::JumpToExpiring,
 			Key::Char('r') | Key::Char('R') | Key::F(5) => {
 				if key_event.modifiers == Modifiers::CONTROL {
 					Command::RefreshKeys
@@ -140,14 +669,14 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 					Command::Scroll(ScrollDirection::Top, false)
 				} else {
 					Command::Scroll(
-						ScrollDirection::Up(1),
+						ScrollDirection::Up(amount),
 						key_event.modifiers == Modifiers::ALT,
 					)
 				}
 			}
 			Key::Right | Key::Char('l') | Key::Char('L') => {
 				if key_event.modifiers == Modifiers::ALT {
-					Command::Scroll(ScrollDirection::Right(1), true)
+					Command::Scroll(ScrollDirection::Right(amount), true)
 				} else {
 					Command::NextTab
 				}
@@ -157,20 +686,28 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 					Command::Scroll(ScrollDirection::Bottom, false)
 				} else {
 					Command::Scroll(
-						ScrollDirection::Down(1),
+						ScrollDirection::Down(amount),
 						key_event.modifiers == Modifiers::ALT,
 					)
 				}
 			}
 			Key::Left | Key::Char('h') | Key::Char('H') => {
 				if key_event.modifiers == Modifiers::ALT {
-					Command::Scroll(ScrollDirection::Left(1), true)
+					Command::Scroll(ScrollDirection::Left(amount), true)
 				} else {
 					Command::PreviousTab
 				}
 			}
-			Key::PageUp => Command::Scroll(ScrollDirection::Top, false),
-			Key::PageDown => Command::Scroll(ScrollDirection::Bottom, false),
+			Key::PageUp => Command::Scroll(
+				ScrollDirection::Up(get_page_size(app).saturating_mul(amount)),
+				false,
+			),
+			Key::PageDown => Command::Scroll(
+				ScrollDirection::Down(
+					get_page_size(app).saturating_mul(amount),
+				),
+				false,
+			),
 			Key::Char('t') | Key::Char('T') => Command::ToggleDetail(true),
 			Key::Tab => Command::ToggleDetail(false),
 			Key::Char('`') => Command::Set(
@@ -187,15 +724,15 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 						String::from("colored"),
 						(!app.state.colored).to_string(),
 					)
+				} else if app.mode == Mode::Copy {
+					Command::Copy(Selection::SshKey)
 				} else {
 					match app.keys_table.selected() {
-						Some(selected_key) => {
-							Command::SignKey(selected_key.get_id())
-						}
-						None => Command::ShowOutput(
-							OutputType::Failure,
-							String::from("invalid selection"),
+						Some(_) => Command::SignKey(
+							app.get_selected_keys(),
+							Vec::new(),
 						),
+						None => get_selection_warning(app),
 					}
 				}
 			}
@@ -204,10 +741,7 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 					Some(selected_key) => {
 						Command::EditKey(selected_key.get_id())
 					}
-					None => Command::ShowOutput(
-						OutputType::Failure,
-						String::from("invalid selection"),
-					),
+					None => get_selection_warning(app),
 				}
 			}
 			Key::Char('x') | Key::Char('X') => {
@@ -215,18 +749,16 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 					Command::Copy(Selection::Key)
 				} else {
 					match app.keys_table.selected() {
-						Some(selected_key) => Command::ExportKeys(
+						Some(_) => Command::ExportKeys(
 							match app.tab {
 								Tab::Keys(key_type) => key_type,
 								_ => KeyType::Public,
 							},
-							vec![selected_key.get_id()],
+							app.get_selected_keys(),
 							false,
+							ExportDestination::File,
 						),
-						None => Command::ShowOutput(
-							OutputType::Failure,
-							String::from("invalid selection"),
-						),
+						None => get_selection_warning(app),
 					}
 				}
 			}
@@ -236,33 +768,10 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 				(!app.gpgme.config.armor).to_string(),
 			),
 			Key::Char('n') | Key::Char('N') => {
-				if app.prompt.command.is_some() {
-					app.prompt.clear();
-					Command::None
-				} else {
-					Command::SwitchMode(Mode::Normal)
-				}
-			}
-			Key::Char('1') => {
-				if app.mode == Mode::Copy {
-					Command::Copy(Selection::TableRow(1))
-				} else {
-					Command::Set(
-						String::from("detail"),
-						String::from("minimum"),
-					)
-				}
-			}
-			Key::Char('2') => {
-				if app.mode == Mode::Copy {
-					Command::Copy(Selection::TableRow(2))
-				} else {
-					Command::Set(
-						String::from("detail"),
-						String::from("standard"),
-					)
-				}
+				Command::SwitchMode(Mode::Normal)
 			}
+			Key::Char('1') => Command::Copy(Selection::TableRow(1)),
+			Key::Char('2') => Command::Copy(Selection::TableRow(2)),
 			Key::Char('3') => {
 				Command::Set(String::from("detail"), String::from("full"))
 			}
@@ -287,39 +796,50 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 				}
 			}
 			Key::Char('u') | Key::Char('U') => {
-				if app.mode == Mode::Copy {
+				if key_event.modifiers == Modifiers::CONTROL {
+					Command::Scroll(
+						ScrollDirection::Up(get_half_page_size(app)),
+						false,
+					)
+				} else if app.mode == Mode::Copy {
 					Command::Copy(Selection::KeyUserId)
 				} else {
 					match app.keys_table.selected() {
 						Some(selected_key) => Command::Confirm(Box::new(
 							Command::SendKey(selected_key.get_id()),
 						)),
-						None => Command::ShowOutput(
-							OutputType::Failure,
-							String::from("invalid selection"),
-						),
+						None => get_selection_warning(app),
 					}
 				}
 			}
-			Key::Char('m') | Key::Char('M') => Command::ToggleTableSize,
-			Key::Char('y') | Key::Char('Y') => {
-				if let Some(command) = &app.prompt.command {
-					command.clone()
-				} else {
-					Command::None
+			Key::Char('m')
+			| Key::Char('\'')
+			| Key::Char('q')
+			| Key::Char('@') => {
+				if let Key::Char(c) = key_event.code {
+					app.state.pending_key = Some((c, amount));
 				}
+				Command::None
+			}
+			Key::Char('M') => Command::ToggleTableSize,
+			Key::Enter
+				if app.state.select.is_none()
+					&& !app.state.show_options
+					&& app.keys_table.selected().is_some()
+					&& matches!(app.tab, Tab::Keys(_)) =>
+			{
+				Command::ShowSubkeys
 			}
 			Key::Char('o') | Key::Char(' ') | Key::Enter => {
-				if let Some(select_type) = app.state.select {
+				if let Some(select_type) = app.state.select.clone() {
 					Command::Copy(select_type)
 				} else if app.state.show_options {
 					app.options.selected().cloned().unwrap_or(Command::None)
-				} else if !app.keys_table.items.is_empty() {
-					Command::ShowOptions
 				} else {
-					Command::None
+					Command::ShowOptions
 				}
 			}
+			Key::Char('.') => Command::Repeat,
 			Key::Char(':') => Command::EnableInput,
 			Key::Char('/') => Command::Search(None),
 			_ => Command::None,
@@ -328,6 +848,66 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 	command
 }
 
+/// Returns the command for showing a warning about the empty selection.
+fn get_selection_warning(app: &App) -> Command {
+	let (output_type, message) = app.get_selection_warning();
+	Command::ShowOutput(output_type, message)
+}
+
+/// Takes the pending count if the given key does not use it and
+/// returns the command for setting the detail level (for `1`-`3`).
+///
+/// This way, a single digit that is not followed by a motion (e.g. `2j`)
+/// is resolved on the next key instead of waiting for the count to expire.
+fn take_unused_count(key_event: KeyEvent, app: &mut App) -> Option<Command> {
+	let (count, _) = app.state.count?;
+	let uses_count = match key_event.code {
+		Key::Char(c) if c.is_ascii_digit() => true,
+		Key::Up
+		| Key::Down
+		| Key::Char('k')
+		| Key::Char('K')
+		| Key::Char('j')
+		| Key::Char('J')
+		| Key::PageUp
+		| Key::PageDown
+		| Key::Char('@')
+		| Key::Esc => true,
+		Key::Left
+		| Key::Right
+		| Key::Char('h')
+		| Key::Char('H')
+		| Key::Char('l')
+		| Key::Char('L') => key_event.modifiers == Modifiers::ALT,
+		_ => false,
+	};
+	if uses_count {
+		None
+	} else {
+		app.state.count = None;
+		Command::from_count(count)
+	}
+}
+
+/// Returns the number of items that fit in a page of the current widget.
+fn get_page_size(app: &App) -> u16 {
+	let page_size = if app.state.show_output {
+		app.command_output.page_size
+	} else if app.state.show_options {
+		app.options.page_size
+	} else if app.tab == Tab::Help {
+		app.key_bindings.page_size
+	} else {
+		app.keys_table.state.page_size
+	};
+	u16::try_from(page_size).unwrap_or(u16::MAX).max(1)
+}
+
+/// Returns the half of the page size for the current widget.
+fn get_half_page_size(app: &App) -> u16 {
+	(get_page_size(app) / 2).max(1)
+}
+
 /// Handles the execution of an application command.
 ///
 /// It checks the additional conditions for determining
@@ -337,7 +917,7 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Command {
 ///
 /// [`Tui`]: Tui
 /// [`paused`]: Tui::paused
-fn handle_command_execution<B: Backend>(
+pub fn handle_command_execution<B: Backend>(
 	mut command: Command,
 	tui: &mut Tui<B>,
 	app: &mut App,
@@ -345,9 +925,22 @@ fn handle_command_execution<B: Backend>(
 	if app.state.show_splash && command != Command::Quit {
 		command = Command::None;
 	}
+	if command == Command::Repeat {
+		command = app.get_repeat_command();
+	}
 	if let Tab::Help = app.tab {
 		match command {
 			Command::ShowOptions
+			| Command::ShowMessages
+			| Command::ShowLegend
+			| Command::ShowError
+			| Command::ShowCommandOutput
+			| Command::ShowAgent
+			| Command::ShowStats
+			| Command::ShowGroups
+			| Command::SetGroup(_, _)
+			| Command::ManageDaemon(_, _)
+			| Command::ChangePin(_)
 			| Command::Scroll(_, _)
 			| Command::ListKeys(_)
 			| Command::SwitchMode(_)
@@ -355,7 +948,10 @@ fn handle_command_execution<B: Backend>(
 			| Command::EnableInput
 			| Command::NextTab
 			| Command::PreviousTab
+			| Command::RecordMacro(_)
+			| Command::ReplayMacro(_, _)
 			| Command::Refresh
+			| Command::Cancel
 			| Command::Quit
 			| Command::None => {}
 			Command::Set(ref option, _) => {
@@ -366,6 +962,16 @@ fn handle_command_execution<B: Backend>(
 			_ => command = Command::None,
 		}
 	}
+	if let Command::ReplayMacro(register, count) = command {
+		if let Some(key_events) = app.macros.get(register) {
+			return replay_macro(key_events, count, tui, app);
+		}
+	}
+	if let Command::RunScript(ref name, ref args) = command {
+		if app.scripts.contains(name) {
+			return run_script(name, args, tui, app);
+		}
+	}
 	let mut toggle_pause = false;
 	match command {
 		Command::SwitchMode(Mode::Normal) | Command::Refresh => {
@@ -386,21 +992,60 @@ fn handle_command_execution<B: Backend>(
 					Ok(files) => {
 						command = Command::ImportKeys(files, false);
 					}
-					Err(e) => eprintln!("{:?}", e),
+					Err(e) => {
+						log::debug!("{:?}", e);
+						command = Command::BrowseFiles(String::from("import"));
+					}
 				}
 			}
 		}
-		Command::ExportKeys(_, _, _)
+		Command::ExportKeys(_, _, false, destination)
+			if destination != ExportDestination::File
+				&& !app.gpgme.config.armor =>
+		{
+			command = Command::ShowOutput(
+				OutputType::Warning,
+				format!(
+					"enable armored output for exporting to the {}",
+					destination
+				),
+			);
+		}
+		Command::ExportKeys(KeyType::Secret, _, _, _)
+		| Command::ExportSymmetric(_)
+		| Command::ExportKeys(_, _, true, ExportDestination::File)
 		| Command::DeleteKey(_, _)
-		| Command::GenerateKey
-		| Command::RefreshKeys
-		| Command::EditKey(_)
-		| Command::SignKey(_)
-		| Command::ImportKeys(_, true) => {
+		| Command::Undo
+		| Command::AddUserId(_, _)
+		| Command::AddSubkey(_, _, _, _)
+		| Command::RevokeUserId(_, _)
+		| Command::SetPrimaryUserId(_, _)
+		| Command::ChangePassphrase(_)
+		| Command::SetExpire(_, Some(_))
+		| Command::SetKeyserver(_, _)
+		| Command::SetPrefs(_, _)
+		| Command::AddRevoker(_, _)
+		| Command::Rotate(RotationAction::Run)
+		| Command::SetSubkeyExpire(_, _, Some(_))
+		| Command::RevokeSubkey(_, _)
+		| Command::KeyToCard(_, _, _)
+		| Command::ChangePin(_)
+		| Command::RevokeCertification(_)
+		| Command::RevokeSig(_, _)
+		| Command::EditConfig(_)
+		| Command::SignKey(_, _) => {
 			tui.toggle_pause()?;
 			toggle_pause = true;
 		}
-		Command::Copy(Selection::Key) => {
+		Command::ExportSubkey(_, _)
+			if app.tab == Tab::Keys(KeyType::Secret) =>
+		{
+			tui.toggle_pause()?;
+			toggle_pause = true;
+		}
+		Command::Copy(Selection::Key)
+		| Command::Copy(Selection::KeyMarkdown)
+		| Command::Copy(Selection::KeyHtml) => {
 			if app.gpgme.config.armor {
 				tui.toggle_pause()?;
 				toggle_pause = true;
@@ -416,6 +1061,7 @@ fn handle_command_execution<B: Backend>(
 		_ => {}
 	}
 	app.run_command(command)?;
+	app.load_key_details(app.state.split_view)?;
 	if toggle_pause {
 		tui.toggle_pause()?;
 	}
@@ -427,6 +1073,7 @@ fn handle_command_execution<B: Backend>(
 mod tests {
 	use super::*;
 	use crate::app::command::Command;
+	use crate::app::worker::TaskOutput;
 	use crate::args::Args;
 	use crate::gpg::config::GpgConfig;
 	use crate::gpg::context::GpgContext;
@@ -446,14 +1093,15 @@ mod tests {
 		let config = GpgConfig::new(&args)?;
 		let mut context = GpgContext::new(config)?;
 		let mut app = App::new(&mut context, &args)?;
-		let key_id = app.gpgme.get_all_keys()?.get(&KeyType::Public).unwrap()
-			[0]
-		.get_id();
+		app.wait_for_keys()?;
+		let key_id =
+			app.gpgme.get_all_keys(None)?.get(&KeyType::Public).unwrap()[0]
+				.get_id();
 		let test_cases = vec![
 			(
 				Command::Confirm(Box::new(Command::DeleteKey(
 					KeyType::Public,
-					key_id.to_string(),
+					vec![key_id.to_string()],
 				))),
 				vec![
 					KeyEvent::new(Key::Char('d'), Modifiers::NONE),
@@ -471,6 +1119,7 @@ mod tests {
 					KeyType::Public,
 					vec![key_id.to_string()],
 					false,
+					ExportDestination::File,
 				),
 				vec![KeyEvent::new(Key::Char('x'), Modifiers::NONE)],
 			),
@@ -479,21 +1128,28 @@ mod tests {
 				vec![KeyEvent::new(Key::Char('e'), Modifiers::NONE)],
 			),
 			(
-				Command::SignKey(key_id),
+				Command::SignKey(vec![key_id], Vec::new()),
 				vec![KeyEvent::new(Key::Char('s'), Modifiers::NONE)],
 			),
 			(
 				Command::ShowHelp,
 				vec![KeyEvent::new(Key::Char('?'), Modifiers::NONE)],
 			),
+			(
+				Command::ShowError,
+				vec![KeyEvent::new(Key::Char('!'), Modifiers::NONE)],
+			),
 			(
 				Command::ShowOptions,
 				vec![
 					KeyEvent::new(Key::Char('o'), Modifiers::NONE),
 					KeyEvent::new(Key::Char(' '), Modifiers::NONE),
-					KeyEvent::new(Key::Enter, Modifiers::NONE),
 				],
 			),
+			(
+				Command::ShowSubkeys,
+				vec![KeyEvent::new(Key::Enter, Modifiers::NONE)],
+			),
 			(
 				Command::GenerateKey,
 				vec![KeyEvent::new(Key::Char('g'), Modifiers::NONE)],
@@ -515,7 +1171,6 @@ mod tests {
 				vec![
 					KeyEvent::new(Key::Up, Modifiers::CONTROL),
 					KeyEvent::new(Key::Char('k'), Modifiers::CONTROL),
-					KeyEvent::new(Key::PageUp, Modifiers::NONE),
 				],
 			),
 			(
@@ -523,6 +1178,8 @@ mod tests {
 				vec![
 					KeyEvent::new(Key::Up, Modifiers::NONE),
 					KeyEvent::new(Key::Char('k'), Modifiers::NONE),
+					KeyEvent::new(Key::Char('u'), Modifiers::CONTROL),
+					KeyEvent::new(Key::PageUp, Modifiers::NONE),
 				],
 			),
 			(
@@ -537,7 +1194,6 @@ mod tests {
 				vec![
 					KeyEvent::new(Key::Down, Modifiers::CONTROL),
 					KeyEvent::new(Key::Char('j'), Modifiers::CONTROL),
-					KeyEvent::new(Key::PageDown, Modifiers::NONE),
 				],
 			),
 			(
@@ -545,6 +1201,8 @@ mod tests {
 				vec![
 					KeyEvent::new(Key::Down, Modifiers::NONE),
 					KeyEvent::new(Key::Char('j'), Modifiers::NONE),
+					KeyEvent::new(Key::Char('d'), Modifiers::CONTROL),
+					KeyEvent::new(Key::PageDown, Modifiers::NONE),
 				],
 			),
 			(
@@ -566,18 +1224,6 @@ mod tests {
 				Command::Set(String::from("armor"), String::from("true")),
 				vec![KeyEvent::new(Key::Char('a'), Modifiers::NONE)],
 			),
-			(
-				Command::Set(String::from("detail"), String::from("minimum")),
-				vec![KeyEvent::new(Key::Char('1'), Modifiers::NONE)],
-			),
-			(
-				Command::Set(String::from("detail"), String::from("standard")),
-				vec![KeyEvent::new(Key::Char('2'), Modifiers::NONE)],
-			),
-			(
-				Command::Set(String::from("detail"), String::from("full")),
-				vec![KeyEvent::new(Key::Char('3'), Modifiers::NONE)],
-			),
 			(
 				Command::Set(String::from("prompt"), String::from(":import ")),
 				vec![KeyEvent::new(Key::Char('i'), Modifiers::NONE)],
@@ -588,7 +1234,7 @@ mod tests {
 			),
 			(
 				Command::ToggleTableSize,
-				vec![KeyEvent::new(Key::Char('m'), Modifiers::NONE)],
+				vec![KeyEvent::new(Key::Char('M'), Modifiers::NONE)],
 			),
 			(
 				Command::SwitchMode(Mode::Normal),
@@ -610,6 +1256,10 @@ mod tests {
 				Command::Paste,
 				vec![KeyEvent::new(Key::Char('p'), Modifiers::NONE)],
 			),
+			(
+				Command::Repeat,
+				vec![KeyEvent::new(Key::Char('.'), Modifiers::NONE)],
+			),
 			(
 				Command::EnableInput,
 				vec![KeyEvent::new(Key::Char(':'), Modifiers::CONTROL)],
@@ -642,9 +1292,8 @@ mod tests {
 			(
 				Command::Quit,
 				vec![
-					KeyEvent::new(Key::Char('q'), Modifiers::NONE),
+					KeyEvent::new(Key::Char('Q'), Modifiers::NONE),
 					KeyEvent::new(Key::Esc, Modifiers::NONE),
-					KeyEvent::new(Key::Char('d'), Modifiers::CONTROL),
 					KeyEvent::new(Key::Char('c'), Modifiers::CONTROL),
 				],
 			),
@@ -662,6 +1311,128 @@ mod tests {
 				assert_eq!(command, handle_key_event(key_event, &mut app));
 			}
 		}
+		for c in String::from("12").chars() {
+			assert_eq!(
+				Command::None,
+				handle_key_event(
+					KeyEvent::new(Key::Char(c), Modifiers::NONE),
+					&mut app,
+				)
+			);
+		}
+		assert_eq!(
+			Command::Scroll(ScrollDirection::Down(12), false),
+			handle_key_event(
+				KeyEvent::new(Key::Char('j'), Modifiers::NONE),
+				&mut app,
+			)
+		);
+		assert_eq!(None, app.state.count);
+		for (count, key) in vec![
+			(1, KeyEvent::new(Key::Char('n'), Modifiers::NONE)),
+			(2, KeyEvent::new(Key::Tab, Modifiers::NONE)),
+			(3, KeyEvent::new(Key::Char('l'), Modifiers::NONE)),
+		] {
+			handle_key_event(
+				KeyEvent::new(
+					Key::Char(std::char::from_digit(count, 10).unwrap()),
+					Modifiers::NONE,
+				),
+				&mut app,
+			);
+			assert_eq!(
+				Some(Command::Set(String::from("detail"), count.to_string())),
+				take_unused_count(key, &mut app)
+			);
+			assert_eq!(None, app.state.count);
+		}
+		for key in vec![
+			KeyEvent::new(Key::Char('j'), Modifiers::NONE),
+			KeyEvent::new(Key::Char('5'), Modifiers::NONE),
+			KeyEvent::new(Key::Char('l'), Modifiers::ALT),
+		] {
+			handle_key_event(
+				KeyEvent::new(Key::Char('2'), Modifiers::NONE),
+				&mut app,
+			);
+			assert_eq!(None, take_unused_count(key, &mut app));
+			handle_key_event(key, &mut app);
+			app.state.count = None;
+		}
+		for (command, key) in vec![
+			(Command::SetMark('a'), 'm'),
+			(Command::JumpToMark('a'), '\''),
+			(Command::RecordMacro(Some('a')), 'q'),
+			(Command::ReplayMacro('a', 1), '@'),
+		] {
+			handle_key_event(
+				KeyEvent::new(Key::Char(key), Modifiers::NONE),
+				&mut app,
+			);
+			assert_eq!(
+				command,
+				handle_key_event(
+					KeyEvent::new(Key::Char('a'), Modifiers::NONE),
+					&mut app,
+				)
+			);
+		}
+		for c in String::from("3@").chars() {
+			handle_key_event(
+				KeyEvent::new(Key::Char(c), Modifiers::NONE),
+				&mut app,
+			);
+		}
+		assert_eq!(
+			Command::ReplayMacro('a', 3),
+			handle_key_event(
+				KeyEvent::new(Key::Char('a'), Modifiers::NONE),
+				&mut app,
+			)
+		);
+		app.run_command(Command::RecordMacro(Some('a')))?;
+		assert_eq!(
+			Command::RecordMacro(None),
+			handle_key_event(
+				KeyEvent::new(Key::Char('q'), Modifiers::NONE),
+				&mut app,
+			)
+		);
+		app.run_command(Command::RecordMacro(None))?;
+		app.run_command(Command::SwitchMode(Mode::Visual))?;
+		app.run_command(Command::Scroll(ScrollDirection::Down(1), false))?;
+		assert_eq!(
+			Command::ExportKeys(
+				KeyType::Public,
+				app.get_selected_keys(),
+				false,
+				ExportDestination::File
+			),
+			handle_key_event(
+				KeyEvent::new(Key::Char('x'), Modifiers::NONE),
+				&mut app,
+			)
+		);
+		app.run_command(Command::SwitchMode(Mode::Normal))?;
+		assert_eq!(None, app.keys_table.state.anchor);
+		app.worker.spawn("test", |handle| {
+			while !handle.is_cancelled() {
+				std::thread::sleep(std::time::Duration::from_millis(10));
+			}
+			TaskOutput::new(OutputType::None, String::new(), false)
+		});
+		for key_event in vec![
+			KeyEvent::new(Key::Esc, Modifiers::NONE),
+			KeyEvent::new(Key::Char('c'), Modifiers::CONTROL),
+		] {
+			assert_eq!(Command::Cancel, handle_key_event(key_event, &mut app));
+		}
+		app.run_command(Command::Cancel)?;
+		assert!(app.worker.is_running());
+		while app.worker.poll().is_none() {
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+		assert!(!app.worker.is_running());
 		app.prompt.enable_command_input();
 		handle_key_event(KeyEvent::new(Key::Esc, Modifiers::NONE), &mut app);
 		assert!(!app.prompt.is_enabled());
@@ -677,7 +1448,7 @@ mod tests {
 			KeyEvent::new(Key::Backspace, Modifiers::NONE),
 			&mut app,
 		);
-		assert_eq!(":normal", app.prompt.text);
+		assert_eq!(":normal", app.prompt.text.value);
 		assert_eq!(
 			Command::SwitchMode(Mode::Normal),
 			handle_key_event(
@@ -688,7 +1459,7 @@ mod tests {
 		app.prompt.enable_command_input();
 		handle_key_event(KeyEvent::new(Key::Down, Modifiers::NONE), &mut app);
 		handle_key_event(KeyEvent::new(Key::Up, Modifiers::NONE), &mut app);
-		assert_eq!(":normal", app.prompt.text);
+		assert_eq!(":normal", app.prompt.text.value);
 		Ok(())
 	}
 }
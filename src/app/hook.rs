@@ -0,0 +1,90 @@
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Names of the hooks that can be configured.
+pub const HOOKS: &[&str] =
+	&["post-import", "post-generate", "post-export", "post-delete"];
+
+/// Returns the given value quoted for the shell.
+fn quote(value: &str) -> String {
+	format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Returns the shell command of the hook with the
+/// `{fingerprints}` and `{paths}` fields replaced.
+pub fn format_hook(
+	hook: &str,
+	fingerprints: &[String],
+	paths: &[String],
+) -> String {
+	hook.replace(
+		"{fingerprints}",
+		&fingerprints
+			.iter()
+			.map(|fingerprint| quote(fingerprint))
+			.collect::<Vec<String>>()
+			.join(" "),
+	)
+	.replace(
+		"{paths}",
+		&paths
+			.iter()
+			.map(|path| quote(path))
+			.collect::<Vec<String>>()
+			.join(" "),
+	)
+}
+
+/// Runs the given hook via the shell.
+///
+/// It is run in a separate thread to not block the interface
+/// and its result is only logged.
+pub fn run_hook(
+	name: &str,
+	hook: &str,
+	fingerprints: &[String],
+	paths: &[String],
+) {
+	let name = name.to_string();
+	let command = format_hook(hook, fingerprints, paths);
+	log::debug!("running {} hook: {}", name, command);
+	thread::spawn(move || {
+		match Command::new("sh")
+			.arg("-c")
+			.arg(&command)
+			.stdin(Stdio::null())
+			.stdout(Stdio::null())
+			.stderr(Stdio::piped())
+			.output()
+		{
+			Ok(output) if output.status.success() => {
+				log::debug!("{} hook exited with {}", name, output.status)
+			}
+			Ok(output) => log::error!(
+				"{} hook exited with {}: {}",
+				name,
+				output.status,
+				String::from_utf8_lossy(&output.stderr).trim()
+			),
+			Err(e) => log::error!("cannot run {} hook: {}", name, e),
+		}
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_hook() {
+		assert_eq!(
+			"sync.sh 'ABC' 'DEF' -- '/tmp/it'\\''s.asc'",
+			format_hook(
+				"sync.sh {fingerprints} -- {paths}",
+				&[String::from("ABC"), String::from("DEF")],
+				&[String::from("/tmp/it's.asc")],
+			)
+		);
+		assert_eq!("true", format_hook("true", &[], &[]));
+	}
+}
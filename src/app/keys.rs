@@ -15,20 +15,33 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
         "#,
 	},
 	KeyBinding {
-		key: "o,space,enter",
+		key: "o,space",
 		action: "show options",
 		description: r#"
         Shows the options menu for the current tab.
         :options
         "#,
 	},
+	KeyBinding {
+		key: "enter",
+		action: "show subkeys",
+		description: r#"
+        Lists the subkeys of the selected key.
+        x: export, e: expire, r: revoke, c: move to card
+        :subkeys
+        :subkey export/expire/revoke <key_id> <subkey>
+        "#,
+	},
 	KeyBinding {
 		key: "hjkl,arrows,pgkeys",
 		action: "navigate",
 		description: r#"
         Scrolls the current widget or selects the next/previous tab.
+        <count><key>: scroll by count (e.g. 5j)
         M-<key>: scroll the table rows
-        C-<key>,pgup,pgdown: scroll to top/bottom
+        C-d,C-u: scroll half a page down/up
+        pgdown,pgup: scroll a page down/up
+        C-<key>: scroll to top/bottom
         :scroll (row) up/down/left/right <amount>
         "#,
 	},
@@ -44,7 +57,9 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
 		key: "v",
 		action: "switch to visual mode",
 		description: r#"
-        Disables the mouse capture.
+        Disables the mouse capture and selects the rows
+        between the current and the moved position.
+        Export, delete and sign the selected keys at once.
         :visual
         "#,
 	},
@@ -56,6 +71,7 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
         i: Copy the key id
         f: Copy the key fingerprint
         u: Copy the user id
+        s: Copy the SSH key
         1,2: Copy the content of the row
         :copy
         "#,
@@ -142,6 +158,15 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
         :refresh keys
         "#,
 	},
+	KeyBinding {
+		key: "w",
+		action: "jump to expiring key",
+		description: r#"
+        Selects the next secret key that is about to expire.
+        The warning period can be changed via `:set expiry-warn <days>`
+        :expiring
+        "#,
+	},
 	KeyBinding {
 		key: "a",
 		action: "toggle armored output",
@@ -158,6 +183,7 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
         1: Minimum
         2: Standard
         3: Full
+        (applied shortly after the key press if no count is given)
         :set detail <level>
         "#,
 	},
@@ -172,10 +198,41 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
 		description: ":set margin <0/1>",
 	},
 	KeyBinding {
-		key: "m",
+		key: "M",
 		action: "toggle table size",
 		description: ":toggle",
 	},
+	KeyBinding {
+		key: "m,'",
+		action: "set/jump to mark",
+		description: r#"
+        m<name>: Sets a mark on the selected key.
+        '<name>: Selects the marked key.
+        Marks are kept across refreshes.
+        :mark <name>
+        :jump <name>
+        :get marks
+        "#,
+	},
+	KeyBinding {
+		key: "q,@",
+		action: "record/replay macro",
+		description: r#"
+        q<register>: Starts recording the keys into the register.
+        q: Stops recording.
+        <count>@<register>: Replays the recorded keys.
+        :record <register>
+        :replay <register> <count>
+        "#,
+	},
+	KeyBinding {
+		key: ".",
+		action: "repeat the last command",
+		description: r#"
+        Runs the last command again on the selected key(s).
+        :repeat
+        "#,
+	},
 	KeyBinding {
 		key: "C-s",
 		action: "toggle style",
@@ -184,7 +241,27 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
 	KeyBinding {
 		key: "/",
 		action: "search",
-		description: ":search <query>",
+		description: r#"
+        Searches in all the fields by default.
+        name:<query>: Searches in user names.
+        email:<query>: Searches in email addresses.
+        fpr:<query>: Searches in the fingerprint.
+        re:<regex>: Matches with a regular expression.
+        expired/revoked/can=<capability>: Matches the key state.
+        Terms separated by whitespace must all match.
+        !<term>: Negates the term.
+        <terms> | <terms>: Matches either of the terms.
+        :search <query>
+        "#,
+	},
+	KeyBinding {
+		key: "!",
+		action: "show error details",
+		description: r#"
+        Shows the last error along with its causes,
+        GPGME error code/source and the suggested remedies.
+        :error
+        "#,
 	},
 	KeyBinding {
 		key: ":",
@@ -197,9 +274,13 @@ pub const KEY_BINDINGS: &[KeyBinding] = &[
 		description: ":refresh",
 	},
 	KeyBinding {
-		key: "q,C-c/d,escape",
+		key: "Q,C-c,escape",
 		action: "quit application",
-		description: ":quit",
+		description: r#"
+        Cancels the running task (if any) with C-c/escape.
+        :cancel
+        :quit
+        "#,
 	},
 ];
 
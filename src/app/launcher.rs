@@ -1,32 +1,89 @@
+use crate::app::clipboard::{self, ClipboardBackend};
 use crate::app::command::Command;
+use crate::app::export::ExportDestination;
+use crate::app::filter::Filter;
+use crate::app::hook;
 use crate::app::keys::{KeyBinding, KEY_BINDINGS};
+use crate::app::loader::KeyLoader;
+use crate::app::macros::MacroRecorder;
+use crate::app::menu::MenuLayout;
 use crate::app::mode::Mode;
-use crate::app::prompt::{OutputType, Prompt, COMMAND_PREFIX, SEARCH_PREFIX};
-use crate::app::selection::Selection;
+use crate::app::prompt::{
+	ConfirmLevel, OutputType, Prompt, COMMAND_PREFIX, SEARCH_PREFIX,
+};
+use crate::app::rotate::{self, KeyRotation, RotationAction, RotationStep};
+use crate::app::script::{self, ScriptContext, Scripts};
+use crate::app::search::Query;
+use crate::app::selection::{self, Selection};
+use crate::app::session::Session;
 use crate::app::splash::SplashScreen;
 use crate::app::state::State;
+use crate::app::statusline;
+use crate::app::style;
 use crate::app::tab::Tab;
+use crate::app::util;
+use crate::app::watch::{KeyringWatcher, WatchMode};
+use crate::app::worker::{Progress, TaskHandle, TaskOutput, Worker};
 use crate::args::Args;
-use crate::gpg::context::GpgContext;
-use crate::gpg::key::{GpgKey, KeyDetail, KeyType};
+use crate::gpg::agent::{self, Component};
+use crate::gpg::cache::KeyCache;
+use crate::gpg::card::{self, CardPin};
+use crate::gpg::cert::Certification;
+use crate::gpg::config::{GpgConfig, TempHomeDir};
+use crate::gpg::context::{GpgContext, DEFAULT_EXPORT_NAME};
+use crate::gpg::doctor::KeyringIssue;
+use crate::gpg::edit::{self, OwnerTrust};
+use crate::gpg::fingerprint::FingerprintFormat;
+use crate::gpg::flood;
+use crate::gpg::group::RecipientGroup;
+use crate::gpg::handler;
+use crate::gpg::key::{ColumnLayout, GpgKey, KeyDetail, KeyType};
+use crate::gpg::keyserver;
+use crate::gpg::locate::{self, LocateMechanism};
+use crate::gpg::prefs::{self, KeyPreferences};
+use crate::gpg::stats::KeyringStats;
+use crate::gpg::wot::{self, TrustGraph};
+use crate::widget::checklist::CheckList;
+use crate::widget::datepicker::{self, DatePicker};
+use crate::widget::dialog::Dialog;
+use crate::widget::filepicker::FilePicker;
+use crate::widget::input::Input;
 use crate::widget::list::StatefulList;
 use crate::widget::row::ScrollDirection;
 use crate::widget::style::Color as WidgetColor;
-use crate::widget::table::{StatefulTable, TableSize, TableState};
+use crate::widget::table::{self, StatefulTable, TableSize, TableState};
+use crate::widget::tree::{Tree, TreeNode};
 use anyhow::{anyhow, Error as AnyhowError, Result};
+use chrono::Local;
 use colorsys::Rgb;
 use copypasta_ext::prelude::ClipboardProvider;
-use copypasta_ext::x11_fork::ClipboardContext;
+use gpgme::{CreateKeyFlags, TofuPolicy};
+use std::cmp;
 use std::collections::HashMap;
-use std::path::Path;
+use std::env;
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
 use std::process::Command as OsCommand;
 use std::str;
 use std::str::FromStr;
-use std::time::Instant;
-use tui::style::Color;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use unicode_width::UnicodeWidthStr;
 
 /// Max duration of prompt messages.
-const MESSAGE_DURATION: u128 = 1750;
+pub const MESSAGE_DURATION: u128 = 1750;
+
+/// Max duration of waiting for the command after a count prefix.
+const COUNT_DURATION: u128 = 500;
+
+/// Duration of waiting for the next keystroke before searching.
+const SEARCH_DEBOUNCE: u128 = 150;
+
+/// Extensions of the files that are listed for importing.
+const IMPORT_EXTENSIONS: &[&str] = &["asc", "gpg", "pgp", "key"];
 
 /// Main application.
 ///
@@ -43,22 +100,95 @@ pub struct App<'a> {
 	pub tab: Tab,
 	/// Content of the options menu.
 	pub options: StatefulList<Command>,
+	/// Customization of the options menu.
+	pub menu_layout: MenuLayout,
+	/// Templates for copying the key information.
+	pub copy_templates: HashMap<String, String>,
+	/// Shell commands to run after the actions.
+	pub hooks: HashMap<String, String>,
+	/// Captured output of the last external command.
+	pub command_output: StatefulList<String>,
 	/// Splash screen of the application.
 	pub splash_screen: SplashScreen,
 	/// Content of the key bindings list.
 	pub key_bindings: StatefulList<KeyBinding<'a>>,
 	/// Public/secret keys.
-	pub keys: HashMap<KeyType, Vec<GpgKey>>,
+	pub keys: HashMap<KeyType, Vec<Arc<GpgKey>>>,
 	/// Table of public/secret keys.
-	pub keys_table: StatefulTable<GpgKey>,
+	pub keys_table: StatefulTable<Arc<GpgKey>>,
 	/// States of the keys table.
 	pub keys_table_states: HashMap<KeyType, TableState>,
 	/// Level of detail to show for keys table.
 	pub keys_table_detail: KeyDetail,
+	/// Levels of detail that are set for the individual keys.
+	///
+	/// Keys are identified by their fingerprints.
+	pub keys_table_details: HashMap<String, KeyDetail>,
 	/// Bottom margin value of the keys table.
 	pub keys_table_margin: u16,
+	/// Filter of the keys table.
+	pub keys_table_filter: Option<Filter>,
+	/// Last applied search query.
+	pub search_query: Option<String>,
+	/// Structure of the selected key.
+	pub key_tree: Tree,
+	/// Identifier of the key that the tree is built for.
+	key_tree_id: String,
+	/// Exported files of the deleted keys (for undo).
+	pub trash: Vec<Vec<String>>,
+	/// Directory of the exported files that is removed on exit.
+	trash_dir: Option<TempHomeDir>,
+	/// Marked keys (fingerprints) by their names.
+	pub marks: HashMap<char, String>,
+	/// Recorded key macros.
+	pub macros: MacroRecorder,
+	/// Scripts that add new commands.
+	pub scripts: Scripts,
+	/// Last repeatable command along with the selected keys.
+	pub last_command: Option<(Command, Vec<String>)>,
+	/// Information about gpg-agent and the other components.
+	pub agent_components: Vec<Component>,
+	/// Certification paths to the selected key (as a tree).
+	pub trust_paths: Vec<String>,
+	/// Statistics about the keyring.
+	pub keyring_stats: KeyringStats,
+	/// Issues that are found in the last keyring check.
+	pub keyring_issues: StatefulList<KeyringIssue>,
+	/// Certifications that are made by the default key.
+	pub certifications: StatefulList<Certification>,
+	/// Subkeys of the selected key (label lines).
+	pub subkeys: StatefulList<Vec<String>>,
+	/// Recipient groups that are defined in `gpg.conf`.
+	pub groups: StatefulList<RecipientGroup>,
+	/// Preferences of the detailed keys by their fingerprints.
+	pub key_preferences: HashMap<String, KeyPreferences>,
+	/// Current preferences and the presets of the selected key.
+	pub preferences: StatefulList<Vec<String>>,
+	/// Key rotation that is in progress.
+	pub rotation: Option<KeyRotation>,
+	/// Modal dialog that is shown on top of the interface.
+	pub dialog: Option<Dialog<Command>>,
+	/// File picker along with its target (e.g. "import").
+	pub file_picker: Option<(String, FilePicker)>,
+	/// Recipient picker along with the file to encrypt.
+	pub recipient_picker: Option<(String, CheckList<String>)>,
+	/// Date picker along with the command to run with the selected date.
+	pub date_picker: Option<(Command, DatePicker)>,
+	/// Snapshot of the keyring that is shown until the keys are loaded.
+	pub key_cache: Option<KeyCache>,
+	/// Loader of the keys in the background.
+	pub key_loader: Option<KeyLoader>,
+	/// Watcher of the keyring files for external changes.
+	pub keyring_watcher: KeyringWatcher,
+	/// Signing key that is configured for git.
+	pub git_signing_key: Option<String>,
+	/// Executor of the long-running tasks.
+	pub worker: Worker,
 	/// Clipboard context.
-	pub clipboard: Option<ClipboardContext>,
+	pub clipboard: Option<Box<dyn ClipboardProvider>>,
+	/// Copy time and hash of the sensitive clipboard content
+	/// that will be cleared.
+	pub clipboard_clear: Option<(Instant, u64)>,
 	/// GPGME context.
 	pub gpgme: &'a mut GpgContext,
 }
@@ -66,19 +196,27 @@ pub struct App<'a> {
 impl<'a> App<'a> {
 	/// Constructs a new instance of `App`.
 	pub fn new(gpgme: &'a mut GpgContext, args: &'a Args) -> Result<Self> {
-		let keys = gpgme.get_all_keys()?;
-		let keys_table = StatefulTable::with_items(
-			keys.get(&KeyType::Public)
-				.expect("failed to get public keys")
-				.to_vec(),
-		);
 		let state = State::from(args);
-		Ok(Self {
+		let key_cache = if state.cache
+			&& state.select.is_none()
+			&& args.focus.is_none()
+			&& !args.ephemeral
+		{
+			KeyCache::load(&gpgme.config.home_dir)
+				.map_err(|e| log::debug!("key cache is not loaded: {}", e))
+				.ok()
+		} else {
+			None
+		};
+		let mut keys = HashMap::new();
+		keys.insert(KeyType::Public, Vec::new());
+		keys.insert(KeyType::Secret, Vec::new());
+		let mut app = Self {
 			mode: Mode::Normal,
 			prompt: if state.select.is_some() {
 				Prompt {
 					output_type: OutputType::Action,
-					text: String::from("-- select --"),
+					text: Input::new("-- select --"),
 					clock: Some(Instant::now()),
 					..Prompt::default()
 				}
@@ -88,383 +226,4228 @@ impl<'a> App<'a> {
 			state,
 			tab: Tab::Keys(KeyType::Public),
 			options: StatefulList::with_items(Vec::new()),
+			menu_layout: args.menu.clone(),
+			copy_templates: args.copy_templates.clone(),
+			hooks: args.hooks.clone(),
+			command_output: StatefulList::with_items(Vec::new()),
 			splash_screen: SplashScreen::new("splash.jpg", 12)?,
 			key_bindings: StatefulList::with_items(KEY_BINDINGS.to_vec()),
 			keys,
-			keys_table,
+			keys_table: StatefulTable::with_items(Vec::new()),
 			keys_table_states: HashMap::new(),
 			keys_table_detail: KeyDetail::Minimum,
+			keys_table_details: HashMap::new(),
 			keys_table_margin: 1,
-			clipboard: match ClipboardContext::new() {
+			keys_table_filter: None,
+			search_query: None,
+			key_tree: Tree::default(),
+			key_tree_id: String::new(),
+			trash: Vec::new(),
+			trash_dir: None,
+			marks: HashMap::new(),
+			macros: MacroRecorder::default(),
+			scripts: script::get_scripts_dir(args.config.as_deref())
+				.filter(|dir| dir.is_dir())
+				.map(|dir| {
+					Scripts::load(&dir).unwrap_or_else(|e| {
+						log::error!("failed to load the scripts: {}", e);
+						Scripts::default()
+					})
+				})
+				.unwrap_or_default(),
+			last_command: None,
+			agent_components: Vec::new(),
+			trust_paths: Vec::new(),
+			keyring_stats: KeyringStats::default(),
+			keyring_issues: StatefulList::with_items(Vec::new()),
+			certifications: StatefulList::with_items(Vec::new()),
+			subkeys: StatefulList::with_items(Vec::new()),
+			groups: StatefulList::with_items(Vec::new()),
+			key_preferences: HashMap::new(),
+			preferences: StatefulList::with_items(Vec::new()),
+			rotation: None,
+			dialog: None,
+			file_picker: None,
+			recipient_picker: None,
+			date_picker: None,
+			key_cache,
+			key_loader: Some(KeyLoader::spawn(gpgme.config.clone())),
+			keyring_watcher: KeyringWatcher::new(&gpgme.config.home_dir),
+			git_signing_key: util::get_git_signing_key().unwrap_or_default(),
+			worker: Worker::default(),
+			clipboard: match args
+				.clipboard
+				.unwrap_or(ClipboardBackend::Auto)
+				.get_context()
+			{
 				Ok(clipboard) => Some(clipboard),
 				Err(e) => {
 					eprintln!("failed to initialize clipboard: {:?}", e);
 					None
 				}
 			},
+			clipboard_clear: None,
 			gpgme,
-		})
+		};
+		for name in app
+			.hooks
+			.keys()
+			.filter(|name| !hook::HOOKS.contains(&name.as_str()))
+		{
+			log::warn!("unknown hook: {}", name);
+		}
+		if let Some(query) = &args.focus {
+			app.wait_for_keys()?;
+			if !app.focus_key(query)? {
+				app.prompt.set_output((
+					OutputType::Failure,
+					format!("no keys found: {}", query),
+				));
+			}
+		}
+		Ok(app)
+	}
+
+	/// Wraps the given keys in [`Arc`] for sharing them
+	/// between the keys table and the tabs without copying.
+	fn share_keys(
+		keys: HashMap<KeyType, Vec<GpgKey>>,
+	) -> HashMap<KeyType, Vec<Arc<GpgKey>>> {
+		keys.into_iter()
+			.map(|(key_type, keys)| {
+				(key_type, keys.into_iter().map(Arc::new).collect())
+			})
+			.collect()
+	}
+
+	/// Waits for the keys that are loaded in the background.
+	pub fn wait_for_keys(&mut self) -> Result<()> {
+		if let Some(mut loader) = self.key_loader.take() {
+			let keys = loader.wait()?;
+			self.load_keys(keys)?;
+		}
+		Ok(())
+	}
+
+	/// Replaces the keys with the keys that are loaded in the
+	/// background and shows the differences from the cached
+	/// keys (if any).
+	fn load_keys(&mut self, keys: HashMap<KeyType, Vec<GpgKey>>) -> Result<()> {
+		self.keys = Self::share_keys(keys);
+		self.keys_table_states.clear();
+		self.keyring_watcher.reset();
+		if let Tab::Keys(key_type) = self.tab {
+			self.keys_table = StatefulTable::with_items(
+				self.keys
+					.get(&key_type)
+					.ok_or_else(|| anyhow!("failed to get {} keys", key_type))?
+					.to_vec(),
+			);
+		}
+		let cache = KeyCache::new(&self.gpgme.config.home_dir, &self.keys);
+		let changes = self.key_cache.take().map(|v| v.get_changes(&cache));
+		self.save_key_cache();
+		if self.is_keyring_empty() && self.state.select.is_none() {
+			self.show_welcome();
+			return Ok(());
+		}
+		match changes {
+			Some((0, 0, 0)) | None => {
+				if self.state.select.is_none() && !self.warn_default_key() {
+					self.warn_expiring_keys()
+				}
+			}
+			Some((added, removed, changed)) => self.prompt.set_output((
+				OutputType::Action,
+				format!(
+					"keyring is changed: {} added, {} removed, {} changed",
+					added, removed, changed
+				),
+			)),
+		}
+		Ok(())
+	}
+
+	/// Saves the snapshot of the keys for the next startup
+	/// if caching is enabled.
+	fn save_key_cache(&self) {
+		if self.state.cache {
+			if let Err(e) =
+				KeyCache::new(&self.gpgme.config.home_dir, &self.keys).save()
+			{
+				log::error!("failed to save the key cache: {}", e);
+			}
+		}
+	}
+
+	/// Returns the current state of the interface as a session.
+	pub fn get_session(&self) -> Session {
+		Session {
+			home_dir: self.gpgme.config.home_dir.clone(),
+			tab: match self.tab {
+				Tab::Keys(key_type) => key_type.to_string(),
+				Tab::Help => String::from("help"),
+			},
+			selection: self
+				.keys_table
+				.selected()
+				.map(|key| key.get_fingerprint()),
+			search: self.search_query.clone(),
+			filter: self.keys_table_filter.map(|filter| filter.to_string()),
+			detail: self.keys_table_detail.to_string(),
+			table_size: format!("{:?}", self.keys_table.state.size)
+				.to_lowercase(),
+			colored: self.state.colored,
+			split_view: self.state.split_view,
+		}
+	}
+
+	/// Restores the state of the interface from the given session.
+	pub fn restore_session(&mut self, session: Session) -> Result<()> {
+		self.state.colored = session.colored;
+		self.state.split_view = session.split_view;
+		if session.tab == "help" {
+			self.run_command(Command::ShowHelp)?;
+		} else if let Ok(key_type) = KeyType::from_str(&session.tab) {
+			self.run_command(Command::ListKeys(key_type))?;
+		}
+		if let Ok(detail) = KeyDetail::from_str(&session.detail) {
+			self.keys_table_detail = detail;
+			self.keys_table_details.clear();
+		}
+		self.keys_table.state.size = match session.table_size.as_str() {
+			"compact" => TableSize::Compact,
+			"minimized" => TableSize::Minimized,
+			_ => TableSize::Normal,
+		};
+		self.keys_table_filter = session
+			.filter
+			.and_then(|filter| Filter::from_str(&filter).ok());
+		self.keys_table.reset_state();
+		if let Some(query) = session.search {
+			self.run_command(Command::Search(Some(query.to_string())))?;
+			self.state.pending_search = Some(Instant::now());
+			self.apply_search();
+			self.search_query = Some(query);
+		}
+		self.filter_keys_table();
+		if let Some(fingerprint) = session.selection {
+			self.select_fingerprint(Some(fingerprint));
+		}
+		self.prompt.clear();
+		Ok(())
+	}
+
+	/// Returns the secret keys that will expire within
+	/// the configured number of days.
+	///
+	/// Expired and revoked keys are not included.
+	pub fn get_expiring_keys(&self) -> Vec<Arc<GpgKey>> {
+		if self.state.expiry_warn == 0 {
+			return Vec::new();
+		}
+		self.keys
+			.get(&KeyType::Secret)
+			.map(|keys| {
+				keys.iter()
+					.filter(|key| !key.is_expired() && !key.is_revoked())
+					.filter(|key| {
+						matches!(key.get_days_until_expiry(), Some(days)
+							if days < i64::from(self.state.expiry_warn))
+					})
+					.cloned()
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Shows a warning about the secret keys that are about to expire.
+	fn warn_expiring_keys(&mut self) {
+		let expiring_keys = self.get_expiring_keys();
+		let output = match expiring_keys.as_slice() {
+			[] => return,
+			[key] => (
+				OutputType::Warning,
+				format!(
+					"key {} expires in {} day(s), press 'w' to select it",
+					key.get_id(),
+					key.get_days_until_expiry().unwrap_or_default()
+				),
+			),
+			keys => (
+				OutputType::Warning,
+				format!(
+					"{} keys expire within {} days, press 'w' to select them",
+					keys.len(),
+					self.state.expiry_warn
+				),
+			),
+		};
+		if self.state.notify {
+			util::send_notification(&output);
+		}
+		self.prompt.set_output(output);
+	}
+
+	/// Shows a warning if the configured default key
+	/// is not found or it is not usable (e.g. expired).
+	///
+	/// Returns `true` if the warning is shown.
+	fn warn_default_key(&mut self) -> bool {
+		let default_key = match &self.gpgme.config.default_key {
+			Some(default_key) => default_key.to_string(),
+			None => return false,
+		};
+		let output =
+			match self.keys.get(&KeyType::Secret).and_then(|keys| {
+				keys.iter().find(|key| self.is_default_key(key))
+			}) {
+				Some(key) if key.is_expired() => (
+					OutputType::Warning,
+					format!("default key is expired: {}", default_key),
+				),
+				Some(key) if key.is_unusable() => (
+					OutputType::Warning,
+					format!("default key is not usable: {}", default_key),
+				),
+				Some(_) => return false,
+				None => (
+					OutputType::Warning,
+					format!("default key is not found: {}", default_key),
+				),
+			};
+		if self.state.notify {
+			util::send_notification(&output);
+		}
+		self.prompt.set_output(output);
+		true
 	}
 
 	/// Resets the application state.
+	///
+	/// The selected key is kept selected if it still exists,
+	/// otherwise the nearest row is selected.
 	pub fn refresh(&mut self) -> Result<()> {
+		let selected_key =
+			self.keys_table.selected().map(|key| key.get_fingerprint());
+		let selected_index = self.keys_table.state.tui.selected();
 		self.state.refresh();
-		self.mode = Mode::Normal;
+		self.set_mode(Mode::Normal);
 		self.prompt.clear();
 		self.options.state.select(Some(0));
-		self.keys = self.gpgme.get_all_keys()?;
+		self.keys = Self::share_keys(self.gpgme.get_all_keys(None)?);
+		self.key_cache = None;
+		self.key_loader = None;
+		self.keyring_watcher.reset();
+		self.save_key_cache();
+		self.key_preferences.clear();
+		self.git_signing_key = util::get_git_signing_key().unwrap_or_default();
 		self.keys_table_states.clear();
 		self.keys_table_detail = KeyDetail::Minimum;
+		self.keys_table_details.clear();
 		self.keys_table_margin = 1;
+		self.search_query = None;
 		match self.tab {
 			Tab::Keys(key_type) => {
 				self.keys_table = StatefulTable::with_items(
-					self.keys
-						.get(&key_type)
-						.unwrap_or_else(|| {
-							panic!("failed to get {} keys", key_type)
-						})
-						.to_vec(),
-				)
+					self.keys.get(&key_type).cloned().unwrap_or_default(),
+				);
+				if !self.select_fingerprint(selected_key) {
+					self.keys_table.state.tui.select(selected_index.map(|i| {
+						cmp::min(
+							i,
+							self.keys_table.items.len().saturating_sub(1),
+						)
+					}));
+				}
 			}
 			Tab::Help => {}
 		};
 		Ok(())
 	}
 
-	/// Handles the tick event of the application.
+	/// Refreshes the keys that match the given patterns (e.g. key IDs).
 	///
-	/// It is currently used to flush the prompt messages.
-	pub fn tick(&mut self) {
-		if let Some(clock) = self.prompt.clock {
-			if clock.elapsed().as_millis() > MESSAGE_DURATION
-				&& self.prompt.command.is_none()
-			{
-				self.prompt.clear()
+	/// Unlike [`refresh`], only the matching keys are listed again and
+	/// they are patched in place so that the table state is kept.
+	/// Falls back to [`refresh`] if no patterns are given.
+	///
+	/// [`refresh`]: App::refresh
+	pub fn refresh_keys(&mut self, patterns: Vec<String>) -> Result<()> {
+		if patterns.is_empty() || self.key_loader.is_some() {
+			return self.refresh();
+		}
+		let updated_keys =
+			Self::share_keys(self.gpgme.get_all_keys(Some(patterns.clone()))?);
+		let get_fingerprint = |key: &Arc<GpgKey>| key.get_fingerprint();
+		let is_changed = |fingerprint: &str| {
+			patterns.iter().any(|pattern| {
+				fingerprint
+					.ends_with(&pattern.trim_start_matches("0x").to_uppercase())
+			})
+		};
+		for (key_type, keys) in self.keys.iter_mut() {
+			let updated_keys = updated_keys
+				.get(key_type)
+				.map(Vec::as_slice)
+				.unwrap_or_default();
+			let changed = keys
+				.iter()
+				.map(get_fingerprint)
+				.filter(|fingerprint| is_changed(fingerprint))
+				.collect::<Vec<String>>();
+			table::patch_items(keys, &changed, updated_keys, get_fingerprint);
+			if self.tab == Tab::Keys(*key_type) {
+				self.keys_table.patch_items(
+					&changed,
+					updated_keys,
+					get_fingerprint,
+				);
 			}
 		}
+		self.keyring_watcher.reset();
+		self.set_mode(Mode::Normal);
+		self.save_key_cache();
+		Ok(())
 	}
 
-	/// Runs the given command which is used to specify
-	/// the widget to render or action to perform.
-	pub fn run_command(&mut self, command: Command) -> Result<()> {
-		let mut show_options = false;
-		if let Command::Confirm(ref cmd) = command {
-			self.prompt.set_command(*cmd.clone())
-		} else if self.prompt.command.is_some() {
-			self.prompt.clear();
+	/// Loads the details of the keys that are shown in full detail.
+	///
+	/// Keys are listed without their details (e.g. signatures) for
+	/// speed, so they are listed again once they are expanded. The
+	/// selected key is also loaded if `selected` is set.
+	pub fn load_key_details(&mut self, selected: bool) -> Result<()> {
+		let key_type = match self.tab {
+			Tab::Keys(key_type) => key_type,
+			Tab::Help => return Ok(()),
+		};
+		let selected_index = self.keys_table.state.tui.selected();
+		let expanded_keys = self
+			.keys_table
+			.items
+			.iter()
+			.enumerate()
+			.filter(|(i, key)| {
+				self.get_key_detail(key) == KeyDetail::Full
+					|| (selected && Some(*i) == selected_index)
+			})
+			.map(|(_, key)| key)
+			.collect::<Vec<&Arc<GpgKey>>>();
+		let patterns = expanded_keys
+			.iter()
+			.filter(|key| !key.has_details())
+			.map(|key| key.get_fingerprint())
+			.collect::<Vec<String>>();
+		let fingerprints = expanded_keys
+			.iter()
+			.map(|key| key.get_fingerprint())
+			.filter(|v| !self.key_preferences.contains_key(v))
+			.collect::<Vec<String>>();
+		if !patterns.is_empty() {
+			let keys =
+				self.gpgme.get_detailed_keys(key_type, Some(patterns))?;
+			self.set_key_details(key_type, &keys);
 		}
-		match command {
-			Command::ShowHelp => {
-				self.tab = Tab::Help;
-				if self.key_bindings.state.selected().is_none() {
-					self.key_bindings.state.select(Some(0));
+		if !fingerprints.is_empty() {
+			self.load_key_preferences(fingerprints);
+		}
+		Ok(())
+	}
+
+	/// Loads the preferences of the given keys.
+	///
+	/// Keys without any preferences are cached as well
+	/// so that they are not listed again.
+	fn load_key_preferences(&mut self, fingerprints: Vec<String>) {
+		let mut preferences = self
+			.gpgme
+			.get_key_preferences(&fingerprints)
+			.unwrap_or_else(|e| {
+				log::error!("failed to get the key preferences: {}", e);
+				HashMap::new()
+			});
+		for fingerprint in fingerprints {
+			let key_preferences =
+				preferences.remove(&fingerprint).unwrap_or_default();
+			self.key_preferences.insert(fingerprint, key_preferences);
+		}
+	}
+
+	/// Returns the preferences of the given key if they are loaded.
+	pub fn get_key_preferences(&self, key: &GpgKey) -> Option<&KeyPreferences> {
+		self.key_preferences.get(&key.get_fingerprint())
+	}
+
+	/// Returns the preferred keyserver of the given key if it is loaded.
+	pub fn get_preferred_keyserver(&self, key: &GpgKey) -> Option<&str> {
+		self.get_key_preferences(key)
+			.and_then(|v| v.keyserver.as_deref())
+	}
+
+	/// Loads the details of all keys in the keyring.
+	///
+	/// It is required for the commands that go through
+	/// the whole keyring (e.g. checking the web of trust).
+	fn load_all_key_details(&mut self) -> Result<()> {
+		for key_type in &[KeyType::Public, KeyType::Secret] {
+			if self
+				.keys
+				.get(key_type)
+				.map(|keys| keys.iter().all(|key| key.has_details()))
+				.unwrap_or(true)
+			{
+				continue;
+			}
+			let keys = self.gpgme.get_detailed_keys(*key_type, None)?;
+			self.set_key_details(*key_type, &keys);
+		}
+		Ok(())
+	}
+
+	/// Replaces the keys of the given type with their detailed versions.
+	fn set_key_details(&mut self, key_type: KeyType, detailed: &[GpgKey]) {
+		let update = |keys: &mut Vec<Arc<GpgKey>>| {
+			for key in keys.iter_mut() {
+				if let Some(detailed_key) = detailed
+					.iter()
+					.find(|v| v.get_fingerprint() == key.get_fingerprint())
+				{
+					Arc::make_mut(key).set_inner(detailed_key);
 				}
 			}
-			Command::ShowOutput(output_type, message) => {
-				self.prompt.set_output((output_type, message))
+		};
+		if let Some(keys) = self.keys.get_mut(&key_type) {
+			update(keys);
+		}
+		if self.tab == Tab::Keys(key_type) {
+			update(&mut self.keys_table.items);
+			update(&mut self.keys_table.default_items);
+		}
+	}
+
+	/// Selects the key with the given fingerprint in the keys table.
+	///
+	/// Returns `false` if the key does not exist in the table.
+	fn select_fingerprint(&mut self, fingerprint: Option<String>) -> bool {
+		match fingerprint.and_then(|fingerprint| {
+			self.keys_table
+				.items
+				.iter()
+				.position(|key| key.get_fingerprint() == fingerprint)
+		}) {
+			Some(index) => {
+				self.keys_table.state.tui.select(Some(index));
+				true
 			}
-			Command::ShowOptions => {
-				let prev_selection = self.options.state.selected();
-				let prev_item_count = self.options.items.len();
-				self.options = StatefulList::with_items(match self.tab {
-					Tab::Keys(key_type) => {
-						let selected_key = &self
-							.keys_table
-							.selected()
-							.expect("invalid selection");
-						vec![
-							Command::None,
-							Command::ShowHelp,
-							Command::Refresh,
-							Command::RefreshKeys,
-							Command::Set(
-								String::from("prompt"),
-								String::from(":import "),
-							),
-							Command::ImportClipboard,
-							Command::Set(
-								String::from("prompt"),
-								String::from(":receive "),
-							),
-							Command::ExportKeys(
-								key_type,
-								vec![selected_key.get_id()],
-								false,
-							),
-							if key_type == KeyType::Secret {
-								Command::ExportKeys(
-									key_type,
-									vec![selected_key.get_id()],
-									true,
-								)
-							} else {
-								Command::None
-							},
-							Command::ExportKeys(key_type, Vec::new(), false),
-							Command::Confirm(Box::new(Command::DeleteKey(
-								key_type,
-								selected_key.get_id(),
-							))),
-							Command::Confirm(Box::new(Command::SendKey(
-								selected_key.get_id(),
-							))),
-							Command::EditKey(selected_key.get_id()),
-							if key_type == KeyType::Secret {
-								Command::Set(
-									String::from("signer"),
-									selected_key.get_id(),
-								)
-							} else {
-								Command::None
-							},
-							Command::SignKey(selected_key.get_id()),
-							Command::GenerateKey,
-							Command::Set(
-								String::from("armor"),
-								(!self.gpgme.config.armor).to_string(),
-							),
-							Command::Copy(Selection::Key),
-							Command::Copy(Selection::KeyId),
-							Command::Copy(Selection::KeyFingerprint),
-							Command::Copy(Selection::KeyUserId),
-							Command::Copy(Selection::TableRow(1)),
-							Command::Copy(Selection::TableRow(2)),
-							Command::Paste,
-							Command::ToggleDetail(false),
-							Command::ToggleDetail(true),
-							Command::Set(
-								String::from("margin"),
-								String::from(if self.keys_table_margin == 1 {
-									"0"
-								} else {
-									"1"
-								}),
-							),
-							Command::ToggleTableSize,
-							Command::Set(
-								String::from("colored"),
-								(!self.state.colored).to_string(),
-							),
-							if self.mode == Mode::Visual {
-								Command::SwitchMode(Mode::Normal)
-							} else {
-								Command::SwitchMode(Mode::Visual)
-							},
-							Command::Quit,
-						]
-						.into_iter()
-						.enumerate()
-						.filter(|(i, c)| {
-							if c == &Command::None {
-								*i == 0
-							} else {
-								true
-							}
-						})
-						.map(|(_, c)| c)
-						.collect()
-					}
-					Tab::Help => {
-						vec![
-							Command::None,
-							Command::ListKeys(KeyType::Public),
-							Command::ListKeys(KeyType::Secret),
-							if self.mode == Mode::Visual {
-								Command::SwitchMode(Mode::Normal)
-							} else {
-								Command::SwitchMode(Mode::Visual)
-							},
-							Command::Refresh,
-							Command::Quit,
-						]
-					}
-				});
-				if prev_item_count == 0
-					|| self.options.items.len() == prev_item_count
+			None => false,
+		}
+	}
+
+	/// Selects the key with the given fingerprint.
+	///
+	/// The keys of the current tab are searched first and the
+	/// other tab is selected if the key only exists there.
+	/// Returns `false` if the key is not found.
+	pub fn select_key(&mut self, fingerprint: &str) -> Result<bool> {
+		let find = |keys: &[Arc<GpgKey>]| {
+			keys.iter()
+				.position(|key| key.get_fingerprint() == fingerprint)
+		};
+		if find(&self.keys_table.items).is_none() {
+			if find(&self.keys_table.default_items).is_some() {
+				self.keys_table.reset_state();
+			} else if let Tab::Keys(key_type) = self.tab {
+				let other_key_type = match key_type {
+					KeyType::Public => KeyType::Secret,
+					KeyType::Secret => KeyType::Public,
+				};
+				if self
+					.keys
+					.get(&other_key_type)
+					.and_then(|keys| find(keys))
+					.is_some()
 				{
-					self.options.state.select(prev_selection.or(Some(0)));
-				} else {
-					self.options.state.select(Some(0));
+					self.run_command(Command::ListKeys(other_key_type))?;
 				}
-				show_options = true;
 			}
-			Command::ListKeys(key_type) => {
-				if let Tab::Keys(previous_key_type) = self.tab {
-					self.keys_table_states.insert(
-						previous_key_type,
-						self.keys_table.state.clone(),
-					);
-					self.keys.insert(
-						previous_key_type,
-						self.keys_table.default_items.clone(),
-					);
+		}
+		Ok(match find(&self.keys_table.items) {
+			Some(index) => {
+				self.keys_table.state.tui.select(Some(index));
+				self.keys_table.reset_scroll();
+				true
+			}
+			None => false,
+		})
+	}
+
+	/// Selects the first key that matches the given search query.
+	///
+	/// Public keys are searched before the secret keys.
+	/// Returns `false` if no keys are matched.
+	pub fn focus_key(&mut self, query: &str) -> Result<bool> {
+		let query = Query::from_str(query)?;
+		let fingerprint = [KeyType::Public, KeyType::Secret]
+			.iter()
+			.filter_map(|key_type| self.keys.get(key_type))
+			.flatten()
+			.find(|key| query.matches(key))
+			.map(|key| key.get_fingerprint());
+		match fingerprint {
+			Some(fingerprint) => self.select_key(&fingerprint),
+			None => Ok(false),
+		}
+	}
+
+	/// Returns `true` if the key is the signing key of git.
+	pub fn is_git_signing_key(&self, key: &GpgKey) -> bool {
+		matches!(&self.git_signing_key, Some(signing_key)
+			if util::is_signing_key(signing_key, key))
+	}
+
+	/// Returns `true` if the key is the default key.
+	pub fn is_default_key(&self, key: &GpgKey) -> bool {
+		matches!(&self.gpgme.config.default_key, Some(default_key)
+			if util::is_signing_key(default_key, key))
+	}
+
+	/// Returns the state of the interface to share with the scripts.
+	pub fn get_script_context(&self) -> ScriptContext {
+		let mut context = ScriptContext::default();
+		for (key_type, keys) in &self.keys {
+			if self.tab != Tab::Keys(*key_type) {
+				context = context.with_keys(*key_type, keys);
+			}
+		}
+		if let Tab::Keys(key_type) = self.tab {
+			context =
+				context.with_keys(key_type, &self.keys_table.default_items);
+			if let Some(key) = self.keys_table.selected() {
+				context = context.with_selected(key_type, key);
+			}
+		}
+		context
+	}
+
+	/// Returns the level of detail to show for the given key.
+	pub fn get_key_detail(&self, key: &GpgKey) -> KeyDetail {
+		self.keys_table_details
+			.get(&key.get_fingerprint())
+			.copied()
+			.unwrap_or(self.keys_table_detail)
+	}
+
+	/// Returns the contents of the keys table columns for the given key.
+	pub fn get_key_info(&self, key: &GpgKey) -> (Vec<String>, Vec<String>) {
+		let detail = self.get_key_detail(key);
+		let subkey_info = key.get_info(
+			&self.state.columns.first,
+			detail,
+			&self.state.date_format,
+			self.keys_table.state.size != TableSize::Normal,
+			self.state.redact,
+		);
+		let mut user_info = key.get_info(
+			&self.state.columns.second,
+			detail,
+			&self.state.date_format,
+			self.keys_table.state.size == TableSize::Minimized,
+			self.state.redact,
+		);
+		if let Some(line) = user_info.first_mut() {
+			if self.is_default_key(key) {
+				line.push_str(" [default]");
+			}
+			if self.is_git_signing_key(key) {
+				line.push_str(" [git]");
+			}
+		}
+		if detail == KeyDetail::Full {
+			if let Some(preferences) = self.get_key_preferences(key) {
+				if preferences.has_algorithms() {
+					user_info.push(format!(
+						"preferences: {}",
+						preferences.get_algorithm_summary()
+					));
 				}
-				self.keys_table = StatefulTable::with_items(
-					self.keys
-						.get(&key_type)
-						.unwrap_or_else(|| {
-							panic!("failed to get {} keys", key_type)
-						})
-						.to_vec(),
-				);
-				if let Some(state) = self.keys_table_states.get(&key_type) {
-					self.keys_table.state = state.clone();
+				if let Some(keyserver) = &preferences.keyserver {
+					user_info
+						.push(format!("preferred keyserver: {}", keyserver));
 				}
-				self.tab = Tab::Keys(key_type);
-			}
-			Command::ImportKeys(_, false) | Command::ImportClipboard => {
-				let mut keys = Vec::new();
-				if let Command::ImportKeys(ref key_files, _) = command {
-					keys = key_files.clone();
-				} else if let Some(clipboard) = self.clipboard.as_mut() {
-					keys = vec![clipboard
-						.get_contents()
-						.expect("failed to get clipboard contents")];
+				if !preferences.revokers.is_empty() {
+					user_info.push(format!(
+						"designated revokers: {}",
+						preferences.revokers.join(", ")
+					));
 				}
-				if keys.is_empty() {
-					self.prompt.set_output((
-						OutputType::Failure,
-						String::from("no files given"),
-					))
-				} else {
-					match self
-						.gpgme
-						.import_keys(keys, command != Command::ImportClipboard)
+			}
+		}
+		(subkey_info, user_info)
+	}
+
+	/// Returns the keys table as text in the same layout as it is shown.
+	///
+	/// Colors are added with ANSI escape codes if `ansi` is set.
+	pub fn get_keys_table_text(&mut self, ansi: bool) -> String {
+		self.filter_keys_table();
+		let rows = self
+			.keys_table
+			.items
+			.iter()
+			.map(|key| self.get_key_info(key))
+			.collect::<Vec<(Vec<String>, Vec<String>)>>();
+		let width = rows
+			.iter()
+			.flat_map(|(subkey_info, _)| subkey_info.iter())
+			.map(|line| line.width())
+			.max()
+			.unwrap_or_default();
+		let format_line = |line: &String| {
+			if ansi {
+				style::get_colored_table_row(&[line.to_string()], false)
+					.lines
+					.first()
+					.map(style::get_ansi_string)
+					.unwrap_or_default()
+			} else {
+				line.to_string()
+			}
+		};
+		rows.iter()
+			.map(|(subkey_info, user_info)| {
+				(0..cmp::max(subkey_info.len(), user_info.len()))
+					.map(|i| {
+						let subkey_line =
+							subkey_info.get(i).cloned().unwrap_or_default();
+						format!(
+							"{}{} {}",
+							format_line(&subkey_line),
+							" ".repeat(width - subkey_line.width()),
+							user_info
+								.get(i)
+								.map(format_line)
+								.unwrap_or_default()
+						)
+						.trim_end()
+						.to_string()
+					})
+					.collect::<Vec<String>>()
+					.join("\n")
+			})
+			.collect::<Vec<String>>()
+			.join(if self.keys_table_margin > 0 {
+				"\n\n"
+			} else {
+				"\n"
+			})
+	}
+
+	/// Builds the tree of the selected key if the selection is changed.
+	///
+	/// The primary key is the root of the tree and the subkeys
+	/// and user IDs (along with their signatures) are its children.
+	pub fn update_key_tree(&mut self) {
+		let key = match self.keys_table.selected() {
+			Some(key) => key,
+			None => {
+				self.key_tree = Tree::default();
+				self.key_tree_id.clear();
+				return;
+			}
+		};
+		let preferences = self.get_key_preferences(key).cloned();
+		let key_tree_id = format!(
+			"{}/{}/{}/{}/{:?}",
+			key.get_fingerprint(),
+			key.has_details(),
+			self.state.redact,
+			self.state.date_format,
+			preferences
+		);
+		if key_tree_id == self.key_tree_id {
+			return;
+		}
+		let mut subkeys = key
+			.get_subkey_labels(&self.state.date_format)
+			.into_iter()
+			.map(|label| TreeNode::new(label, Vec::new()));
+		let mut primary_key = subkeys.next().unwrap_or_default().expanded(true);
+		primary_key.children.extend(subkeys);
+		if let Some(preferences) = preferences {
+			if preferences.has_algorithms() {
+				let [ciphers, hashes, compressions] =
+					preferences.get_algorithm_names();
+				primary_key.children.push(TreeNode::new(
+					String::from("preferences"),
+					vec![
+						TreeNode::new(
+							format!("cipher: {}", ciphers),
+							Vec::new(),
+						),
+						TreeNode::new(
+							format!("digest: {}", hashes),
+							Vec::new(),
+						),
+						TreeNode::new(
+							format!("compression: {}", compressions),
+							Vec::new(),
+						),
+					],
+				));
+			}
+			primary_key
+				.children
+				.extend(preferences.keyserver.map(|url| {
+					TreeNode::new(
+						format!("preferred keyserver: {}", url),
+						Vec::new(),
+					)
+				}));
+			if !preferences.revokers.is_empty() {
+				primary_key.children.push(TreeNode::new(
+					String::from("designated revokers"),
+					preferences
+						.revokers
+						.into_iter()
+						.map(|revoker| TreeNode::new(revoker, Vec::new()))
+						.collect(),
+				));
+			}
+		}
+		primary_key.children.extend(
+			key.get_user_labels(&self.state.date_format, self.state.redact)
+				.into_iter()
+				.map(|(label, children)| {
+					TreeNode::new(
+						label,
+						children
+							.into_iter()
+							.map(|label| TreeNode::new(label, Vec::new()))
+							.collect(),
+					)
+				}),
+		);
+		self.key_tree = Tree::new(vec![primary_key]);
+		self.key_tree_id = key_tree_id;
+	}
+
+	/// Filters the items of the keys table if they are changed.
+	///
+	/// Keys are filtered via the search query, the active filter and
+	/// `hide-unusable` setting. The selected key is kept selected if it
+	/// still exists, otherwise the nearest row is selected.
+	pub fn filter_keys_table(&mut self) {
+		if !self.keys_table.dirty {
+			return;
+		}
+		self.keys_table.dirty = false;
+		let query = if self.prompt.is_search_enabled() {
+			Some(Query::from_str(
+				&self.prompt.text.value.replacen("/", "", 1),
+			))
+		} else {
+			None
+		};
+		let item_count = self.keys_table.items.len();
+		let selected_key =
+			self.keys_table.selected().map(|key| key.get_fingerprint());
+		let mut items = mem::take(&mut self.keys_table.items);
+		items.retain(|key| {
+			if let Some(filter) = self.keys_table_filter {
+				if !filter.matches(key) {
+					return false;
+				}
+			}
+			if self.is_key_hidden(key) {
+				return false;
+			}
+			match &query {
+				Some(Ok(query)) => query.matches(key),
+				Some(Err(_)) => false,
+				None => true,
+			}
+		});
+		self.keys_table.items = items;
+		if self.keys_table.items.len() != item_count {
+			let index = selected_key
+				.and_then(|fingerprint| {
+					self.keys_table
+						.items
+						.iter()
+						.position(|key| key.get_fingerprint() == fingerprint)
+				})
+				.or_else(|| {
+					self.keys_table.state.tui.selected().map(|i| {
+						cmp::min(
+							i,
+							self.keys_table.items.len().saturating_sub(1),
+						)
+					})
+				});
+			self.keys_table.state.tui.select(index);
+		}
+	}
+
+	/// Applies the pending search query to the keys table.
+	pub fn apply_search(&mut self) {
+		if self.state.pending_search.take().is_some() {
+			self.keys_table.reset_state();
+			self.filter_keys_table();
+		}
+	}
+
+	/// Returns `true` if there are no keys in the keyring.
+	pub fn is_keyring_empty(&self) -> bool {
+		self.keys.values().all(|keys| keys.is_empty())
+	}
+
+	/// Shows a dialog for getting started with an empty keyring.
+	fn show_welcome(&mut self) {
+		self.dialog = Some(Dialog::new(
+			"Welcome",
+			"Your keyring is empty.\n\n\
+			Generate your first key pair or import the existing keys\n\
+			from a file, the clipboard or a keyserver.",
+			vec![
+				(String::from("generate"), Command::GenerateKey),
+				(
+					String::from("import file"),
+					Command::Set(
+						String::from("prompt"),
+						String::from(":import "),
+					),
+				),
+				(String::from("clipboard"), Command::ImportClipboard),
+				(
+					String::from("keyserver"),
+					Command::Set(
+						String::from("prompt"),
+						String::from(":receive "),
+					),
+				),
+				(String::from("skip"), Command::None),
+			],
+		));
+	}
+
+	/// Shows a warning about the keys that are flooded with signatures
+	/// and offers to import them with only their self-signatures.
+	fn show_flooded_keys(&mut self, keys: Vec<String>) {
+		let flooded_keys =
+			flood::get_flooded_keys(&keys, flood::SIGNATURE_LIMIT);
+		self.dialog = Some(Dialog::new(
+			"Warning",
+			format!(
+				"The following keys have an excessive number of signatures,\n\
+				which is a sign of a certificate flooding attack:\n\n{}\n\n\
+				Importing them as is can make gpg (and the interface)\n\
+				unresponsive. Do you want to import them with\n\
+				only their self-signatures?",
+				flooded_keys
+					.iter()
+					.map(|key| format!(
+						"- {} ({} signatures)",
+						key.user_id, key.signatures
+					))
+					.collect::<Vec<String>>()
+					.join("\n")
+			),
+			vec![
+				(String::from("import clean"), Command::ImportCleanKeys(keys)),
+				(String::from("cancel"), Command::None),
+			],
+		));
+	}
+
+	/// Shows a dialog for confirming the given command.
+	fn confirm_command(&mut self, command: Command) {
+		self.dialog = Some(Dialog::new(
+			"Confirm",
+			format!("Do you want to {}?", command),
+			vec![
+				(String::from("yes"), command),
+				(String::from("no"), Command::None),
+			],
+		));
+	}
+
+	/// Shows a dialog for the current step of the key rotation.
+	fn show_rotation_step(&mut self) {
+		if let Some(rotation) = &self.rotation {
+			let mut buttons = vec![(
+				rotation.step.get_label().to_string(),
+				Command::Rotate(RotationAction::Run),
+			)];
+			if rotation.new_key.is_some() {
+				buttons.push((
+					String::from("skip"),
+					Command::Rotate(RotationAction::Skip),
+				));
+			}
+			buttons.push((
+				String::from("cancel"),
+				Command::Rotate(RotationAction::Cancel),
+			));
+			self.dialog = Some(Dialog::new(
+				"Key rotation",
+				rotation.get_description(),
+				buttons,
+			));
+		}
+	}
+
+	/// Moves the key rotation to the next step and shows it.
+	fn advance_rotation(&mut self) {
+		match self.rotation.as_mut().map(|rotation| rotation.advance()) {
+			Some(true) => self.show_rotation_step(),
+			Some(false) => {
+				self.rotation = None;
+				self.prompt.set_output((
+					OutputType::Success,
+					String::from("key rotation is completed"),
+				))
+			}
+			None => self.prompt.set_output((
+				OutputType::Failure,
+				String::from("no key rotation in progress"),
+			)),
+		}
+	}
+
+	/// Runs the current step of the key rotation.
+	///
+	/// Returns `true` if the step is succeeded.
+	fn run_rotation_step(&mut self) -> Result<bool> {
+		let mut rotation = match self.rotation.clone() {
+			Some(rotation) => rotation,
+			None => {
+				self.prompt.set_output((
+					OutputType::Failure,
+					String::from("no key rotation in progress"),
+				));
+				return Ok(false);
+			}
+		};
+		let old_key = rotation.old_key.to_string();
+		let new_key = rotation.new_key.clone().unwrap_or_default();
+		match rotation.step {
+			RotationStep::Generate => {
+				let secret_keys =
+					self.gpgme.get_fingerprints(KeyType::Secret)?;
+				let user_id =
+					rotation.user_ids.first().cloned().unwrap_or_default();
+				let mut os_command = self.get_gpg_command();
+				os_command
+					.arg("--yes")
+					.arg("--quick-generate-key")
+					.arg(user_id);
+				if !self.run_gpg_command(&mut os_command, None, Vec::new())? {
+					return Ok(false);
+				}
+				match self
+					.gpgme
+					.get_fingerprints(KeyType::Secret)?
+					.into_iter()
+					.find(|key| !secret_keys.contains(key))
+				{
+					Some(new_key) => {
+						self.run_hook(
+							"post-generate",
+							&[new_key.to_string()],
+							&[],
+						);
+						self.prompt.set_output((
+							OutputType::Success,
+							format!("new key generated: {}", new_key),
+						));
+						rotation.new_key = Some(new_key);
+					}
+					None => {
+						self.prompt.set_output((
+							OutputType::Failure,
+							String::from("new key is not found"),
+						));
+						return Ok(false);
+					}
+				}
+			}
+			RotationStep::CopyUserIds => {
+				for user_id in rotation.user_ids.iter().skip(1) {
+					let mut os_command = self.get_gpg_command();
+					os_command
+						.arg("--quick-add-uid")
+						.arg(&new_key)
+						.arg(user_id);
+					if !self.run_gpg_command(
+						&mut os_command,
+						Some(format!("user ID added: {}", user_id)),
+						vec![new_key.to_string()],
+					)? {
+						return Ok(false);
+					}
+				}
+			}
+			RotationStep::Certify => {
+				let mut os_command = self.get_gpg_command();
+				os_command
+					.arg("--local-user")
+					.arg(&old_key)
+					.arg("--quick-sign-key")
+					.arg(&new_key);
+				if !self.run_gpg_command(
+					&mut os_command,
+					Some(format!("new key is certified: {}", new_key)),
+					vec![new_key.to_string()],
+				)? {
+					return Ok(false);
+				}
+			}
+			RotationStep::ExpireOld => {
+				let mut os_command = self.get_gpg_command();
+				os_command
+					.arg("--quick-set-expire")
+					.arg(&old_key)
+					.arg(rotate::OLD_KEY_EXPIRY);
+				if !self.run_gpg_command(
+					&mut os_command,
+					Some(format!(
+						"old key expires in {}: {}",
+						rotate::OLD_KEY_EXPIRY,
+						old_key
+					)),
+					vec![old_key.to_string()],
+				)? {
+					return Ok(false);
+				}
+			}
+			RotationStep::Statement => {
+				let output_dir = self.gpgme.config.output_dir.clone();
+				fs::create_dir_all(&output_dir)?;
+				let path =
+					output_dir.join(format!("transition_{}.txt", old_key));
+				fs::write(
+					&path,
+					rotation.get_statement(
+						&Local::today().format("%Y-%m-%d").to_string(),
+					),
+				)?;
+				let signed_path = path.with_extension("txt.asc");
+				let mut os_command = self.get_gpg_command();
+				os_command
+					.arg("--yes")
+					.arg("--local-user")
+					.arg(&old_key)
+					.arg("--local-user")
+					.arg(&new_key)
+					.arg("--output")
+					.arg(&signed_path)
+					.arg("--clearsign")
+					.arg(&path);
+				if !self.run_gpg_command(
+					&mut os_command,
+					Some(format!(
+						"statement: {}",
+						signed_path.to_string_lossy()
+					)),
+					Vec::new(),
+				)? {
+					return Ok(false);
+				}
+			}
+			RotationStep::Publish => {
+				let keys = [old_key, new_key];
+				self.spawn_task("sending keys", move |config, handle| {
+					let mut sent = Vec::new();
+					for (i, key) in keys.iter().enumerate() {
+						handle.set_progress(Progress {
+							what: key.to_string(),
+							current: i as u64,
+							total: keys.len() as u64,
+						});
+						match util::send_key(&config, key, &handle) {
+							Ok(true) => sent.push(key.to_string()),
+							Ok(false) => {
+								return TaskOutput::new(
+									OutputType::Warning,
+									format!(
+										"cancelled ({}/{} keys sent)",
+										sent.len(),
+										keys.len()
+									),
+									false,
+								)
+							}
+							Err(e) => {
+								return TaskOutput::new(
+									OutputType::Failure,
+									format!("send error: {}", e),
+									false,
+								)
+								.with_details(handler::get_error_details(&e))
+							}
+						}
+					}
+					TaskOutput::new(
+						OutputType::Success,
+						format!(
+							"keys sent to the keyserver: {}",
+							sent.join(", ")
+						),
+						false,
+					)
+				});
+			}
+		}
+		self.rotation = Some(rotation);
+		Ok(true)
+	}
+
+	/// Returns the directory for exporting the deleted keys.
+	///
+	/// It is created on the first deletion with restricted
+	/// permissions and removed on exit.
+	fn get_trash_dir(&mut self) -> Result<PathBuf> {
+		if self.trash_dir.is_none() {
+			self.trash_dir = Some(TempHomeDir::new()?);
+		}
+		Ok(self
+			.trash_dir
+			.as_ref()
+			.map(|dir| dir.path.clone())
+			.unwrap_or_default())
+	}
+
+	/// Runs the hook with the given name if it is configured.
+	fn run_hook(&self, name: &str, fingerprints: &[String], paths: &[String]) {
+		if let Some(command) = self.hooks.get(name) {
+			hook::run_hook(name, command, fingerprints, paths);
+		}
+	}
+
+	/// Sets the application mode.
+	///
+	/// Switching to visual mode places the selection anchor
+	/// of the keys table on the selected row.
+	fn set_mode(&mut self, mode: Mode) {
+		self.mode = mode;
+		self.keys_table.set_anchor(mode == Mode::Visual);
+	}
+
+	/// Runs the given task in the background with
+	/// a copy of the GnuPG configuration.
+	///
+	/// A warning is shown if there is already a running task.
+	fn spawn_task<F>(&mut self, description: &str, task: F)
+	where
+		F: FnOnce(GpgConfig, TaskHandle) -> TaskOutput + Send + 'static,
+	{
+		let config = self.gpgme.config.clone();
+		if self
+			.worker
+			.spawn(description, move |handle| task(config, handle))
+		{
+			self.prompt.clear();
+		} else {
+			self.prompt.set_output((
+				OutputType::Warning,
+				String::from("another task is running, please wait"),
+			));
+		}
+	}
+
+	/// Returns the identifiers of the selected keys.
+	///
+	/// Key ID is used for a single key while the fingerprints
+	/// are used for the keys that are selected in visual mode.
+	pub fn get_selected_keys(&self) -> Vec<String> {
+		let keys = self.keys_table.selected_items();
+		if keys.len() > 1 {
+			keys.iter().map(|key| key.get_fingerprint()).collect()
+		} else {
+			keys.iter().map(|key| key.get_id()).collect()
+		}
+	}
+
+	/// Returns the warning to show when there is no selected key.
+	///
+	/// It tells whether the table is empty or filtered to nothing.
+	pub fn get_selection_warning(&self) -> (OutputType, String) {
+		(
+			OutputType::Warning,
+			String::from(if self.keys_table.default_items.is_empty() {
+				"no keys to select"
+			} else if self.keys_table.items.is_empty() {
+				"no keys match the search/filter"
+			} else {
+				"invalid selection"
+			}),
+		)
+	}
+
+	/// Returns the `gpg` command with the common arguments
+	/// (e.g. home directory) for running it as a fallback.
+	fn get_gpg_command(&self) -> OsCommand {
+		let mut os_command = OsCommand::new(&self.gpgme.config.gpg_binary);
+		os_command
+			.arg("--homedir")
+			.arg(self.gpgme.config.home_dir.as_os_str());
+		if self.gpgme.config.armor {
+			os_command.arg("--armor");
+		}
+		os_command
+	}
+
+	/// Returns the mechanisms for locating the keys (in order).
+	fn get_locate_mechanisms(&self) -> String {
+		self.gpgme
+			.config
+			.auto_key_locate
+			.iter()
+			.map(LocateMechanism::to_string)
+			.collect::<Vec<String>>()
+			.join(",")
+	}
+
+	/// Returns the key with the given ID along with
+	/// the fingerprint of its subkey (0 for the primary key).
+	fn get_subkey(
+		&mut self,
+		key_type: KeyType,
+		key_id: &str,
+		subkey: usize,
+	) -> Result<(GpgKey, String)> {
+		let key =
+			GpgKey::from(self.gpgme.get_key(key_type, key_id.to_string())?);
+		let fingerprint = key
+			.get_subkey_fingerprints()
+			.get(subkey)
+			.cloned()
+			.ok_or_else(|| anyhow!("subkey is not found: {}", subkey))?;
+		Ok((key, fingerprint))
+	}
+
+	/// Runs the given `gpg` command and captures its output.
+	///
+	/// The output is shown in a popup if the command fails,
+	/// otherwise the given success message is shown (if any).
+	/// Keys with the given IDs are refreshed afterwards.
+	///
+	/// Returns `true` if the command is succeeded.
+	fn run_gpg_command(
+		&mut self,
+		os_command: &mut OsCommand,
+		success_msg: Option<String>,
+		key_ids: Vec<String>,
+	) -> Result<bool> {
+		let mut succeeded = false;
+		match util::run_captured(os_command) {
+			Ok((status, output)) => {
+				self.command_output = StatefulList::with_items(output.clone());
+				self.refresh_keys(key_ids)?;
+				if !status.success() {
+					self.prompt.set_error(
+						format!(
+							"execution error: {}",
+							output
+								.last()
+								.cloned()
+								.unwrap_or_else(|| status.to_string())
+						),
+						output,
+					);
+					self.state.show_output =
+						!self.command_output.items.is_empty();
+				} else {
+					succeeded = true;
+					if let Some(msg) = success_msg {
+						self.prompt.set_output((OutputType::Success, msg))
+					}
+				}
+			}
+			Err(e) => self.prompt.set_output((
+				OutputType::Failure,
+				format!("execution error: {}", e),
+			)),
+		}
+		Ok(succeeded)
+	}
+
+	/// Returns the last command for repeating it on the selected keys.
+	pub fn get_repeat_command(&self) -> Command {
+		match &self.last_command {
+			Some((_, keys))
+				if !keys.is_empty() && self.keys_table.selected().is_none() =>
+			{
+				let (output_type, message) = self.get_selection_warning();
+				Command::ShowOutput(output_type, message)
+			}
+			Some((command, keys)) => {
+				command.replace_keys(keys, &self.get_selected_keys())
+			}
+			None => Command::ShowOutput(
+				OutputType::Warning,
+				String::from("nothing to repeat"),
+			),
+		}
+	}
+
+	/// Returns `true` if the secret part of the key is in the keyring.
+	fn is_own_key(&self, key: &GpgKey) -> bool {
+		self.keys
+			.get(&KeyType::Secret)
+			.map(|keys| {
+				keys.iter()
+					.any(|v| v.get_fingerprint() == key.get_fingerprint())
+			})
+			.unwrap_or_default()
+	}
+
+	/// Returns the default key (or the first secret key).
+	fn get_default_key(&mut self) -> Result<GpgKey> {
+		match self.gpgme.config.default_key.clone() {
+			Some(default_key) => Ok(GpgKey::from(
+				self.gpgme.get_key(KeyType::Secret, default_key)?,
+			)),
+			None => self
+				.keys
+				.get(&KeyType::Secret)
+				.and_then(|keys| keys.first())
+				.map(|key| GpgKey::clone(key))
+				.ok_or_else(|| anyhow!("no secret keys")),
+		}
+	}
+
+	/// Returns the certification paths from the default key
+	/// (or the first secret key) to the given key as a tree.
+	///
+	/// The selected key is used if the key is not specified.
+	fn get_trust_paths(
+		&mut self,
+		key_id: Option<String>,
+	) -> Result<Vec<String>> {
+		let target = match key_id {
+			Some(key_id) => key_id,
+			None => self
+				.keys_table
+				.selected()
+				.map(|key| key.get_id())
+				.ok_or_else(|| anyhow!("no key selected"))?,
+		};
+		let target = GpgKey::from(self.gpgme.get_key(KeyType::Public, target)?);
+		self.load_all_key_details()?;
+		let source = self.get_default_key()?;
+		let public_keys = self
+			.keys
+			.get(&KeyType::Public)
+			.ok_or_else(|| anyhow!("failed to get public keys"))?;
+		let paths = TrustGraph::from(public_keys.as_slice()).find_paths(
+			&source.get_id(),
+			&target.get_id(),
+			wot::MAX_DEPTH,
+		);
+		Ok(wot::get_path_tree(&paths, |key_id| {
+			let user_id = public_keys
+				.iter()
+				.find(|key| key.get_id() == key_id)
+				.map(|key| key.get_user_id())
+				.unwrap_or_else(|| String::from("[?]"));
+			format!(
+				"{} {}",
+				key_id,
+				if self.state.redact {
+					handler::redact_user_id(&user_id)
+				} else {
+					user_id
+				}
+			)
+		}))
+	}
+
+	/// Returns `true` if the given key is hidden from the keys table.
+	///
+	/// Unusable keys are hidden if `hide-unusable` is set, unless
+	/// they are explicitly listed with the corresponding filter.
+	pub fn is_key_hidden(&self, key: &GpgKey) -> bool {
+		self.state.hide_unusable
+			&& !matches!(
+				self.keys_table_filter,
+				Some(Filter::Expired) | Some(Filter::Revoked)
+			) && key.is_unusable()
+	}
+
+	/// Handles the tick event of the application.
+	///
+	/// It is currently used to flush the prompt messages, to apply
+	/// the pending search and to discard the expired count prefixes.
+	///
+	/// An expired count that consists of a single digit falls back
+	/// to setting the detail level (e.g. `2` sets it to standard).
+	pub fn tick(&mut self) -> Result<()> {
+		if let Some(result) = self.key_loader.as_mut().and_then(KeyLoader::poll)
+		{
+			self.key_loader = None;
+			match result {
+				Ok(keys) => self.load_keys(keys)?,
+				Err(e) => {
+					log::error!("failed to load the keys: {}", e);
+					self.refresh()?;
+				}
+			}
+		}
+		if let Some(task) = self.worker.poll() {
+			if task.refresh {
+				self.refresh()?;
+			}
+			if self.state.notify {
+				util::send_notification(&task.output);
+			}
+			if !task.lines.is_empty() {
+				self.command_output = StatefulList::with_items(task.lines);
+				self.state.show_output = true;
+			}
+			if task.details.is_empty() {
+				self.prompt.set_output(task.output);
+			} else {
+				self.prompt.set_error(task.output.1, task.details);
+			}
+			if task.dialog.is_some() {
+				self.dialog = task.dialog;
+			}
+		}
+		if self.state.watch != WatchMode::Off
+			&& self.key_loader.is_none()
+			&& !self.worker.is_running()
+			&& self.keyring_watcher.poll()
+		{
+			log::info!("keyring is changed externally");
+			self.state.keyring_changed = true;
+		}
+		if self.state.keyring_changed
+			&& self.state.watch == WatchMode::Reload
+			&& !self.prompt.is_enabled()
+			&& self.prompt.command.is_none()
+			&& self.dialog.is_none()
+		{
+			self.refresh()?;
+			self.prompt.set_output((
+				OutputType::Action,
+				String::from(
+					"keyring is changed externally, keys are reloaded",
+				),
+			));
+		}
+		if let Some(clock) = self.prompt.clock {
+			if clock.elapsed().as_millis() > MESSAGE_DURATION
+				&& self.prompt.command.is_none()
+			{
+				self.prompt.clear()
+			}
+		}
+		if self.get_clipboard_countdown() == Some(0) {
+			if let Some((_, hash)) = self.clipboard_clear.take() {
+				if let Some(clipboard) = self.clipboard.as_mut() {
+					if clipboard
+						.get_contents()
+						.map(|contents| clipboard::get_content_hash(&contents))
+						.unwrap_or(hash) == hash
+					{
+						self.prompt.set_output(
+							match clipboard.set_contents(String::new()) {
+								Ok(()) => (
+									OutputType::Success,
+									String::from("clipboard cleared"),
+								),
+								Err(e) => (
+									OutputType::Failure,
+									format!("clipboard error: {}", e),
+								),
+							},
+						);
+					}
+				}
+			}
+		} else if self.state.clipboard_timeout == 0 {
+			self.clipboard_clear = None;
+		}
+		if let Some(clock) = self.state.pending_search {
+			if clock.elapsed().as_millis() > SEARCH_DEBOUNCE {
+				self.apply_search();
+			}
+		}
+		if let Some((count, clock)) = self.state.count {
+			if clock.elapsed().as_millis() > COUNT_DURATION {
+				self.state.count = None;
+				if let Some(command) = Command::from_count(count) {
+					self.run_command(command)?;
+				}
+			}
+		}
+		self.load_key_details(self.state.split_view)
+	}
+
+	/// Returns the remaining seconds for clearing
+	/// the sensitive content from clipboard.
+	pub fn get_clipboard_countdown(&self) -> Option<u64> {
+		match (self.clipboard_clear, self.state.clipboard_timeout) {
+			(Some(_), 0) | (None, _) => None,
+			(Some((clock, _)), timeout) => {
+				Some(timeout.saturating_sub(clock.elapsed().as_secs()))
+			}
+		}
+	}
+
+	/// Runs the given command which is used to specify
+	/// the widget to render or action to perform.
+	pub fn run_command(&mut self, command: Command) -> Result<()> {
+		if command != Command::None {
+			log::info!("running command: {:?}", command);
+		}
+		if command.is_repeatable()
+			&& !matches!(&self.last_command,
+				Some((Command::Confirm(cmd), _)) if **cmd == command)
+		{
+			self.last_command =
+				Some((command.clone(), self.get_selected_keys()));
+		}
+		let mut show_options = false;
+		if let Command::Confirm(ref cmd) = command {
+			match **cmd {
+				Command::DeleteKey(KeyType::Secret, ref key_ids)
+					if self.state.confirm != ConfirmLevel::Simple =>
+				{
+					let mut answers = vec![if key_ids.len() == 1 {
+						key_ids[0][key_ids[0].len().saturating_sub(8)..]
+							.to_string()
+					} else {
+						key_ids.len().to_string()
+					}];
+					if self.state.confirm == ConfirmLevel::Typed {
+						answers.push(String::from("yes"));
+					}
+					self.prompt.set_typed_command(*cmd.clone(), answers)
+				}
+				Command::AddRevoker(_, ref revoker) => {
+					self.dialog = Some(Dialog::new(
+						"Warning",
+						format!(
+							"Adding a designated revoker cannot be undone.\n\n\
+							The owner of {} will be able to revoke this key\n\
+							permanently, even without your consent.\n\n\
+							Do you want to continue?",
+							revoker
+						),
+						vec![
+							(String::from("cancel"), Command::None),
+							(String::from("add revoker"), *cmd.clone()),
+						],
+					))
+				}
+				_ => self.confirm_command(*cmd.clone()),
+			}
+		} else if self.prompt.command.is_some() {
+			self.prompt.clear();
+		}
+		match command {
+			Command::SignKey(ref key_ids, ref notations)
+				if key_ids.is_empty()
+					&& !notations.is_empty()
+					&& self.keys_table.selected().is_some() =>
+			{
+				self.run_command(Command::SignKey(
+					self.get_selected_keys(),
+					notations.to_vec(),
+				))?;
+			}
+			Command::CleanKeys(Some(ref key_ids), minimize)
+				if key_ids.is_empty()
+					&& self.keys_table.selected().is_some() =>
+			{
+				self.run_command(Command::CleanKeys(
+					Some(self.get_selected_keys()),
+					minimize,
+				))?;
+			}
+			Command::DeleteKey(_, ref key_ids)
+			| Command::SignKey(ref key_ids, _)
+			| Command::CleanKeys(Some(ref key_ids), _)
+				if key_ids.is_empty() =>
+			{
+				self.prompt.set_output(self.get_selection_warning())
+			}
+			Command::ShowHelp => {
+				self.tab = Tab::Help;
+				if self.key_bindings.state.selected().is_none() {
+					self.key_bindings.state.select(Some(0));
+				}
+			}
+			Command::ShowMessages => {
+				if self.prompt.messages.toasts.is_empty() {
+					self.prompt.set_output((
+						OutputType::Warning,
+						String::from("no messages"),
+					))
+				} else {
+					self.state.show_messages = true;
+				}
+			}
+			Command::ShowLegend => self.state.show_legend = true,
+			Command::ShowFingerprint => match self.keys_table.selected() {
+				Some(selected_key) => {
+					let fingerprint = selected_key.get_fingerprint();
+					let mut lines = vec![selected_key.get_user_id()];
+					for (format, groups_per_line) in &[
+						(FingerprintFormat::Groups, 5),
+						(FingerprintFormat::Words, 2),
+						(FingerprintFormat::Nato, 1),
+					] {
+						lines.push(String::new());
+						lines.push(format!("{}:", format));
+						lines.extend(
+							format
+								.get_groups(&fingerprint)
+								.chunks(*groups_per_line)
+								.map(|groups| {
+									format!("  {}", groups.join("  "))
+								}),
+						);
+					}
+					self.command_output = StatefulList::with_items(lines);
+					self.state.show_output = true;
+				}
+				None => self.prompt.set_output(self.get_selection_warning()),
+			},
+			Command::ShowCommandOutput => {
+				if self.command_output.items.is_empty() {
+					self.prompt.set_output((
+						OutputType::Warning,
+						String::from("no command output"),
+					))
+				} else {
+					self.state.show_output = true;
+				}
+			}
+			Command::ShowError => match self.prompt.error.clone() {
+				Some((message, details)) => {
+					let mut lines = vec![Spans::from(Span::styled(
+						message.to_string(),
+						style::get_output_style(
+							&OutputType::Failure,
+							self.state.colored,
+						)
+						.add_modifier(Modifier::BOLD),
+					))];
+					lines.extend(
+						details
+							.iter()
+							.map(|detail| Spans::from(detail.to_string())),
+					);
+					let mut messages = details;
+					messages.push(message);
+					let remedies = handler::get_error_remedies(&messages);
+					if !remedies.is_empty() {
+						lines.push(Spans::default());
+						lines.extend(remedies.into_iter().map(|remedy| {
+							Spans::from(Span::styled(
+								format!("hint: {}", remedy),
+								Style::default().fg(Color::DarkGray),
+							))
+						}));
+					}
+					self.dialog = Some(Dialog::new(
+						"Error",
+						lines,
+						vec![(String::from("close"), Command::None)],
+					));
+				}
+				None => self.prompt.set_output((
+					OutputType::Warning,
+					String::from("no errors"),
+				)),
+			},
+			Command::ShowOutput(output_type, message) => {
+				self.prompt.set_output((output_type, message))
+			}
+			Command::ShowOptions => {
+				self.load_key_details(true)?;
+				let default_key = self.get_default_key().ok();
+				let prev_selection = self.options.state.selected();
+				let prev_item_count = self.options.items.len();
+				let mut commands = match self.tab {
+					Tab::Keys(key_type) => match self.keys_table.selected() {
+						Some(selected_key) => {
+							let selected_keys = self.get_selected_keys();
+							let is_own_key = key_type == KeyType::Secret
+								|| self.is_own_key(selected_key);
+							let card_present = is_own_key
+								&& card::is_present(
+									&self.gpgme.config.home_dir,
+								);
+							vec![
+								Command::None,
+								Command::ShowHelp,
+								Command::Refresh,
+								if self.worker.is_running() {
+									Command::Cancel
+								} else {
+									Command::None
+								},
+								Command::RefreshKeys,
+								Command::Set(
+									String::from("prompt"),
+									String::from(":import "),
+								),
+								Command::ImportClipboard,
+								Command::Set(
+									String::from("prompt"),
+									String::from(":receive "),
+								),
+								Command::ExportKeys(
+									key_type,
+									selected_keys.to_vec(),
+									false,
+									ExportDestination::File,
+								),
+								if key_type == KeyType::Secret {
+									Command::ExportKeys(
+										key_type,
+										vec![selected_key.get_id()],
+										true,
+										ExportDestination::File,
+									)
+								} else {
+									Command::None
+								},
+								if key_type == KeyType::Secret {
+									Command::ExportSymmetric(
+										selected_keys.to_vec(),
+									)
+								} else {
+									Command::None
+								},
+								if selected_key.can_authenticate() {
+									Command::ExportSshKey(selected_key.get_id())
+								} else {
+									Command::None
+								},
+								if key_type == KeyType::Secret
+									&& selected_key.can_authenticate()
+								{
+									Command::AddSshKey(selected_key.get_id())
+								} else {
+									Command::None
+								},
+								Command::ExportKeys(
+									key_type,
+									Vec::new(),
+									false,
+									ExportDestination::File,
+								),
+								Command::BrowseFiles(String::from("output")),
+								Command::Confirm(Box::new(Command::DeleteKey(
+									key_type,
+									selected_keys.to_vec(),
+								))),
+								if self.trash.is_empty() {
+									Command::None
+								} else {
+									Command::Undo
+								},
+								if key_type == KeyType::Secret {
+									Command::None
+								} else {
+									Command::Confirm(Box::new(Command::SendKey(
+										selected_key.get_id(),
+									)))
+								},
+								Command::EditKey(selected_key.get_id()),
+								if is_own_key {
+									Command::SetExpire(
+										Some(selected_key.get_id()),
+										None,
+									)
+								} else {
+									Command::None
+								},
+								if is_own_key {
+									Command::Rotate(RotationAction::Start(
+										Some(selected_key.get_id()),
+									))
+								} else {
+									Command::None
+								},
+								Command::ShowPrefs,
+							]
+							.into_iter()
+							.chain(
+								if card_present && key_type == KeyType::Secret {
+									selected_key.get_card_candidates()
+								} else {
+									Vec::new()
+								}
+								.into_iter()
+								.map(|(subkey, slot)| {
+									Command::Confirm(Box::new(
+										Command::KeyToCard(
+											selected_key.get_id(),
+											subkey,
+											slot,
+										),
+									))
+								})
+								.chain(
+									if card_present {
+										vec![
+											Command::ChangePin(CardPin::Pin),
+											Command::ChangePin(
+												CardPin::AdminPin,
+											),
+										]
+									} else {
+										Vec::new()
+									},
+								),
+							)
+							.chain(
+								if selected_key.has_tofu_info() {
+									vec![
+										TofuPolicy::Good,
+										TofuPolicy::Bad,
+										TofuPolicy::Ask,
+									]
+								} else {
+									Vec::new()
+								}
+								.into_iter()
+								.map(|policy| {
+									Command::SetTofuPolicy(
+										Some(selected_key.get_id()),
+										policy,
+									)
+								}),
+							)
+							.chain(vec![
+								if key_type == KeyType::Secret {
+									Command::Set(
+										String::from("signer"),
+										selected_key.get_id(),
+									)
+								} else {
+									Command::None
+								},
+								if key_type == KeyType::Secret {
+									Command::SetDefaultKey(selected_key.get_id())
+								} else {
+									Command::None
+								},
+								if key_type == KeyType::Secret {
+									Command::SetGitKey(
+										selected_key.get_id(),
+										false,
+									)
+								} else {
+									Command::None
+								},
+								Command::SignKey(selected_keys, Vec::new()),
+								match default_key {
+									Some(default_key)
+										if selected_key
+											.get_certifications_by(
+												&default_key
+													.get_id()
+													.replacen("0x", "", 1),
+											)
+											.iter()
+											.any(|v| !v.revoked) =>
+									{
+										Command::Confirm(Box::new(
+											Command::RevokeSig(
+												selected_key.get_id(),
+												default_key.get_id(),
+											),
+										))
+									}
+									_ => Command::None,
+								},
+								Command::ShowTrustPaths(Some(
+									selected_key.get_id(),
+								)),
+								Command::ShowFingerprint,
+								Command::ShowSubkeys,
+								Command::GenerateKey,
+								Command::Set(
+									String::from("armor"),
+									(!self.gpgme.config.armor).to_string(),
+								),
+								Command::Copy(Selection::Key),
+								Command::Copy(Selection::KeyMarkdown),
+								Command::Copy(Selection::KeyHtml),
+								Command::Copy(Selection::KeyId),
+								Command::Copy(Selection::KeyFingerprint),
+								Command::Copy(Selection::KeyUserId),
+								Command::Copy(Selection::TableRow(1)),
+								Command::Copy(Selection::TableRow(2)),
+								Command::Paste,
+								Command::ToggleDetail(false),
+								Command::ToggleDetail(true),
+								Command::Set(
+									String::from("margin"),
+									String::from(
+										if self.keys_table_margin == 1 {
+											"0"
+										} else {
+											"1"
+										},
+									),
+								),
+								Command::ToggleTableSize,
+								Command::Set(
+									String::from("colored"),
+									(!self.state.colored).to_string(),
+								),
+								Command::Set(
+									String::from("splitview"),
+									(!self.state.split_view).to_string(),
+								),
+								Command::Set(
+									String::from("log"),
+									(!self.state.show_log).to_string(),
+								),
+								Command::Set(
+									String::from("notify"),
+									(!self.state.notify).to_string(),
+								),
+								Command::Set(
+									String::from("redact"),
+									(!self.state.redact).to_string(),
+								),
+								Command::Set(
+									String::from("hide-unusable"),
+									(!self.state.hide_unusable).to_string(),
+								),
+								if self.mode == Mode::Visual {
+									Command::SwitchMode(Mode::Normal)
+								} else {
+									Command::SwitchMode(Mode::Visual)
+								},
+								Command::ShowMessages,
+								Command::ShowLegend,
+								Command::ShowStats,
+								Command::CheckKeyring,
+								Command::ShowCertifications,
+								Command::ShowGroups,
+								if self.command_output.items.is_empty() {
+									Command::None
+								} else {
+									Command::ShowCommandOutput
+								},
+								Command::Quit,
+							])
+							.enumerate()
+							.filter(|(i, c)| {
+								if c == &Command::None {
+									*i == 0
+								} else {
+									true
+								}
+							})
+							.map(|(_, c)| c)
+							.collect()
+						}
+						None => vec![
+							Command::None,
+							Command::ShowHelp,
+							Command::Refresh,
+							Command::Set(
+								String::from("prompt"),
+								String::from(":import "),
+							),
+							Command::ImportClipboard,
+							Command::Set(
+								String::from("prompt"),
+								String::from(":receive "),
+							),
+							Command::GenerateKey,
+							Command::Paste,
+							Command::ShowMessages,
+							Command::ShowLegend,
+							Command::Quit,
+						],
+					},
+					Tab::Help => {
+						vec![
+							Command::None,
+							Command::ListKeys(KeyType::Public),
+							Command::ListKeys(KeyType::Secret),
+							if self.mode == Mode::Visual {
+								Command::SwitchMode(Mode::Normal)
+							} else {
+								Command::SwitchMode(Mode::Visual)
+							},
+							Command::Refresh,
+							Command::ShowMessages,
+							Command::ShowLegend,
+							Command::Quit,
+						]
+					}
+				};
+				if let Some(i) = commands.iter().position(|command| {
+					*command == Command::Copy(Selection::TableRow(2))
+				}) {
+					let mut names =
+						self.copy_templates.keys().collect::<Vec<&String>>();
+					names.sort();
+					commands.splice(
+						i + 1..i + 1,
+						names.into_iter().map(|name| {
+							Command::Copy(Selection::Template(name.to_string()))
+						}),
+					);
+				}
+				self.options =
+					StatefulList::with_items(self.menu_layout.apply(commands));
+				if prev_item_count == 0
+					|| self.options.items.len() == prev_item_count
+				{
+					self.options.state.select(prev_selection.or(Some(0)));
+				} else {
+					self.options.state.select(Some(0));
+				}
+				show_options = true;
+			}
+			Command::ListKeys(key_type) => {
+				let selected_key =
+					self.keys_table.selected().map(|key| key.get_fingerprint());
+				if let Tab::Keys(previous_key_type) = self.tab {
+					self.keys_table_states.insert(
+						previous_key_type,
+						self.keys_table.state.clone(),
+					);
+					self.keys.insert(
+						previous_key_type,
+						self.keys_table.default_items.clone(),
+					);
+				}
+				self.keys_table = StatefulTable::with_items(
+					self.keys.get(&key_type).cloned().unwrap_or_default(),
+				);
+				if let Some(state) = self.keys_table_states.get(&key_type) {
+					self.keys_table.state = state.clone();
+				}
+				self.select_fingerprint(selected_key);
+				self.set_mode(self.mode);
+				self.tab = Tab::Keys(key_type);
+			}
+			// Keys from the files and the clipboard are checked here
+			// before importing (or previewing) them.
+			Command::ImportKeys(ref keys, false)
+			| Command::PreviewImport(ref keys)
+				if !flood::get_flooded_keys(keys, flood::SIGNATURE_LIMIT)
+					.is_empty() =>
+			{
+				self.show_flooded_keys(keys.to_vec())
+			}
+			Command::ImportCleanKeys(ref keys) => {
+				let mut os_command =
+					OsCommand::new(&self.gpgme.config.gpg_binary);
+				os_command
+					.arg("--batch")
+					.arg("--homedir")
+					.arg(self.gpgme.config.home_dir.as_os_str())
+					.arg("--import-options")
+					.arg("import-clean,self-sigs-only")
+					.arg("--import");
+				let keys = keys.to_vec();
+				self.spawn_task("importing keys", move |_, handle| {
+					let result = TempHomeDir::new().and_then(|temp_dir| {
+						for (i, key) in keys.iter().enumerate() {
+							if handler::is_armored_block(key) {
+								let path =
+									temp_dir.path.join(format!("{}.asc", i));
+								fs::write(&path, key)?;
+								os_command.arg(path);
+							} else {
+								os_command.arg(key);
+							}
+						}
+						util::run_cancellable(os_command, &handle)
+					});
+					match result {
+						Ok(Some((status, _))) if status.success() => {
+							TaskOutput::new(
+								OutputType::Success,
+								String::from(
+									"key(s) imported with only self-signatures",
+								),
+								true,
+							)
+						}
+						Ok(Some((_, stderr))) => TaskOutput::new(
+							OutputType::Failure,
+							format!(
+								"execution error: {}",
+								stderr.lines().last().unwrap_or_default()
+							),
+							true,
+						)
+						.with_details(
+							stderr.lines().map(String::from).collect(),
+						),
+						Ok(None) => TaskOutput::new(
+							OutputType::Warning,
+							String::from("cancelled"),
+							true,
+						),
+						Err(e) => TaskOutput::new(
+							OutputType::Failure,
+							format!("import error: {}", e),
+							false,
+						),
+					}
+				});
+			}
+			Command::LocateKey(email) => {
+				self.spawn_task("locating key", move |config, handle| {
+					let mechanisms = &config.auto_key_locate;
+					let mut details = Vec::new();
+					for (i, mechanism) in mechanisms.iter().enumerate() {
+						handle.set_progress(Progress {
+							what: mechanism.to_string(),
+							current: i as u64,
+							total: mechanisms.len() as u64,
+						});
+						let result = locate::locate_key(
+							&config,
+							&email,
+							*mechanism,
+							|os_command| {
+								util::run_cancellable(os_command, &handle)
+							},
+						);
+						if handle.is_cancelled() {
+							return TaskOutput::new(
+								OutputType::Warning,
+								String::from("cancelled"),
+								false,
+							);
+						}
+						let key = match result {
+							Ok(Some(key)) => key,
+							Ok(None) => continue,
+							Err(e) => {
+								details.push(format!("{}: {}", mechanism, e));
+								continue;
+							}
+						};
+						let body =
+							format!("{}\n{}", key.user_id, key.fingerprint);
+						return TaskOutput::new(
+							OutputType::Success,
+							format!("key found via {}: {}", mechanism, email),
+							false,
+						)
+						.with_dialog(match key.data {
+							Some(data) => Dialog::new(
+								"Key found",
+								format!(
+									"The key of {} is found via {}:\n\n{}\n\n\
+									Do you want to import it?",
+									email, mechanism, body
+								),
+								vec![
+									(
+										String::from("import"),
+										Command::ImportKeys(vec![data], false),
+									),
+									(String::from("cancel"), Command::None),
+								],
+							),
+							None => Dialog::new(
+								"Key found",
+								format!(
+									"The key of {} is in the keyring:\n\n{}",
+									email, body
+								),
+								vec![
+									(
+										String::from("select"),
+										Command::Focus(key.fingerprint),
+									),
+									(String::from("close"), Command::None),
+								],
+							),
+						});
+					}
+					TaskOutput::new(
+						OutputType::Failure,
+						format!("key not found: {}", email),
+						false,
+					)
+					.with_details(details)
+				});
+			}
+			Command::ImportKeys(ref keys, false) if !keys.is_empty() => {
+				let keys = keys.to_vec();
+				let hook = self.hooks.get("post-import").cloned();
+				self.spawn_task("importing keys", move |config, handle| {
+					match GpgContext::new(config).and_then(|mut gpgme| {
+						gpgme.set_progress_callback(
+							handle.get_progress_callback(),
+						);
+						gpgme.import_keys(keys, true)
+					}) {
+						Ok(summary) => {
+							if let Some(hook) = hook {
+								hook::run_hook(
+									"post-import",
+									&hook,
+									&summary.get_fingerprints(),
+									&[],
+								);
+							}
+							TaskOutput::new(
+								OutputType::Success,
+								format!("{} key(s) imported", summary.new_keys),
+								true,
+							)
+							.with_lines(summary.get_lines())
+						}
+						Err(e) => TaskOutput::new(
+							OutputType::Failure,
+							format!("import error: {}", e),
+							false,
+						)
+						.with_details(handler::get_error_details(&e)),
+					}
+				});
+			}
+			Command::PreviewImport(ref keys) if keys.is_empty() => {
+				self.prompt.set_output((
+					OutputType::Failure,
+					String::from("no files given"),
+				))
+			}
+			Command::PreviewImport(ref keys) => {
+				match self.gpgme.preview_import(keys.to_vec()) {
+					Ok(summary) => {
+						let mut body = summary
+							.get_totals()
+							.iter()
+							.map(|(name, value)| format!("{}: {}", name, value))
+							.collect::<Vec<String>>();
+						body.push(String::new());
+						body.push(String::from(if summary.is_empty() {
+							"The keyring will not be changed."
+						} else {
+							"Do you want to import?"
+						}));
+						self.dialog = Some(Dialog::new(
+							"Import",
+							body.join("\n"),
+							vec![
+								(
+									String::from("import"),
+									Command::ImportKeys(keys.to_vec(), false),
+								),
+								(String::from("cancel"), Command::None),
+							],
+						));
+					}
+					Err(e) => self.prompt.set_error(
+						format!("import error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::ImportClipboard => {
+				match self
+					.clipboard
+					.as_mut()
+					.and_then(|clipboard| clipboard.get_contents().ok())
+				{
+					Some(contents) if handler::is_armored_block(&contents) => {
+						self.run_command(Command::ImportKeys(
+							vec![contents],
+							false,
+						))?;
+					}
+					_ => self.prompt.set_output((
+						OutputType::Failure,
+						String::from("no keys in the clipboard"),
+					)),
+				}
+			}
+			Command::ImportKeys(_, false) => {
+				self.run_command(Command::BrowseFiles(String::from("import")))?
+			}
+			Command::BrowseFiles(ref target) => {
+				let picker = if target == "output" {
+					let output_dir = &self.gpgme.config.output_dir;
+					FilePicker::new(
+						if output_dir.exists() {
+							output_dir
+						} else {
+							output_dir.parent().unwrap_or(output_dir)
+						},
+						Vec::new(),
+						true,
+					)
+				} else if target == "encrypt" {
+					FilePicker::new(&env::current_dir()?, Vec::new(), false)
+				} else {
+					FilePicker::new(
+						&env::current_dir()?,
+						IMPORT_EXTENSIONS
+							.iter()
+							.map(|v| v.to_string())
+							.collect(),
+						false,
+					)
+				};
+				match picker {
+					Ok(picker) => {
+						self.file_picker = Some((target.to_string(), picker))
+					}
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("browse error: {}", e),
+					)),
+				}
+			}
+			Command::Encrypt(ref file, ref recipients)
+				if recipients.is_empty() =>
+			{
+				let mut recipients = self
+					.gpgme
+					.get_groups()
+					.unwrap_or_default()
+					.into_iter()
+					.map(|group| {
+						(
+							format!(
+								"group: {} ({} members)",
+								group.name,
+								group.members.len()
+							),
+							group.name,
+						)
+					})
+					.collect::<Vec<(String, String)>>();
+				let keys = self
+					.keys
+					.get(&KeyType::Public)
+					.map(|keys| {
+						keys.iter()
+							.filter(|key| {
+								key.can_encrypt() && !key.is_unusable()
+							})
+							.map(|key| {
+								(
+									format!(
+										"{} {}",
+										key.get_id(),
+										key.get_user_id()
+									),
+									key.get_fingerprint(),
+								)
+							})
+							.collect::<Vec<(String, String)>>()
+					})
+					.unwrap_or_default();
+				if keys.is_empty() {
+					self.prompt.set_output((
+						OutputType::Failure,
+						String::from("no keys to encrypt for"),
+					))
+				} else {
+					recipients.extend(keys);
+					self.recipient_picker =
+						Some((file.to_string(), CheckList::new(recipients)));
+				}
+			}
+			Command::Encrypt(ref file, ref recipients) => {
+				match self.gpgme.encrypt_file(file, recipients.to_vec()) {
+					Ok(path) => self.prompt.set_output((
+						OutputType::Success,
+						format!("encrypt: {}", path),
+					)),
+					Err(e) => self.prompt.set_error(
+						format!("encrypt error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::ExportKeys(_, _, true, destination)
+				if destination != ExportDestination::File =>
+			{
+				self.prompt.set_output((
+					OutputType::Failure,
+					String::from("subkeys can only be exported to a file"),
+				));
+			}
+			Command::ExportKeys(key_type, ref patterns, false, destination)
+				if destination != ExportDestination::File =>
+			{
+				let content = self
+					.gpgme
+					.get_exported_keys(key_type, Some(patterns.to_vec()))
+					.and_then(|keys| {
+						str::from_utf8(&keys)
+							.map(String::from)
+							.map_err(AnyhowError::from)
+					});
+				match content {
+					Ok(content) if destination == ExportDestination::Stdout => {
+						self.state.exit_message = Some(content);
+						self.run_command(Command::Quit)?;
+					}
+					Ok(content) => {
+						if let Some(clipboard) = self.clipboard.as_mut() {
+							let hash = clipboard::get_content_hash(&content);
+							self.prompt.set_output(
+								match clipboard.set_contents(content) {
+									Ok(()) => {
+										self.clipboard_clear =
+											if key_type == KeyType::Secret {
+												Some((Instant::now(), hash))
+											} else {
+												None
+											};
+										(
+											OutputType::Success,
+											String::from(
+												"keys copied to clipboard",
+											),
+										)
+									}
+									Err(e) => (
+										OutputType::Failure,
+										format!("clipboard error: {}", e),
+									),
+								},
+							);
+						} else {
+							self.prompt.set_output((
+								OutputType::Failure,
+								String::from("clipboard not available"),
+							));
+						}
+					}
+					Err(e) => self.prompt.set_error(
+						format!("export error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::ExportKeys(KeyType::Public, ref patterns, false, _) => {
+				let patterns = patterns.to_vec();
+				let hook = self.hooks.get("post-export").cloned();
+				self.spawn_task("exporting keys", move |config, handle| {
+					match GpgContext::new(config).and_then(|mut gpgme| {
+						gpgme.set_progress_callback(
+							handle.get_progress_callback(),
+						);
+						gpgme.export_keys(
+							KeyType::Public,
+							Some(patterns.clone()),
+						)
+					}) {
+						Ok(paths) if handle.is_cancelled() => {
+							for path in paths {
+								let _ = fs::remove_file(path);
+							}
+							TaskOutput::new(
+								OutputType::Warning,
+								String::from("cancelled"),
+								false,
+							)
+						}
+						Ok(paths) => {
+							if let Some(hook) = hook {
+								hook::run_hook(
+									"post-export",
+									&hook,
+									&patterns,
+									&paths,
+								);
+							}
+							TaskOutput::new(
+								OutputType::Success,
+								format!("export: {}", paths.join(", ")),
+								false,
+							)
+						}
+						Err(e) => TaskOutput::new(
+							OutputType::Failure,
+							format!("export error: {}", e),
+							false,
+						)
+						.with_details(handler::get_error_details(&e)),
+					}
+				});
+			}
+			Command::ExportKeys(key_type, ref patterns, false, _) => {
+				self.prompt.set_output(
+					match self
+						.gpgme
+						.export_keys(key_type, Some(patterns.to_vec()))
+					{
+						Ok(paths) => {
+							self.run_hook("post-export", patterns, &paths);
+							(
+								OutputType::Success,
+								format!("export: {}", paths.join(", ")),
+							)
+						}
+						Err(e) => (
+							OutputType::Failure,
+							format!("export error: {}", e),
+						),
+					},
+				);
+			}
+			Command::ExportSymmetric(ref patterns) => {
+				self.prompt.set_output(
+					match self
+						.gpgme
+						.export_keys_symmetric(Some(patterns.to_vec()))
 					{
-						Ok(key_count) => {
-							self.refresh()?;
+						Ok(path) => {
+							self.run_hook(
+								"post-export",
+								patterns,
+								&[path.to_string()],
+							);
+							(OutputType::Success, format!("export: {}", path))
+						}
+						Err(e) => (
+							OutputType::Failure,
+							format!("export error: {}", e),
+						),
+					},
+				);
+			}
+			Command::ExportJson(key_type, ref patterns) => {
+				self.prompt.set_output(
+					match self.gpgme.export_keys_json(
+						key_type,
+						Some(patterns.to_vec()),
+						self.state.redact,
+					) {
+						Ok(path) => {
+							(OutputType::Success, format!("export: {}", path))
+						}
+						Err(e) => (
+							OutputType::Failure,
+							format!("export error: {}", e),
+						),
+					},
+				);
+			}
+			Command::ExportSshKey(ref key_id) => {
+				self.prompt.set_output(
+					match self.gpgme.export_ssh_key(key_id) {
+						Ok(path) => {
+							(OutputType::Success, format!("export: {}", path))
+						}
+						Err(e) => (
+							OutputType::Failure,
+							format!("export error: {}", e),
+						),
+					},
+				);
+			}
+			Command::DumpKeys(ref path, ansi) => {
+				let text = self.get_keys_table_text(ansi);
+				if let Some(path) = path {
+					let path = shellexpand::tilde(path).to_string();
+					self.prompt.set_output(match fs::write(&path, text) {
+						Ok(()) => {
+							(OutputType::Success, format!("dump: {}", path))
+						}
+						Err(e) => {
+							(OutputType::Failure, format!("dump error: {}", e))
+						}
+					});
+				} else if let Some(clipboard) = self.clipboard.as_mut() {
+					self.prompt.set_output(
+						match clipboard.set_contents(text) {
+							Ok(()) => (
+								OutputType::Success,
+								String::from("keys table copied to clipboard"),
+							),
+							Err(e) => (
+								OutputType::Failure,
+								format!("clipboard error: {}", e),
+							),
+						},
+					);
+				} else {
+					self.prompt.set_output((
+						OutputType::Failure,
+						String::from("clipboard not available"),
+					));
+				}
+			}
+			Command::EditConfig(ref name) => {
+				let (file, daemon) = agent::CONFIG_FILES
+					.iter()
+					.find(|(config, _, _)| config == name)
+					.map(|(_, file, daemon)| (*file, *daemon))
+					.ok_or_else(|| {
+						anyhow!("unknown configuration: {}", name)
+					})?;
+				let path = self.gpgme.config.home_dir.join(file);
+				let editor = env::var("VISUAL")
+					.or_else(|_| env::var("EDITOR"))
+					.unwrap_or_else(|_| String::from("vi"));
+				let mut editor = editor.split_whitespace();
+				match OsCommand::new(editor.next().unwrap_or("vi"))
+					.args(editor)
+					.arg(&path)
+					.status()
+				{
+					Ok(status) if status.success() => match daemon {
+						Some(daemon) => {
+							self.confirm_command(Command::ManageDaemon(
+								String::from("reload"),
+								daemon.to_string(),
+							))
+						}
+						None => self.prompt.set_output((
+							OutputType::Success,
+							format!("edited: {}", path.to_string_lossy()),
+						)),
+					},
+					Ok(status) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("editor exited with {}", status),
+					)),
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("execution error: {}", e),
+					)),
+				}
+			}
+			Command::ShowAgent => {
+				match agent::get_components(&self.gpgme.config.home_dir) {
+					Ok(components) => {
+						self.agent_components = components;
+						self.state.show_agent = true;
+					}
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("gpgconf error: {}", e),
+					)),
+				}
+			}
+			Command::ManageDaemon(ref action, ref daemon) => {
+				self.prompt.set_output(
+					match agent::run_gpgconf(
+						&self.gpgme.config.home_dir,
+						action,
+						daemon,
+					) {
+						Ok(()) => (
+							OutputType::Success,
+							format!("{}: {}", action, daemon),
+						),
+						Err(e) => (
+							OutputType::Failure,
+							format!("gpgconf error: {}", e),
+						),
+					},
+				);
+			}
+			Command::ShowSshKeys => {
+				let home_dir = &self.gpgme.config.home_dir;
+				let ssh_support = agent::is_ssh_support_enabled(home_dir);
+				self.prompt.set_output(
+					match agent::get_ssh_keygrips(home_dir) {
+						Ok(keygrips) => {
+							let key_ids = self
+								.keys
+								.get(&KeyType::Secret)
+								.map(|keys| {
+									keys.iter()
+										.filter(|key| {
+											key.get_auth_keygrips()
+												.iter()
+												.any(|v| keygrips.contains(v))
+										})
+										.map(|key| key.get_id())
+										.collect::<Vec<String>>()
+								})
+								.unwrap_or_default();
+							(
+								if ssh_support {
+									OutputType::Success
+								} else {
+									OutputType::Warning
+								},
+								format!(
+									"ssh support: {}, {} key(s) exposed{}",
+									if ssh_support {
+										"enabled"
+									} else {
+										"disabled"
+									},
+									key_ids.len(),
+									if key_ids.is_empty() {
+										String::new()
+									} else {
+										format!(": {}", key_ids.join(", "))
+									}
+								),
+							)
+						}
+						Err(e) => {
+							(OutputType::Failure, format!("ssh error: {}", e))
+						}
+					},
+				);
+			}
+			Command::AddSshKey(ref key_id) => {
+				let keygrips = self
+					.gpgme
+					.get_key(KeyType::Secret, key_id.to_string())
+					.map(|key| GpgKey::from(key).get_auth_keygrips());
+				let home_dir = &self.gpgme.config.home_dir;
+				self.prompt.set_output(match keygrips {
+					Ok(keygrips) if keygrips.is_empty() => (
+						OutputType::Failure,
+						String::from("no authentication subkey"),
+					),
+					Ok(keygrips) => {
+						match agent::add_ssh_keygrips(home_dir, &keygrips) {
+							Ok(count)
+								if !agent::is_ssh_support_enabled(home_dir) =>
+							{
+								(
+									OutputType::Warning,
+									format!(
+										"{} keygrip(s) added to sshcontrol \
+									(enable-ssh-support is not set)",
+										count
+									),
+								)
+							}
+							Ok(count) => (
+								OutputType::Success,
+								format!(
+									"{} keygrip(s) added to sshcontrol",
+									count
+								),
+							),
+							Err(e) => (
+								OutputType::Failure,
+								format!("ssh error: {}", e),
+							),
+						}
+					}
+					Err(e) => {
+						(OutputType::Failure, format!("ssh error: {}", e))
+					}
+				});
+			}
+			Command::ShowGitKey => {
+				self.git_signing_key =
+					util::get_git_signing_key().unwrap_or_default();
+				self.prompt.set_output(match &self.git_signing_key {
+					Some(key) => (
+						OutputType::Success,
+						format!("git signing key: {}", key),
+					),
+					None => (
+						OutputType::Warning,
+						String::from("git signing key is not set"),
+					),
+				});
+			}
+			Command::SetDefaultKey(ref key_id) => {
+				self.prompt.set_output(
+					match self.gpgme.set_default_key(key_id) {
+						Ok(()) => (
+							OutputType::Success,
+							format!("default key: {}", key_id),
+						),
+						Err(e) => (
+							OutputType::Failure,
+							format!("default key error: {}", e),
+						),
+					},
+				);
+			}
+			Command::SetGitKey(ref key_id, global) => {
+				self.prompt.set_output(
+					match util::set_git_signing_key(key_id, global) {
+						Ok(()) => {
+							self.git_signing_key = Some(key_id.to_string());
+							(
+								OutputType::Success,
+								format!("git signing key: {}", key_id),
+							)
+						}
+						Err(e) => {
+							(OutputType::Failure, format!("git error: {}", e))
+						}
+					},
+				);
+			}
+			Command::DeleteKey(key_type, ref key_ids) => {
+				let mut errors = Vec::new();
+				let mut trash = Vec::new();
+				let mut deleted_keys = Vec::new();
+				for key_id in key_ids {
+					// Secret keys are not kept on the disk for undo.
+					let result = match key_type {
+						KeyType::Public => {
+							self.get_trash_dir().and_then(|dir| {
+								self.gpgme
+									.export_to_trash(&dir, key_id.to_string())
+									.map(Some)
+							})
+						}
+						KeyType::Secret => Ok(None),
+					}
+					.and_then(|path| {
+						self.gpgme.delete_key(key_type, key_id.to_string())?;
+						Ok(path)
+					});
+					match result {
+						Ok(path) => {
+							trash.extend(path);
+							deleted_keys.push(key_id.to_string());
+						}
+						Err(e) => errors.push(format!("{} ({})", e, key_id)),
+					}
+				}
+				if !deleted_keys.is_empty() {
+					self.run_hook("post-delete", &deleted_keys, &[]);
+					if !trash.is_empty() {
+						self.trash.push(trash);
+					}
+					self.refresh_keys(key_ids.clone())?;
+				}
+				if !errors.is_empty() {
+					self.prompt.set_output((
+						OutputType::Failure,
+						format!("delete error: {}", errors.join(", ")),
+					))
+				} else {
+					self.prompt.set_output((
+						OutputType::Success,
+						format!(
+							"{}{}",
+							if key_ids.len() > 1 {
+								format!("{} keys deleted", key_ids.len())
+							} else {
+								String::from("key deleted")
+							},
+							if key_type == KeyType::Public {
+								" (:undo to restore)"
+							} else {
+								""
+							}
+						),
+					))
+				}
+			}
+			Command::ShowSubkeys => match self.keys_table.selected() {
+				Some(selected_key) => {
+					let subkeys = selected_key
+						.get_subkey_labels(&self.state.date_format)
+						.into_iter()
+						.zip(selected_key.get_subkey_keygrips())
+						.enumerate()
+						.map(|(i, (label, keygrip))| {
+							vec![
+								format!("{} {}", i, label),
+								format!("  └─keygrip: {}", keygrip),
+							]
+						})
+						.collect();
+					self.subkeys = StatefulList::with_items(subkeys);
+					self.subkeys.state.select(Some(0));
+					self.state.show_subkeys = true;
+				}
+				None => self.prompt.set_output(self.get_selection_warning()),
+			},
+			Command::ExportSubkey(ref key_id, subkey) => {
+				let key_type = match self.tab {
+					Tab::Keys(key_type) => key_type,
+					_ => KeyType::Public,
+				};
+				match self.get_subkey(key_type, key_id, subkey).and_then(
+					|(key, fingerprint)| {
+						let path = self.gpgme.get_output_file(
+							key_type,
+							vec![fingerprint.clone()],
+						)?;
+						Ok((key, fingerprint, path))
+					},
+				) {
+					Ok((key, fingerprint, path)) => {
+						let mut os_command = self.get_gpg_command();
+						os_command
+							.arg("--output")
+							.arg(&path)
+							.arg(if key_type == KeyType::Secret {
+								"--export-secret-subkeys"
+							} else {
+								"--export"
+							})
+							.arg(format!("{}!", fingerprint));
+						self.run_gpg_command(
+							&mut os_command,
+							Some(format!("export: {}", path.to_string_lossy())),
+							vec![key.get_id()],
+						)?;
+					}
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("export error: {}", e),
+					)),
+				}
+			}
+			Command::SetSubkeyExpire(key_id, subkey, None) => {
+				self.date_picker = Some((
+					Command::SetSubkeyExpire(key_id, subkey, None),
+					DatePicker::new(Local::today().naive_local()),
+				))
+			}
+			Command::SetSubkeyExpire(ref key_id, subkey, Some(ref expiry)) => {
+				match self.get_subkey(KeyType::Secret, key_id, subkey).and_then(
+					|(key, fingerprint)| {
+						let expiry = datepicker::parse_expiry(
+							expiry,
+							Local::today().naive_local(),
+						)?;
+						Ok((key, fingerprint, expiry))
+					},
+				) {
+					Ok((key, fingerprint, expiry)) => {
+						self.state.show_subkeys = false;
+						let expiry = expiry
+							.map(|date| {
+								date.format(datepicker::DATE_FORMAT).to_string()
+							})
+							.unwrap_or_else(|| String::from(datepicker::NEVER));
+						let mut os_command = self.get_gpg_command();
+						os_command
+							.arg("--quick-set-expire")
+							.arg(key.get_fingerprint())
+							.arg(&expiry);
+						if subkey != 0 {
+							os_command.arg(fingerprint);
+						}
+						self.run_gpg_command(
+							&mut os_command,
+							Some(format!(
+								"expiration time of subkey {} set to {}",
+								subkey, expiry
+							)),
+							vec![key.get_id()],
+						)?;
+					}
+					Err(e) => self.prompt.set_error(
+						format!("expire error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::RevokeSubkey(ref key_id, subkey) => {
+				match self.get_subkey(KeyType::Secret, key_id, subkey) {
+					Ok(_) if subkey == 0 => self.prompt.set_output((
+						OutputType::Failure,
+						String::from(
+							"revoke error: the primary key can only be revoked \
+							with a revocation certificate",
+						),
+					)),
+					Ok((key, _)) => {
+						self.state.show_subkeys = false;
+						match self
+							.gpgme
+							.revoke_subkey(key.get_fingerprint(), subkey)
+						{
+							Ok(()) => {
+								self.refresh_keys(vec![key.get_id()])?;
+								self.prompt.set_output((
+									OutputType::Success,
+									format!(
+										"subkey {} revoked: {}",
+										subkey,
+										key.get_id()
+									),
+								))
+							}
+							Err(e) => self.prompt.set_error(
+								format!("revoke error: {}", e),
+								handler::get_error_details(&e),
+							),
+						}
+					}
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("revoke error: {}", e),
+					)),
+				}
+			}
+			Command::KeyToCard(key_id, subkey, slot) => {
+				self.state.show_subkeys = false;
+				match self.gpgme.move_to_card(key_id.to_string(), subkey, slot)
+				{
+					Ok(_) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.prompt.set_output((
+							OutputType::Warning,
+							format!(
+								"key moved to the card ({}), \
+								the local secret key is now a stub: {}",
+								slot, key_id
+							),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("keytocard error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::ShowStats => {
+				let keys = |key_type| {
+					self.keys
+						.get(&key_type)
+						.map(Vec::as_slice)
+						.unwrap_or_default()
+				};
+				self.keyring_stats = KeyringStats::new(
+					keys(KeyType::Public),
+					keys(KeyType::Secret),
+					self.state.expiry_warn,
+				);
+				self.state.show_stats = true;
+			}
+			Command::CheckKeyring => {
+				self.load_all_key_details()?;
+				let keys = |key_type| {
+					self.keys
+						.get(&key_type)
+						.map(Vec::as_slice)
+						.unwrap_or_default()
+				};
+				let issues = KeyringIssue::check(
+					keys(KeyType::Public),
+					keys(KeyType::Secret),
+				);
+				if issues.is_empty() {
+					self.prompt.set_output((
+						OutputType::Success,
+						String::from("no issues found in the keyring"),
+					));
+				} else {
+					self.prompt.set_output((
+						OutputType::Warning,
+						format!(
+							"{} issue(s) found in the keyring",
+							issues.len()
+						),
+					));
+					self.keyring_issues = StatefulList::with_items(issues);
+					self.keyring_issues.state.select(Some(0));
+					self.state.show_doctor = true;
+				}
+			}
+			Command::JumpToIssue(index) => {
+				match index
+					.checked_sub(1)
+					.and_then(|i| self.keyring_issues.items.get(i))
+					.cloned()
+				{
+					Some(issue) => {
+						self.state.show_doctor = false;
+						if self.tab != Tab::Keys(issue.key_type) {
+							self.run_command(Command::ListKeys(
+								issue.key_type,
+							))?;
+						}
+						if self.select_key(&issue.fingerprint)? {
 							self.prompt.set_output((
-								OutputType::Success,
-								format!("{} key(s) imported", key_count),
-							))
+								OutputType::Warning,
+								issue.to_string(),
+							));
+						} else {
+							self.prompt.set_output((
+								OutputType::Failure,
+								format!(
+									"key is not found: {}",
+									issue.fingerprint
+								),
+							));
 						}
-						Err(e) => self.prompt.set_output((
-							OutputType::Failure,
-							format!("import error: {}", e),
-						)),
 					}
+					None => self.prompt.set_output((
+						OutputType::Failure,
+						format!("keyring issue is not found: {}", index),
+					)),
 				}
 			}
-			Command::ExportKeys(key_type, ref patterns, false) => {
-				self.prompt.set_output(
-					match self
-						.gpgme
-						.export_keys(key_type, Some(patterns.to_vec()))
-					{
-						Ok(path) => {
-							(OutputType::Success, format!("export: {}", path))
+			Command::ShowGroups => match self.gpgme.get_groups() {
+				Ok(groups) if groups.is_empty() => {
+					self.prompt.set_output((
+						OutputType::Warning,
+						String::from("no recipient groups found"),
+					));
+				}
+				Ok(groups) => {
+					self.groups = StatefulList::with_items(groups);
+					self.groups.state.select(Some(0));
+					self.state.show_groups = true;
+				}
+				Err(e) => self.prompt.set_output((
+					OutputType::Failure,
+					format!("groups error: {}", e),
+				)),
+			},
+			Command::SetGroup(ref name, ref members) => {
+				match self.gpgme.set_group(name, members) {
+					Ok(()) => {
+						if self.state.show_groups {
+							let selected = self.groups.state.selected();
+							self.groups = StatefulList::with_items(
+								self.gpgme.get_groups()?,
+							);
+							self.groups.state.select(selected.map(|i| {
+								cmp::min(
+									i,
+									self.groups.items.len().saturating_sub(1),
+								)
+							}));
+							self.state.show_groups =
+								!self.groups.items.is_empty();
 						}
-						Err(e) => (
-							OutputType::Failure,
-							format!("export error: {}", e),
-						),
-					},
-				);
+						self.prompt.set_output((
+							OutputType::Success,
+							if members.is_empty() {
+								format!("group removed: {}", name)
+							} else {
+								format!("group updated: {}", name)
+							},
+						));
+					}
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("group error: {}", e),
+					)),
+				}
 			}
-			Command::DeleteKey(key_type, ref key_id) => {
-				match self.gpgme.delete_key(key_type, key_id.to_string()) {
-					Ok(_) => {
-						self.refresh()?;
+			Command::ShowCertifications => match self
+				.load_all_key_details()
+				.and_then(|_| self.get_default_key())
+			{
+				Ok(default_key) => {
+					let signer_id = default_key.get_id().replacen("0x", "", 1);
+					let certifications = self
+						.keys
+						.get(&KeyType::Public)
+						.map(Vec::as_slice)
+						.unwrap_or_default()
+						.iter()
+						.flat_map(|key| key.get_certifications_by(&signer_id))
+						.collect::<Vec<Certification>>();
+					if certifications.is_empty() {
+						self.prompt.set_output((
+							OutputType::Warning,
+							format!(
+								"no certifications found: {}",
+								default_key.get_id()
+							),
+						));
+					} else {
+						self.certifications =
+							StatefulList::with_items(certifications);
+						self.certifications.state.select(Some(0));
+						self.state.show_certs = true;
+					}
+				}
+				Err(e) => self.prompt.set_output((
+					OutputType::Failure,
+					format!("certifications error: {}", e),
+				)),
+			},
+			Command::JumpToCertification(index) => {
+				match index
+					.checked_sub(1)
+					.and_then(|i| self.certifications.items.get(i))
+					.cloned()
+				{
+					Some(certification) => {
+						self.state.show_certs = false;
+						if self.tab != Tab::Keys(KeyType::Public) {
+							self.run_command(Command::ListKeys(
+								KeyType::Public,
+							))?;
+						}
+						if self.select_key(&certification.fingerprint)? {
+							self.prompt.set_output((
+								OutputType::Success,
+								certification.get_info(
+									&self.state.date_format,
+									self.state.redact,
+								),
+							));
+						} else {
+							self.prompt.set_output((
+								OutputType::Failure,
+								format!(
+									"key is not found: {}",
+									certification.fingerprint
+								),
+							));
+						}
+					}
+					None => self.prompt.set_output((
+						OutputType::Failure,
+						format!("certification is not found: {}", index),
+					)),
+				}
+			}
+			Command::RevokeCertification(index) => {
+				match index
+					.checked_sub(1)
+					.and_then(|i| self.certifications.items.get(i))
+					.cloned()
+					.ok_or_else(|| {
+						anyhow!("certification is not found: {}", index)
+					})
+					.and_then(|certification| {
+						Ok((certification, self.get_default_key()?))
+					}) {
+					Ok((certification, default_key)) => {
+						self.state.show_certs = false;
+						let mut os_command = self.get_gpg_command();
+						os_command
+							.arg("--quick-revoke-sig")
+							.arg(&certification.fingerprint)
+							.arg(default_key.get_fingerprint())
+							.arg(&certification.user_id);
+						self.run_gpg_command(
+							&mut os_command,
+							Some(format!(
+								"certification revoked: {}",
+								certification.key_id
+							)),
+							vec![certification.fingerprint.to_string()],
+						)?;
 					}
 					Err(e) => self.prompt.set_output((
 						OutputType::Failure,
-						format!("delete error: {}", e),
+						format!("revoke error: {}", e),
 					)),
 				}
 			}
-			Command::SendKey(key_id) => {
-				self.prompt.set_output(match self.gpgme.send_key(key_id) {
-					Ok(key_id) => (
-						OutputType::Success,
-						format!("key sent to the keyserver: 0x{}", key_id),
+			Command::RevokeSig(key_id, signing_key) => {
+				self.state.show_certs = false;
+				match self
+					.gpgme
+					.revoke_sig(key_id.to_string(), signing_key.to_string())
+				{
+					Ok(count) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.prompt.set_output((
+							OutputType::Success,
+							format!(
+								"{} certification(s) revoked: {}",
+								count, key_id
+							),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("revsig error: {}", e),
+						handler::get_error_details(&e),
 					),
-					Err(e) => {
-						(OutputType::Failure, format!("send error: {}", e))
+				}
+			}
+			Command::ShowTrustPaths(key_id) => {
+				match self.get_trust_paths(key_id) {
+					Ok(paths) if paths.is_empty() => self.prompt.set_output((
+						OutputType::Warning,
+						format!(
+							"no certification paths found (max depth: {})",
+							wot::MAX_DEPTH
+						),
+					)),
+					Ok(paths) => {
+						self.trust_paths = paths;
+						self.state.show_wot = true;
 					}
-				});
+					Err(e) => self.prompt.set_output((
+						OutputType::Failure,
+						format!("trust paths error: {}", e),
+					)),
+				}
 			}
-			Command::GenerateKey
-			| Command::RefreshKeys
-			| Command::EditKey(_)
-			| Command::SignKey(_)
-			| Command::ImportKeys(_, true)
-			| Command::ExportKeys(_, _, true) => {
-				let mut success_msg = None;
-				let mut os_command = OsCommand::new("gpg");
-				os_command
-					.arg("--homedir")
-					.arg(self.gpgme.config.home_dir.as_os_str());
-				if self.gpgme.config.armor {
-					os_command.arg("--armor");
+			Command::EditKey(key_id) => {
+				match self.gpgme.get_key(KeyType::Public, key_id) {
+					Ok(key) => {
+						let key = GpgKey::from(key);
+						let key_id = key.get_id();
+						let mut commands = vec![Command::None];
+						if self.is_own_key(&key) {
+							let user_ids = key.get_user_ids();
+							commands.extend(vec![
+								Command::Set(
+									String::from("prompt"),
+									format!(":adduid {} ", key_id),
+								),
+								Command::Set(
+									String::from("prompt"),
+									format!(":addkey {} ", key_id),
+								),
+								Command::SetExpire(
+									Some(key_id.to_string()),
+									None,
+								),
+								Command::ChangePassphrase(key_id.to_string()),
+							]);
+							commands.extend(user_ids.iter().skip(1).map(
+								|user_id| {
+									Command::SetPrimaryUserId(
+										key_id.to_string(),
+										user_id.to_string(),
+									)
+								},
+							));
+							if user_ids.len() > 1 {
+								commands.extend(user_ids.iter().map(
+									|user_id| {
+										Command::Confirm(Box::new(
+											Command::RevokeUserId(
+												key_id.to_string(),
+												user_id.to_string(),
+											),
+										))
+									},
+								));
+							}
+						}
+						commands.push(Command::SetOwnerTrust(key_id, None));
+						self.options = StatefulList::with_items(commands);
+						self.options.state.select(Some(0));
+						show_options = true;
+					}
+					Err(e) => self.prompt.set_error(
+						format!("edit error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::SetOwnerTrust(key_id, None) => {
+				let mut commands = vec![Command::None];
+				commands.extend(OwnerTrust::ALL.iter().map(|trust| {
+					Command::SetOwnerTrust(key_id.to_string(), Some(*trust))
+				}));
+				self.options = StatefulList::with_items(commands);
+				self.options.state.select(Some(0));
+				show_options = true;
+			}
+			Command::AddUserId(ref key_id, _)
+			| Command::RevokeUserId(ref key_id, _)
+			| Command::SetPrimaryUserId(ref key_id, _)
+			| Command::ChangePassphrase(ref key_id)
+			| Command::SetOwnerTrust(ref key_id, Some(_)) => {
+				let result = match command {
+					Command::AddUserId(_, ref user_id) => self
+						.gpgme
+						.add_user_id(key_id.to_string(), user_id)
+						.map(|_| format!("user ID added: {}", user_id)),
+					Command::RevokeUserId(_, ref user_id) => self
+						.gpgme
+						.revoke_user_id(key_id.to_string(), user_id)
+						.map(|_| format!("user ID revoked: {}", user_id)),
+					Command::SetPrimaryUserId(_, ref user_id) => self
+						.gpgme
+						.set_primary_user_id(key_id.to_string(), user_id)
+						.map(|_| format!("primary user ID set: {}", user_id)),
+					Command::SetOwnerTrust(_, Some(trust)) => self
+						.gpgme
+						.set_owner_trust(key_id.to_string(), trust)
+						.map(|_| {
+							format!(
+								"owner trust of {} set to {}",
+								key_id, trust
+							)
+						}),
+					_ => self
+						.gpgme
+						.change_passphrase(key_id.to_string())
+						.map(|_| format!("passphrase changed: {}", key_id)),
+				};
+				match result {
+					Ok(message) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.prompt.set_output((OutputType::Success, message))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("edit error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::AddSubkey(key_id, algo, usage, expiry) => {
+				let today = Local::today().naive_local();
+				match expiry
+					.map(|expiry| datepicker::parse_expiry(&expiry, today))
+					.transpose()
+					.and_then(|expiry| {
+						let mut flags = edit::get_subkey_flags(&usage)
+							.ok_or_else(|| {
+								anyhow!("invalid usage: {}", usage)
+							})?;
+						let expires = match expiry {
+							Some(Some(date)) => Some(Duration::from_secs(
+								(date - today).num_seconds().max(0) as u64,
+							)),
+							Some(None) => {
+								flags |= CreateKeyFlags::NOEXPIRE;
+								None
+							}
+							None => None,
+						};
+						self.gpgme.add_subkey(
+							key_id.to_string(),
+							&algo,
+							flags,
+							expires,
+						)
+					}) {
+					Ok(fingerprint) => {
+						self.refresh_keys(vec![key_id])?;
+						self.prompt.set_output((
+							OutputType::Success,
+							format!("subkey added: {}", fingerprint),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("edit error: {}", e),
+						handler::get_error_details(&e),
+					),
 				}
-				let os_command = match command {
-					Command::EditKey(ref key) => {
-						os_command.arg("--edit-key").arg(key)
+			}
+			Command::SetExpire(key_id, None) => {
+				match key_id.or_else(|| {
+					self.keys_table.selected().map(|key| key.get_id())
+				}) {
+					Some(key_id) => {
+						self.date_picker = Some((
+							Command::SetExpire(Some(key_id), None),
+							DatePicker::new(Local::today().naive_local()),
+						))
 					}
-					Command::SignKey(ref key) => {
-						if let Some(default_key) =
-							&self.gpgme.config.default_key
-						{
-							os_command.arg("--default-key").arg(default_key);
+					None => {
+						self.prompt.set_output(self.get_selection_warning())
+					}
+				}
+			}
+			Command::SetExpire(key_id, Some(expiry)) => {
+				match key_id
+					.or_else(|| {
+						self.keys_table.selected().map(|key| key.get_id())
+					})
+					.ok_or_else(|| anyhow!("no key selected"))
+					.and_then(|key_id| {
+						let expiry = datepicker::parse_expiry(
+							&expiry,
+							Local::today().naive_local(),
+						)?
+						.map(|date| {
+							date.format(datepicker::DATE_FORMAT).to_string()
+						});
+						let key = GpgKey::from(
+							self.gpgme.get_key(KeyType::Secret, key_id)?,
+						);
+						self.gpgme.set_expire(
+							key.get_fingerprint(),
+							expiry.as_deref().unwrap_or("0"),
+							false,
+						)?;
+						Ok((
+							key.get_id(),
+							expiry.unwrap_or_else(|| {
+								String::from(datepicker::NEVER)
+							}),
+						))
+					}) {
+					Ok((key_id, expiry)) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.prompt.set_output((
+							OutputType::Success,
+							format!(
+								"expiration time of {} set to {}",
+								key_id, expiry
+							),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("expire error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::SetExpireAll(expiry, subkeys) => {
+				match datepicker::parse_expiry(
+					&expiry,
+					Local::today().naive_local(),
+				) {
+					Ok(date) => {
+						let expiry = date.map(|date| {
+							date.format(datepicker::DATE_FORMAT).to_string()
+						});
+						let keys = self
+							.keys
+							.get(&KeyType::Secret)
+							.map(|keys| {
+								keys.iter()
+									.filter(|key| !key.is_revoked())
+									.map(|key| {
+										(
+											key.get_fingerprint(),
+											key.get_id(),
+											key.get_user_id(),
+										)
+									})
+									.collect::<Vec<(String, String, String)>>()
+							})
+							.unwrap_or_default();
+						self.spawn_task(
+							"extending expiration times",
+							move |config, handle| {
+								let mut gpgme = match GpgContext::new(config) {
+									Ok(gpgme) => gpgme,
+									Err(e) => {
+										return TaskOutput::new(
+											OutputType::Failure,
+											format!("expire error: {}", e),
+											false,
+										)
+										.with_details(
+											handler::get_error_details(&e),
+										)
+									}
+								};
+								let mut extended = 0;
+								let mut lines = Vec::new();
+								for (i, (fingerprint, key_id, user_id)) in
+									keys.iter().enumerate()
+								{
+									if handle.is_cancelled() {
+										break;
+									}
+									handle.set_progress(Progress {
+										what: key_id.to_string(),
+										current: i as u64,
+										total: keys.len() as u64,
+									});
+									let result = match gpgme.set_expire(
+										fingerprint.to_string(),
+										expiry.as_deref().unwrap_or("0"),
+										subkeys,
+									) {
+										Ok(()) => {
+											extended += 1;
+											expiry.clone().unwrap_or_else(
+												|| {
+													String::from(
+														datepicker::NEVER,
+													)
+												},
+											)
+										}
+										Err(e) => format!("error: {}", e),
+									};
+									lines.push(format!(
+										"{} ({}): {}",
+										key_id, user_id, result
+									));
+								}
+								TaskOutput::new(
+									if handle.is_cancelled()
+										|| extended != keys.len()
+									{
+										OutputType::Warning
+									} else {
+										OutputType::Success
+									},
+									format!(
+										"expiration time extended for {}/{} keys",
+										extended,
+										keys.len()
+									),
+									extended != 0,
+								)
+								.with_lines(lines)
+							},
+						);
+					}
+					Err(e) => self.prompt.set_error(
+						format!("expire error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::SetTofuPolicy(key_id, policy) => {
+				match key_id
+					.or_else(|| {
+						self.keys_table.selected().map(|key| key.get_id())
+					})
+					.ok_or_else(|| anyhow!("no key selected"))
+					.and_then(|key_id| {
+						self.gpgme
+							.set_tofu_policy(key_id.to_string(), policy)?;
+						Ok(key_id)
+					}) {
+					Ok(key_id) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.prompt.set_output((
+							OutputType::Success,
+							format!(
+								"TOFU policy set to {}: {}",
+								handler::get_tofu_policy_name(policy),
+								key_id
+							),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("TOFU policy error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::SetKeyserver(key_id, url) => {
+				match key_id
+					.or_else(|| {
+						self.keys_table.selected().map(|key| key.get_id())
+					})
+					.ok_or_else(|| anyhow!("no key selected"))
+					.and_then(|key_id| {
+						self.gpgme.set_preferred_keyserver(
+							key_id.to_string(),
+							&url,
+						)?;
+						Ok(key_id)
+					}) {
+					Ok(key_id) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.key_preferences.clear();
+						self.prompt.set_output((
+							OutputType::Success,
+							if url == keyserver::NO_KEYSERVER {
+								format!(
+									"preferred keyserver removed: {}",
+									key_id
+								)
+							} else {
+								format!(
+									"preferred keyserver set to {}: {}",
+									url, key_id
+								)
+							},
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("keyserver error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::AddRevoker(key_id, revoker) => {
+				match key_id
+					.or_else(|| {
+						self.keys_table.selected().map(|key| key.get_id())
+					})
+					.ok_or_else(|| anyhow!("no key selected"))
+					.and_then(|key_id| {
+						let fingerprint = self
+							.gpgme
+							.add_revoker(key_id.to_string(), revoker)?;
+						Ok((key_id, fingerprint))
+					}) {
+					Ok((key_id, fingerprint)) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.key_preferences.clear();
+						self.prompt.set_output((
+							OutputType::Success,
+							format!(
+								"designated revoker added to {}: {}",
+								key_id, fingerprint
+							),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("revoker error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::Rotate(RotationAction::Start(key_id)) => match key_id {
+				None if self.rotation.is_some() => self.show_rotation_step(),
+				_ => match key_id
+					.or_else(|| {
+						self.keys_table.selected().map(|key| key.get_id())
+					})
+					.ok_or_else(|| anyhow!("no key selected"))
+					.and_then(|key_id| {
+						Ok(GpgKey::from(
+							self.gpgme.get_key(KeyType::Secret, key_id)?,
+						))
+					}) {
+					Ok(key) => {
+						self.rotation = Some(KeyRotation::new(
+							key.get_fingerprint(),
+							key.get_user_ids(),
+						));
+						self.show_rotation_step();
+					}
+					Err(e) => self.prompt.set_error(
+						format!("rotation error: {}", e),
+						handler::get_error_details(&e),
+					),
+				},
+			},
+			Command::Rotate(RotationAction::Run) => {
+				if self.run_rotation_step()? {
+					self.advance_rotation();
+				}
+			}
+			Command::Rotate(RotationAction::Skip) => match &self.rotation {
+				Some(rotation) if rotation.new_key.is_none() => {
+					self.prompt.set_output((
+						OutputType::Warning,
+						String::from("new key is not generated yet"),
+					))
+				}
+				_ => self.advance_rotation(),
+			},
+			Command::Rotate(RotationAction::Cancel) => {
+				self.rotation = None;
+				self.prompt.set_output((
+					OutputType::Warning,
+					String::from("key rotation is cancelled"),
+				))
+			}
+			Command::ShowPrefs => match self.keys_table.selected().cloned() {
+				Some(selected_key) => {
+					let fingerprint = selected_key.get_fingerprint();
+					if !self.key_preferences.contains_key(&fingerprint) {
+						self.load_key_preferences(vec![fingerprint]);
+					}
+					let [ciphers, hashes, compressions] = self
+						.get_key_preferences(&selected_key)
+						.cloned()
+						.unwrap_or_default()
+						.get_algorithm_names();
+					let mut items = vec![vec![
+						String::from("current"),
+						format!("  ├─cipher: {}", ciphers),
+						format!("  ├─digest: {}", hashes),
+						format!("  └─compression: {}", compressions),
+					]];
+					if self.is_own_key(&selected_key) {
+						items.extend(prefs::PREFERENCE_PRESETS.iter().map(
+							|(name, preferences)| {
+								vec![
+									name.to_string(),
+									format!(
+										"  └─{}",
+										if preferences.is_empty() {
+											"defaults of gpg"
+										} else {
+											*preferences
+										}
+									),
+								]
+							},
+						));
+					}
+					self.preferences = StatefulList::with_items(items);
+					self.preferences.state.select(Some(0));
+					self.state.show_prefs = true;
+				}
+				None => self.prompt.set_output(self.get_selection_warning()),
+			},
+			Command::SetPrefs(key_id, preferences) => {
+				self.state.show_prefs = false;
+				match key_id
+					.or_else(|| {
+						self.keys_table.selected().map(|key| key.get_id())
+					})
+					.ok_or_else(|| anyhow!("no key selected"))
+					.and_then(|key_id| {
+						self.gpgme.set_key_preferences(
+							key_id.to_string(),
+							&preferences,
+						)?;
+						Ok(key_id)
+					}) {
+					Ok(key_id) => {
+						self.refresh_keys(vec![key_id.to_string()])?;
+						self.key_preferences.clear();
+						self.prompt.set_output((
+							OutputType::Success,
+							format!("preferences updated: {}", key_id),
+						))
+					}
+					Err(e) => self.prompt.set_error(
+						format!("preferences error: {}", e),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::ChangePin(pin) => {
+				let home_dir = &self.gpgme.config.home_dir;
+				let result = pin.change(home_dir);
+				let retries = match pin.get_retries(home_dir) {
+					Ok(retries) => format!(" ({} retries left)", retries),
+					Err(_) => String::new(),
+				};
+				match result {
+					Ok(_) => self.prompt.set_output((
+						OutputType::Success,
+						format!("{} changed{}", pin, retries),
+					)),
+					Err(e) => self.prompt.set_error(
+						format!("{} change error: {}{}", pin, e, retries),
+						handler::get_error_details(&e),
+					),
+				}
+			}
+			Command::Undo => match self.trash.pop() {
+				Some(files) => {
+					match self.gpgme.import_keys(files.to_vec(), true) {
+						Ok(summary) => {
+							for file in &files {
+								if let Err(e) = fs::remove_file(file) {
+									log::error!(
+										"failed to remove {}: {}",
+										file,
+										e
+									);
+								}
+							}
+							self.refresh_keys(summary.get_fingerprints())?;
+							self.prompt.set_output((
+								OutputType::Success,
+								format!("{} key(s) restored", files.len()),
+							))
+						}
+						Err(e) => {
+							self.trash.push(files);
+							self.prompt.set_output((
+								OutputType::Failure,
+								format!("undo error: {}", e),
+							))
+						}
+					}
+				}
+				None => self.prompt.set_output((
+					OutputType::Failure,
+					String::from("nothing to undo"),
+				)),
+			},
+			Command::SignKey(ref keys, ref notations) if keys.len() > 1 => {
+				for key in keys {
+					self.run_command(Command::SignKey(
+						vec![key.to_string()],
+						notations.to_vec(),
+					))?;
+				}
+			}
+			Command::CleanKeys(key_ids, minimize) => {
+				let key_ids = key_ids.unwrap_or_else(|| {
+					self.keys
+						.get(&KeyType::Public)
+						.map(|keys| {
+							keys.iter()
+								.map(|key| key.get_fingerprint())
+								.collect()
+						})
+						.unwrap_or_default()
+				});
+				let description = if minimize {
+					"minimizing keys"
+				} else {
+					"cleaning keys"
+				};
+				self.spawn_task(description, move |config, handle| {
+					let mut gpgme = match GpgContext::new(config) {
+						Ok(gpgme) => gpgme,
+						Err(e) => {
+							return TaskOutput::new(
+								OutputType::Failure,
+								format!("clean error: {}", e),
+								false,
+							)
+							.with_details(handler::get_error_details(&e))
 						}
-						os_command.arg("--sign-key").arg(key)
+					};
+					let mut cleaned = 0;
+					let mut removed = 0;
+					let mut lines = Vec::new();
+					for (i, key_id) in key_ids.iter().enumerate() {
+						if handle.is_cancelled() {
+							break;
+						}
+						handle.set_progress(Progress {
+							what: key_id.to_string(),
+							current: i as u64,
+							total: key_ids.len() as u64,
+						});
+						let result =
+							gpgme.clean_key(key_id.to_string(), minimize);
+						lines.push(match result {
+							Ok(count) => {
+								cleaned += 1;
+								removed += count;
+								format!(
+									"{}: {} signatures removed",
+									key_id, count
+								)
+							}
+							Err(e) => format!("{}: error: {}", key_id, e),
+						});
 					}
+					let output_type =
+						if handle.is_cancelled() || cleaned != key_ids.len() {
+							OutputType::Warning
+						} else {
+							OutputType::Success
+						};
+					TaskOutput::new(
+						output_type,
+						format!(
+							"{} signatures removed from {}/{} keys",
+							removed,
+							cleaned,
+							key_ids.len()
+						),
+						true,
+					)
+					.with_lines(if key_ids.len() > 1 {
+						lines
+					} else {
+						Vec::new()
+					})
+				});
+			}
+			Command::SendKey(key_id) => {
+				self.spawn_task("sending key", move |config, handle| {
+					match util::send_key(&config, &key_id, &handle) {
+						Ok(true) => TaskOutput::new(
+							OutputType::Success,
+							format!("key sent to the keyserver: {}", key_id),
+							false,
+						),
+						Ok(false) => TaskOutput::new(
+							OutputType::Warning,
+							String::from("cancelled"),
+							false,
+						),
+						Err(e) => TaskOutput::new(
+							OutputType::Failure,
+							format!("send error: {}", e),
+							false,
+						)
+						.with_details(handler::get_error_details(&e)),
+					}
+				});
+			}
+			Command::RefreshKeys | Command::ImportKeys(_, true) => {
+				let mut os_command =
+					OsCommand::new(&self.gpgme.config.gpg_binary);
+				os_command
+					.arg("--batch")
+					.arg("--homedir")
+					.arg(self.gpgme.config.home_dir.as_os_str())
+					.arg("--import-options")
+					.arg("import-clean,self-sigs-only");
+				if self.gpgme.config.honor_keyserver_url {
+					os_command
+						.arg("--keyserver-options")
+						.arg("honor-keyserver-url");
+				}
+				let description = match command {
 					Command::ImportKeys(ref keys, _) => {
-						os_command.arg("--receive-keys").args(keys)
+						os_command.arg("--receive-keys").args(keys);
+						"receiving keys"
 					}
-					Command::ExportKeys(key_type, ref keys, true) => {
-						let path = self
-							.gpgme
-							.get_output_file(key_type, keys.to_vec())?;
-						success_msg =
-							Some(format!("export: {}", path.to_string_lossy()));
-						os_command
-							.arg("--output")
-							.arg(path)
-							.arg("--export-secret-subkeys")
-							.args(keys)
+					_ => {
+						os_command.arg("--refresh-keys");
+						"refreshing keys"
 					}
-					Command::RefreshKeys => os_command.arg("--refresh-keys"),
-					_ => os_command.arg("--full-gen-key"),
 				};
-				match os_command.spawn() {
-					Ok(mut child) => {
-						child.wait()?;
-						self.refresh()?;
-						if let Some(msg) = success_msg {
-							self.prompt.set_output((OutputType::Success, msg))
+				self.spawn_task(description, move |_, handle| {
+					match util::run_cancellable(os_command, &handle) {
+						Ok(Some((status, _))) if status.success() => {
+							TaskOutput::new(
+								OutputType::Success,
+								format!("{} finished", description),
+								true,
+							)
 						}
+						Ok(Some((_, stderr))) => TaskOutput::new(
+							OutputType::Failure,
+							format!(
+								"execution error: {}",
+								stderr.lines().last().unwrap_or_default()
+							),
+							true,
+						)
+						.with_details(
+							stderr.lines().map(String::from).collect(),
+						),
+						Ok(None) => TaskOutput::new(
+							OutputType::Warning,
+							String::from("cancelled"),
+							true,
+						),
+						Err(e) => TaskOutput::new(
+							OutputType::Failure,
+							format!("execution error: {}", e),
+							false,
+						),
 					}
-					Err(e) => self.prompt.set_output((
-						OutputType::Failure,
-						format!("execution error: {}", e),
-					)),
+				});
+			}
+			Command::Cancel => {
+				self.prompt.set_output(if self.worker.cancel() {
+					(OutputType::Warning, String::from("cancelling the task"))
+				} else {
+					(OutputType::Failure, String::from("no running task"))
+				});
+			}
+			Command::GenerateKey => {
+				self.prompt.clear();
+				self.prompt
+					.text
+					.set_value(format!("{}generate ", COMMAND_PREFIX));
+			}
+			Command::CreateKey(user_id, algo, expiry) => {
+				let today = Local::today().naive_local();
+				match expiry
+					.map(|expiry| datepicker::parse_expiry(&expiry, today))
+					.transpose()
+				{
+					Ok(expiry) => {
+						let mut flags = CreateKeyFlags::empty();
+						let expires = match expiry {
+							Some(Some(date)) => Some(Duration::from_secs(
+								(date - today).num_seconds().max(0) as u64,
+							)),
+							Some(None) => {
+								flags |= CreateKeyFlags::NOEXPIRE;
+								None
+							}
+							None => None,
+						};
+						let hook = self.hooks.get("post-generate").cloned();
+						self.spawn_task(
+							"generating key",
+							move |config, handle| {
+								let result = GpgContext::new(config).and_then(
+									|mut gpgme| {
+										gpgme.set_progress_callback(
+											handle.get_progress_callback(),
+										);
+										gpgme.generate_key(
+											&user_id, &algo, flags, expires,
+										)
+									},
+								);
+								match result {
+									Ok(fingerprint) => {
+										if let Some(hook) = hook {
+											hook::run_hook(
+												"post-generate",
+												&hook,
+												&[fingerprint.to_string()],
+												&[],
+											);
+										}
+										TaskOutput::new(
+											OutputType::Success,
+											format!(
+												"new key generated: {}",
+												fingerprint
+											),
+											true,
+										)
+									}
+									Err(e) => TaskOutput::new(
+										OutputType::Failure,
+										format!("generate error: {}", e),
+										false,
+									)
+									.with_details(
+										handler::get_error_details(&e),
+									),
+								}
+							},
+						);
+					}
+					Err(e) => self.prompt.set_error(
+						format!("generate error: {}", e),
+						handler::get_error_details(&e),
+					),
 				}
 			}
-			Command::ToggleDetail(true) => {
-				self.keys_table_detail.increase();
-				for key in self.keys_table.items.iter_mut() {
-					key.detail = self.keys_table_detail;
+			Command::SignKey(ref keys, ref notations) => {
+				let mut os_command = self.get_gpg_command();
+				for notation in notations {
+					os_command.arg("--cert-notation").arg(notation);
+				}
+				if let Some(default_key) = &self.gpgme.config.default_key {
+					os_command.arg("--default-key").arg(default_key);
 				}
-				for key in self.keys_table.default_items.iter_mut() {
-					key.detail = self.keys_table_detail;
+				let today = Local::today().naive_local();
+				if let Some(Ok(Some(date))) = self
+					.gpgme
+					.config
+					.cert_expire
+					.as_ref()
+					.map(|v| datepicker::parse_expiry(v, today))
+				{
+					os_command
+						.arg("--default-cert-expire")
+						.arg(format!("{}d", (date - today).num_days()));
 				}
+				os_command.arg("--sign-key").args(keys);
+				self.run_gpg_command(&mut os_command, None, keys.to_vec())?;
+			}
+			Command::ExportKeys(key_type, ref keys, true, _) => {
+				let path =
+					self.gpgme.get_output_file(key_type, keys.to_vec())?;
+				let mut os_command = self.get_gpg_command();
+				os_command
+					.arg("--output")
+					.arg(&path)
+					.arg("--export-secret-subkeys")
+					.args(keys);
+				self.run_gpg_command(
+					&mut os_command,
+					Some(format!("export: {}", path.to_string_lossy())),
+					Vec::new(),
+				)?;
+			}
+			Command::ToggleDetail(true) => {
+				self.keys_table_detail.increase();
+				self.keys_table_details.clear();
 			}
 			Command::ToggleDetail(false) => {
-				if let Some(index) = self.keys_table.state.tui.selected() {
-					if let Some(key) = self.keys_table.items.get_mut(index) {
-						key.detail.increase()
-					}
-					if self.keys_table.items.len()
-						== self.keys_table.default_items.len()
-					{
-						if let Some(key) =
-							self.keys_table.default_items.get_mut(index)
-						{
-							key.detail.increase()
-						}
-					}
+				if let Some(key) = self.keys_table.selected() {
+					let mut detail = self.get_key_detail(key);
+					detail.increase();
+					self.keys_table_details
+						.insert(key.get_fingerprint(), detail);
 				}
 			}
 			Command::ToggleTableSize => {
@@ -480,28 +4463,34 @@ impl<'a> App<'a> {
 				));
 			}
 			Command::Scroll(direction, false) => match direction {
-				ScrollDirection::Down(_) => {
-					if self.state.show_options {
-						self.options.next();
+				ScrollDirection::Down(value) => {
+					if self.state.show_output {
+						self.command_output.next_by(value.into());
+					} else if self.state.show_options {
+						self.options.next_by(value.into());
 						show_options = true;
 					} else if Tab::Help == self.tab {
-						self.key_bindings.next();
+						self.key_bindings.next_by(value.into());
 					} else {
-						self.keys_table.next();
+						self.keys_table.next_by(value.into());
 					}
 				}
-				ScrollDirection::Up(_) => {
-					if self.state.show_options {
-						self.options.previous();
+				ScrollDirection::Up(value) => {
+					if self.state.show_output {
+						self.command_output.previous_by(value.into());
+					} else if self.state.show_options {
+						self.options.previous_by(value.into());
 						show_options = true;
 					} else if Tab::Help == self.tab {
-						self.key_bindings.previous();
+						self.key_bindings.previous_by(value.into());
 					} else {
-						self.keys_table.previous();
+						self.keys_table.previous_by(value.into());
 					}
 				}
 				ScrollDirection::Top => {
-					if self.state.show_options {
+					if self.state.show_output {
+						self.command_output.state.select(Some(0));
+					} else if self.state.show_options {
 						self.options.state.select(Some(0));
 						show_options = true;
 					} else if Tab::Help == self.tab {
@@ -511,7 +4500,11 @@ impl<'a> App<'a> {
 					}
 				}
 				ScrollDirection::Bottom => {
-					if self.state.show_options {
+					if self.state.show_output {
+						self.command_output.state.select(Some(
+							self.command_output.items.len().saturating_sub(1),
+						));
+					} else if self.state.show_options {
 						self.options.state.select(Some(
 							self.options
 								.items
@@ -536,16 +4529,41 @@ impl<'a> App<'a> {
 				}
 				_ => {}
 			},
+			Command::Scroll(direction, true) if self.state.split_view => {
+				match direction {
+					ScrollDirection::Up(value) => {
+						(0..value).for_each(|_| self.key_tree.previous())
+					}
+					ScrollDirection::Down(value) => {
+						(0..value).for_each(|_| self.key_tree.next())
+					}
+					ScrollDirection::Right(_) => {
+						self.key_tree.set_expanded(true)
+					}
+					ScrollDirection::Left(_) => {
+						self.key_tree.set_expanded(false)
+					}
+					_ => {}
+				}
+			}
 			Command::Scroll(direction, true) => {
 				self.keys_table.scroll_row(direction);
 			}
+			Command::Set(option, value)
+				if option == "cert-expire" && value.is_empty() =>
+			{
+				self.date_picker = Some((
+					Command::Set(option, value),
+					DatePicker::new(Local::today().naive_local()),
+				))
+			}
 			Command::Set(option, value) => {
 				if option == *"prompt"
 					&& (value.starts_with(COMMAND_PREFIX)
 						| value.starts_with(SEARCH_PREFIX))
 				{
 					self.prompt.clear();
-					self.prompt.text = value;
+					self.prompt.text.set_value(value);
 				} else {
 					self.prompt.set_output(match option.as_str() {
 						"output" => {
@@ -556,124 +4574,420 @@ impl<'a> App<'a> {
 								(
 									OutputType::Success,
 									format!(
-										"output directory: {:?}",
-										self.gpgme.config.output_dir
+										"output directory: {:?}",
+										self.gpgme.config.output_dir
+									),
+								)
+							} else {
+								(
+									OutputType::Failure,
+									String::from("path does not exist"),
+								)
+							}
+						}
+						"mode" => {
+							if let Ok(mode) = Mode::from_str(&value) {
+								self.mode = mode;
+								self.keys_table
+									.set_anchor(mode == Mode::Visual);
+								(
+									OutputType::Success,
+									format!(
+										"mode: {}",
+										format!("{:?}", mode).to_lowercase()
+									),
+								)
+							} else {
+								(
+									OutputType::Failure,
+									String::from("invalid mode"),
+								)
+							}
+						}
+						"armor" => {
+							if let Ok(value) = FromStr::from_str(&value) {
+								self.gpgme.config.armor = value;
+								self.gpgme.apply_config();
+								(
+									OutputType::Success,
+									format!("armor: {}", value),
+								)
+							} else {
+								(
+									OutputType::Failure,
+									String::from(
+										"usage: set armor <true/false>",
+									),
+								)
+							}
+						}
+						"honor-keyserver-url" => {
+							if let Ok(value) = FromStr::from_str(&value) {
+								self.gpgme.config.honor_keyserver_url = value;
+								(
+									OutputType::Success,
+									format!("honor-keyserver-url: {}", value),
+								)
+							} else {
+								(
+									OutputType::Failure,
+									String::from(
+										"usage: set honor-keyserver-url \
+										 <true/false>",
+									),
+								)
+							}
+						}
+						"auto-key-locate" => {
+							match locate::parse_mechanisms(&value) {
+								Ok(mechanisms) => {
+									self.gpgme.config.auto_key_locate =
+										mechanisms;
+									(
+										OutputType::Success,
+										format!(
+											"auto-key-locate: {}",
+											self.get_locate_mechanisms()
+										),
+									)
+								}
+								Err(_) => (
+									OutputType::Failure,
+									String::from(
+										"usage: set auto-key-locate \
+										 <mechanisms>",
+									),
+								),
+							}
+						}
+						"export-name" => {
+							self.gpgme.config.export_name = if value.is_empty()
+							{
+								None
+							} else {
+								Some(value.to_string())
+							};
+							(
+								OutputType::Success,
+								format!(
+									"export name: {}",
+									self.gpgme
+										.config
+										.export_name
+										.as_deref()
+										.unwrap_or(DEFAULT_EXPORT_NAME)
+								),
+							)
+						}
+						"signer" => {
+							self.gpgme.config.default_key =
+								Some(value.to_string());
+							(OutputType::Success, format!("signer: {}", value))
+						}
+						"cert-expire" => match datepicker::parse_expiry(
+							&value,
+							Local::today().naive_local(),
+						) {
+							Ok(expiry) => {
+								self.gpgme.config.cert_expire =
+									expiry.map(|date| {
+										date.format(datepicker::DATE_FORMAT)
+											.to_string()
+									});
+								(
+									OutputType::Success,
+									format!(
+										"signature expiration: {}",
+										self.gpgme
+											.config
+											.cert_expire
+											.as_deref()
+											.unwrap_or(datepicker::NEVER)
+									),
+								)
+							}
+							Err(e) => (OutputType::Failure, e.to_string()),
+						},
+						"minimize" => {
+							self.keys_table.state.minimize_threshold =
+								value.parse().unwrap_or_default();
+							(
+								OutputType::Success,
+								format!(
+									"minimize threshold: {}",
+									self.keys_table.state.minimize_threshold
+								),
+							)
+						}
+						"detail" => {
+							if let Ok(detail_level) =
+								KeyDetail::from_str(&value)
+							{
+								if let Some(key) = self.keys_table.selected() {
+									self.keys_table_details.insert(
+										key.get_fingerprint(),
+										detail_level,
+									);
+								}
+								(
+									OutputType::Success,
+									format!("detail: {}", detail_level),
+								)
+							} else {
+								(
+									OutputType::Failure,
+									String::from("usage: set detail <level>"),
+								)
+							}
+						}
+						"margin" => {
+							self.keys_table_margin =
+								value.parse().unwrap_or_default();
+							(
+								OutputType::Success,
+								format!(
+									"table margin: {}",
+									self.keys_table_margin
+								),
+							)
+						}
+						"colored" => match value.parse() {
+							Ok(colored) => {
+								self.state.colored = colored;
+								(
+									OutputType::Success,
+									format!("colored: {}", self.state.colored),
+								)
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from("usage: set colored <true/false>"),
+							),
+						},
+						"splitview" => match value.parse() {
+							Ok(split_view) => {
+								self.state.split_view = split_view;
+								(
+									OutputType::Success,
+									format!(
+										"split view: {}",
+										self.state.split_view
+									),
+								)
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from(
+									"usage: set splitview <true/false>",
+								),
+							),
+						},
+						"clipboard" => match ClipboardBackend::from_str(&value)
+						{
+							Ok(backend) => match backend.get_context() {
+								Ok(clipboard) => {
+									self.clipboard = Some(clipboard);
+									self.state.clipboard = backend;
+									(
+										OutputType::Success,
+										format!(
+											"clipboard: {} ({})",
+											backend,
+											backend.resolve()
+										),
+									)
+								}
+								Err(e) => (
+									OutputType::Failure,
+									format!("clipboard error: {}", e),
+								),
+							},
+							Err(_) => (
+								OutputType::Failure,
+								String::from(
+									"usage: set clipboard <auto/x11/x11-bin/wayland>",
+								),
+							),
+						},
+						"clipboard-timeout" => match value.parse() {
+							Ok(timeout) => {
+								self.state.clipboard_timeout = timeout;
+								(
+									OutputType::Success,
+									format!(
+										"clipboard timeout: {}",
+										self.state.clipboard_timeout
+									),
+								)
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from(
+									"usage: set clipboard-timeout <seconds>",
+								),
+							),
+						},
+						"notify" => match value.parse() {
+							Ok(notify) => {
+								self.state.notify = notify;
+								(
+									OutputType::Success,
+									format!("notify: {}", self.state.notify),
+								)
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from("usage: set notify <true/false>"),
+							),
+						},
+						"redact" => match value.parse() {
+							Ok(redact) => {
+								self.state.redact = redact;
+								(
+									OutputType::Success,
+									format!("redact: {}", self.state.redact),
+								)
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from("usage: set redact <true/false>"),
+							),
+						},
+						"expiry-warn" => match value.parse() {
+							Ok(days) => {
+								self.state.expiry_warn = days;
+								(
+									OutputType::Success,
+									format!(
+										"expiry warn: {}",
+										self.state.expiry_warn
+									),
+								)
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from("usage: set expiry-warn <days>"),
+							),
+						},
+						"columns" => match ColumnLayout::from_str(&value) {
+							Ok(columns) => {
+								self.state.columns = columns;
+								(
+									OutputType::Success,
+									format!("columns: {}", self.state.columns),
+								)
+							}
+							Err(e) => (
+								OutputType::Failure,
+								format!("usage: set columns <layout> ({})", e),
+							),
+						},
+						"datefmt" => {
+							if handler::is_valid_date_format(&value) {
+								self.state.date_format = value;
+								(
+									OutputType::Success,
+									format!(
+										"datefmt: {}",
+										self.state.date_format
 									),
 								)
 							} else {
 								(
 									OutputType::Failure,
-									String::from("path does not exist"),
+									String::from("usage: set datefmt <format>"),
 								)
 							}
 						}
-						"mode" => {
-							if let Ok(mode) = Mode::from_str(&value) {
-								self.mode = mode;
+						"statusline" => {
+							if value.is_empty() || value == "default" {
+								self.state.status_line = None;
 								(
 									OutputType::Success,
-									format!(
-										"mode: {}",
-										format!("{:?}", mode).to_lowercase()
-									),
+									String::from("statusline: default"),
 								)
 							} else {
-								(
-									OutputType::Failure,
-									String::from("invalid mode"),
-								)
+								match statusline::validate_template(&value) {
+									Ok(()) => {
+										self.state.status_line = Some(value);
+										(
+											OutputType::Success,
+											format!(
+												"statusline: {}",
+												self.state
+													.status_line
+													.as_deref()
+													.unwrap_or_default()
+											),
+										)
+									}
+									Err(e) => {
+										(OutputType::Failure, e.to_string())
+									}
+								}
 							}
 						}
-						"armor" => {
-							if let Ok(value) = FromStr::from_str(&value) {
-								self.gpgme.config.armor = value;
-								self.gpgme.apply_config();
+						"log" => match value.parse() {
+							Ok(show_log) => {
+								self.state.show_log = show_log;
 								(
 									OutputType::Success,
-									format!("armor: {}", value),
+									format!("log: {}", self.state.show_log),
 								)
-							} else {
+							}
+							Err(_) => (
+								OutputType::Failure,
+								String::from("usage: set log <true/false>"),
+							),
+						},
+						"hide-unusable" => match value.parse() {
+							Ok(hide_unusable) => {
+								self.state.hide_unusable = hide_unusable;
+								self.keys_table.reset_state();
 								(
-									OutputType::Failure,
-									String::from(
-										"usage: set armor <true/false>",
+									OutputType::Success,
+									format!(
+										"hide unusable: {}",
+										self.state.hide_unusable
 									),
 								)
 							}
-						}
-						"signer" => {
-							self.gpgme.config.default_key =
-								Some(value.to_string());
-							(OutputType::Success, format!("signer: {}", value))
-						}
-						"minimize" => {
-							self.keys_table.state.minimize_threshold =
-								value.parse().unwrap_or_default();
-							(
-								OutputType::Success,
-								format!(
-									"minimize threshold: {}",
-									self.keys_table.state.minimize_threshold
+							Err(_) => (
+								OutputType::Failure,
+								String::from(
+									"usage: set hide-unusable <true/false>",
 								),
-							)
-						}
-						"detail" => {
-							if let Ok(detail_level) =
-								KeyDetail::from_str(&value)
-							{
-								if let Some(index) =
-									self.keys_table.state.tui.selected()
-								{
-									if let Some(key) =
-										self.keys_table.items.get_mut(index)
-									{
-										key.detail = detail_level;
-									}
-									if self.keys_table.items.len()
-										== self.keys_table.default_items.len()
-									{
-										if let Some(key) = self
-											.keys_table
-											.default_items
-											.get_mut(index)
-										{
-											key.detail = detail_level;
-										}
-									}
-								}
+							),
+						},
+						"watch" => match WatchMode::from_str(&value) {
+							Ok(watch) => {
+								self.state.watch = watch;
+								self.keyring_watcher.reset();
 								(
 									OutputType::Success,
-									format!("detail: {}", detail_level),
-								)
-							} else {
-								(
-									OutputType::Failure,
-									String::from("usage: set detail <level>"),
+									format!("watch: {}", self.state.watch),
 								)
 							}
-						}
-						"margin" => {
-							self.keys_table_margin =
-								value.parse().unwrap_or_default();
-							(
-								OutputType::Success,
-								format!(
-									"table margin: {}",
-									self.keys_table_margin
+							Err(_) => (
+								OutputType::Failure,
+								String::from(
+									"usage: set watch <off/notify/reload>",
 								),
-							)
-						}
-						"colored" => match value.parse() {
-							Ok(colored) => {
-								self.state.colored = colored;
+							),
+						},
+						"confirm" => match ConfirmLevel::from_str(&value) {
+							Ok(confirm) => {
+								self.state.confirm = confirm;
 								(
 									OutputType::Success,
-									format!("colored: {}", self.state.colored),
+									format!("confirm: {}", self.state.confirm),
 								)
 							}
 							Err(_) => (
 								OutputType::Failure,
-								String::from("usage: set colored <true/false>"),
+								String::from(
+									"usage: set confirm <simple/typed/strict>",
+								),
 							),
 						},
 						"color" => {
@@ -704,6 +5018,16 @@ impl<'a> App<'a> {
 				}
 			}
 			Command::Get(option) => {
+				if option == "pref-keyserver" {
+					if let Some(fingerprint) = self
+						.keys_table
+						.selected()
+						.map(|key| key.get_fingerprint())
+						.filter(|v| !self.key_preferences.contains_key(v))
+					{
+						self.load_key_preferences(vec![fingerprint]);
+					}
+				}
 				self.prompt.set_output(match option.as_str() {
 					"output" => (
 						OutputType::Success,
@@ -723,6 +5047,42 @@ impl<'a> App<'a> {
 						OutputType::Success,
 						format!("armor: {}", self.gpgme.config.armor),
 					),
+					"honor-keyserver-url" => (
+						OutputType::Success,
+						format!(
+							"honor-keyserver-url: {}",
+							self.gpgme.config.honor_keyserver_url
+						),
+					),
+					"pref-keyserver" => match self.keys_table.selected() {
+						Some(key) => (
+							OutputType::Success,
+							format!(
+								"preferred keyserver: {}",
+								self.get_preferred_keyserver(key)
+									.unwrap_or("not set")
+							),
+						),
+						None => self.get_selection_warning(),
+					},
+					"auto-key-locate" => (
+						OutputType::Success,
+						format!(
+							"auto-key-locate: {}",
+							self.get_locate_mechanisms()
+						),
+					),
+					"export-name" => (
+						OutputType::Success,
+						format!(
+							"export name: {}",
+							self.gpgme
+								.config
+								.export_name
+								.as_deref()
+								.unwrap_or(DEFAULT_EXPORT_NAME)
+						),
+					),
 					"signer" => (
 						OutputType::Success,
 						match &self.gpgme.config.default_key {
@@ -730,6 +5090,17 @@ impl<'a> App<'a> {
 							None => String::from("signer key is not specified"),
 						},
 					),
+					"cert-expire" => (
+						OutputType::Success,
+						format!(
+							"signature expiration: {}",
+							self.gpgme
+								.config
+								.cert_expire
+								.as_deref()
+								.unwrap_or(datepicker::NEVER)
+						),
+					),
 					"minimize" => (
 						OutputType::Success,
 						format!(
@@ -745,7 +5116,10 @@ impl<'a> App<'a> {
 							{
 								(
 									OutputType::Success,
-									format!("detail: {}", key.detail),
+									format!(
+										"detail: {}",
+										self.get_key_detail(key)
+									),
 								)
 							} else {
 								(
@@ -764,10 +5138,119 @@ impl<'a> App<'a> {
 						OutputType::Success,
 						format!("table margin: {}", self.keys_table_margin),
 					),
+					"scripts" => {
+						let mut commands = self
+							.scripts
+							.commands
+							.iter()
+							.map(|(name, command)| {
+								if command.description.is_empty() {
+									name.to_string()
+								} else {
+									format!(
+										"{} ({})",
+										name, command.description
+									)
+								}
+							})
+							.collect::<Vec<String>>();
+						commands.sort();
+						(
+							OutputType::Success,
+							if commands.is_empty() {
+								String::from("no scripts are loaded")
+							} else {
+								format!("scripts: {}", commands.join(", "))
+							},
+						)
+					}
+					"marks" => {
+						let mut marks = self
+							.marks
+							.iter()
+							.map(|(name, fingerprint)| {
+								format!("{}={}", name, fingerprint)
+							})
+							.collect::<Vec<String>>();
+						marks.sort();
+						(
+							OutputType::Success,
+							if marks.is_empty() {
+								String::from("no marks are set")
+							} else {
+								format!("marks: {}", marks.join(" "))
+							},
+						)
+					}
 					"colored" => (
 						OutputType::Success,
 						format!("colored: {}", self.state.colored),
 					),
+					"splitview" => (
+						OutputType::Success,
+						format!("split view: {}", self.state.split_view),
+					),
+					"clipboard" => (
+						OutputType::Success,
+						format!(
+							"clipboard: {} ({})",
+							self.state.clipboard,
+							self.state.clipboard.resolve()
+						),
+					),
+					"clipboard-timeout" => (
+						OutputType::Success,
+						format!(
+							"clipboard timeout: {}",
+							self.state.clipboard_timeout
+						),
+					),
+					"notify" => (
+						OutputType::Success,
+						format!("notify: {}", self.state.notify),
+					),
+					"redact" => (
+						OutputType::Success,
+						format!("redact: {}", self.state.redact),
+					),
+					"expiry-warn" => (
+						OutputType::Success,
+						format!("expiry warn: {}", self.state.expiry_warn),
+					),
+					"columns" => (
+						OutputType::Success,
+						format!("columns: {}", self.state.columns),
+					),
+					"statusline" => (
+						OutputType::Success,
+						format!(
+							"statusline: {}",
+							self.state
+								.status_line
+								.as_deref()
+								.unwrap_or("default")
+						),
+					),
+					"datefmt" => (
+						OutputType::Success,
+						format!("datefmt: {}", self.state.date_format),
+					),
+					"log" => (
+						OutputType::Success,
+						format!("log: {}", self.state.show_log),
+					),
+					"hide-unusable" => (
+						OutputType::Success,
+						format!("hide unusable: {}", self.state.hide_unusable),
+					),
+					"confirm" => (
+						OutputType::Success,
+						format!("confirm: {}", self.state.confirm),
+					),
+					"watch" => (
+						OutputType::Success,
+						format!("watch: {}", self.state.watch),
+					),
 					"color" => (
 						OutputType::Success,
 						format!(
@@ -792,47 +5275,85 @@ impl<'a> App<'a> {
 			}
 			Command::SwitchMode(mode) => {
 				if !(mode == Mode::Copy && self.keys_table.items.is_empty()) {
-					self.mode = mode;
+					self.set_mode(mode);
 					self.prompt
 						.set_output((OutputType::Action, mode.to_string()))
 				}
 			}
+			Command::Copy(_) | Command::SetMark(_)
+				if self.keys_table.selected().is_none() =>
+			{
+				self.prompt.set_output(self.get_selection_warning())
+			}
 			Command::Copy(copy_type) => {
-				let selected_key =
-					&self.keys_table.selected().expect("invalid selection");
-				let content = match copy_type {
-					Selection::TableRow(1) => Ok(selected_key
-						.get_subkey_info(
-							self.keys_table.state.size != TableSize::Normal,
-						)
-						.join("\n")),
-					Selection::TableRow(2) => Ok(selected_key
-						.get_user_info(
-							self.keys_table.state.size == TableSize::Minimized,
-						)
-						.join("\n")),
-					Selection::TableRow(_) => {
-						Err(anyhow!("invalid row number"))
-					}
-					Selection::Key => {
-						match self.gpgme.get_exported_keys(
-							match self.tab {
-								Tab::Keys(key_type) => key_type,
-								_ => KeyType::Public,
-							},
-							Some(vec![selected_key.get_id()]),
-						) {
-							Ok(key) => str::from_utf8(&key)
-								.map(|v| v.to_string())
-								.map_err(AnyhowError::from),
-							Err(e) => Err(e),
+				let content = match self.keys_table.selected() {
+					Some(selected_key) => match copy_type {
+						Selection::TableRow(1) => Ok(selected_key
+							.get_info(
+								&self.state.columns.first,
+								self.get_key_detail(selected_key),
+								&self.state.date_format,
+								self.keys_table.state.size != TableSize::Normal,
+								self.state.redact,
+							)
+							.join("\n")),
+						Selection::TableRow(2) => Ok(selected_key
+							.get_info(
+								&self.state.columns.second,
+								self.get_key_detail(selected_key),
+								&self.state.date_format,
+								self.keys_table.state.size
+									== TableSize::Minimized,
+								self.state.redact,
+							)
+							.join("\n")),
+						Selection::TableRow(_) => {
+							Err(anyhow!("invalid row number"))
 						}
-					}
-					Selection::KeyId => Ok(selected_key.get_id()),
-					Selection::KeyFingerprint => {
-						Ok(selected_key.get_fingerprint())
-					}
-					Selection::KeyUserId => Ok(selected_key.get_user_id()),
+						Selection::Key
+						| Selection::KeyMarkdown
+						| Selection::KeyHtml => {
+							match self.gpgme.get_exported_keys(
+								match self.tab {
+									Tab::Keys(key_type) => key_type,
+									_ => KeyType::Public,
+								},
+								Some(vec![selected_key.get_id()]),
+							) {
+								Ok(key) => str::from_utf8(&key)
+									.map(|v| copy_type.wrap_exported_key(v))
+									.map_err(AnyhowError::from),
+								Err(e) => Err(e),
+							}
+						}
+						Selection::KeyId => Ok(selected_key.get_id()),
+						Selection::KeyFingerprint => {
+							Ok(selected_key.get_fingerprint())
+						}
+						Selection::FormattedFingerprint(format) => {
+							Ok(format.format(&selected_key.get_fingerprint()))
+						}
+						Selection::KeyUserId => Ok(selected_key.get_user_id()),
+						Selection::SshKey => {
+							self.gpgme.get_ssh_key(&selected_key.get_id())
+						}
+						Selection::Template(ref name) => {
+							match self.copy_templates.get(name) {
+								Some(template) => {
+									Ok(selection::format_template(
+										template,
+										selected_key,
+										&self.state.date_format,
+									))
+								}
+								None => Err(anyhow!(
+									"unknown copy template: {}",
+									name
+								)),
+							}
+						}
+					},
+					None => Err(anyhow!("no key selected")),
 				};
 				match content {
 					Ok(content) => {
@@ -841,13 +5362,31 @@ impl<'a> App<'a> {
 							self.run_command(Command::Quit)?;
 						} else if let Some(clipboard) = self.clipboard.as_mut()
 						{
-							clipboard
-								.set_contents(content)
-								.expect("failed to set clipboard contents");
-							self.prompt.set_output((
-								OutputType::Success,
-								format!("{} copied to clipboard", copy_type),
-							));
+							let sensitive = copy_type.is_exported_key()
+								&& self.tab == Tab::Keys(KeyType::Secret);
+							let hash = clipboard::get_content_hash(&content);
+							self.prompt.set_output(
+								match clipboard.set_contents(content) {
+									Ok(()) => {
+										self.clipboard_clear = if sensitive {
+											Some((Instant::now(), hash))
+										} else {
+											None
+										};
+										(
+											OutputType::Success,
+											format!(
+												"{} copied to clipboard",
+												copy_type
+											),
+										)
+									}
+									Err(e) => (
+										OutputType::Failure,
+										format!("clipboard error: {}", e),
+									),
+								},
+							);
 						} else {
 							self.prompt.set_output((
 								OutputType::Failure,
@@ -862,17 +5401,27 @@ impl<'a> App<'a> {
 						));
 					}
 				}
-				self.mode = Mode::Normal;
+				self.set_mode(Mode::Normal);
 			}
 			Command::Paste => {
 				if let Some(clipboard) = self.clipboard.as_mut() {
-					self.prompt.clear();
-					self.prompt.text = format!(
-						":{}",
-						clipboard
-							.get_contents()
-							.expect("failed to get clipboard contents")
-					);
+					match clipboard.get_contents() {
+						Ok(contents)
+							if handler::is_armored_block(&contents) =>
+						{
+							self.confirm_command(Command::ImportClipboard)
+						}
+						Ok(contents) => {
+							self.prompt.clear();
+							self.prompt
+								.text
+								.set_value(format!(":{}", contents));
+						}
+						Err(e) => self.prompt.set_output((
+							OutputType::Failure,
+							format!("clipboard error: {}", e),
+						)),
+					}
 				} else {
 					self.prompt.set_output((
 						OutputType::Failure,
@@ -882,10 +5431,123 @@ impl<'a> App<'a> {
 			}
 			Command::EnableInput => self.prompt.enable_command_input(),
 			Command::Search(query) => {
-				self.prompt.text = format!("/{}", query.unwrap_or_default());
+				self.prompt
+					.text
+					.set_value(format!("/{}", query.unwrap_or_default()));
 				self.prompt.enable_search();
-				self.keys_table.items = self.keys_table.default_items.clone();
+				self.keys_table.reset_items();
+			}
+			Command::Filter(filter) => {
+				self.keys_table_filter = filter;
+				self.keys_table.reset_state();
+				self.prompt.set_output((
+					OutputType::Success,
+					format!(
+						"filter: {}",
+						filter.map_or(String::from("none"), |v| v.to_string())
+					),
+				));
+			}
+			Command::SetMark(name) => {
+				self.prompt.set_output(match self.keys_table.selected() {
+					Some(selected_key) => {
+						self.marks.insert(name, selected_key.get_fingerprint());
+						(
+							OutputType::Success,
+							format!(
+								"mark set: {} ({})",
+								name,
+								selected_key.get_id()
+							),
+						)
+					}
+					None => {
+						(OutputType::Failure, String::from("invalid selection"))
+					}
+				});
+			}
+			Command::JumpToMark(name) => match self.marks.get(&name).cloned() {
+				Some(fingerprint) => {
+					if !self.select_key(&fingerprint)? {
+						self.prompt.set_output((
+							OutputType::Failure,
+							format!("marked key is not found: {}", name),
+						));
+					}
+				}
+				None => self.prompt.set_output((
+					OutputType::Failure,
+					format!("mark is not set: {}", name),
+				)),
+			},
+			Command::Focus(ref query) => {
+				if !self.focus_key(query)? {
+					self.prompt.set_output((
+						OutputType::Failure,
+						format!("no keys found: {}", query),
+					));
+				}
+			}
+			Command::JumpToExpiring => {
+				let fingerprints = self
+					.get_expiring_keys()
+					.iter()
+					.map(|key| key.get_fingerprint())
+					.collect::<Vec<String>>();
+				let selected_key =
+					self.keys_table.selected().map(|key| key.get_fingerprint());
+				let next = fingerprints
+					.iter()
+					.position(|fpr| Some(fpr) == selected_key.as_ref())
+					.map_or(0, |i| (i + 1) % fingerprints.len());
+				match fingerprints.get(next) {
+					Some(fingerprint) => {
+						if self.tab != Tab::Keys(KeyType::Secret) {
+							self.run_command(Command::ListKeys(
+								KeyType::Secret,
+							))?;
+						}
+						if !self.select_key(fingerprint)? {
+							self.prompt.set_output((
+								OutputType::Failure,
+								String::from("expiring key is not found"),
+							));
+						}
+					}
+					None => self.prompt.set_output((
+						OutputType::Success,
+						String::from("no expiring keys"),
+					)),
+				}
+			}
+			Command::RecordMacro(Some(register)) => {
+				self.macros.start(register);
+				self.prompt.set_output((
+					OutputType::Action,
+					format!("recording @{}", register),
+				));
+			}
+			Command::RecordMacro(None) => {
+				self.prompt.set_output(match self.macros.stop() {
+					Some((register, len)) => (
+						OutputType::Success,
+						format!("macro recorded: @{} ({} keys)", register, len),
+					),
+					None => (
+						OutputType::Warning,
+						String::from("no macro is being recorded"),
+					),
+				})
 			}
+			Command::ReplayMacro(register, _) => self.prompt.set_output((
+				OutputType::Failure,
+				format!("macro is not recorded: {}", register),
+			)),
+			Command::RunScript(ref name, _) => self.prompt.set_output((
+				OutputType::Failure,
+				format!("script not found: {}", name),
+			)),
+			Command::Repeat => self.run_command(self.get_repeat_command())?,
 			Command::NextTab => {
 				self.run_command(self.tab.next().get_command())?
 			}
@@ -915,6 +5577,7 @@ mod tests {
 		let config = GpgConfig::new(&args)?;
 		let mut context = GpgContext::new(config)?;
 		let mut app = App::new(&mut context, &args)?;
+		app.wait_for_keys()?;
 		app.run_command(Command::Refresh)?;
 
 		app.run_command(Command::ShowHelp)?;
@@ -923,18 +5586,33 @@ mod tests {
 		assert!(app.state.show_options);
 
 		app.run_command(Command::ListKeys(KeyType::Public))?;
-		app.run_command(Command::ToggleDetail(false))?;
-		let mut detail = app.keys_table_detail.clone();
+		let selected_index = app.keys_table.items.len() - 1;
+		app.keys_table.state.tui.select(Some(selected_index));
+		app.refresh()?;
+		assert_eq!(Some(selected_index), app.keys_table.state.tui.selected());
+		let fingerprint = app.keys_table.items[0].get_fingerprint();
+		assert!(app.focus_key(&format!("fpr:{}", fingerprint))?);
+		assert_eq!(Some(0), app.keys_table.state.tui.selected());
+		assert!(!app.focus_key("fpr:xyz")?);
+		app.keys_table.state.tui.select(Some(selected_index));
+		let mut detail = app.keys_table_detail;
 		detail.increase();
+		app.run_command(Command::ToggleDetail(false))?;
+		assert_eq!(
+			detail,
+			app.get_key_detail(&app.keys_table.items[selected_index])
+		);
+		assert_ne!(detail, app.keys_table_detail);
 		app.run_command(Command::ToggleDetail(true))?;
 		assert_eq!(detail, app.keys_table_detail);
+		assert!(app.keys_table_details.is_empty());
 
 		let prompt_text = format!("{}test", COMMAND_PREFIX);
 		app.run_command(Command::Set(
 			String::from("prompt"),
 			prompt_text.to_string(),
 		))?;
-		assert_eq!(prompt_text, app.prompt.text);
+		assert_eq!(prompt_text, app.prompt.text.value);
 
 		let home_dir =
 			dirs_next::home_dir().unwrap().to_str().unwrap().to_string();
@@ -943,16 +5621,30 @@ mod tests {
 			home_dir.to_string(),
 		))?;
 		app.run_command(Command::Get(String::from("output")))?;
-		assert!(app.prompt.text.contains(&home_dir));
+		assert!(app.prompt.text.value.contains(&home_dir));
 
 		let mut test_values = vec![
 			("output", "/tmp"),
 			("mode", "normal"),
 			("armor", "true"),
+			("honor-keyserver-url", "true"),
 			("signer", "0x0"),
 			("minimize", "10"),
 			("margin", "2"),
 			("colored", "true"),
+			("splitview", "true"),
+			("log", "true"),
+			("clipboard", "wayland"),
+			("clipboard-timeout", "10"),
+			("notify", "false"),
+			("redact", "true"),
+			("expiry-warn", "7"),
+			("datefmt", "%d %b %Y"),
+			("statusline", "{tab} {selection}/{total}"),
+			("columns", "uid|flags,id"),
+			("hide-unusable", "true"),
+			("confirm", "strict"),
+			("watch", "reload"),
 			("color", "#123123"),
 		];
 		if cfg!(feature = "gpg-tests") {
@@ -965,8 +5657,8 @@ mod tests {
 			))?;
 			app.run_command(Command::Get(option.to_string()))?;
 			assert!(
-				(app.prompt.text == format!("{}: {}", option, value))
-					|| app.prompt.text.contains(value)
+				(app.prompt.text.value == format!("{}: {}", option, value))
+					|| app.prompt.text.value.contains(value)
 			);
 		}
 
@@ -976,11 +5668,77 @@ mod tests {
 
 		app.run_command(Command::EnableInput)?;
 		assert!(app.prompt.is_command_input_enabled());
-		assert_eq!(COMMAND_PREFIX.to_string(), app.prompt.text);
+		assert_eq!(COMMAND_PREFIX.to_string(), app.prompt.text.value);
 
 		app.run_command(Command::Search(Some(String::from("x"))))?;
 		assert!(app.prompt.is_search_enabled());
-		assert_eq!(format!("{}x", SEARCH_PREFIX), app.prompt.text);
+		assert_eq!(format!("{}x", SEARCH_PREFIX), app.prompt.text.value);
+
+		app.run_command(Command::Filter(Some(Filter::Expired)))?;
+		assert_eq!(Some(Filter::Expired), app.keys_table_filter);
+		assert_eq!("filter: expired", app.prompt.text.value);
+		app.run_command(Command::Filter(None))?;
+		assert_eq!(None, app.keys_table_filter);
+
+		app.run_command(Command::Confirm(Box::new(Command::DeleteKey(
+			KeyType::Secret,
+			vec![String::from("0x0123456789ABCDEF")],
+		))))?;
+		assert!(app.prompt.is_typed_confirmation());
+		assert_eq!(vec![String::from("89ABCDEF")], app.prompt.answers);
+		app.prompt.clear();
+
+		app.run_command(Command::Undo)?;
+		assert_eq!("nothing to undo", app.prompt.text.value);
+
+		app.run_command(Command::JumpToMark('a'))?;
+		assert_eq!("mark is not set: a", app.prompt.text.value);
+		app.run_command(Command::Get(String::from("marks")))?;
+		assert_eq!("no marks are set", app.prompt.text.value);
+
+		let session = app.get_session();
+		app.run_command(Command::ToggleTableSize)?;
+		app.run_command(Command::Filter(Some(Filter::CanSign)))?;
+		app.restore_session(session.clone())?;
+		assert_eq!(session, app.get_session());
+
+		let text = app.get_keys_table_text(false);
+		assert!(text.lines().count() >= app.keys_table.items.len());
+		assert!(!text.contains('\x1b'));
+		assert!(app.get_keys_table_text(true).contains('\x1b'));
+
+		app.run_command(Command::SignKey(Vec::new(), Vec::new()))?;
+		assert_eq!("invalid selection", app.prompt.text.value);
+		app.keys_table.items.clear();
+		app.run_command(Command::Copy(Selection::KeyId))?;
+		assert_eq!("no keys match the search/filter", app.prompt.text.value);
+		app.keys_table.reset_state();
+
+		app.last_command = None;
+		app.run_command(Command::Repeat)?;
+		assert_eq!("nothing to repeat", app.prompt.text.value);
+		app.run_command(Command::Set(
+			String::from("armor"),
+			String::from("true"),
+		))?;
+		app.run_command(Command::Scroll(ScrollDirection::Down(1), false))?;
+		app.gpgme.config.armor = false;
+		app.run_command(Command::Repeat)?;
+		assert!(app.gpgme.config.armor);
+		app.run_command(Command::Set(
+			String::from("armor"),
+			String::from("false"),
+		))?;
+		app.run_command(Command::RecordMacro(None))?;
+		assert_eq!("no macro is being recorded", app.prompt.text.value);
+		app.run_command(Command::RecordMacro(Some('a')))?;
+		assert_eq!("recording @a", app.prompt.text.value);
+		app.run_command(Command::RecordMacro(None))?;
+		assert_eq!("macro recorded: @a (0 keys)", app.prompt.text.value);
+		app.run_command(Command::ReplayMacro('b', 1))?;
+		assert_eq!("macro is not recorded: b", app.prompt.text.value);
+		app.run_command(Command::RunScript(String::from("x"), Vec::new()))?;
+		assert_eq!("script not found: x", app.prompt.text.value);
 
 		app.tab = Tab::Keys(KeyType::Public);
 		app.run_command(Command::NextTab)?;
@@ -988,17 +5746,28 @@ mod tests {
 		app.run_command(Command::NextTab)?;
 		assert_eq!(Tab::Keys(KeyType::Public), app.tab);
 
-		app.tick();
+		app.run_command(Command::ExportKeys(
+			KeyType::Secret,
+			Vec::new(),
+			true,
+			ExportDestination::Clipboard,
+		))?;
+		assert_eq!(
+			"subkeys can only be exported to a file",
+			app.prompt.text.value
+		);
+
+		app.tick()?;
 		app.run_command(Command::ShowOutput(
 			OutputType::Success,
 			String::from("test"),
 		))?;
-		assert_eq!("test", app.prompt.text);
+		assert_eq!("test", app.prompt.text.value);
 		thread::sleep(Duration::from_millis(
 			(MESSAGE_DURATION + 10).try_into().unwrap(),
 		));
-		app.tick();
-		assert_eq!("", app.prompt.text);
+		app.tick()?;
+		assert_eq!("", app.prompt.text.value);
 
 		app.run_command(Command::Quit)?;
 		assert!(!app.state.running);
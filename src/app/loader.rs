@@ -0,0 +1,145 @@
+use crate::gpg::config::GpgConfig;
+use crate::gpg::context::GpgContext;
+use crate::gpg::key::{GpgKey, KeyType};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::mem;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// Message that is sent from the loader threads.
+#[derive(Debug)]
+enum LoaderMessage {
+	/// Number of the keys that are listed so far.
+	Progress(KeyType, usize),
+	/// Result of the key listing.
+	Done(KeyType, Result<Vec<GpgKey>>),
+}
+
+/// Loader of the keys in the background.
+///
+/// Public and secret keys are listed concurrently on separate
+/// threads (with their own contexts) and merged once both are done.
+#[derive(Debug)]
+pub struct KeyLoader {
+	/// Receiver of the loader messages.
+	receiver: Receiver<LoaderMessage>,
+	/// Number of the listed keys.
+	counts: HashMap<KeyType, usize>,
+	/// Keys that are loaded so far.
+	keys: HashMap<KeyType, Vec<GpgKey>>,
+}
+
+impl KeyLoader {
+	/// Spawns the loader threads for listing the keys.
+	pub fn spawn(config: GpgConfig) -> Self {
+		let (sender, receiver) = mpsc::channel();
+		for key_type in &[KeyType::Public, KeyType::Secret] {
+			let key_type = *key_type;
+			let (sender, config) = (sender.clone(), config.clone());
+			thread::spawn(move || {
+				let result = GpgContext::new(config).and_then(|mut context| {
+					context.get_keys_with_progress(key_type, |count| {
+						let _ = sender
+							.send(LoaderMessage::Progress(key_type, count));
+					})
+				});
+				let _ = sender.send(LoaderMessage::Done(key_type, result));
+			});
+		}
+		Self {
+			receiver,
+			counts: HashMap::new(),
+			keys: HashMap::new(),
+		}
+	}
+
+	/// Returns the number of the listed keys of the given type.
+	pub fn get_count(&self, key_type: KeyType) -> usize {
+		self.counts.get(&key_type).copied().unwrap_or_default()
+	}
+
+	/// Returns the status of the loader for showing in the status bar.
+	pub fn get_status(&self) -> String {
+		format!(
+			"loading: {} public, {} secret",
+			self.get_count(KeyType::Public),
+			self.get_count(KeyType::Secret)
+		)
+	}
+
+	/// Handles the messages of the loader threads.
+	///
+	/// Returns the keys once both listings are done.
+	pub fn poll(&mut self) -> Option<Result<HashMap<KeyType, Vec<GpgKey>>>> {
+		loop {
+			match self.receiver.try_recv() {
+				Ok(message) => {
+					if let Some(result) = self.handle_message(message) {
+						return Some(result);
+					}
+				}
+				Err(TryRecvError::Empty) => return None,
+				Err(TryRecvError::Disconnected) => {
+					return Some(Err(anyhow!("key loader is disconnected")))
+				}
+			}
+		}
+	}
+
+	/// Blocks until both listings are done and returns the keys.
+	pub fn wait(&mut self) -> Result<HashMap<KeyType, Vec<GpgKey>>> {
+		loop {
+			let message = self.receiver.recv()?;
+			if let Some(result) = self.handle_message(message) {
+				return result;
+			}
+		}
+	}
+
+	/// Handles the given message and returns the keys
+	/// if all of them are loaded.
+	fn handle_message(
+		&mut self,
+		message: LoaderMessage,
+	) -> Option<Result<HashMap<KeyType, Vec<GpgKey>>>> {
+		match message {
+			LoaderMessage::Progress(key_type, count) => {
+				self.counts.insert(key_type, count);
+				None
+			}
+			LoaderMessage::Done(key_type, Ok(keys)) => {
+				self.counts.insert(key_type, keys.len());
+				self.keys.insert(key_type, keys);
+				if self.keys.len() == 2 {
+					Some(Ok(mem::take(&mut self.keys)))
+				} else {
+					None
+				}
+			}
+			LoaderMessage::Done(_, Err(e)) => Some(Err(e)),
+		}
+	}
+}
+
+#[cfg(feature = "gpg-tests")]
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::args::Args;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_loader() -> Result<()> {
+		let args = Args::default();
+		let config = GpgConfig::new(&args)?;
+		let mut context = GpgContext::new(config.clone())?;
+		let mut loader = KeyLoader::spawn(config);
+		let keys = loader.wait()?;
+		for (key_type, expected_keys) in context.get_all_keys(None)? {
+			assert_eq!(expected_keys.len(), keys[&key_type].len());
+			assert_eq!(expected_keys.len(), loader.get_count(key_type));
+		}
+		assert!(loader.get_status().starts_with("loading: "));
+		Ok(())
+	}
+}
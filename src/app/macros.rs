@@ -0,0 +1,105 @@
+use crossterm::event::KeyEvent;
+use std::collections::HashMap;
+
+/// Max depth of the nested macro replays (e.g. `@a` inside `@b`).
+const MAX_DEPTH: u8 = 10;
+
+/// Recorder of the key sequences (macros) by their registers.
+#[derive(Clone, Debug, Default)]
+pub struct MacroRecorder {
+	/// Recorded key events by their registers.
+	registers: HashMap<char, Vec<KeyEvent>>,
+	/// Register and the key events that are being recorded.
+	recording: Option<(char, Vec<KeyEvent>)>,
+	/// Depth of the replay that is in progress.
+	depth: u8,
+}
+
+impl MacroRecorder {
+	/// Starts recording the key events into the given register.
+	pub fn start(&mut self, register: char) {
+		self.recording = Some((register, Vec::new()));
+	}
+
+	/// Stops recording and saves the recorded key events.
+	///
+	/// Returns the register along with the number of recorded key events.
+	pub fn stop(&mut self) -> Option<(char, usize)> {
+		let (register, key_events) = self.recording.take()?;
+		let len = key_events.len();
+		self.registers.insert(register, key_events);
+		Some((register, len))
+	}
+
+	/// Returns the register that is being recorded.
+	pub fn get_recording(&self) -> Option<char> {
+		self.recording.as_ref().map(|(register, _)| *register)
+	}
+
+	/// Returns `true` if a macro is being recorded.
+	pub fn is_recording(&self) -> bool {
+		self.recording.is_some()
+	}
+
+	/// Records the key event if a macro is being recorded.
+	///
+	/// Key events that are sent during a replay are not recorded.
+	pub fn record(&mut self, key_event: KeyEvent) {
+		if self.depth == 0 {
+			if let Some((_, key_events)) = self.recording.as_mut() {
+				key_events.push(key_event);
+			}
+		}
+	}
+
+	/// Returns the key events of the given register.
+	pub fn get(&self, register: char) -> Option<Vec<KeyEvent>> {
+		self.registers.get(&register).cloned()
+	}
+
+	/// Enters a replay.
+	///
+	/// Returns `false` if the max depth of the nested replays is reached.
+	pub fn enter(&mut self) -> bool {
+		if self.depth >= MAX_DEPTH {
+			false
+		} else {
+			self.depth += 1;
+			true
+		}
+	}
+
+	/// Exits the replay.
+	pub fn exit(&mut self) {
+		self.depth = self.depth.saturating_sub(1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crossterm::event::{KeyCode as Key, KeyModifiers as Modifiers};
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_macros() {
+		let mut macros = MacroRecorder::default();
+		let key_event = KeyEvent::new(Key::Char('j'), Modifiers::NONE);
+		macros.record(key_event);
+		assert_eq!(None, macros.stop());
+		macros.start('a');
+		assert!(macros.is_recording());
+		assert_eq!(Some('a'), macros.get_recording());
+		macros.record(key_event);
+		assert!(macros.enter());
+		macros.record(key_event);
+		macros.exit();
+		assert_eq!(Some(('a', 1)), macros.stop());
+		assert!(!macros.is_recording());
+		assert_eq!(Some(vec![key_event]), macros.get('a'));
+		assert_eq!(None, macros.get('b'));
+		for _ in 0..MAX_DEPTH {
+			assert!(macros.enter());
+		}
+		assert!(!macros.enter());
+	}
+}
@@ -0,0 +1,93 @@
+use crate::app::command::Command;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Customization of the options menu.
+///
+/// Entries are matched by their descriptions (case-insensitive)
+/// as they are shown in the menu (e.g. "send key to the keyserver").
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct MenuLayout {
+	/// Commands to add to the menu (e.g. ":set armor true").
+	#[serde(default)]
+	pub add: Vec<String>,
+	/// Entries to remove from the menu.
+	#[serde(default)]
+	pub remove: Vec<String>,
+	/// Entries to show on top of the menu in the given order.
+	#[serde(default)]
+	pub order: Vec<String>,
+}
+
+/// Checks if the description of the command contains the given value.
+fn matches(command: &Command, value: &str) -> bool {
+	command
+		.to_string()
+		.to_lowercase()
+		.contains(&value.to_lowercase())
+}
+
+impl MenuLayout {
+	/// Applies the layout to the given menu entries.
+	///
+	/// The first entry (i.e. "close menu") is always kept on top.
+	pub fn apply(&self, mut commands: Vec<Command>) -> Vec<Command> {
+		let first = if commands.first() == Some(&Command::None) {
+			Some(commands.remove(0))
+		} else {
+			None
+		};
+		commands.retain(|command| {
+			!self.remove.iter().any(|value| matches(command, value))
+		});
+		commands.extend(self.add.iter().filter_map(|value| {
+			match Command::from_str(value) {
+				Ok(command) => Some(command),
+				Err(_) => {
+					log::warn!("invalid menu command: {:?}", value);
+					None
+				}
+			}
+		}));
+		commands.sort_by_key(|command| {
+			self.order
+				.iter()
+				.position(|value| matches(command, value))
+				.unwrap_or(self.order.len())
+		});
+		first.into_iter().chain(commands).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::gpg::key::KeyType;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_menu() {
+		let commands = vec![
+			Command::None,
+			Command::ShowHelp,
+			Command::Refresh,
+			Command::SendKey(String::from("0x0")),
+			Command::Quit,
+		];
+		assert_eq!(commands, MenuLayout::default().apply(commands.to_vec()));
+		let layout = MenuLayout {
+			add: vec![String::from(":list sec"), String::from(":xyz")],
+			remove: vec![String::from("Send key")],
+			order: vec![String::from("quit"), String::from("list")],
+		};
+		assert_eq!(
+			vec![
+				Command::None,
+				Command::Quit,
+				Command::ListKeys(KeyType::Secret),
+				Command::ShowHelp,
+				Command::Refresh,
+			],
+			layout.apply(commands)
+		);
+	}
+}
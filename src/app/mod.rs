@@ -24,12 +24,51 @@ pub mod tab;
 /// Input and command handler.
 pub mod handler;
 
+/// Clipboard helper.
+pub mod clipboard;
+
 /// Selection helper.
 pub mod selection;
 
+/// Keys table filter.
+pub mod filter;
+
+/// Export options.
+pub mod export;
+
+/// Post-action hooks.
+pub mod hook;
+
+/// Options menu customization.
+pub mod menu;
+
+/// Key macro recorder.
+pub mod macros;
+
+/// Scripts for custom commands.
+pub mod script;
+
+/// Search query parser.
+pub mod search;
+
+/// Session persistence.
+pub mod session;
+
+/// Background task executor.
+pub mod worker;
+
+/// Background key loader.
+pub mod loader;
+
+/// Keyring watcher.
+pub mod watch;
+
 /// Style helper.
 pub mod style;
 
+/// Status line template.
+pub mod statusline;
+
 /// Key bindings helper.
 pub mod keys;
 
@@ -39,5 +78,8 @@ pub mod banner;
 /// Application splash screen.
 pub mod splash;
 
+/// Key rotation wizard.
+pub mod rotate;
+
 /// Utilities.
 pub mod util;
@@ -1,6 +1,10 @@
 use crate::app::command::Command;
+use crate::widget::input::Input;
+use crate::widget::toast::ToastStack;
+use chrono::{DateTime, Local};
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
 use std::time::Instant;
 
 /// Prefix character for indicating command input.
@@ -56,6 +60,44 @@ impl From<String> for OutputType {
 	}
 }
 
+/// Strictness of the confirmation for deleting secret keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ConfirmLevel {
+	/// Press 'y' to confirm.
+	Simple,
+	/// Type the short key ID or "yes" to confirm.
+	#[default]
+	Typed,
+	/// Type the short key ID to confirm.
+	Strict,
+}
+
+impl Display for ConfirmLevel {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Simple => "simple",
+				Self::Typed => "typed",
+				Self::Strict => "strict",
+			}
+		)
+	}
+}
+
+impl FromStr for ConfirmLevel {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"simple" => Ok(Self::Simple),
+			"typed" => Ok(Self::Typed),
+			"strict" => Ok(Self::Strict),
+			_ => Err(String::from("could not parse the confirmation level")),
+		}
+	}
+}
+
 /// Application prompt which is responsible for
 /// handling user input ([`text`]), showing the
 /// output of [`commands`] and ask for confirmation.
@@ -65,17 +107,27 @@ impl From<String> for OutputType {
 #[derive(Clone, Debug, Default)]
 pub struct Prompt {
 	/// Input/output text.
-	pub text: String,
+	pub text: Input,
 	/// Output type.
 	pub output_type: OutputType,
 	/// Clock for tracking the duration of output messages.
 	pub clock: Option<Instant>,
 	/// Command that will be confirmed for execution.
 	pub command: Option<Command>,
+	/// Accepted answers for the typed confirmation of the command.
+	pub answers: Vec<String>,
+	/// Typed answer for confirmation.
+	pub answer: String,
 	/// Command history.
 	pub history: Vec<String>,
 	/// Index of the selected command from history.
 	pub history_index: usize,
+	/// Recent output messages.
+	pub messages: ToastStack<(OutputType, String)>,
+	/// All output messages with their timestamps.
+	pub log: Vec<(DateTime<Local>, OutputType, String)>,
+	/// Last error message and its details.
+	pub error: Option<(String, Vec<String>)>,
 }
 
 impl Prompt {
@@ -86,9 +138,9 @@ impl Prompt {
 	/// * `/`: search
 	fn enable(&mut self, prefix: char) {
 		self.text = if self.text.is_empty() || self.clock.is_some() {
-			prefix.to_string()
+			Input::new(prefix)
 		} else {
-			format!("{}{}", prefix, &self.text[1..self.text.len()])
+			Input::new(format!("{}{}", prefix, &self.text.value[1..]))
 		};
 		self.output_type = OutputType::None;
 		self.clock = None;
@@ -108,7 +160,7 @@ impl Prompt {
 
 	/// Checks if the command input is enabled.
 	pub fn is_command_input_enabled(&self) -> bool {
-		self.text.starts_with(COMMAND_PREFIX)
+		self.text.value.starts_with(COMMAND_PREFIX)
 	}
 
 	/// Enables the search.
@@ -118,36 +170,103 @@ impl Prompt {
 
 	/// Checks if the search is enabled.
 	pub fn is_search_enabled(&self) -> bool {
-		self.text.starts_with(SEARCH_PREFIX)
+		self.text.value.starts_with(SEARCH_PREFIX)
 	}
 
 	/// Sets the output message.
 	pub fn set_output<S: AsRef<str>>(&mut self, output: (OutputType, S)) {
 		let (output_type, message) = output;
 		self.output_type = output_type;
-		self.text = message.as_ref().to_string();
+		self.text = Input::new(message.as_ref());
 		self.clock = Some(Instant::now());
+		match self.output_type {
+			OutputType::Success => log::info!("{}", self.text),
+			OutputType::Warning => log::warn!("{}", self.text),
+			OutputType::Failure => log::error!("{}", self.text),
+			_ => log::debug!("{}", self.text),
+		}
+		if self.output_type == OutputType::Failure {
+			self.error = Some((self.text.to_string(), Vec::new()));
+		}
+		if let OutputType::Success | OutputType::Warning | OutputType::Failure =
+			self.output_type
+		{
+			self.messages
+				.push((self.output_type.clone(), self.text.to_string()));
+			self.log.push((
+				Local::now(),
+				self.output_type.clone(),
+				self.text.to_string(),
+			));
+		}
 	}
 
-	/// Sets the command that will be asked to confirm.
-	pub fn set_command(&mut self, command: Command) {
-		self.text = format!("press 'y' to {}", command);
+	/// Sets the error message along with its details.
+	///
+	/// See [`get_error_details`].
+	///
+	/// [`get_error_details`]: crate::gpg::handler::get_error_details
+	pub fn set_error<S: AsRef<str>>(
+		&mut self,
+		message: S,
+		details: Vec<String>,
+	) {
+		self.set_output((OutputType::Failure, message));
+		for detail in &details {
+			log::error!("{}", detail);
+		}
+		self.error = Some((self.text.to_string(), details));
+	}
+
+	/// Sets the command that will be confirmed
+	/// by typing one of the given answers.
+	pub fn set_typed_command(
+		&mut self,
+		command: Command,
+		answers: Vec<String>,
+	) {
+		self.text = Input::new(format!(
+			"type '{}' to {}: ",
+			answers.join("' or '"),
+			command
+		));
 		self.output_type = OutputType::Action;
 		self.command = Some(command);
+		self.answers = answers;
+		self.answer.clear();
 		self.clock = Some(Instant::now());
 	}
 
+	/// Checks if the command is waiting for a typed confirmation.
+	pub fn is_typed_confirmation(&self) -> bool {
+		self.command.is_some() && !self.answers.is_empty()
+	}
+
+	/// Checks if the typed answer confirms the command.
+	pub fn is_confirmed(&self) -> bool {
+		let answer = self.answer.trim();
+		let answer = answer.strip_prefix("0x").unwrap_or(answer);
+		self.answers
+			.iter()
+			.any(|expected| expected.eq_ignore_ascii_case(answer))
+	}
+
 	/// Select the next command.
+	///
+	/// Moves the cursor to the next line instead if there is one.
 	pub fn next(&mut self) {
+		if self.text.move_down() {
+			return;
+		}
 		match self.history_index.cmp(&1) {
 			Ordering::Greater => {
 				self.history_index -= 1;
-				self.text = self.history
-					[self.history.len() - self.history_index]
-					.to_string();
+				self.text = Input::new(
+					&self.history[self.history.len() - self.history_index],
+				);
 			}
 			Ordering::Equal => {
-				self.text = String::from(":");
+				self.text = Input::new(COMMAND_PREFIX);
 				self.history_index = 0;
 			}
 			Ordering::Less => {}
@@ -155,21 +274,28 @@ impl Prompt {
 	}
 
 	/// Select the previous command.
+	///
+	/// Moves the cursor to the previous line instead if there is one.
 	pub fn previous(&mut self) {
+		if self.text.move_up() {
+			return;
+		}
 		if self.history.len() > self.history_index {
-			self.text = self.history
-				[self.history.len() - (self.history_index + 1)]
-				.to_string();
+			self.text = Input::new(
+				&self.history[self.history.len() - (self.history_index + 1)],
+			);
 			self.history_index += 1;
 		}
 	}
 
 	/// Clears the prompt.
 	pub fn clear(&mut self) {
-		self.text.clear();
+		self.text = Input::default();
 		self.output_type = OutputType::None;
 		self.clock = None;
 		self.command = None;
+		self.answers.clear();
+		self.answer.clear();
 		self.history_index = 0;
 	}
 }
@@ -187,23 +313,78 @@ mod tests {
 		assert!(prompt.is_search_enabled());
 		assert!(prompt.is_enabled());
 		prompt.set_output((OutputType::from(String::from("success")), "Test"));
-		assert_eq!(String::from("Test"), prompt.text);
+		assert_eq!(String::from("Test"), prompt.text.value);
 		assert_eq!(OutputType::Success, prompt.output_type);
 		assert_ne!(0, prompt.clock.unwrap().elapsed().as_nanos());
 		assert!(!prompt.is_enabled());
+		prompt.set_output((OutputType::Action, "-- test --"));
+		assert_eq!(
+			vec![&(OutputType::Success, String::from("Test"))],
+			prompt
+				.messages
+				.toasts
+				.iter()
+				.map(|toast| &toast.content)
+				.collect::<Vec<&(OutputType, String)>>()
+		);
 		prompt.clear();
-		assert_eq!(String::new(), prompt.text);
+		assert_eq!(1, prompt.messages.toasts.len());
+		assert_eq!(
+			vec![(OutputType::Success, "Test")],
+			prompt
+				.log
+				.iter()
+				.map(|(_, output_type, message)| (
+					output_type.clone(),
+					message.as_str()
+				))
+				.collect::<Vec<(OutputType, &str)>>()
+		);
+		assert_eq!(String::new(), prompt.text.value);
 		assert_eq!(None, prompt.clock);
+		assert_eq!(None, prompt.error);
+		prompt.set_error("test error", vec![String::from("caused by: x")]);
+		assert_eq!(OutputType::Failure, prompt.output_type);
+		assert_eq!(
+			Some((
+				String::from("test error"),
+				vec![String::from("caused by: x")]
+			)),
+			prompt.error
+		);
+		prompt.set_output((OutputType::Failure, "other error"));
+		assert_eq!(Some((String::from("other error"), vec![])), prompt.error);
+		prompt.clear();
 		prompt.history =
 			vec![String::from("0"), String::from("1"), String::from("2")];
 		for i in 0..prompt.history.len() {
 			prompt.previous();
-			assert_eq!((prompt.history.len() - i - 1).to_string(), prompt.text);
+			assert_eq!(
+				(prompt.history.len() - i - 1).to_string(),
+				prompt.text.value
+			);
 		}
 		for i in 1..prompt.history.len() {
 			prompt.next();
-			assert_eq!(i.to_string(), prompt.text);
+			assert_eq!(i.to_string(), prompt.text.value);
 		}
+		prompt.set_typed_command(
+			Command::None,
+			vec![String::from("ABCD1234"), String::from("yes")],
+		);
+		assert_eq!(
+			"type 'ABCD1234' or 'yes' to close menu: ",
+			prompt.text.value
+		);
+		assert!(prompt.is_typed_confirmation());
+		prompt.answer = String::from("0xabcd1234");
+		assert!(prompt.is_confirmed());
+		prompt.answer = String::from("no");
+		assert!(!prompt.is_confirmed());
+		prompt.clear();
+		assert!(!prompt.is_typed_confirmation());
+		assert_eq!(Ok(ConfirmLevel::Strict), ConfirmLevel::from_str("strict"));
+		assert_eq!("typed", ConfirmLevel::default().to_string());
 		for output_type in vec![
 			OutputType::from(String::from("warning")),
 			OutputType::from(String::from("failure")),
@@ -1,10 +1,15 @@
 use crate::app::banner::Banner;
-use crate::app::launcher::App;
-use crate::app::prompt::OutputType;
+use crate::app::command::Command;
+use crate::app::launcher::{App, MESSAGE_DURATION};
+use crate::app::statusline;
 use crate::app::style;
-use crate::app::tab::Tab;
+use crate::app::tab::{Tab, TABS};
+use crate::gpg::agent;
+use crate::gpg::handler;
 use crate::widget::row::RowItem;
+use crate::widget::scrollbar::Scrollbar;
 use crate::widget::table::TableSize;
+use chrono::Datelike;
 use std::cmp;
 use std::convert::{TryFrom, TryInto};
 use tui::backend::Backend;
@@ -14,41 +19,262 @@ use tui::terminal::Frame;
 use tui::text::{Span, Spans, Text};
 use tui::widgets::canvas::{Canvas, Points};
 use tui::widgets::{
-	Block, Borders, Clear, List, ListItem, Paragraph, Row, Table, Wrap,
+	BarChart, Block, Borders, Clear, Gauge, List, ListItem, Paragraph, Row,
+	Table, Tabs, Wrap,
 };
 use unicode_width::UnicodeWidthStr;
 
 /// Lengths of keys row in minimized/normal mode.
 const KEYS_ROW_LENGTH: (u16, u16) = (31, 55);
 
+/// Maximum number of toasts to show at once.
+const MAX_TOASTS: usize = 5;
+
+/// Height of the log pane (including borders).
+const LOG_HEIGHT: u16 = 8;
+
 /// Renders all the widgets thus the user interface.
 pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
 	let rect = frame.size();
 	if app.keys_table.state.minimize_threshold != 0 {
+		let table_width = if app.state.split_view {
+			rect.width / 2
+		} else {
+			rect.width
+		};
 		app.keys_table.state.size.set_minimized(
-			rect.width < app.keys_table.state.minimize_threshold,
+			table_width < app.keys_table.state.minimize_threshold,
 		);
 	}
 	if app.state.show_splash {
 		render_splash_screen(app, frame, rect);
 	} else {
+		let prompt_height = if app.prompt.command.is_some() {
+			let text_width = format!(
+				"{}{}{}",
+				app.prompt.output_type, app.prompt.text, app.prompt.answer
+			)
+			.width() as u16;
+			cmp::min(
+				text_width.saturating_sub(1) / cmp::max(rect.width, 1) + 1,
+				cmp::max(rect.height / 3, 1),
+			)
+		} else if app.prompt.is_enabled() {
+			cmp::min(
+				app.prompt.text.get_lines().len() as u16,
+				cmp::max(rect.height / 3, 1),
+			)
+		} else {
+			1
+		};
+		let progress_height = if app.worker.get_progress().is_some() {
+			1
+		} else {
+			0
+		};
+		let log_height = if app.state.show_log {
+			cmp::min(LOG_HEIGHT, rect.height / 3)
+		} else {
+			0
+		};
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Min(rect.height - 1), Constraint::Min(1)].as_ref(),
+				[
+					Constraint::Length(1),
+					Constraint::Min(rect.height.saturating_sub(
+						prompt_height + progress_height + log_height + 1,
+					)),
+					Constraint::Length(log_height),
+					Constraint::Length(progress_height),
+					Constraint::Min(prompt_height),
+				]
+				.as_ref(),
 			)
 			.split(rect);
-		render_command_prompt(app, frame, chunks[1]);
+		render_tab_bar(app, frame, chunks[0]);
+		if app.state.show_log {
+			render_log(app, frame, chunks[2]);
+		}
+		render_progress_bar(app, frame, chunks[3]);
+		render_command_prompt(app, frame, chunks[4]);
+		let toasts_rect = chunks[3];
 		match app.tab {
-			Tab::Keys(_) => render_keys_table(app, frame, chunks[0]),
-			Tab::Help => render_help_tab(app, frame, chunks[0]),
+			Tab::Keys(_) => {
+				if app.state.split_view {
+					let chunks = Layout::default()
+						.direction(Direction::Horizontal)
+						.constraints(
+							[
+								Constraint::Percentage(50),
+								Constraint::Percentage(50),
+							]
+							.as_ref(),
+						)
+						.split(chunks[1]);
+					render_keys_table(app, frame, chunks[0]);
+					render_key_details(app, frame, chunks[1]);
+				} else {
+					render_keys_table(app, frame, chunks[1])
+				}
+			}
+			Tab::Help => render_help_tab(app, frame, chunks[1]),
+		}
+		if !app.state.show_log {
+			render_toasts(app, frame, toasts_rect);
 		}
 		if app.state.show_options {
 			render_options_menu(app, frame, rect);
 		}
+		if app.state.show_messages {
+			render_messages(app, frame, rect);
+		}
+		if app.state.show_legend {
+			render_legend(app, frame, rect);
+		}
+		if app.state.show_agent {
+			render_agent(app, frame, rect);
+		}
+		if app.state.show_wot {
+			render_trust_paths(app, frame, rect);
+		}
+		if app.state.show_stats {
+			render_stats(app, frame, rect);
+		}
+		if app.state.show_doctor {
+			render_doctor(app, frame, rect);
+		}
+		if app.state.show_certs {
+			render_certifications(app, frame, rect);
+		}
+		if app.state.show_subkeys {
+			render_subkeys(app, frame, rect);
+		}
+		if app.state.show_groups {
+			render_groups(app, frame, rect);
+		}
+		if app.state.show_prefs {
+			render_prefs(app, frame, rect);
+		}
+		if app.state.show_output {
+			render_command_output(app, frame, rect);
+		}
+		if app.file_picker.is_some() {
+			render_file_picker(app, frame, rect);
+		}
+		if app.recipient_picker.is_some() {
+			render_recipient_picker(app, frame, rect);
+		}
+		if app.date_picker.is_some() {
+			render_date_picker(app, frame, rect);
+		}
+		if app.dialog.is_some() {
+			render_dialog(app, frame, rect);
+		}
 	}
 }
 
+/// Returns the values of the status line fields.
+fn get_status_line_values(app: &App) -> Vec<(&'static str, String)> {
+	let is_keys_tab = matches!(app.tab, Tab::Keys(_));
+	vec![
+		(
+			"tab",
+			match app.tab {
+				Tab::Keys(key_type) => key_type.to_string(),
+				Tab::Help => String::from("help"),
+			},
+		),
+		(
+			"selection",
+			match app.keys_table.state.tui.selected() {
+				Some(index) if is_keys_tab => (index + 1).to_string(),
+				_ => String::new(),
+			},
+		),
+		(
+			"total",
+			if is_keys_tab {
+				app.keys_table.items.len().to_string()
+			} else {
+				String::new()
+			},
+		),
+		(
+			"filter",
+			app.keys_table_filter
+				.map(|filter| filter.to_string())
+				.unwrap_or_default(),
+		),
+		(
+			"hidden",
+			match app
+				.keys_table
+				.default_items
+				.iter()
+				.filter(|key| app.is_key_hidden(key))
+				.count()
+			{
+				0 => String::new(),
+				hidden => format!("{} hidden", hidden),
+			},
+		),
+		("mode", format!("{:?}", app.mode).to_lowercase()),
+		("armor", app.gpgme.config.armor.to_string()),
+		(
+			"signer",
+			app.gpgme.config.default_key.clone().unwrap_or_default(),
+		),
+		(
+			"output",
+			app.gpgme.config.output_dir.to_string_lossy().to_string(),
+		),
+	]
+}
+
+/// Renders the tab bar.
+///
+/// Tabs of the keys show the number of keys in the keyring.
+fn render_tab_bar<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let titles = TABS
+		.iter()
+		.map(|tab| {
+			Spans::from(match tab {
+				Tab::Keys(key_type) => match app.keys.get(key_type) {
+					Some(keys) => {
+						format!("{} ({})", tab.get_title(), keys.len())
+					}
+					None => tab.get_title().to_string(),
+				},
+				Tab::Help => tab.get_title().to_string(),
+			})
+		})
+		.collect();
+	frame.render_widget(
+		Tabs::new(titles)
+			.select(
+				TABS.iter()
+					.position(|tab| *tab == app.tab)
+					.unwrap_or_default(),
+			)
+			.style(Style::default().fg(Color::DarkGray))
+			.highlight_style(if app.state.colored {
+				Style::default()
+					.fg(app.state.color)
+					.add_modifier(Modifier::BOLD)
+			} else {
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+			}),
+		rect,
+	);
+}
+
 /// Renders the splash screen.
 fn render_splash_screen<B: Backend>(
 	app: &mut App,
@@ -81,6 +307,68 @@ fn render_splash_screen<B: Backend>(
 	);
 }
 
+/// Renders the progress bar of the running task.
+fn render_progress_bar<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	if let Some(progress) = app.worker.get_progress() {
+		frame.render_widget(
+			Gauge::default()
+				.gauge_style(if app.state.colored {
+					Style::default().fg(Color::LightBlue)
+				} else {
+					Style::default().fg(Color::DarkGray)
+				})
+				.label(if progress.total == 0 {
+					format!("{} ({})", progress.what, progress.current)
+				} else {
+					format!(
+						"{} ({}/{})",
+						progress.what, progress.current, progress.total
+					)
+				})
+				.ratio(progress.get_ratio()),
+			rect,
+		);
+	}
+}
+
+/// Renders the recent messages as a stack of toasts
+/// above the given area (newest at the bottom).
+///
+/// The message that is shown on the prompt is skipped.
+fn render_toasts<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let mut toasts = app.prompt.messages.get_active(MESSAGE_DURATION);
+	if app.prompt.clock.is_some()
+		&& toasts.last().map(|toast| &toast.content.1)
+			== Some(&app.prompt.text.value)
+	{
+		toasts.pop();
+	}
+	for (i, toast) in toasts.iter().rev().take(MAX_TOASTS).enumerate() {
+		let (output_type, message) = &toast.content;
+		let text = format!(" {}{} ", output_type, message);
+		let width = cmp::min(text.width() as u16, rect.width);
+		let y = match rect.y.checked_sub(i as u16 + 1) {
+			Some(y) => y,
+			None => break,
+		};
+		let area = Rect::new(rect.x + rect.width - width, y, width, 1);
+		frame.render_widget(Clear, area);
+		frame.render_widget(
+			Paragraph::new(text)
+				.style(style::get_output_style(output_type, app.state.colored)),
+			area,
+		);
+	}
+}
+
 /// Renders the command prompt.
 fn render_command_prompt<B: Backend>(
 	app: &mut App,
@@ -88,22 +376,58 @@ fn render_command_prompt<B: Backend>(
 	rect: Rect,
 ) {
 	frame.render_widget(
-		Paragraph::new(Spans::from(if !app.prompt.text.is_empty() {
-			vec![Span::raw(format!(
-				"{}{}",
-				app.prompt.output_type, app.prompt.text
-			))]
+		Paragraph::new(if app.prompt.is_enabled() {
+			Text::from(app.prompt.text.to_string())
+		} else if !app.prompt.text.is_empty() {
+			Text::from(format!(
+				"{}{}{}",
+				app.prompt.output_type, app.prompt.text, app.prompt.answer
+			))
 		} else {
 			let arrow_color = if app.state.colored {
 				Color::LightBlue
 			} else {
 				Color::DarkGray
 			};
-			vec![
+			Text::from(Spans::from(vec![
+				Span::raw(match app.macros.get_recording() {
+					Some(register) => format!("recording @{} ", register),
+					None => String::new(),
+				}),
+				Span::raw(match (app.state.count, app.state.pending_key) {
+					(Some((count, _)), _) => format!("{} ", count),
+					(None, Some((key, 1))) => format!("{} ", key),
+					(None, Some((key, count))) => format!("{}{} ", count, key),
+					(None, None) => String::new(),
+				}),
+				Span::styled(
+					match app.worker.get_status() {
+						Some(status) => format!("{} ", status),
+						None => String::new(),
+					},
+					Style::default().fg(arrow_color),
+				),
+				Span::raw(match app.get_clipboard_countdown() {
+					Some(countdown) => {
+						format!("clipboard clears in {}s ", countdown)
+					}
+					None => String::new(),
+				}),
+				Span::raw(if app.state.keyring_changed {
+					"keyring changed, press 'r' to reload "
+				} else {
+					""
+				}),
 				Span::styled("< ", Style::default().fg(arrow_color)),
-				match app.tab {
-					Tab::Keys(key_type) => Span::raw(format!(
-						"list {}{}",
+				match (&app.state.status_line, app.tab) {
+					(Some(template), _) => {
+						Span::raw(statusline::format_status_line(
+							template,
+							&get_status_line_values(app),
+						))
+					}
+					(None, Tab::Keys(key_type)) => Span::raw(format!(
+						"list {}{}{}{}",
 						key_type,
 						if !app.keys_table.items.is_empty() {
 							format!(
@@ -115,42 +439,43 @@ fn render_command_prompt<B: Backend>(
 									.unwrap_or_default() + 1,
 								app.keys_table.items.len()
 							)
+						} else if let Some(loader) = &app.key_loader {
+							format!(
+								" ({}{})",
+								if app.key_cache.is_some() {
+									"cached, "
+								} else {
+									""
+								},
+								loader.get_status()
+							)
 						} else {
 							String::new()
+						},
+						match app.keys_table_filter {
+							Some(filter) => format!(" [{}]", filter),
+							None => String::new(),
+						},
+						match app
+							.keys_table
+							.default_items
+							.iter()
+							.filter(|key| app.is_key_hidden(key))
+							.count()
+						{
+							0 => String::new(),
+							hidden => format!(" [{} hidden]", hidden),
 						}
 					)),
-					Tab::Help => Span::raw("help"),
+					(None, Tab::Help) => Span::raw("help"),
 				},
 				Span::styled(" >", Style::default().fg(arrow_color)),
-			]
-		}))
-		.style(if app.state.colored {
-			match app.prompt.output_type {
-				OutputType::Success => Style::default()
-					.fg(Color::LightGreen)
-					.add_modifier(Modifier::BOLD),
-				OutputType::Warning => Style::default()
-					.fg(Color::LightYellow)
-					.add_modifier(Modifier::BOLD),
-				OutputType::Failure => Style::default()
-					.fg(Color::LightRed)
-					.add_modifier(Modifier::BOLD),
-				OutputType::Action => {
-					if app.state.colored {
-						Style::default()
-							.fg(Color::LightBlue)
-							.add_modifier(Modifier::BOLD)
-					} else {
-						Style::default().add_modifier(Modifier::BOLD)
-					}
-				}
-				OutputType::None => Style::default(),
-			}
-		} else if app.prompt.output_type != OutputType::None {
-			Style::default().add_modifier(Modifier::BOLD)
-		} else {
-			Style::default()
+			]))
 		})
+		.style(style::get_output_style(
+			&app.prompt.output_type,
+			app.state.colored,
+		))
 		.alignment(if !app.prompt.text.is_empty() {
 			Alignment::Left
 		} else {
@@ -160,7 +485,18 @@ fn render_command_prompt<B: Backend>(
 		rect,
 	);
 	if app.prompt.is_enabled() {
-		frame.set_cursor(rect.x + app.prompt.text.width() as u16, rect.y + 1);
+		let (row, column) = app.prompt.text.get_cursor_position();
+		frame.set_cursor(rect.x + column as u16, rect.y + row as u16 + 1);
+	} else if app.prompt.is_typed_confirmation() {
+		let text_width = format!(
+			"{}{}{}",
+			app.prompt.output_type, app.prompt.text, app.prompt.answer
+		)
+		.width() as u16;
+		frame.set_cursor(
+			rect.x + text_width % cmp::max(rect.width, 1),
+			rect.y + text_width / cmp::max(rect.width, 1),
+		);
 	}
 }
 
@@ -219,37 +555,54 @@ fn render_help_tab<B: Backend>(
 				.as_ref(),
 			)
 			.split(chunks[0]);
-		frame.render_stateful_widget(
-			List::new(
-				app.key_bindings
-					.items
-					.iter()
-					.enumerate()
-					.map(|(i, v)| {
-						v.as_list_item(
-							app.state.colored,
-							app.key_bindings.state.selected() == Some(i),
-						)
-					})
-					.collect::<Vec<ListItem>>(),
-			)
-			.block(
-				Block::default()
-					.borders(Borders::RIGHT)
-					.border_style(Style::default().fg(Color::DarkGray)),
-			)
-			.style(Style::default().fg(app.state.color))
-			.highlight_style(if app.state.colored {
-				Style::default().add_modifier(Modifier::BOLD)
-			} else {
-				Style::default()
-					.fg(Color::Reset)
-					.add_modifier(Modifier::BOLD)
+		let items = app
+			.key_bindings
+			.items
+			.iter()
+			.enumerate()
+			.map(|(i, v)| {
+				v.as_list_item(
+					app.state.colored,
+					app.key_bindings.state.selected() == Some(i),
+				)
 			})
-			.highlight_symbol("> "),
+			.collect::<Vec<ListItem>>();
+		if let Some(item) = items.first() {
+			app.key_bindings.page_size =
+				usize::from(chunks[0].height) / cmp::max(item.height(), 1);
+		}
+		frame.render_stateful_widget(
+			List::new(items)
+				.block(
+					Block::default()
+						.borders(Borders::RIGHT)
+						.border_style(Style::default().fg(Color::DarkGray)),
+				)
+				.style(Style::default().fg(app.state.color))
+				.highlight_style(if app.state.colored {
+					Style::default().add_modifier(Modifier::BOLD)
+				} else {
+					Style::default()
+						.fg(Color::Reset)
+						.add_modifier(Modifier::BOLD)
+				})
+				.highlight_symbol("> "),
 			chunks[0],
 			&mut app.key_bindings.state,
 		);
+		frame.render_widget(
+			Scrollbar::new(
+				app.key_bindings.state.selected().unwrap_or_default(),
+				app.key_bindings.items.len(),
+				app.key_bindings.page_size,
+			)
+			.style(Style::default().fg(app.state.color)),
+			Rect {
+				y: chunks[0].y,
+				height: chunks[0].height,
+				..Scrollbar::get_track(chunks[0])
+			},
+		);
 		frame.render_widget(
 			Paragraph::new(description)
 				.block(
@@ -322,57 +675,181 @@ fn render_help_tab<B: Backend>(
 	}
 }
 
-/// Renders the options menu.
-fn render_options_menu<B: Backend>(
+/// Renders the log pane that shows the latest output messages.
+fn render_log<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>, rect: Rect) {
+	let items = app
+		.prompt
+		.log
+		.iter()
+		.rev()
+		.take(rect.height.saturating_sub(2).into())
+		.rev()
+		.map(|(time, output_type, message)| {
+			ListItem::new(Span::styled(
+				format!("{} {}{}", time.format("%F %T"), output_type, message),
+				style::get_output_style(output_type, app.state.colored),
+			))
+		})
+		.collect::<Vec<ListItem>>();
+	frame.render_widget(
+		List::new(items).block(
+			Block::default()
+				.title("Log")
+				.style(if app.state.colored {
+					Style::default().fg(Color::LightBlue)
+				} else {
+					Style::default()
+				})
+				.borders(Borders::ALL),
+		),
+		rect,
+	);
+	frame.render_widget(
+		Scrollbar::new(
+			app.prompt.log.len().saturating_sub(1),
+			app.prompt.log.len(),
+			rect.height.saturating_sub(2).into(),
+		)
+		.style(Style::default().fg(app.state.color)),
+		Scrollbar::get_track(rect),
+	);
+}
+
+/// Renders the popup of recent messages (newest first).
+fn render_messages<B: Backend>(
 	app: &mut App,
 	frame: &mut Frame<'_, B>,
 	rect: Rect,
 ) {
 	let items = app
-		.options
+		.prompt
+		.messages
+		.toasts
+		.iter()
+		.rev()
+		.map(|toast| {
+			let (output_type, message) = &toast.content;
+			ListItem::new(Span::styled(
+				format!(
+					"[{}s ago] {}{}",
+					toast.clock.elapsed().as_secs(),
+					output_type,
+					message
+				),
+				style::get_output_style(output_type, app.state.colored),
+			))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 40), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		List::new(items).block(
+			Block::default()
+				.title("Messages")
+				.style(if app.state.colored {
+					Style::default().fg(Color::LightBlue)
+				} else {
+					Style::default()
+				})
+				.borders(Borders::ALL),
+		),
+		area,
+	);
+}
+
+/// Renders the captured output of the last external command.
+fn render_command_output<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.command_output
 		.items
 		.iter()
-		.map(|v| ListItem::new(Span::raw(v.to_string())))
+		.map(|line| ListItem::new(Span::raw(line.to_string())))
 		.collect::<Vec<ListItem>>();
-	let (length_x, mut percent_y) = (38, 60);
-	let text_height = items.iter().map(|v| v.height() as f32).sum::<f32>() + 3.;
-	if rect.height.checked_sub(5).unwrap_or(rect.height) as f32 > text_height {
-		percent_y = ((text_height / rect.height as f32) * 100.) as u16;
-	}
-	let popup_layout = Layout::default()
-		.direction(Direction::Vertical)
-		.constraints(
-			[
-				Constraint::Percentage((100 - percent_y) / 2),
-				Constraint::Percentage(percent_y),
-				Constraint::Percentage((100 - percent_y) / 2),
-			]
-			.as_ref(),
-		)
-		.split(rect);
-	let area = Layout::default()
-		.direction(Direction::Horizontal)
-		.constraints(
-			[
-				Constraint::Length(
-					(popup_layout[1].width.checked_sub(length_x))
-						.unwrap_or_default() / 2,
-				),
-				Constraint::Min(length_x),
-				Constraint::Length(
-					(popup_layout[1].width.checked_sub(length_x))
-						.unwrap_or_default() / 2,
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(
+		cmp::max(items.len() as u16 + 2, rect.height / 2),
+		rect.height,
+	);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	app.command_output.page_size = usize::from(area.height.saturating_sub(2));
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title("Output")
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			),
+		area,
+		&mut app.command_output.state,
+	);
+}
+
+/// Renders the issues that are found in the keyring.
+fn render_doctor<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.keyring_issues
+		.items
+		.iter()
+		.map(|issue| {
+			ListItem::new(Spans::from(vec![
+				Span::styled(
+					format!("[{}] ", issue.kind),
+					if app.state.colored {
+						Style::default().fg(Color::LightRed)
+					} else {
+						Style::default()
+					},
 				),
-			]
-			.as_ref(),
-		)
-		.split(popup_layout[1])[1];
+				Span::raw(issue.description.to_string()),
+			]))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
 	frame.render_widget(Clear, area);
 	frame.render_stateful_widget(
 		List::new(items)
 			.block(
 				Block::default()
-					.title("Options")
+					.title("Doctor (press enter to jump)")
 					.style(if app.state.colored {
 						Style::default().fg(Color::LightBlue)
 					} else {
@@ -388,26 +865,914 @@ fn render_options_menu<B: Backend>(
 			)
 			.highlight_symbol("> "),
 		area,
-		&mut app.options.state,
+		&mut app.keyring_issues.state,
 	);
 }
 
-/// Renders the table of keys.
-fn render_keys_table<B: Backend>(
+/// Renders the certifications that are made by the default key.
+fn render_certifications<B: Backend>(
 	app: &mut App,
 	frame: &mut Frame<'_, B>,
 	rect: Rect,
 ) {
-	let keys_row_length = if app.keys_table.state.size != TableSize::Normal {
-		KEYS_ROW_LENGTH.0
-	} else {
-		KEYS_ROW_LENGTH.1
-	};
-	frame.render_stateful_widget(
-		Table::new(get_keys_table_rows(
+	let items = app
+		.certifications
+		.items
+		.iter()
+		.map(|certification| {
+			ListItem::new(Spans::from(Span::styled(
+				certification
+					.get_info(&app.state.date_format, app.state.redact),
+				if !app.state.colored {
+					Style::default()
+				} else if certification.revoked {
+					Style::default().fg(Color::LightRed)
+				} else if !certification.exportable {
+					Style::default().fg(Color::LightYellow)
+				} else {
+					Style::default()
+				},
+			)))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title("Certifications (enter: jump, r/R: revoke)")
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut app.certifications.state,
+	);
+}
+
+/// Renders the recipient groups.
+fn render_groups<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.groups
+		.items
+		.iter()
+		.map(|group| {
+			let members = group.members.join(" ");
+			ListItem::new(Spans::from(vec![
+				Span::styled(
+					format!("{} ", group.name),
+					if app.state.colored {
+						Style::default().fg(Color::LightGreen)
+					} else {
+						Style::default()
+					},
+				),
+				Span::raw(format!(
+					"({}): {}",
+					group.members.len(),
+					if app.state.redact {
+						handler::redact_user_id(&members)
+					} else {
+						members
+					}
+				)),
+			]))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title(
+						"Groups (enter: edit, a/x: add/remove key, n: new, d: delete)",
+					)
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut app.groups.state,
+	);
+}
+
+/// Renders the preferences of the selected key.
+fn render_prefs<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.preferences
+		.items
+		.iter()
+		.map(|lines| {
+			ListItem::new(if app.state.colored {
+				style::get_colored_table_row(lines, false)
+			} else {
+				Text::from(lines.join("\n"))
+			})
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(
+		app.preferences
+			.items
+			.iter()
+			.map(|lines| lines.len() as u16)
+			.sum::<u16>()
+			+ 2,
+		rect.height,
+	);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title("Preferences (enter: apply preset, e: edit)")
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut app.preferences.state,
+	);
+}
+
+/// Renders the subkeys of the selected key.
+fn render_subkeys<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.subkeys
+		.items
+		.iter()
+		.map(|lines| {
+			ListItem::new(if app.state.colored {
+				style::get_colored_table_row(lines, false)
+			} else {
+				Text::from(lines.join("\n"))
+			})
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(items.len() as u16 * 2 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title("Subkeys (x: export, e: expire, r: revoke, c: card)")
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut app.subkeys.state,
+	);
+}
+
+/// Renders the file picker.
+fn render_file_picker<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let colored = app.state.colored;
+	let picker = match app.file_picker.as_mut() {
+		Some((_, picker)) => picker,
+		None => return,
+	};
+	let items = picker
+		.entries
+		.items
+		.iter()
+		.map(|entry| {
+			ListItem::new(Spans::from(if entry.is_dir {
+				Span::styled(
+					format!("{}/", entry.name),
+					if colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default().add_modifier(Modifier::BOLD)
+					},
+				)
+			} else {
+				Span::raw(entry.name.to_string())
+			}))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(
+		cmp::max(items.len() as u16, rect.height / 2) + 2,
+		rect.height,
+	);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title(format!(
+						"{} (.: hidden, f: filter)",
+						picker.get_title()
+					))
+					.style(if colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut picker.entries.state,
+	);
+}
+
+/// Renders the recipient picker.
+fn render_recipient_picker<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let colored = app.state.colored;
+	let (file, recipients) = match app.recipient_picker.as_mut() {
+		Some(picker) => picker,
+		None => return,
+	};
+	let items = recipients
+		.get_visible()
+		.into_iter()
+		.map(|item| {
+			ListItem::new(Spans::from(vec![
+				Span::styled(
+					if item.checked { "[x] " } else { "[ ] " },
+					if colored && item.checked {
+						Style::default().fg(Color::LightGreen)
+					} else {
+						Style::default()
+					},
+				),
+				Span::raw(item.label.to_string()),
+			]))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(
+		cmp::max(items.len() as u16, rect.height / 2) + 2,
+		rect.height,
+	);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title(format!(
+						"Encrypt {} (space: toggle, enter: encrypt) /{}",
+						file, recipients.query
+					))
+					.style(if colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut recipients.state,
+	);
+}
+
+/// Renders the date picker.
+///
+/// The days until today are dimmed and the selected day is highlighted.
+fn render_date_picker<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let colored = app.state.colored;
+	let (action, picker) = match app.date_picker.as_ref() {
+		Some(picker) => picker,
+		None => return,
+	};
+	let mut lines = vec![
+		Spans::from(Span::styled(
+			picker.get_title(),
+			Style::default().add_modifier(Modifier::BOLD),
+		)),
+		Spans::from(Span::styled(
+			"Mo Tu We Th Fr Sa Su",
+			Style::default().fg(Color::DarkGray),
+		)),
+	];
+	for week in picker.get_weeks() {
+		let mut spans = Vec::new();
+		for (i, day) in week.iter().enumerate() {
+			if i != 0 {
+				spans.push(Span::raw(" "));
+			}
+			spans.push(match day {
+				Some(day) => {
+					let date =
+						picker.date.with_day(*day).unwrap_or(picker.date);
+					Span::styled(
+						format!("{:>2}", day),
+						if date == picker.date && !picker.never {
+							Style::default().add_modifier(Modifier::REVERSED)
+						} else if date <= picker.today {
+							Style::default().fg(Color::DarkGray)
+						} else {
+							Style::default()
+						},
+					)
+				}
+				None => Span::raw("  "),
+			});
+		}
+		lines.push(Spans::from(spans));
+	}
+	lines.push(Spans::default());
+	lines.push(Spans::from(format!("expires: {}", picker.get_value())));
+	lines.push(if picker.input.is_empty() {
+		Spans::from(Span::styled(
+			"type e.g. 6m, 1y or YYYY-MM-DD",
+			Style::default().fg(Color::DarkGray),
+		))
+	} else {
+		Spans::from(format!("> {}", picker.input))
+	});
+	let width = cmp::min(40, rect.width);
+	let height = cmp::min(lines.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		Paragraph::new(Text::from(lines))
+			.block(
+				Block::default()
+					.title(match action {
+						Command::SetExpire(Some(key_id), _) => {
+							format!("Expiration of {} (tab: never)", key_id)
+						}
+						_ => String::from("Signature expiration (tab: never)"),
+					})
+					.style(if colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.alignment(Alignment::Center),
+		area,
+	);
+}
+
+/// Renders the keyring statistics.
+///
+/// Totals are shown on top of the bar chart of the key algorithms.
+fn render_stats<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let stats = &app.keyring_stats;
+	let totals = stats
+		.get_totals()
+		.into_iter()
+		.map(|(name, value)| {
+			Spans::from(vec![
+				Span::styled(
+					format!("{:<15}", name),
+					Style::default().add_modifier(Modifier::BOLD),
+				),
+				Span::raw(value),
+			])
+		})
+		.collect::<Vec<Spans>>();
+	let data = stats
+		.algorithms
+		.iter()
+		.map(|(algorithm, count)| (algorithm.as_str(), *count))
+		.collect::<Vec<(&str, u64)>>();
+	let bar_width = data
+		.iter()
+		.map(|(algorithm, _)| algorithm.len() as u16)
+		.max()
+		.unwrap_or_default()
+		.max(3);
+	let width = cmp::min(
+		cmp::max(
+			(bar_width + 1) * data.len() as u16 + 2,
+			cmp::max(rect.width / 2, 40),
+		),
+		rect.width,
+	);
+	let height = cmp::min(totals.len() as u16 + 14, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	let block_style = if app.state.colored {
+		Style::default().fg(Color::LightBlue)
+	} else {
+		Style::default()
+	};
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(
+			[
+				Constraint::Length(totals.len() as u16 + 2),
+				Constraint::Min(3),
+			]
+			.as_ref(),
+		)
+		.split(area);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		Paragraph::new(totals)
+			.block(
+				Block::default()
+					.title("Statistics")
+					.style(block_style)
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color)),
+		chunks[0],
+	);
+	frame.render_widget(
+		BarChart::default()
+			.block(
+				Block::default()
+					.title("Algorithms")
+					.style(block_style)
+					.borders(Borders::ALL),
+			)
+			.data(&data)
+			.bar_width(bar_width)
+			.bar_gap(1)
+			.bar_style(Style::default().fg(if app.state.colored {
+				Color::LightGreen
+			} else {
+				app.state.color
+			}))
+			.value_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::REVERSED),
+			)
+			.label_style(Style::default().fg(app.state.color)),
+		chunks[1],
+	);
+}
+
+/// Renders the certification paths.
+fn render_trust_paths<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.trust_paths
+		.iter()
+		.map(|line| {
+			let (tree, key) = line.split_at(
+				line.find(|c: char| c.is_ascii_alphanumeric())
+					.unwrap_or_default(),
+			);
+			ListItem::new(Spans::from(vec![
+				Span::styled(
+					tree.to_string(),
+					Style::default().fg(Color::DarkGray),
+				),
+				Span::raw(key.to_string()),
+			]))
+		})
+		.collect::<Vec<ListItem>>();
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title("Trust paths")
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color)),
+		area,
+	);
+}
+
+/// Renders the modal dialog.
+fn render_dialog<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let dialog = match &app.dialog {
+		Some(dialog) => dialog,
+		None => return,
+	};
+	let mut lines = dialog.body.lines.clone();
+	lines.push(Spans::default());
+	let mut buttons = Vec::new();
+	for (i, (label, _)) in dialog.buttons.iter().enumerate() {
+		if i != 0 {
+			buttons.push(Span::raw(" "));
+		}
+		buttons.push(Span::styled(
+			format!("[ {} ]", label),
+			if i == dialog.selected {
+				Style::default().add_modifier(Modifier::REVERSED)
+			} else {
+				Style::default()
+			},
+		));
+	}
+	lines.push(Spans::from(buttons));
+	let content_width = lines.iter().map(|line| line.width()).max();
+	let width = cmp::min(
+		cmp::min(
+			cmp::max(content_width.unwrap_or_default() as u16 + 4, 40),
+			cmp::max(rect.width * 3 / 4, 40),
+		),
+		rect.width,
+	);
+	let line_width = usize::from(width.saturating_sub(2)).max(1);
+	let line_count = lines
+		.iter()
+		.map(|line| line.width().saturating_sub(1) / line_width + 1)
+		.sum::<usize>();
+	let height = cmp::min(line_count as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		Paragraph::new(lines)
+			.block(
+				Block::default()
+					.title(dialog.title.to_string())
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.wrap(Wrap { trim: false }),
+		area,
+	);
+}
+
+/// Renders the explanation of the key flags.
+fn render_legend<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let mut items = Vec::new();
+	for (i, (title, flags)) in
+		handler::get_flag_legend().into_iter().enumerate()
+	{
+		if i != 0 {
+			items.push(ListItem::new(Span::raw("")));
+		}
+		items.push(ListItem::new(Span::styled(
+			title,
+			Style::default().add_modifier(Modifier::BOLD),
+		)));
+		for (symbol, description) in flags {
+			let line = format!(" {} {}", symbol, description);
+			items.push(ListItem::new(if app.state.colored {
+				style::get_colored_table_row(&[line], false)
+			} else {
+				Text::raw(line)
+			}));
+		}
+	}
+	let width = cmp::min(cmp::max(rect.width / 2, 50), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		List::new(items).block(
+			Block::default()
+				.title("Legend")
+				.style(if app.state.colored {
+					Style::default().fg(Color::LightBlue)
+				} else {
+					Style::default()
+				})
+				.borders(Borders::ALL),
+		),
+		area,
+	);
+}
+
+/// Renders the information about gpg-agent and the other components.
+fn render_agent<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let mut items = app
+		.agent_components
+		.iter()
+		.map(|component| {
+			let (status, color) = match component.running {
+				Some(true) => ("running", Color::LightGreen),
+				Some(false) => ("stopped", Color::LightRed),
+				None => ("-", Color::Reset),
+			};
+			ListItem::new(Spans::from(vec![
+				Span::styled(
+					format!("{:<10}", component.name),
+					Style::default().add_modifier(Modifier::BOLD),
+				),
+				Span::raw(format!(
+					"{:<8} ",
+					component.version.as_deref().unwrap_or("?")
+				)),
+				Span::styled(
+					format!("{:<8} ", status),
+					if app.state.colored {
+						Style::default().fg(color)
+					} else {
+						Style::default()
+					},
+				),
+				Span::raw(component.path.to_string()),
+			]))
+		})
+		.collect::<Vec<ListItem>>();
+	items.push(ListItem::new(Span::raw("")));
+	items.push(ListItem::new(Span::styled(
+		format!(
+			":agent <{}> <{}>",
+			agent::DAEMON_ACTIONS.join("|"),
+			agent::DAEMONS.join("|")
+		),
+		Style::default().fg(Color::DarkGray),
+	)));
+	let width = cmp::min(cmp::max(rect.width * 3 / 4, 60), rect.width);
+	let height = cmp::min(items.len() as u16 + 2, rect.height);
+	let area = Rect::new(
+		rect.x + (rect.width - width) / 2,
+		rect.y + (rect.height - height) / 2,
+		width,
+		height,
+	);
+	frame.render_widget(Clear, area);
+	frame.render_widget(
+		List::new(items).block(
+			Block::default()
+				.title("Agent")
+				.style(if app.state.colored {
+					Style::default().fg(Color::LightBlue)
+				} else {
+					Style::default()
+				})
+				.borders(Borders::ALL),
+		),
+		area,
+	);
+}
+
+/// Renders the options menu.
+fn render_options_menu<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let items = app
+		.options
+		.items
+		.iter()
+		.map(|v| ListItem::new(Span::raw(v.to_string())))
+		.collect::<Vec<ListItem>>();
+	let (length_x, mut percent_y) = (38, 60);
+	let text_height = items.iter().map(|v| v.height() as f32).sum::<f32>() + 3.;
+	if rect.height.checked_sub(5).unwrap_or(rect.height) as f32 > text_height {
+		percent_y = ((text_height / rect.height as f32) * 100.) as u16;
+	}
+	let popup_layout = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(
+			[
+				Constraint::Percentage((100 - percent_y) / 2),
+				Constraint::Percentage(percent_y),
+				Constraint::Percentage((100 - percent_y) / 2),
+			]
+			.as_ref(),
+		)
+		.split(rect);
+	let area =
+		Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints(
+				[
+					Constraint::Length(
+						(popup_layout[1].width.checked_sub(length_x))
+							.unwrap_or_default() / 2,
+					),
+					Constraint::Min(length_x),
+					Constraint::Length(
+						(popup_layout[1].width.checked_sub(length_x))
+							.unwrap_or_default() / 2,
+					),
+				]
+				.as_ref(),
+			)
+			.split(popup_layout[1])[1];
+	app.options.page_size = usize::from(area.height.saturating_sub(2));
+	frame.render_widget(Clear, area);
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.title("Options")
+					.style(if app.state.colored {
+						Style::default().fg(Color::LightBlue)
+					} else {
+						Style::default()
+					})
+					.borders(Borders::ALL),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			)
+			.highlight_symbol("> "),
+		area,
+		&mut app.options.state,
+	);
+	frame.render_widget(
+		Scrollbar::new(
+			app.options.state.selected().unwrap_or_default(),
+			app.options.items.len(),
+			app.options.page_size,
+		)
+		.style(Style::default().fg(app.state.color)),
+		Scrollbar::get_track(area),
+	);
+}
+
+/// Renders the table of keys.
+fn render_keys_table<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	let keys_row_length = if app.keys_table.state.size != TableSize::Normal {
+		KEYS_ROW_LENGTH.0
+	} else {
+		KEYS_ROW_LENGTH.1
+	};
+	let mut widths = vec![
+		Constraint::Min(keys_row_length),
+		Constraint::Percentage(100),
+	];
+	let marker_length = if app.keys_table.state.anchor.is_some() {
+		widths.insert(0, Constraint::Length(1));
+		2
+	} else {
+		0
+	};
+	frame.render_stateful_widget(
+		Table::new(get_keys_table_rows(
 			app,
 			rect.width
-				.checked_sub(keys_row_length + 7)
+				.checked_sub(keys_row_length + marker_length + 7)
 				.unwrap_or(rect.width),
 			rect.height.checked_sub(2).unwrap_or(rect.height),
 		))
@@ -425,14 +1790,107 @@ fn render_keys_table<B: Backend>(
 				.borders(Borders::ALL)
 				.border_style(Style::default().fg(Color::DarkGray)),
 		)
-		.widths(&[
-			Constraint::Min(keys_row_length),
-			Constraint::Percentage(100),
-		])
+		.widths(&widths)
 		.column_spacing(1),
 		rect,
 		&mut app.keys_table.state.tui,
 	);
+	if app.key_loader.is_none() && app.is_keyring_empty() {
+		frame.render_widget(
+			Paragraph::new(
+				"No keys found. Press 'g' to generate a key, \
+				'i' to import from a file or 'f' to receive from a keyserver.",
+			)
+			.style(Style::default().fg(Color::DarkGray))
+			.alignment(Alignment::Center)
+			.wrap(Wrap { trim: true }),
+			Rect {
+				x: rect.x + 1,
+				y: rect.y + rect.height / 2,
+				width: rect.width.saturating_sub(2),
+				height: cmp::min(2, rect.height.saturating_sub(2)),
+			},
+		);
+	}
+	frame.render_widget(
+		Scrollbar::new(
+			app.keys_table.state.tui.selected().unwrap_or_default(),
+			app.keys_table.items.len(),
+			app.keys_table.state.page_size,
+		)
+		.style(Style::default().fg(app.state.color)),
+		Scrollbar::get_track(rect),
+	);
+}
+
+/// Renders the full details of the selected key.
+fn render_key_details<B: Backend>(
+	app: &mut App,
+	frame: &mut Frame<'_, B>,
+	rect: Rect,
+) {
+	app.update_key_tree();
+	let items = app
+		.key_tree
+		.get_lines()
+		.into_iter()
+		.map(|line| {
+			ListItem::new(if app.state.colored {
+				style::get_colored_table_row(&[line], false)
+			} else {
+				Text::from(line)
+			})
+		})
+		.collect::<Vec<ListItem>>();
+	let item_count = items.len();
+	frame.render_stateful_widget(
+		List::new(items)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_style(Style::default().fg(Color::DarkGray)),
+			)
+			.style(Style::default().fg(app.state.color))
+			.highlight_style(
+				Style::default()
+					.fg(Color::Reset)
+					.add_modifier(Modifier::BOLD),
+			),
+		rect,
+		&mut app.key_tree.state,
+	);
+	frame.render_widget(
+		Scrollbar::new(
+			app.key_tree.state.selected().unwrap_or_default(),
+			item_count,
+			rect.height.saturating_sub(2).into(),
+		)
+		.style(Style::default().fg(app.state.color)),
+		Scrollbar::get_track(rect),
+	);
+}
+
+/// Returns the rows for keys table from the cached keys.
+fn get_cached_table_rows<'a>(
+	app: &App,
+	rows: Vec<[String; 2]>,
+) -> Vec<Row<'a>> {
+	rows.iter()
+		.map(|row| {
+			Row::new(if app.state.colored {
+				vec![
+					style::get_colored_table_row(&row[..1], false),
+					style::get_colored_table_row(&row[1..], false),
+				]
+			} else {
+				vec![
+					Text::from(row[0].to_string()),
+					Text::from(row[1].to_string()),
+				]
+			})
+			.bottom_margin(app.keys_table_margin)
+		})
+		.collect()
 }
 
 /// Returns the rows for keys table.
@@ -441,76 +1899,74 @@ fn get_keys_table_rows<'a>(
 	max_width: u16,
 	max_height: u16,
 ) -> Vec<Row<'a>> {
-	let mut rows = Vec::new();
-	app.keys_table.items = app
-		.keys_table
-		.items
-		.clone()
-		.into_iter()
-		.enumerate()
-		.filter(|(i, key)| {
-			let subkey_info = key.get_subkey_info(
-				app.keys_table.state.size != TableSize::Normal,
-			);
-			let user_info = key.get_user_info(
-				app.keys_table.state.size == TableSize::Minimized,
-			);
-			if app.prompt.is_search_enabled() {
-				let search_term =
-					app.prompt.text.replacen("/", "", 1).to_lowercase();
-				if !subkey_info.join("\n").to_lowercase().contains(&search_term)
-					&& !user_info
-						.join("\n")
-						.to_lowercase()
-						.contains(&search_term)
-				{
-					return false;
-				}
-			}
-			let keys_row = RowItem::new(
-				subkey_info,
-				None,
-				max_height,
-				app.keys_table.state.scroll,
-			);
-			let users_row = RowItem::new(
-				user_info,
-				Some(max_width),
-				max_height,
-				app.keys_table.state.scroll,
+	if let (Some(cache), Tab::Keys(key_type)) = (&app.key_cache, app.tab) {
+		if app.keys_table.items.is_empty() {
+			return get_cached_table_rows(
+				app,
+				cache.get_rows(key_type, app.state.redact),
 			);
-			rows.push(
-				Row::new(if app.state.colored {
-					let highlighted =
-						app.keys_table.state.tui.selected() == Some(*i);
-					vec![
-						style::get_colored_table_row(
-							&keys_row.data,
-							highlighted,
-						),
-						style::get_colored_table_row(
-							&users_row.data,
-							highlighted,
-						),
-					]
-				} else {
-					vec![
-						Text::from(keys_row.data.join("\n")),
-						Text::from(users_row.data.join("\n")),
-					]
-				})
-				.height(
-					cmp::max(keys_row.data.len(), users_row.data.len())
-						.try_into()
-						.unwrap_or(1),
-				)
+		}
+	}
+	app.filter_keys_table();
+	let mut rows = Vec::new();
+	let mut rows_height = 0;
+	let selection = if app.keys_table.state.anchor.is_some() {
+		Some(app.keys_table.selected_indices())
+	} else {
+		None
+	};
+	for (i, key) in app.keys_table.items.iter().enumerate() {
+		let (subkey_info, user_info) = app.get_key_info(key);
+		let keys_row = RowItem::new(
+			subkey_info,
+			None,
+			max_height,
+			app.keys_table.state.scroll,
+		);
+		let users_row = RowItem::new(
+			user_info,
+			Some(max_width),
+			max_height,
+			app.keys_table.state.scroll,
+		);
+		let row_height = cmp::max(keys_row.data.len(), users_row.data.len());
+		rows_height += row_height + usize::from(app.keys_table_margin);
+		let mut cells = Vec::new();
+		if let Some(selection) = &selection {
+			cells.push(Text::styled(
+				if selection.contains(&i) { "*" } else { " " },
+				Style::default()
+					.fg(if app.state.colored {
+						Color::LightBlue
+					} else {
+						Color::Reset
+					})
+					.add_modifier(Modifier::BOLD),
+			));
+		}
+		cells.extend(if app.state.colored {
+			let highlighted = app.keys_table.state.tui.selected() == Some(i);
+			vec![
+				style::get_colored_table_row(&keys_row.data, highlighted),
+				style::get_colored_table_row(&users_row.data, highlighted),
+			]
+		} else {
+			vec![
+				Text::from(keys_row.data.join("\n")),
+				Text::from(users_row.data.join("\n")),
+			]
+		});
+		rows.push(
+			Row::new(cells)
+				.height(row_height.try_into().unwrap_or(1))
 				.bottom_margin(app.keys_table_margin)
 				.style(Style::default()),
-			);
-			true
-		})
-		.map(|(_, v)| v)
-		.collect();
+		);
+	}
+	if !rows.is_empty() {
+		app.keys_table.state.page_size =
+			usize::from(max_height) / cmp::max(rows_height / rows.len(), 1);
+	}
 	rows
 }
 
@@ -554,11 +2010,12 @@ mod tests {
 		let config = GpgConfig::new(&args)?;
 		let mut context = GpgContext::new(config)?;
 		let mut app = App::new(&mut context, &args)?;
+		app.wait_for_keys()?;
 		let backend = TestBackend::new(70, 10);
 		let mut terminal = Terminal::new(backend)?;
 		let test_key = format!(
 			"│> [sc--] rsa3072/{} [u] test@example.org              │",
-			app.gpgme.get_all_keys()?.get(&KeyType::Public).unwrap()[0]
+			app.gpgme.get_all_keys(None)?.get(&KeyType::Public).unwrap()[0]
 				.get_id()
 		)
 		.replace("0x", "");
@@ -0,0 +1,251 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+/// Expiration time of the old key after the rotation.
+pub const OLD_KEY_EXPIRY: &str = "3m";
+
+/// Steps of the key rotation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RotationStep {
+	/// Generate a new key with the primary user ID.
+	Generate,
+	/// Add the other user IDs to the new key.
+	CopyUserIds,
+	/// Certify the new key with the old key.
+	Certify,
+	/// Set an expiration time on the old key.
+	ExpireOld,
+	/// Write the transition statement.
+	Statement,
+	/// Send both keys to the keyserver.
+	Publish,
+}
+
+impl RotationStep {
+	/// All of the steps in order.
+	const ALL: &'static [Self] = &[
+		Self::Generate,
+		Self::CopyUserIds,
+		Self::Certify,
+		Self::ExpireOld,
+		Self::Statement,
+		Self::Publish,
+	];
+
+	/// Returns the number of the step (starting from 1).
+	pub fn get_number(&self) -> usize {
+		Self::ALL.iter().position(|v| v == self).unwrap_or_default() + 1
+	}
+
+	/// Returns the next step (if any).
+	fn next(&self) -> Option<Self> {
+		Self::ALL.get(self.get_number()).copied()
+	}
+
+	/// Returns the label of the option that runs the step.
+	pub fn get_label(&self) -> &'static str {
+		match self {
+			Self::Generate => "generate",
+			Self::CopyUserIds => "add user IDs",
+			Self::Certify => "certify",
+			Self::ExpireOld => "set expiry",
+			Self::Statement => "write",
+			Self::Publish => "publish",
+		}
+	}
+}
+
+/// Action in the key rotation wizard.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RotationAction {
+	/// Start the rotation of the key (or the selected key).
+	Start(Option<String>),
+	/// Run the current step.
+	Run,
+	/// Skip the current step.
+	Skip,
+	/// Cancel the rotation.
+	Cancel,
+}
+
+impl Display for RotationAction {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Start(_) => "rotate the key",
+				Self::Run => "run the rotation step",
+				Self::Skip => "skip the rotation step",
+				Self::Cancel => "cancel the rotation",
+			}
+		)
+	}
+}
+
+impl FromStr for RotationAction {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"run" | "next" => Ok(Self::Run),
+			"skip" => Ok(Self::Skip),
+			"cancel" | "abort" => Ok(Self::Cancel),
+			_ => Err(()),
+		}
+	}
+}
+
+/// State of the key rotation wizard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyRotation {
+	/// Fingerprint of the old key.
+	pub old_key: String,
+	/// User IDs of the old key (primary first).
+	pub user_ids: Vec<String>,
+	/// Fingerprint of the new key (after it is generated).
+	pub new_key: Option<String>,
+	/// Current step.
+	pub step: RotationStep,
+}
+
+impl KeyRotation {
+	/// Constructs a new instance of `KeyRotation`.
+	pub fn new(old_key: String, user_ids: Vec<String>) -> Self {
+		Self {
+			old_key,
+			user_ids,
+			new_key: None,
+			step: RotationStep::Generate,
+		}
+	}
+
+	/// Moves to the next step.
+	///
+	/// Adding the user IDs is skipped if there is only one.
+	/// Returns `false` if the rotation is finished.
+	pub fn advance(&mut self) -> bool {
+		match self.step.next() {
+			Some(RotationStep::CopyUserIds) if self.user_ids.len() < 2 => {
+				self.step = RotationStep::CopyUserIds;
+				self.advance()
+			}
+			Some(step) => {
+				self.step = step;
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Returns the description of the current step.
+	pub fn get_description(&self) -> String {
+		let description = match self.step {
+			RotationStep::Generate => format!(
+				"Generate a new key for:\n{}\n\n\
+				The old key ({}) stays in the keyring\n\
+				and it will be used for certifying the new key.",
+				self.user_ids.first().cloned().unwrap_or_default(),
+				self.old_key
+			),
+			RotationStep::CopyUserIds => format!(
+				"Add the other user IDs to the new key:\n{}",
+				self.user_ids
+					.iter()
+					.skip(1)
+					.map(|v| format!("- {}", v))
+					.collect::<Vec<String>>()
+					.join("\n")
+			),
+			RotationStep::Certify => format!(
+				"Certify the new key ({}) with the old key.",
+				self.new_key.as_deref().unwrap_or("?")
+			),
+			RotationStep::ExpireOld => format!(
+				"Set the old key to expire in {}.\n\n\
+				It is still valid for the others during the transition.",
+				OLD_KEY_EXPIRY
+			),
+			RotationStep::Statement => String::from(
+				"Write a transition statement to the output directory\n\
+				and sign it with both of the keys.",
+			),
+			RotationStep::Publish => {
+				String::from("Send both of the keys to the keyserver.")
+			}
+		};
+		format!(
+			"Step {}/{}: {}",
+			self.step.get_number(),
+			RotationStep::ALL.len(),
+			description
+		)
+	}
+
+	/// Returns the text of the transition statement.
+	pub fn get_statement(&self, date: &str) -> String {
+		format!(
+			"OpenPGP key transition statement ({date})\n\n\
+			I am transitioning to a new OpenPGP key and the old key\n\
+			will be retired. Please use the new key from now on and\n\
+			update your records accordingly.\n\n\
+			Old key: {old_key}\n\
+			New key: {new_key}\n\n\
+			User IDs:\n{user_ids}\n\n\
+			This statement is signed by both of the keys and\n\
+			the new key is certified by the old key.\n",
+			date = date,
+			old_key = self.old_key,
+			new_key = self.new_key.as_deref().unwrap_or_default(),
+			user_ids = self
+				.user_ids
+				.iter()
+				.map(|v| format!("  {}", v))
+				.collect::<Vec<String>>()
+				.join("\n"),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_rotate() {
+		let mut rotation = KeyRotation::new(
+			String::from("ABCD"),
+			vec![String::from("Alice <alice@example.org>")],
+		);
+		assert_eq!(RotationStep::Generate, rotation.step);
+		assert!(rotation.get_description().starts_with("Step 1/6: "));
+		rotation.new_key = Some(String::from("EF01"));
+		for step in &[
+			RotationStep::Certify,
+			RotationStep::ExpireOld,
+			RotationStep::Statement,
+			RotationStep::Publish,
+		] {
+			assert!(rotation.advance());
+			assert_eq!(*step, rotation.step);
+		}
+		assert!(!rotation.advance());
+		assert!(rotation.get_description().starts_with("Step 6/6: "));
+		rotation
+			.user_ids
+			.push(String::from("Alice <alice@example.com>"));
+		rotation.step = RotationStep::Generate;
+		assert!(rotation.advance());
+		assert_eq!(RotationStep::CopyUserIds, rotation.step);
+		assert!(rotation
+			.get_description()
+			.ends_with("\n- Alice <alice@example.com>"));
+		let statement = rotation.get_statement("2022-01-01");
+		assert!(statement
+			.starts_with("OpenPGP key transition statement (2022-01-01)\n"));
+		assert!(statement.contains("Old key: ABCD\nNew key: EF01\n"));
+		assert!(statement.contains("  Alice <alice@example.com>\n"));
+		assert_eq!(Ok(RotationAction::Skip), RotationAction::from_str("skip"));
+		assert!(RotationAction::from_str("xyz").is_err());
+		assert_eq!("cancel the rotation", RotationAction::Cancel.to_string());
+	}
+}
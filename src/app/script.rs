@@ -0,0 +1,471 @@
+use crate::app::command::Command;
+use crate::app::export::ExportDestination;
+use crate::app::prompt::OutputType;
+use crate::gpg::key::{GpgKey, KeyType};
+use anyhow::{anyhow, Result};
+use rhai::{
+	Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST,
+};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Name of the directory that contains the scripts.
+const SCRIPTS_DIR: &str = "scripts";
+
+/// Extension of the script files.
+const SCRIPT_EXTENSION: &str = "rhai";
+
+/// Max number of operations that a script can run.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Max depth of the function calls in a script.
+const MAX_CALL_LEVELS: usize = 32;
+
+/// Result type of the functions that are exposed to the scripts.
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// State of a running script that is shared with its functions.
+type SharedContext = Arc<Mutex<ScriptContext>>;
+
+/// Key information that is available to the scripts.
+#[derive(Clone, Debug, PartialEq)]
+struct ScriptKey {
+	/// Type of the key.
+	key_type: KeyType,
+	/// Key ID with '0x' prefix.
+	id: String,
+	/// Fingerprint of the key.
+	fingerprint: String,
+	/// Primary user ID of the key.
+	user_id: String,
+	/// Email addresses of the users.
+	emails: Vec<String>,
+}
+
+impl ScriptKey {
+	/// Constructs a new instance from the given key.
+	fn new(key_type: KeyType, key: &GpgKey) -> Self {
+		Self {
+			key_type,
+			id: key.get_id(),
+			fingerprint: key.get_fingerprint(),
+			user_id: key.get_user_id(),
+			emails: key.get_user_emails(),
+		}
+	}
+
+	/// Returns the key as an object map.
+	fn to_map(&self) -> Dynamic {
+		let mut map = Map::new();
+		map.insert("type".into(), self.key_type.to_string().into());
+		map.insert("id".into(), self.id.clone().into());
+		map.insert("fingerprint".into(), self.fingerprint.clone().into());
+		map.insert("user_id".into(), self.user_id.clone().into());
+		map.insert(
+			"emails".into(),
+			Dynamic::from_array(
+				self.emails.iter().cloned().map(Dynamic::from).collect(),
+			),
+		);
+		Dynamic::from_map(map)
+	}
+}
+
+/// State of the interface that is available to the scripts.
+///
+/// Scripts only see a snapshot of the keys and they act on
+/// the interface by requesting commands which are run after
+/// the script returns.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptContext {
+	/// Listed keys.
+	keys: Vec<ScriptKey>,
+	/// Selected key.
+	selected: Option<ScriptKey>,
+	/// Requested commands.
+	commands: Vec<Command>,
+}
+
+impl ScriptContext {
+	/// Adds the given keys to the context.
+	pub fn with_keys(
+		mut self,
+		key_type: KeyType,
+		keys: &[Arc<GpgKey>],
+	) -> Self {
+		self.keys
+			.extend(keys.iter().map(|key| ScriptKey::new(key_type, key)));
+		self
+	}
+
+	/// Sets the selected key.
+	pub fn with_selected(mut self, key_type: KeyType, key: &GpgKey) -> Self {
+		self.selected = Some(ScriptKey::new(key_type, key));
+		self
+	}
+}
+
+/// Command that is registered by a script.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptCommand {
+	/// Description of the command.
+	pub description: String,
+	/// Index of the script that registered the command.
+	script: usize,
+	/// Name of the function to call.
+	function: String,
+}
+
+/// Scripts that add new commands to the prompt.
+///
+/// Each script registers its commands on load via
+/// `register(name, description, function)` and the function
+/// is called with the arguments of the command (as an array).
+#[derive(Debug, Default)]
+pub struct Scripts {
+	/// Compiled scripts.
+	asts: Vec<AST>,
+	/// Registered commands by their names.
+	pub commands: HashMap<String, ScriptCommand>,
+}
+
+impl Scripts {
+	/// Loads the scripts in the given directory.
+	///
+	/// Scripts that fail to load are logged and skipped.
+	pub fn load(dir: &Path) -> Result<Self> {
+		let mut paths = fs::read_dir(dir)?
+			.map(|entry| entry.map(|entry| entry.path()))
+			.collect::<Result<Vec<PathBuf>, _>>()?;
+		paths.sort();
+		let mut scripts = Self::default();
+		for path in paths.iter().filter(|path| {
+			path.is_file()
+				&& path.extension() == Some(OsStr::new(SCRIPT_EXTENSION))
+		}) {
+			if let Err(e) = scripts.load_script(&fs::read_to_string(path)?) {
+				log::error!("failed to load {:?}: {}", path, e);
+			}
+		}
+		Ok(scripts)
+	}
+
+	/// Compiles the given script and registers its commands.
+	///
+	/// None of the key functions are available while loading.
+	fn load_script(&mut self, content: &str) -> Result<()> {
+		let registered = Arc::new(Mutex::new(Vec::new()));
+		let mut engine = create_engine();
+		let commands = Arc::clone(&registered);
+		engine.register_fn(
+			"register",
+			move |name: &str, description: &str, function: &str| {
+				if let Ok(mut commands) = commands.lock() {
+					commands.push((
+						name.to_lowercase(),
+						description.to_string(),
+						function.to_string(),
+					));
+				}
+			},
+		);
+		let ast = engine.compile(content).map_err(|e| anyhow!("{}", e))?;
+		engine.run_ast(&ast).map_err(|e| anyhow!("{}", e))?;
+		let registered = registered
+			.lock()
+			.map_err(|e| anyhow!("{}", e))?
+			.drain(..)
+			.collect::<Vec<(String, String, String)>>();
+		if let Some((_, _, function)) =
+			registered.iter().find(|(_, _, function)| {
+				!ast.iter_functions().any(|f| f.name == function)
+			}) {
+			return Err(anyhow!("function not found: {}", function));
+		}
+		for (name, description, function) in registered {
+			self.commands.insert(
+				name,
+				ScriptCommand {
+					description,
+					script: self.asts.len(),
+					function,
+				},
+			);
+		}
+		self.asts.push(ast);
+		Ok(())
+	}
+
+	/// Returns `true` if a command with the given name is registered.
+	pub fn contains(&self, name: &str) -> bool {
+		self.commands.contains_key(name)
+	}
+
+	/// Runs the command with the given name and arguments.
+	///
+	/// Returns the commands that are requested by the script.
+	pub fn run(
+		&self,
+		name: &str,
+		args: &[String],
+		context: ScriptContext,
+	) -> Result<Vec<Command>> {
+		let command = self
+			.commands
+			.get(name)
+			.ok_or_else(|| anyhow!("script not found: {}", name))?;
+		let ast = self
+			.asts
+			.get(command.script)
+			.ok_or_else(|| anyhow!("script not found: {}", name))?;
+		let context = Arc::new(Mutex::new(context));
+		let mut engine = create_engine();
+		register_api(&mut engine, &context);
+		engine
+			.call_fn_with_options::<Dynamic>(
+				CallFnOptions::new().eval_ast(false),
+				&mut Scope::new(),
+				ast,
+				&command.function,
+				(args.iter().cloned().map(Dynamic::from).collect::<Array>(),),
+			)
+			.map(drop)
+			.map_err(|e| anyhow!("{}", e))?;
+		let commands = context
+			.lock()
+			.map_err(|e| anyhow!("{}", e))?
+			.commands
+			.drain(..)
+			.collect();
+		Ok(commands)
+	}
+}
+
+/// Creates a script engine with the resource limits.
+///
+/// Output of `print` and `debug` goes to the log file.
+fn create_engine() -> Engine {
+	let mut engine = Engine::new();
+	engine
+		.set_max_operations(MAX_OPERATIONS)
+		.set_max_call_levels(MAX_CALL_LEVELS)
+		.disable_symbol("eval");
+	engine.on_print(|text| log::info!("{}", text));
+	engine.on_debug(|text, _, position| log::debug!("{} ({})", text, position));
+	engine
+}
+
+/// Adds the given command to the requested commands.
+fn push_command(context: &SharedContext, command: Command) -> ScriptResult<()> {
+	context
+		.lock()
+		.map_err(|e| e.to_string())?
+		.commands
+		.push(command);
+	Ok(())
+}
+
+/// Converts the given array into a list of strings.
+fn get_strings(values: Array) -> ScriptResult<Vec<String>> {
+	values
+		.into_iter()
+		.map(|value| value.into_string().map_err(Into::into))
+		.collect()
+}
+
+/// Parses the given key type (`pub` or `sec`).
+fn get_key_type(value: &str) -> ScriptResult<KeyType> {
+	KeyType::from_str(value)
+		.map_err(|_| format!("invalid key type: {}", value).into())
+}
+
+/// Registers the functions for accessing the keys and the interface.
+///
+/// * `keys(type)`: returns the keys of the given type
+/// * `selected()`: returns the selected key (or `()`)
+/// * `select(query)`: selects the key that matches the search query
+/// * `export_keys(type, ids)`: exports the keys to the output directory
+/// * `import_keys(files)`: imports the keys from the given files
+/// * `message(type, text)`: shows a message (e.g. `success`)
+fn register_api(engine: &mut Engine, context: &SharedContext) {
+	let keys_context = Arc::clone(context);
+	engine.register_fn("keys", move |key_type: &str| -> ScriptResult<Array> {
+		let key_type = get_key_type(key_type)?;
+		Ok(keys_context
+			.lock()
+			.map_err(|e| e.to_string())?
+			.keys
+			.iter()
+			.filter(|key| key.key_type == key_type)
+			.map(ScriptKey::to_map)
+			.collect())
+	});
+	let selected_context = Arc::clone(context);
+	engine.register_fn("selected", move || -> ScriptResult<Dynamic> {
+		Ok(selected_context
+			.lock()
+			.map_err(|e| e.to_string())?
+			.selected
+			.as_ref()
+			.map_or(Dynamic::UNIT, ScriptKey::to_map))
+	});
+	let select_context = Arc::clone(context);
+	engine.register_fn("select", move |query: &str| {
+		push_command(&select_context, Command::Focus(query.to_string()))
+	});
+	let export_context = Arc::clone(context);
+	engine.register_fn("export_keys", move |key_type: &str, ids: Array| {
+		push_command(
+			&export_context,
+			Command::ExportKeys(
+				get_key_type(key_type)?,
+				get_strings(ids)?,
+				false,
+				ExportDestination::File,
+			),
+		)
+	});
+	let import_context = Arc::clone(context);
+	engine.register_fn("import_keys", move |files: Array| {
+		push_command(
+			&import_context,
+			Command::ImportKeys(get_strings(files)?, false),
+		)
+	});
+	let message_context = Arc::clone(context);
+	engine.register_fn("message", move |output_type: &str, text: &str| {
+		push_command(
+			&message_context,
+			Command::ShowOutput(
+				OutputType::from(output_type.to_string()),
+				text.to_string(),
+			),
+		)
+	});
+}
+
+/// Returns the directory of the scripts.
+///
+/// It is placed next to the given configuration file or
+/// in `$XDG_CONFIG_HOME/gpg-tui` if it is not given.
+pub fn get_scripts_dir(config_file: Option<&str>) -> Option<PathBuf> {
+	match config_file {
+		Some(file) => Path::new(file).parent().map(Path::to_path_buf),
+		None => dirs_next::config_dir()
+			.map(|path| path.join(env!("CARGO_PKG_NAME"))),
+	}
+	.map(|path| path.join(SCRIPTS_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn test_app_script() -> Result<()> {
+		let mut scripts = Scripts::default();
+		scripts.load_script(
+			r#"
+			fn backup(args) {
+				let key = if args.is_empty() { selected() } else { #{ id: args[0] } };
+				if key == () {
+					message("warning", "no key is selected");
+				} else {
+					export_keys("sec", [key.id]);
+					message("success", `backed up ${key.id}`);
+				}
+			}
+			fn find(args) {
+				for key in keys("pub") {
+					if key.emails.contains(args[0]) {
+						select(key.fingerprint);
+					}
+				}
+			}
+			register("Backup", "Backs up a secret key.", "backup");
+			register("find", "Selects the key by email.", "find");
+			"#,
+		)?;
+		assert!(scripts.contains("backup"));
+		assert_eq!(
+			Some(String::from("Backs up a secret key.")),
+			scripts
+				.commands
+				.get("backup")
+				.map(|command| command.description.to_string())
+		);
+		assert_eq!(
+			vec![
+				Command::ExportKeys(
+					KeyType::Secret,
+					vec![String::from("0x0")],
+					false,
+					ExportDestination::File
+				),
+				Command::ShowOutput(
+					OutputType::Success,
+					String::from("backed up 0x0")
+				),
+			],
+			scripts.run(
+				"backup",
+				&[String::from("0x0")],
+				ScriptContext::default()
+			)?
+		);
+		assert_eq!(
+			vec![Command::ShowOutput(
+				OutputType::Warning,
+				String::from("no key is selected")
+			)],
+			scripts.run("backup", &[], ScriptContext::default())?
+		);
+		assert!(scripts
+			.run("find", &[], ScriptContext::default())?
+			.is_empty());
+		assert!(scripts.run("xyz", &[], ScriptContext::default()).is_err());
+		assert!(scripts
+			.load_script("register(\"x\", \"\", \"x\");")
+			.is_err());
+		assert!(scripts.load_script("message(\"success\", \"\");").is_err());
+		assert!(scripts.load_script("loop {}").is_err());
+		assert!(!scripts.contains("x"));
+		assert_eq!(
+			Some(Path::new("/tmp").join(SCRIPTS_DIR)),
+			get_scripts_dir(Some("/tmp/gpg-tui.toml"))
+		);
+		let dir = env::temp_dir().join(format!(
+			"{}-{}",
+			env!("CARGO_PKG_NAME"),
+			SCRIPTS_DIR
+		));
+		fs::create_dir_all(&dir)?;
+		fs::write(
+			dir.join("refresh.rhai"),
+			"fn refresh(args) { import_keys(args); }
+			register(\"refresh\", \"\", \"refresh\");",
+		)?;
+		fs::write(dir.join("invalid.rhai"), "fn (")?;
+		fs::write(dir.join("other.txt"), "register(\"other\", \"\", \"x\");")?;
+		let scripts = Scripts::load(&dir)?;
+		assert_eq!(
+			vec!["refresh"],
+			scripts.commands.keys().collect::<Vec<_>>()
+		);
+		assert_eq!(
+			vec![Command::ImportKeys(vec![String::from("a.asc")], false)],
+			scripts.run(
+				"refresh",
+				&[String::from("a.asc")],
+				ScriptContext::default()
+			)?
+		);
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+}
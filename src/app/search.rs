@@ -0,0 +1,254 @@
+use crate::app::filter::Filter;
+use crate::gpg::key::GpgKey;
+use regex::{Error as RegexError, Regex};
+use std::str::FromStr;
+
+/// Prefix of the regular expression patterns.
+const REGEX_PREFIX: &str = "re:";
+
+/// Prefix of the negated terms.
+const NEGATION_PREFIX: char = '!';
+
+/// Separator of the alternative groups of terms.
+const OR_SEPARATOR: char = '|';
+
+/// Field of the key to search in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchField {
+	/// All the information that is shown in the table rows.
+	All,
+	/// Names of the users.
+	Name,
+	/// Email addresses of the users.
+	Email,
+	/// Fingerprint of the key.
+	Fingerprint,
+}
+
+impl SearchField {
+	/// Returns the values of the field from the given key.
+	fn get_values(&self, key: &GpgKey) -> Vec<String> {
+		match self {
+			Self::All => vec![key.get_row_text()],
+			Self::Name => key.get_user_names(),
+			Self::Email => key.get_user_emails(),
+			Self::Fingerprint => vec![key.get_fingerprint()],
+		}
+	}
+
+	/// Returns `true` if one of the values of the field
+	/// contains the given lowercase text.
+	///
+	/// The search index of the key is used for the values.
+	fn contains(&self, key: &GpgKey, text: &str) -> bool {
+		let index = key.get_search_index();
+		let contains =
+			|values: &[String]| values.iter().any(|v| v.contains(text));
+		match self {
+			Self::All => index.row.contains(text),
+			Self::Name => contains(&index.names),
+			Self::Email => contains(&index.emails),
+			Self::Fingerprint => index.fingerprint.contains(text),
+		}
+	}
+}
+
+/// Pattern to match the field values with.
+#[derive(Clone, Debug)]
+pub enum Pattern {
+	/// Case-insensitive substring.
+	Text(String),
+	/// Regular expression.
+	Regex(Regex),
+}
+
+impl Pattern {
+	/// Returns `true` if the given value matches the pattern.
+	fn is_match(&self, value: &str) -> bool {
+		match self {
+			Self::Text(text) => value.to_lowercase().contains(text),
+			Self::Regex(regex) => regex.is_match(value),
+		}
+	}
+}
+
+/// Condition to check for a key.
+#[derive(Clone, Debug)]
+pub enum Condition {
+	/// Field value that matches with the pattern.
+	Field(SearchField, Pattern),
+	/// State or capability of the key (e.g. `expired`).
+	State(Filter),
+}
+
+/// Single term of the search query.
+///
+/// It consists of an optional field scope (e.g. `email:`) and
+/// a pattern which is a regular expression if it starts with `re:`.
+/// Terms that start with `!` are negated.
+#[derive(Clone, Debug)]
+pub struct Term {
+	/// Is the term negated?
+	pub negated: bool,
+	/// Condition of the term.
+	pub condition: Condition,
+}
+
+impl FromStr for Term {
+	type Err = RegexError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (negated, s) = match s.strip_prefix(NEGATION_PREFIX) {
+			Some(s) => (true, s),
+			None => (false, s),
+		};
+		if let Ok(filter) = Filter::from_str(s) {
+			if filter.to_string() == s {
+				return Ok(Self {
+					negated,
+					condition: Condition::State(filter),
+				});
+			}
+		}
+		let (field, value) = match s.split_once(':') {
+			Some(("name", value)) => (SearchField::Name, value),
+			Some(("email", value)) => (SearchField::Email, value),
+			Some(("fpr", value)) => (
+				SearchField::Fingerprint,
+				value.strip_prefix("0x").unwrap_or(value),
+			),
+			_ => (SearchField::All, s),
+		};
+		Ok(Self {
+			negated,
+			condition: Condition::Field(
+				field,
+				match value.strip_prefix(REGEX_PREFIX) {
+					Some(regex) => Pattern::Regex(Regex::new(regex)?),
+					None => Pattern::Text(value.to_lowercase()),
+				},
+			),
+		})
+	}
+}
+
+impl Term {
+	/// Returns `true` if the given key matches the term.
+	pub fn matches(&self, key: &GpgKey) -> bool {
+		let matches = match &self.condition {
+			Condition::Field(field, Pattern::Text(text)) => {
+				field.contains(key, text)
+			}
+			Condition::Field(field, pattern) => field
+				.get_values(key)
+				.iter()
+				.any(|value| pattern.is_match(value)),
+			Condition::State(filter) => filter.matches(key),
+		};
+		matches != self.negated
+	}
+}
+
+/// Search query for the keys table.
+///
+/// Terms that are separated by whitespace must all match
+/// while the groups of terms are separated by `|` (or).
+#[derive(Clone, Debug)]
+pub struct Query {
+	/// Groups of terms.
+	pub groups: Vec<Vec<Term>>,
+}
+
+impl FromStr for Query {
+	type Err = RegexError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(Self {
+			groups: s
+				.split(OR_SEPARATOR)
+				.map(|group| {
+					group
+						.split_whitespace()
+						.map(Term::from_str)
+						.collect::<Result<Vec<Term>, Self::Err>>()
+				})
+				.collect::<Result<Vec<Vec<Term>>, Self::Err>>()?
+				.into_iter()
+				.filter(|group| !group.is_empty())
+				.collect(),
+		})
+	}
+}
+
+impl Query {
+	/// Returns `true` if the given key matches the query.
+	///
+	/// An empty query matches all the keys.
+	pub fn matches(&self, key: &GpgKey) -> bool {
+		self.groups.is_empty()
+			|| self
+				.groups
+				.iter()
+				.any(|group| group.iter().all(|term| term.matches(key)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_search() -> Result<(), RegexError> {
+		let term = Term::from_str("email:Foo")?;
+		assert!(!term.negated);
+		match term.condition {
+			Condition::Field(field, pattern) => {
+				assert_eq!(SearchField::Email, field);
+				assert!(pattern.is_match("foo@bar.com"));
+			}
+			_ => panic!("invalid condition"),
+		}
+		let term = Term::from_str("!fpr:0xABCD")?;
+		assert!(term.negated);
+		match term.condition {
+			Condition::Field(field, pattern) => {
+				assert_eq!(SearchField::Fingerprint, field);
+				assert!(pattern.is_match("1234ABCD"));
+			}
+			_ => panic!("invalid condition"),
+		}
+		match Term::from_str("re:^[a-z]+@example\\.org$")?.condition {
+			Condition::Field(field, pattern) => {
+				assert_eq!(SearchField::All, field);
+				assert!(pattern.is_match("alice@example.org"));
+				assert!(!pattern.is_match("alice@example.org.com"));
+			}
+			_ => panic!("invalid condition"),
+		}
+		match Term::from_str("name:re:^Al")?.condition {
+			Condition::Field(field, pattern) => {
+				assert_eq!(SearchField::Name, field);
+				assert!(pattern.is_match("Alice"));
+				assert!(!pattern.is_match("alice"));
+			}
+			_ => panic!("invalid condition"),
+		}
+		match Term::from_str("other:value")?.condition {
+			Condition::Field(field, pattern) => {
+				assert_eq!(SearchField::All, field);
+				assert!(pattern.is_match("OTHER:VALUE"));
+			}
+			_ => panic!("invalid condition"),
+		}
+		match Term::from_str("!expired")?.condition {
+			Condition::State(filter) => assert_eq!(Filter::Expired, filter),
+			_ => panic!("invalid condition"),
+		}
+		assert!(Term::from_str("re:(").is_err());
+		let query = Query::from_str("rust !expired | alice |")?;
+		assert_eq!(2, query.groups.len());
+		assert_eq!(2, query.groups[0].len());
+		assert_eq!(1, query.groups[1].len());
+		assert!(Query::from_str("  ")?.groups.is_empty());
+		assert!(Query::from_str("a | re:(").is_err());
+		Ok(())
+	}
+}
@@ -1,19 +1,32 @@
+use crate::gpg::fingerprint::FingerprintFormat;
+use crate::gpg::key::GpgKey;
+use chrono::{DateTime, Utc};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
 
 /// Application property to copy to clipboard.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Selection {
 	/// Selected row of the keys table.
 	TableRow(usize),
 	/// Exported key.
 	Key,
+	/// Exported key in a fenced code block of Markdown.
+	KeyMarkdown,
+	/// Exported key in a `<pre>` block of HTML.
+	KeyHtml,
 	/// ID of the selected key.
 	KeyId,
 	/// Fingerprint of the selected key.
 	KeyFingerprint,
+	/// Fingerprint of the selected key in a human-verification format.
+	FormattedFingerprint(FingerprintFormat),
 	/// User ID of the selected key.
 	KeyUserId,
+	/// OpenSSH public key of the selected key.
+	SshKey,
+	/// Copy template with the given name (from the configuration file).
+	Template(String),
 }
 
 impl Display for Selection {
@@ -24,9 +37,16 @@ impl Display for Selection {
 			match self {
 				Self::TableRow(i) => format!("table row ({})", i),
 				Self::Key => String::from("exported key"),
+				Self::KeyMarkdown => String::from("exported key (Markdown)"),
+				Self::KeyHtml => String::from("exported key (HTML)"),
 				Self::KeyId => String::from("key ID"),
 				Self::KeyFingerprint => String::from("key fingerprint"),
+				Self::FormattedFingerprint(format) => {
+					format!("key fingerprint ({})", format)
+				}
 				Self::KeyUserId => String::from("user ID"),
+				Self::SshKey => String::from("SSH key"),
+				Self::Template(name) => format!("template ({})", name),
 			}
 		)
 	}
@@ -39,16 +59,100 @@ impl FromStr for Selection {
 			"row1" | "1" => Ok(Self::TableRow(1)),
 			"row2" | "2" => Ok(Self::TableRow(2)),
 			"key" => Ok(Self::Key),
+			"key_markdown" | "key_md" | "markdown" | "md" => {
+				Ok(Self::KeyMarkdown)
+			}
+			"key_html" | "html" => Ok(Self::KeyHtml),
 			"key_id" | "id" => Ok(Self::KeyId),
 			"key_fingerprint" | "key_fpr" | "fingerprint" | "fpr" => {
 				Ok(Self::KeyFingerprint)
 			}
+			"fpr_groups" => {
+				Ok(Self::FormattedFingerprint(FingerprintFormat::Groups))
+			}
+			"fpr_words" => {
+				Ok(Self::FormattedFingerprint(FingerprintFormat::Words))
+			}
+			"fpr_nato" => {
+				Ok(Self::FormattedFingerprint(FingerprintFormat::Nato))
+			}
 			"key_user_id" | "user" | "user_id" => Ok(Self::KeyUserId),
-			_ => Err(String::from("could not parse the type")),
+			"ssh_key" | "ssh" => Ok(Self::SshKey),
+			_ => match s.strip_prefix("template:") {
+				Some(name) if !name.is_empty() => {
+					Ok(Self::Template(name.to_string()))
+				}
+				_ => Err(String::from("could not parse the type")),
+			},
 		}
 	}
 }
 
+impl Selection {
+	/// Returns `true` if the selection is the exported key.
+	pub fn is_exported_key(&self) -> bool {
+		matches!(self, Self::Key | Self::KeyMarkdown | Self::KeyHtml)
+	}
+
+	/// Wraps the exported key in a block of the selected markup.
+	pub fn wrap_exported_key(&self, key: &str) -> String {
+		match self {
+			Self::KeyMarkdown => format!("```\n{}\n```", key.trim_end()),
+			Self::KeyHtml => format!(
+				"<pre>\n{}\n</pre>",
+				key.trim_end()
+					.replace('&', "&amp;")
+					.replace('<', "&lt;")
+					.replace('>', "&gt;")
+			),
+			_ => key.to_string(),
+		}
+	}
+}
+
+/// Formats the copy template by replacing the fields with the key values.
+///
+/// Available fields are `{id}`, `{fpr}`, `{uid}`, `{name}`,
+/// `{email}`, `{algo}`, `{created}` and `{expires}`.
+pub fn format_template(
+	template: &str,
+	key: &GpgKey,
+	date_format: &str,
+) -> String {
+	let format_date = |date: Option<DateTime<Utc>>| {
+		date.map(|date| date.format(date_format).to_string())
+	};
+	let values = [
+		("id", key.get_id()),
+		("fpr", key.get_fingerprint()),
+		("uid", key.get_user_id()),
+		(
+			"name",
+			key.get_user_names().first().cloned().unwrap_or_default(),
+		),
+		(
+			"email",
+			key.get_user_emails().first().cloned().unwrap_or_default(),
+		),
+		("algo", key.get_algorithm()),
+		(
+			"created",
+			format_date(key.get_creation_time())
+				.unwrap_or_else(|| String::from("?")),
+		),
+		(
+			"expires",
+			format_date(key.get_expiration_time())
+				.unwrap_or_else(|| String::from("never")),
+		),
+	];
+	let mut output = template.to_string();
+	for (field, value) in values.iter() {
+		output = output.replace(&format!("{{{}}}", field), value);
+	}
+	output
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -61,14 +165,49 @@ mod tests {
 		let copy_type = Selection::from_str("key").unwrap();
 		assert_eq!(Selection::Key, copy_type);
 		assert_eq!(String::from("exported key"), copy_type.to_string());
+		let copy_type = Selection::from_str("key_md").unwrap();
+		assert_eq!(Selection::KeyMarkdown, copy_type);
+		assert_eq!(
+			String::from("exported key (Markdown)"),
+			copy_type.to_string()
+		);
+		assert!(copy_type.is_exported_key());
+		assert_eq!(
+			"```\n-----BEGIN PGP PUBLIC KEY BLOCK-----\n```",
+			copy_type
+				.wrap_exported_key("-----BEGIN PGP PUBLIC KEY BLOCK-----\n")
+		);
+		let copy_type = Selection::from_str("key_html").unwrap();
+		assert_eq!(Selection::KeyHtml, copy_type);
+		assert_eq!(
+			"<pre>\nComment: &lt;a&gt; &amp; b\n</pre>",
+			copy_type.wrap_exported_key("Comment: <a> & b")
+		);
 		let copy_type = Selection::from_str("key_id").unwrap();
+		assert!(!copy_type.is_exported_key());
 		assert_eq!(Selection::KeyId, copy_type);
 		assert_eq!(String::from("key ID"), copy_type.to_string());
 		let copy_type = Selection::from_str("key_fingerprint").unwrap();
 		assert_eq!(Selection::KeyFingerprint, copy_type);
 		assert_eq!(String::from("key fingerprint"), copy_type.to_string());
+		let copy_type = Selection::from_str("fpr_words").unwrap();
+		assert_eq!(
+			Selection::FormattedFingerprint(FingerprintFormat::Words),
+			copy_type
+		);
+		assert_eq!(
+			String::from("key fingerprint (words)"),
+			copy_type.to_string()
+		);
 		let copy_type = Selection::from_str("key_user_id").unwrap();
 		assert_eq!(Selection::KeyUserId, copy_type);
 		assert_eq!(String::from("user ID"), copy_type.to_string());
+		let copy_type = Selection::from_str("ssh").unwrap();
+		assert_eq!(Selection::SshKey, copy_type);
+		assert_eq!(String::from("SSH key"), copy_type.to_string());
+		let copy_type = Selection::from_str("template:signature").unwrap();
+		assert_eq!(Selection::Template(String::from("signature")), copy_type);
+		assert_eq!(String::from("template (signature)"), copy_type.to_string());
+		assert!(Selection::from_str("template:").is_err());
 	}
 }
@@ -0,0 +1,99 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Name of the session file.
+const SESSION_FILE: &str = "session.json";
+
+/// State of the interface that is restored on the next launch.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+	/// Home directory of the keyring.
+	pub home_dir: PathBuf,
+	/// Active tab (e.g. "pub", "sec" or "help").
+	pub tab: String,
+	/// Fingerprint of the selected key.
+	pub selection: Option<String>,
+	/// Search query.
+	pub search: Option<String>,
+	/// Filter of the keys table.
+	pub filter: Option<String>,
+	/// Level of detail.
+	pub detail: String,
+	/// Table size (e.g. "normal", "compact" or "minimized").
+	pub table_size: String,
+	/// Is the interface colored?
+	pub colored: bool,
+	/// Is the detail panel (split view) showing?
+	pub split_view: bool,
+}
+
+impl Session {
+	/// Returns the path of the session file.
+	///
+	/// It is placed in `$XDG_STATE_HOME` (or `~/.local/state`).
+	pub fn get_path() -> Result<PathBuf> {
+		env::var_os("XDG_STATE_HOME")
+			.map(PathBuf::from)
+			.filter(|path| path.is_absolute())
+			.or_else(|| {
+				dirs_next::home_dir()
+					.map(|path| path.join(".local").join("state"))
+			})
+			.map(|path| path.join(env!("CARGO_PKG_NAME")).join(SESSION_FILE))
+			.ok_or_else(|| anyhow!("failed to find the state directory"))
+	}
+
+	/// Loads the session of the given home directory.
+	pub fn load(home_dir: &Path) -> Result<Self> {
+		let session: Self =
+			serde_json::from_reader(File::open(Self::get_path()?)?)?;
+		if session.home_dir == home_dir {
+			Ok(session)
+		} else {
+			Err(anyhow!("session belongs to {:?}", session.home_dir))
+		}
+	}
+
+	/// Saves the session to the state file.
+	pub fn save(&self) -> Result<()> {
+		let path = Self::get_path()?;
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		serde_json::to_writer(File::create(&path)?, &self)?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_session() -> Result<()> {
+		env::set_var("XDG_STATE_HOME", env::temp_dir());
+		assert_eq!(
+			env::temp_dir()
+				.join(env!("CARGO_PKG_NAME"))
+				.join(SESSION_FILE),
+			Session::get_path()?
+		);
+		let session = Session {
+			home_dir: PathBuf::from("/tmp/gnupg"),
+			tab: String::from("sec"),
+			selection: Some(String::from("ABC")),
+			filter: Some(String::from("can=sign")),
+			detail: String::from("full"),
+			colored: true,
+			..Session::default()
+		};
+		session.save()?;
+		assert_eq!(session, Session::load(Path::new("/tmp/gnupg"))?);
+		assert!(Session::load(Path::new("/tmp/other")).is_err());
+		Ok(())
+	}
+}
@@ -1,8 +1,19 @@
+use crate::app::clipboard::{ClipboardBackend, DEFAULT_CLEAR_TIMEOUT};
+use crate::app::prompt::ConfirmLevel;
 use crate::app::selection::Selection;
+use crate::app::watch::WatchMode;
 use crate::args::Args;
+use crate::gpg::key::ColumnLayout;
 use crate::widget::style::Color;
+use std::time::Instant;
 use tui::style::Color as TuiColor;
 
+/// Default number of days for warning about the expiring keys.
+pub const DEFAULT_EXPIRY_WARN: u32 = 30;
+
+/// Default format of the absolute dates.
+pub const DEFAULT_DATE_FORMAT: &str = "%F";
+
 /// Application states (flags) for managing the launcher.
 #[derive(Clone, Debug)]
 pub struct State {
@@ -14,12 +25,74 @@ pub struct State {
 	pub color: TuiColor,
 	/// Is the options menu (popup) showing?
 	pub show_options: bool,
+	/// Are the recent messages (popup) showing?
+	pub show_messages: bool,
+	/// Is the flag legend showing?
+	pub show_legend: bool,
+	/// Is the agent information showing?
+	pub show_agent: bool,
+	/// Is the output of the last external command showing?
+	pub show_output: bool,
+	/// Are the certification paths (web of trust) showing?
+	pub show_wot: bool,
+	/// Are the keyring statistics showing?
+	pub show_stats: bool,
+	/// Are the keyring issues showing?
+	pub show_doctor: bool,
+	/// Are the certifications showing?
+	pub show_certs: bool,
+	/// Are the subkeys of the selected key showing?
+	pub show_subkeys: bool,
+	/// Are the recipient groups showing?
+	pub show_groups: bool,
+	/// Are the preferences of the selected key showing?
+	pub show_prefs: bool,
 	/// Is the splash screen showing?
 	pub show_splash: bool,
+	/// Is the detail panel (split view) showing?
+	pub split_view: bool,
+	/// Number of days for warning about the expiring keys (0 for disabling).
+	pub expiry_warn: u32,
+	/// Format of the absolute dates.
+	pub date_format: String,
+	/// Template of the status line.
+	pub status_line: Option<String>,
+	/// Fields to show in the table columns.
+	pub columns: ColumnLayout,
+	/// Are the desktop notifications enabled?
+	pub notify: bool,
+	/// Are the names and email addresses redacted?
+	pub redact: bool,
+	/// Is the key listing cached?
+	pub cache: bool,
+	/// Is the log pane showing?
+	pub show_log: bool,
+	/// Are the unusable keys hidden?
+	pub hide_unusable: bool,
+	/// Strictness of the confirmation for deleting secret keys.
+	pub confirm: ConfirmLevel,
+	/// Clipboard backend.
+	pub clipboard: ClipboardBackend,
+	/// Duration (in seconds) for clearing the sensitive
+	/// content from clipboard (0 for disabling).
+	pub clipboard_timeout: u64,
+	/// Action to take when the keyring is changed externally.
+	pub watch: WatchMode,
+	/// Is the keyring changed externally since the keys are loaded?
+	pub keyring_changed: bool,
 	/// Is the selection mode enabled?
 	pub select: Option<Selection>,
 	/// Exit message of the app.
 	pub exit_message: Option<String>,
+	/// Numeric prefix (count) of the next command
+	/// along with the time that it is entered.
+	pub count: Option<(u16, Instant)>,
+	/// Time of the last change to the search query
+	/// that is not applied to the keys table yet.
+	pub pending_search: Option<Instant>,
+	/// Key that waits for an argument (e.g. the name of a mark)
+	/// along with its count.
+	pub pending_key: Option<(char, u16)>,
 }
 
 impl Default for State {
@@ -29,9 +102,38 @@ impl Default for State {
 			colored: false,
 			color: Color::default().get(),
 			show_options: false,
+			show_messages: false,
+			show_legend: false,
+			show_agent: false,
+			show_output: false,
+			show_wot: false,
+			show_stats: false,
+			show_doctor: false,
+			show_certs: false,
+			show_subkeys: false,
+			show_groups: false,
+			show_prefs: false,
 			show_splash: false,
+			split_view: false,
+			show_log: false,
+			notify: false,
+			redact: false,
+			cache: false,
+			expiry_warn: DEFAULT_EXPIRY_WARN,
+			date_format: String::from(DEFAULT_DATE_FORMAT),
+			status_line: None,
+			columns: ColumnLayout::default(),
+			hide_unusable: false,
+			confirm: ConfirmLevel::default(),
+			clipboard: ClipboardBackend::Auto,
+			clipboard_timeout: DEFAULT_CLEAR_TIMEOUT,
+			watch: WatchMode::default(),
+			keyring_changed: false,
 			select: None,
 			exit_message: None,
+			count: None,
+			pending_search: None,
+			pending_key: None,
 		}
 	}
 }
@@ -41,8 +143,25 @@ impl<'a> From<&'a Args> for State {
 		State {
 			colored: args.style == *"colored",
 			color: args.color.get(),
-			show_splash: args.splash,
-			select: args.select,
+			show_splash: Args::get_flag(args.splash),
+			hide_unusable: Args::get_flag(args.hide_unusable),
+			notify: Args::get_flag(args.notify),
+			redact: Args::get_flag(args.redact),
+			cache: Args::get_flag(args.cache),
+			expiry_warn: args.expiry_warn.unwrap_or(DEFAULT_EXPIRY_WARN),
+			date_format: args
+				.date_format
+				.clone()
+				.unwrap_or_else(|| String::from(DEFAULT_DATE_FORMAT)),
+			status_line: args.statusline.clone(),
+			columns: args.columns.clone().unwrap_or_default(),
+			confirm: args.confirm.unwrap_or_default(),
+			clipboard: args.clipboard.unwrap_or(ClipboardBackend::Auto),
+			clipboard_timeout: args
+				.clipboard_timeout
+				.unwrap_or(DEFAULT_CLEAR_TIMEOUT),
+			watch: args.watch.unwrap_or_default(),
+			select: args.select.clone(),
 			..Self::default()
 		}
 	}
@@ -52,8 +171,36 @@ impl State {
 	/// Reverts back the values to default.
 	pub fn refresh(&mut self) {
 		let colored = self.colored;
+		let split_view = self.split_view;
+		let show_log = self.show_log;
+		let notify = self.notify;
+		let redact = self.redact;
+		let cache = self.cache;
+		let expiry_warn = self.expiry_warn;
+		let date_format = self.date_format.clone();
+		let status_line = self.status_line.clone();
+		let columns = self.columns.clone();
+		let hide_unusable = self.hide_unusable;
+		let confirm = self.confirm;
+		let clipboard = self.clipboard;
+		let clipboard_timeout = self.clipboard_timeout;
+		let watch = self.watch;
 		*self = Self::default();
 		self.colored = colored;
+		self.split_view = split_view;
+		self.show_log = show_log;
+		self.notify = notify;
+		self.redact = redact;
+		self.cache = cache;
+		self.expiry_warn = expiry_warn;
+		self.date_format = date_format;
+		self.status_line = status_line;
+		self.columns = columns;
+		self.hide_unusable = hide_unusable;
+		self.confirm = confirm;
+		self.clipboard = clipboard;
+		self.clipboard_timeout = clipboard_timeout;
+		self.watch = watch;
 	}
 }
 
@@ -69,8 +216,37 @@ mod tests {
 		assert_eq!(false, state.colored);
 		assert_eq!(TuiColor::Gray, state.color);
 		assert_eq!(false, state.show_options);
+		assert_eq!(false, state.show_messages);
+		assert_eq!(false, state.show_legend);
+		assert_eq!(false, state.show_agent);
+		assert_eq!(false, state.show_output);
+		assert_eq!(false, state.show_wot);
+		assert_eq!(false, state.show_stats);
+		assert_eq!(false, state.show_doctor);
+		assert_eq!(false, state.show_certs);
+		assert_eq!(false, state.show_subkeys);
+		assert_eq!(false, state.show_groups);
+		assert_eq!(false, state.show_prefs);
 		assert_eq!(false, state.show_splash);
+		assert_eq!(false, state.split_view);
+		assert_eq!(false, state.show_log);
+		assert_eq!(false, state.notify);
+		assert_eq!(false, state.redact);
+		assert_eq!(false, state.cache);
+		assert_eq!(DEFAULT_EXPIRY_WARN, state.expiry_warn);
+		assert_eq!(DEFAULT_DATE_FORMAT, state.date_format);
+		assert_eq!(None, state.status_line);
+		assert_eq!(ColumnLayout::default(), state.columns);
+		assert_eq!(false, state.hide_unusable);
+		assert_eq!(ConfirmLevel::Typed, state.confirm);
+		assert_eq!(ClipboardBackend::Auto, state.clipboard);
+		assert_eq!(DEFAULT_CLEAR_TIMEOUT, state.clipboard_timeout);
+		assert_eq!(WatchMode::Notify, state.watch);
+		assert_eq!(false, state.keyring_changed);
 		assert_eq!(None, state.select);
 		assert_eq!(None, state.exit_message);
+		assert_eq!(None, state.count);
+		assert_eq!(None, state.pending_search);
+		assert_eq!(None, state.pending_key);
 	}
 }
@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+
+/// Fields that can be used in the status line template.
+pub const STATUS_LINE_FIELDS: &[&str] = &[
+	"tab",
+	"selection",
+	"total",
+	"filter",
+	"hidden",
+	"mode",
+	"armor",
+	"signer",
+	"output",
+];
+
+/// Returns the names of the fields in the given template.
+fn get_fields(template: &str) -> Vec<&str> {
+	template
+		.split('{')
+		.skip(1)
+		.filter_map(|v| v.split_once('}').map(|(field, _)| field))
+		.collect()
+}
+
+/// Checks if the given template contains only the known fields.
+pub fn validate_template(template: &str) -> Result<()> {
+	match get_fields(template)
+		.into_iter()
+		.find(|field| !STATUS_LINE_FIELDS.contains(field))
+	{
+		Some(field) => Err(anyhow!("unknown status line field: {{{}}}", field)),
+		None => Ok(()),
+	}
+}
+
+/// Formats the status line by replacing the fields with their values.
+///
+/// Brackets that are left empty after the replacement
+/// (e.g. `[{filter}]` without a filter) are removed
+/// along with the redundant whitespace.
+pub fn format_status_line(template: &str, values: &[(&str, String)]) -> String {
+	let mut status_line = template.to_string();
+	for (field, value) in values {
+		status_line = status_line.replace(&format!("{{{}}}", field), value);
+	}
+	for brackets in &["[]", "()"] {
+		status_line = status_line.replace(brackets, "");
+	}
+	status_line
+		.split_whitespace()
+		.collect::<Vec<&str>>()
+		.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_app_statusline() {
+		assert!(
+			validate_template("{tab} {selection}/{total} [{filter}]").is_ok()
+		);
+		assert!(validate_template("no fields {").is_ok());
+		assert_eq!(
+			Some(String::from("unknown status line field: {profile}")),
+			validate_template("{tab} {profile}")
+				.err()
+				.map(|e| e.to_string())
+		);
+		let values = vec![
+			("tab", String::from("pub")),
+			("selection", String::from("2")),
+			("total", String::from("10")),
+			("filter", String::new()),
+			("armor", String::from("true")),
+		];
+		assert_eq!(
+			"pub 2/10 armor: true",
+			format_status_line(
+				"{tab} {selection}/{total}  [{filter}] armor: {armor}",
+				&values
+			)
+		);
+		assert_eq!(
+			"pub {unknown}",
+			format_status_line("{tab} {unknown}", &values)
+		);
+	}
+}
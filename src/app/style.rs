@@ -1,10 +1,61 @@
-use tui::style::{Color, Style};
+use crate::app::prompt::OutputType;
+use crate::gpg::handler;
+use crate::gpg::key::KeyValidity;
+use std::str::FromStr;
+use tui::style::{Color, Modifier, Style};
 use tui::text::{Span, Spans, Text};
 
+/// Returns the style for the given output type.
+pub fn get_output_style(output_type: &OutputType, colored: bool) -> Style {
+	if colored {
+		match output_type {
+			OutputType::Success => Style::default()
+				.fg(Color::LightGreen)
+				.add_modifier(Modifier::BOLD),
+			OutputType::Warning => Style::default()
+				.fg(Color::LightYellow)
+				.add_modifier(Modifier::BOLD),
+			OutputType::Failure => Style::default()
+				.fg(Color::LightRed)
+				.add_modifier(Modifier::BOLD),
+			OutputType::Action => Style::default()
+				.fg(Color::LightBlue)
+				.add_modifier(Modifier::BOLD),
+			OutputType::None => Style::default(),
+		}
+	} else if output_type != &OutputType::None {
+		Style::default().add_modifier(Modifier::BOLD)
+	} else {
+		Style::default()
+	}
+}
+
+/// Returns the color of the given key validity.
+///
+/// * red: revoked, expired, disabled, invalid or never trusted
+/// * yellow: not trusted (unknown/undefined)
+/// * blue: marginal
+/// * magenta: full
+/// * green: ultimate
+pub fn get_validity_color(validity: KeyValidity) -> Color {
+	match validity {
+		KeyValidity::Revoked
+		| KeyValidity::Expired
+		| KeyValidity::Disabled
+		| KeyValidity::Invalid
+		| KeyValidity::Never => Color::Red,
+		KeyValidity::Unknown | KeyValidity::Undefined => Color::Yellow,
+		KeyValidity::Marginal => Color::Blue,
+		KeyValidity::Full => Color::Magenta,
+		KeyValidity::Ultimate => Color::Green,
+	}
+}
+
 /// Converts the given multi-line row value to colored [`Text`] widget.
 ///
 /// It adds colors to:
 /// * flags in bracket characters. (e.g. `[?]`)
+/// * key validity in bracket characters. (e.g. `[full]`)
 /// * parts separated by slash character. (e.g. `rsa2048/abc123`)
 /// * values in arrow characters (e.g. `<test@example.com>`)
 pub fn get_colored_table_row<'a>(
@@ -46,6 +97,25 @@ pub fn get_colored_table_row<'a>(
 						data,
 						Style::default().fg(Color::Red),
 					))
+				} else if let Ok(validity) = KeyValidity::from_str(&data) {
+					colored_line.push(Span::styled(
+						data,
+						Style::default().fg(get_validity_color(validity)),
+					))
+				} else if handler::parse_tofu_policy(&data).is_some() {
+					let color = match data.as_ref() {
+						"good" => Color::Green,
+						"bad" => Color::Red,
+						"ask" => Color::Yellow,
+						_ => Color::DarkGray,
+					};
+					colored_line
+						.push(Span::styled(data, Style::default().fg(color)))
+				} else if data.starts_with("card:") {
+					colored_line.push(Span::styled(
+						data,
+						Style::default().fg(Color::LightMagenta),
+					))
 				} else if data.len() == 2 {
 					let style = match data.as_ref() {
 						// 0x10: no indication
@@ -88,14 +158,19 @@ pub fn get_colored_table_row<'a>(
 					}
 				}
 				let data = line[second_bracket..].to_string();
+				// Skip the badge of the keys that are on a smartcard.
+				let offset = if data.starts_with("]>") { 2 } else { 1 };
 				// Colorize the separate parts using slash character.
-				if data.find('/') == Some(9) {
-					colored_line.push(Span::styled(
-						data.chars().next().unwrap_or_default().to_string(),
-						highlight_style,
-					));
+				if data.find('/') == Some(8 + offset) {
+					colored_line.push(Span::styled("]", highlight_style));
+					if offset == 2 {
+						colored_line.push(Span::styled(
+							">",
+							Style::default().fg(Color::LightMagenta),
+						));
+					}
 					colored_line.push(Span::styled(
-						data[1..9].to_string(),
+						data[offset..8 + offset].to_string(),
 						Style::default().fg(Color::Cyan),
 					));
 					colored_line.push(Span::styled(
@@ -103,7 +178,7 @@ pub fn get_colored_table_row<'a>(
 						Style::default().fg(Color::DarkGray),
 					));
 					colored_line.push(Span::styled(
-						data[10..].to_string(),
+						data[9 + offset..].to_string(),
 						highlight_style,
 					));
 				// Colorize inside the arrows.
@@ -177,6 +252,55 @@ pub fn get_colored_info(info: &str, color: Color) -> Text<'_> {
 	)
 }
 
+/// Returns the ANSI escape code of the given color.
+fn get_ansi_color_code(color: Color) -> Option<String> {
+	match color {
+		Color::Reset => None,
+		Color::Black => Some(String::from("30")),
+		Color::Red => Some(String::from("31")),
+		Color::Green => Some(String::from("32")),
+		Color::Yellow => Some(String::from("33")),
+		Color::Blue => Some(String::from("34")),
+		Color::Magenta => Some(String::from("35")),
+		Color::Cyan => Some(String::from("36")),
+		Color::Gray => Some(String::from("37")),
+		Color::DarkGray => Some(String::from("90")),
+		Color::LightRed => Some(String::from("91")),
+		Color::LightGreen => Some(String::from("92")),
+		Color::LightYellow => Some(String::from("93")),
+		Color::LightBlue => Some(String::from("94")),
+		Color::LightMagenta => Some(String::from("95")),
+		Color::LightCyan => Some(String::from("96")),
+		Color::White => Some(String::from("97")),
+		Color::Indexed(i) => Some(format!("38;5;{}", i)),
+		Color::Rgb(r, g, b) => Some(format!("38;2;{};{};{}", r, g, b)),
+	}
+}
+
+/// Converts the given [`Spans`] to a string with ANSI escape codes.
+///
+/// Only the foreground colors and bold modifier are preserved.
+pub fn get_ansi_string(spans: &Spans<'_>) -> String {
+	spans
+		.0
+		.iter()
+		.map(|span| {
+			let mut codes = Vec::new();
+			if span.style.add_modifier.contains(Modifier::BOLD) {
+				codes.push(String::from("1"));
+			}
+			if let Some(code) = span.style.fg.and_then(get_ansi_color_code) {
+				codes.push(code);
+			}
+			if codes.is_empty() {
+				span.content.to_string()
+			} else {
+				format!("\x1b[{}m{}\x1b[0m", codes.join(";"), span.content)
+			}
+		})
+		.collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -399,6 +523,26 @@ mod tests {
 			},
 			get_colored_table_row(&row_data, false)
 		);
+		assert_eq!(
+			vec!["[", "-", "-", "e", "-", "]", ">", " rsa2048", "/", "ABCD"],
+			get_colored_table_row(
+				&[String::from("[--e-]> rsa2048/ABCD")],
+				false
+			)
+			.lines[0]
+				.0
+				.iter()
+				.map(|span| span.content.as_ref())
+				.collect::<Vec<&str>>()
+		);
+		assert_eq!(
+			Some(Color::LightMagenta),
+			get_colored_table_row(&[String::from("└─(2021) [card:01]")], false)
+				.lines[0]
+				.0[1]
+				.style
+				.fg
+		);
 		assert_eq!(
 			Text {
 				lines: vec![
@@ -471,6 +615,46 @@ mod tests {
 				reset",
 				Color::LightRed
 			)
-		)
+		);
+		assert_eq!(
+			Spans(vec![
+				Span {
+					content: Borrowed("["),
+					style: Style::default(),
+				},
+				Span {
+					content: Borrowed("expired"),
+					style: Style {
+						fg: Some(Color::Red),
+						..Style::default()
+					},
+				},
+				Span {
+					content: Borrowed("] trust: ultimate"),
+					style: Style::default(),
+				},
+			]),
+			get_colored_table_row(
+				&[String::from("[expired] trust: ultimate")],
+				false
+			)
+			.lines[0]
+		);
+		assert_eq!(
+			"[\x1b[91mexpired\x1b[0m] \x1b[1;38;5;7mtrust\x1b[0m",
+			get_ansi_string(&Spans(vec![
+				Span::raw("["),
+				Span::styled("expired", Style::default().fg(Color::LightRed)),
+				Span::raw("] "),
+				Span::styled(
+					"trust",
+					Style::default()
+						.fg(Color::Indexed(7))
+						.add_modifier(Modifier::BOLD)
+				),
+			]))
+		);
+		assert_eq!(Color::Yellow, get_validity_color(KeyValidity::Unknown));
+		assert_eq!(Color::Green, get_validity_color(KeyValidity::Ultimate));
 	}
 }
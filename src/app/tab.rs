@@ -10,7 +10,23 @@ pub enum Tab {
 	Keys(KeyType),
 }
 
+/// Tabs in the order of the tab bar.
+pub const TABS: [Tab; 3] = [
+	Tab::Keys(KeyType::Public),
+	Tab::Keys(KeyType::Secret),
+	Tab::Help,
+];
+
 impl Tab {
+	/// Returns the title to show in the tab bar.
+	pub fn get_title(&self) -> &str {
+		match self {
+			Self::Keys(KeyType::Public) => "Public",
+			Self::Keys(KeyType::Secret) => "Secret",
+			Self::Help => "Help",
+		}
+	}
+
 	/// Returns the corresponding application command.
 	pub fn get_command(&self) -> Command {
 		match self {
@@ -51,5 +67,11 @@ mod tests {
 		let tab = tab.previous();
 		assert_eq!(Tab::Keys(KeyType::Public), tab);
 		assert_ne!(Tab::Keys(KeyType::Secret), tab);
+		assert_eq!(
+			vec!["Public", "Secret", "Help"],
+			TABS.iter()
+				.map(|tab| tab.get_title())
+				.collect::<Vec<&str>>()
+		);
 	}
 }
@@ -1,5 +1,13 @@
+use crate::app::prompt::OutputType;
+use crate::app::worker::TaskHandle;
+use crate::gpg::config::GpgConfig;
+use crate::gpg::key::GpgKey;
 use anyhow::{anyhow, Result};
-use std::process::{Command, Stdio};
+use notify_rust::Notification;
+use std::io::{self, Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::Duration;
 
 /// Runs [`xplr`] command and returns the selected files.
 ///
@@ -20,3 +28,193 @@ pub fn run_xplr() -> Result<Vec<String>> {
 		Err(e) => Err(anyhow!("cannot run xplr: {:?}", e)),
 	}
 }
+
+/// Runs the given command until it exits or the task is cancelled.
+///
+/// Returns the exit status and the error output of the command,
+/// or `None` if the command is killed due to cancellation.
+pub fn run_cancellable(
+	mut command: Command,
+	handle: &TaskHandle,
+) -> Result<Option<(ExitStatus, String)>> {
+	let description = format!("{:?}", command);
+	log::debug!("running {}", description);
+	let mut child = command
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::piped())
+		.spawn()?;
+	let mut stderr = child
+		.stderr
+		.take()
+		.ok_or_else(|| anyhow!("failed to capture the error output"))?;
+	let reader = thread::spawn(move || {
+		let mut output = String::new();
+		let _ = stderr.read_to_string(&mut output);
+		output
+	});
+	loop {
+		if let Some(status) = child.try_wait()? {
+			let stderr = reader.join().unwrap_or_default();
+			if status.success() {
+				log::debug!("{} exited with {}", description, status);
+			} else {
+				log::error!(
+					"{} exited with {}: {}",
+					description,
+					status,
+					stderr
+				);
+			}
+			return Ok(Some((status, stderr)));
+		} else if handle.is_cancelled() {
+			child.kill()?;
+			child.wait()?;
+			log::warn!("{} is cancelled", description);
+			return Ok(None);
+		}
+		thread::sleep(Duration::from_millis(50));
+	}
+}
+
+/// Sends the given key to the keyserver via `gpg --send-keys`.
+///
+/// Returns `false` if the command is killed due to cancellation.
+pub fn send_key(
+	config: &GpgConfig,
+	key_id: &str,
+	handle: &TaskHandle,
+) -> Result<bool> {
+	let mut command = Command::new(&config.gpg_binary);
+	command
+		.arg("--batch")
+		.arg("--homedir")
+		.arg(&config.home_dir)
+		.arg("--send-keys")
+		.arg(key_id);
+	match run_cancellable(command, handle)? {
+		Some((status, _)) if status.success() => Ok(true),
+		Some((_, stderr)) => Err(anyhow!(
+			"{}",
+			stderr.lines().last().unwrap_or("failed to send the key")
+		)),
+		None => Ok(false),
+	}
+}
+
+/// Runs the given interactive command while capturing its error output.
+///
+/// The error output is still passed through to stderr as it arrives
+/// so that the prompts and messages are visible while running.
+pub fn run_captured(
+	command: &mut Command,
+) -> Result<(ExitStatus, Vec<String>)> {
+	let mut child = command.stderr(Stdio::piped()).spawn()?;
+	let mut stderr = child
+		.stderr
+		.take()
+		.ok_or_else(|| anyhow!("failed to capture the error output"))?;
+	let reader = thread::spawn(move || {
+		let mut output = Vec::new();
+		let mut buffer = [0; 1024];
+		loop {
+			match stderr.read(&mut buffer) {
+				Ok(0) | Err(_) => break output,
+				Ok(size) => {
+					let mut passthrough = io::stderr();
+					let _ = passthrough.write_all(&buffer[..size]);
+					let _ = passthrough.flush();
+					output.extend_from_slice(&buffer[..size]);
+				}
+			}
+		}
+	});
+	let status = child.wait()?;
+	let output = reader.join().unwrap_or_default();
+	Ok((
+		status,
+		String::from_utf8_lossy(&output)
+			.lines()
+			.map(String::from)
+			.collect(),
+	))
+}
+
+/// Sends a desktop notification with the given output message.
+///
+/// It is sent from a separate thread to not block the interface.
+pub fn send_notification(output: &(OutputType, String)) {
+	let summary = format!(
+		"{}{}",
+		env!("CARGO_PKG_NAME"),
+		match output.0 {
+			OutputType::Success => ": success",
+			OutputType::Warning => ": warning",
+			OutputType::Failure => ": error",
+			_ => "",
+		}
+	);
+	let body = output.1.to_string();
+	thread::spawn(move || {
+		let _ = Notification::new().summary(&summary).body(&body).show();
+	});
+}
+
+/// Returns the signing key that is configured for git (`user.signingkey`).
+///
+/// Returns `None` if the signing key is not set.
+pub fn get_git_signing_key() -> Result<Option<String>> {
+	let output = Command::new("git")
+		.args(["config", "--get", "user.signingkey"])
+		.output()
+		.map_err(|e| anyhow!("cannot run git: {:?}", e))?;
+	if output.status.success() {
+		Ok(Some(String::from_utf8(output.stdout)?.trim().to_string())
+			.filter(|key| !key.is_empty()))
+	} else if output.status.code() == Some(1) {
+		Ok(None)
+	} else {
+		Err(anyhow!(
+			"{}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		))
+	}
+}
+
+/// Sets the signing key of git in the repository or global configuration.
+pub fn set_git_signing_key(key_id: &str, global: bool) -> Result<()> {
+	let mut command = Command::new("git");
+	command.arg("config");
+	if global {
+		command.arg("--global");
+	}
+	let output = command
+		.args(["user.signingkey", key_id])
+		.output()
+		.map_err(|e| anyhow!("cannot run git: {:?}", e))?;
+	if output.status.success() {
+		Ok(())
+	} else {
+		Err(anyhow!(
+			"{}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		))
+	}
+}
+
+/// Returns `true` if the given signing key (e.g. the git signing key
+/// or the default key) refers to the key.
+///
+/// Signing key might be a key ID, fingerprint or an email address.
+pub fn is_signing_key(signing_key: &str, key: &GpgKey) -> bool {
+	let signing_key = signing_key.trim_end_matches('!');
+	if signing_key.contains('@') {
+		let email = signing_key.trim_matches(|c| c == '<' || c == '>');
+		return key
+			.get_user_emails()
+			.iter()
+			.any(|v| v.eq_ignore_ascii_case(email));
+	}
+	let id = signing_key.strip_prefix("0x").unwrap_or(signing_key);
+	id.len() >= 8 && key.get_fingerprint().ends_with(&id.to_uppercase())
+}
@@ -0,0 +1,145 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Instant, SystemTime};
+
+/// Files (and directories) in the GnuPG home directory that
+/// are changed when the keys are imported or deleted.
+const WATCHED_FILES: &[&str] =
+	&["pubring.kbx", "pubring.gpg", "private-keys-v1.d"];
+
+/// Interval (in milliseconds) for checking the keyring files.
+const WATCH_INTERVAL: u128 = 1000;
+
+/// Action to take when the keyring is changed by another process.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WatchMode {
+	/// Do not watch the keyring.
+	Off,
+	/// Show a message for reloading the keys.
+	#[default]
+	Notify,
+	/// Reload the keys automatically.
+	Reload,
+}
+
+impl Display for WatchMode {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Off => "off",
+				Self::Notify => "notify",
+				Self::Reload => "reload",
+			}
+		)
+	}
+}
+
+impl FromStr for WatchMode {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"off" | "false" => Ok(Self::Off),
+			"notify" => Ok(Self::Notify),
+			"reload" => Ok(Self::Reload),
+			_ => Err(String::from("could not parse the watch mode")),
+		}
+	}
+}
+
+/// Watcher of the keyring files.
+///
+/// The modification times of the files are polled periodically
+/// since it does not require any platform specific notifications.
+#[derive(Debug)]
+pub struct KeyringWatcher {
+	/// Paths of the watched files.
+	paths: Vec<PathBuf>,
+	/// Last known modification times of the files.
+	modified: Vec<Option<SystemTime>>,
+	/// Time of the last check.
+	clock: Instant,
+}
+
+impl KeyringWatcher {
+	/// Constructs a new instance of `KeyringWatcher`.
+	pub fn new(home_dir: &Path) -> Self {
+		let paths = WATCHED_FILES
+			.iter()
+			.map(|file| home_dir.join(file))
+			.collect::<Vec<PathBuf>>();
+		Self {
+			modified: get_modified_times(&paths),
+			paths,
+			clock: Instant::now(),
+		}
+	}
+
+	/// Takes a new snapshot of the modification times.
+	///
+	/// It should be called after the keys are (re)loaded so that
+	/// the changes of the application itself are not reported.
+	pub fn reset(&mut self) {
+		self.modified = get_modified_times(&self.paths);
+		self.clock = Instant::now();
+	}
+
+	/// Returns `true` if the keyring is changed since the last check.
+	pub fn poll(&mut self) -> bool {
+		if self.clock.elapsed().as_millis() < WATCH_INTERVAL {
+			return false;
+		}
+		self.clock = Instant::now();
+		let modified = get_modified_times(&self.paths);
+		if modified != self.modified {
+			self.modified = modified;
+			true
+		} else {
+			false
+		}
+	}
+}
+
+/// Returns the modification times of the given paths.
+fn get_modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+	paths
+		.iter()
+		.map(|path| fs::metadata(path).and_then(|v| v.modified()).ok())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	use std::thread;
+	use std::time::Duration;
+	#[test]
+	fn test_app_watch() -> Result<()> {
+		assert_eq!(WatchMode::Notify, WatchMode::default());
+		for mode in &[WatchMode::Off, WatchMode::Notify, WatchMode::Reload] {
+			assert_eq!(*mode, WatchMode::from_str(&mode.to_string()).unwrap());
+		}
+		assert!(WatchMode::from_str("x").is_err());
+		let home_dir =
+			env::temp_dir().join(format!("{}-watch", env!("CARGO_PKG_NAME")));
+		fs::create_dir_all(&home_dir)?;
+		let mut watcher = KeyringWatcher::new(&home_dir);
+		assert!(!watcher.poll());
+		thread::sleep(Duration::from_millis(WATCH_INTERVAL as u64));
+		fs::write(home_dir.join("pubring.kbx"), "")?;
+		assert!(watcher.poll());
+		assert!(!watcher.poll());
+		fs::create_dir_all(home_dir.join("private-keys-v1.d"))?;
+		watcher.reset();
+		thread::sleep(Duration::from_millis(WATCH_INTERVAL as u64));
+		assert!(!watcher.poll());
+		fs::remove_dir_all(home_dir)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,318 @@
+use crate::app::command::Command;
+use crate::app::prompt::OutputType;
+use crate::gpg::context::ProgressCallback;
+use crate::widget::dialog::Dialog;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// Frames of the spinner that is shown while a task is running.
+const SPINNER_FRAMES: &[char] =
+	&['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Duration of a single spinner frame in milliseconds.
+const SPINNER_INTERVAL: u128 = 80;
+
+/// Result of a task that is executed on the worker thread.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskOutput {
+	/// Output message to show in the prompt.
+	pub output: (OutputType, String),
+	/// Whether if the keys should be refreshed.
+	pub refresh: bool,
+	/// Details of the error (if failed).
+	pub details: Vec<String>,
+	/// Lines to show in the command output popup.
+	pub lines: Vec<String>,
+	/// Dialog to show (e.g. for confirming the next action).
+	pub dialog: Option<Dialog<Command>>,
+}
+
+impl TaskOutput {
+	/// Constructs a new instance of `TaskOutput`.
+	pub fn new(
+		output_type: OutputType,
+		message: String,
+		refresh: bool,
+	) -> Self {
+		Self {
+			output: (output_type, message),
+			refresh,
+			details: Vec::new(),
+			lines: Vec::new(),
+			dialog: None,
+		}
+	}
+
+	/// Sets the details of the error.
+	pub fn with_details(mut self, details: Vec<String>) -> Self {
+		self.details = details;
+		self
+	}
+
+	/// Sets the lines to show in the command output popup.
+	pub fn with_lines(mut self, lines: Vec<String>) -> Self {
+		self.lines = lines;
+		self
+	}
+
+	/// Sets the dialog to show.
+	pub fn with_dialog(mut self, dialog: Dialog<Command>) -> Self {
+		self.dialog = Some(dialog);
+		self
+	}
+}
+
+/// Progress of a task.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+	/// Description of the current step.
+	pub what: String,
+	/// Current value.
+	pub current: u64,
+	/// Total value (zero if unknown).
+	pub total: u64,
+}
+
+impl Progress {
+	/// Returns the completed ratio (between 0 and 1).
+	pub fn get_ratio(&self) -> f64 {
+		if self.total == 0 {
+			0.
+		} else {
+			(self.current as f64 / self.total as f64).min(1.)
+		}
+	}
+}
+
+/// Shared handle of a running task.
+///
+/// Tasks should check the cancellation flag periodically
+/// and stop (and clean up) as soon as it is set.
+/// They can also report their progress through it.
+#[derive(Clone, Debug, Default)]
+pub struct TaskHandle {
+	/// Cancellation flag.
+	cancelled: Arc<AtomicBool>,
+	/// Last reported progress.
+	progress: Arc<Mutex<Option<Progress>>>,
+}
+
+impl TaskHandle {
+	/// Requests the cancellation of the task.
+	pub fn cancel(&self) {
+		self.cancelled.store(true, Ordering::SeqCst);
+	}
+
+	/// Checks if the task is cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::SeqCst)
+	}
+
+	/// Reports the progress of the task.
+	pub fn set_progress(&self, progress: Progress) {
+		if let Ok(mut value) = self.progress.lock() {
+			*value = Some(progress);
+		}
+	}
+
+	/// Returns the last reported progress.
+	pub fn get_progress(&self) -> Option<Progress> {
+		self.progress.lock().ok().and_then(|value| value.clone())
+	}
+
+	/// Returns a callback for reporting the progress
+	/// of GPGME operations via this handle.
+	pub fn get_progress_callback(&self) -> ProgressCallback {
+		let handle = self.clone();
+		ProgressCallback(Arc::new(move |what, current, total| {
+			handle.set_progress(Progress {
+				what: what.to_string(),
+				current,
+				total,
+			})
+		}))
+	}
+}
+
+/// Executor of the long-running tasks.
+///
+/// Tasks are run on a separate thread and their results
+/// are sent back to the application via a channel.
+#[derive(Debug)]
+pub struct Worker {
+	/// Sender of the task results.
+	sender: Sender<(usize, TaskOutput)>,
+	/// Receiver of the task results.
+	receiver: Receiver<(usize, TaskOutput)>,
+	/// Identifier of the last spawned task.
+	task_id: usize,
+	/// Handle of the last spawned task.
+	handle: TaskHandle,
+	/// Description and start time of the running task.
+	running: Option<(String, Instant)>,
+}
+
+impl Default for Worker {
+	fn default() -> Self {
+		let (sender, receiver) = mpsc::channel();
+		Self {
+			sender,
+			receiver,
+			task_id: 0,
+			handle: TaskHandle::default(),
+			running: None,
+		}
+	}
+}
+
+impl Worker {
+	/// Checks if a task is currently running.
+	pub fn is_running(&self) -> bool {
+		self.running.is_some()
+	}
+
+	/// Runs the given task on a new thread.
+	///
+	/// Returns `false` if there is already a running task.
+	pub fn spawn<F>(&mut self, description: &str, task: F) -> bool
+	where
+		F: FnOnce(TaskHandle) -> TaskOutput + Send + 'static,
+	{
+		if self.is_running() {
+			return false;
+		}
+		self.task_id += 1;
+		self.handle = TaskHandle::default();
+		let (sender, task_id, handle) =
+			(self.sender.clone(), self.task_id, self.handle.clone());
+		thread::spawn(move || {
+			let _ = sender.send((task_id, task(handle)));
+		});
+		self.running = Some((description.to_string(), Instant::now()));
+		true
+	}
+
+	/// Requests the cancellation of the running task.
+	///
+	/// The task is considered as running until it stops and
+	/// reports back (e.g. with its partial results) so that
+	/// another task can not be spawned in the meantime.
+	/// Returns `false` if there is no running task.
+	pub fn cancel(&mut self) -> bool {
+		if self.is_running() {
+			self.handle.cancel();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Returns the result of the finished (or cancelled) task (if any).
+	pub fn poll(&mut self) -> Option<TaskOutput> {
+		while let Ok((task_id, output)) = self.receiver.try_recv() {
+			if task_id == self.task_id && self.is_running() {
+				self.running = None;
+				return Some(output);
+			}
+		}
+		None
+	}
+
+	/// Returns the progress of the running task (if reported).
+	pub fn get_progress(&self) -> Option<Progress> {
+		if self.is_running() {
+			self.handle.get_progress()
+		} else {
+			None
+		}
+	}
+
+	/// Returns the status text of the running task with a spinner.
+	pub fn get_status(&self) -> Option<String> {
+		self.running.as_ref().map(|(description, clock)| {
+			let frame = (clock.elapsed().as_millis() / SPINNER_INTERVAL)
+				as usize % SPINNER_FRAMES.len();
+			if self.handle.is_cancelled() {
+				format!("{} cancelling {}", SPINNER_FRAMES[frame], description)
+			} else {
+				format!("{} {}", SPINNER_FRAMES[frame], description)
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::thread;
+	use std::time::Duration;
+	#[test]
+	fn test_app_worker() {
+		let mut worker = Worker::default();
+		assert!(!worker.is_running());
+		assert_eq!(None, worker.get_status());
+		assert!(worker.spawn("test", |_| {
+			thread::sleep(Duration::from_millis(100));
+			TaskOutput::new(OutputType::Success, String::from("done"), true)
+		}));
+		assert!(worker.is_running());
+		assert!(!worker.spawn("test2", |_| {
+			TaskOutput::new(OutputType::Failure, String::new(), false)
+		}));
+		assert!(worker.get_status().unwrap_or_default().ends_with(" test"));
+		let mut output = None;
+		while output.is_none() {
+			output = worker.poll();
+			thread::sleep(Duration::from_millis(10));
+		}
+		assert_eq!(
+			Some(TaskOutput::new(
+				OutputType::Success,
+				String::from("done"),
+				true
+			)),
+			output
+		);
+		assert!(!worker.is_running());
+		assert!(!worker.cancel());
+		assert!(worker.spawn("test", |handle| {
+			handle.set_progress(Progress {
+				what: String::from("test"),
+				current: 3,
+				total: 4,
+			});
+			while !handle.is_cancelled() {
+				thread::sleep(Duration::from_millis(10));
+			}
+			TaskOutput::new(OutputType::Success, String::from("done"), true)
+		}));
+		while worker.get_progress().is_none() {
+			thread::sleep(Duration::from_millis(10));
+		}
+		assert_eq!(
+			Some(0.75),
+			worker.get_progress().map(|progress| progress.get_ratio())
+		);
+		assert!(worker.cancel());
+		assert!(worker.is_running());
+		assert!(worker
+			.get_status()
+			.unwrap_or_default()
+			.ends_with(" cancelling test"));
+		assert!(!worker.spawn("test2", |_| {
+			TaskOutput::new(OutputType::Failure, String::new(), false)
+		}));
+		let mut output = None;
+		while output.is_none() {
+			output = worker.poll();
+			thread::sleep(Duration::from_millis(10));
+		}
+		assert!(!worker.is_running());
+		assert_eq!(None, worker.get_progress());
+		assert!(!worker.cancel());
+	}
+}
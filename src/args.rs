@@ -1,8 +1,17 @@
 //! Command-line argument parser.
 
 use crate::app::banner::BANNERS;
+use crate::app::clipboard::ClipboardBackend;
+use crate::app::export::ExportDestination;
+use crate::app::menu::MenuLayout;
+use crate::app::prompt::ConfirmLevel;
 use crate::app::selection::Selection;
+use crate::app::watch::WatchMode;
+use crate::cli::OutputFormat;
+use crate::gpg::key::{ColumnLayout, KeyType};
 use crate::widget::style::Color;
+use std::collections::HashMap;
+use std::str::FromStr;
 use structopt::clap::AppSettings;
 use structopt::StructOpt;
 
@@ -23,17 +32,65 @@ use structopt::StructOpt;
 )]
 pub struct Args {
 	/// Enables ASCII armored output.
-	#[structopt(short, long)]
-	pub armor: bool,
+	#[structopt(short, long, value_name = "bool", require_equals = true)]
+	pub armor: Option<Option<bool>>,
+	/// Refreshes the keys from their preferred keyservers.
+	#[structopt(long, value_name = "bool", require_equals = true)]
+	pub honor_keyserver_url: Option<Option<bool>>,
 	/// Shows the splash screen on startup.
+	#[structopt(long, value_name = "bool", require_equals = true)]
+	pub splash: Option<Option<bool>>,
+	/// Hides the expired, revoked and invalid keys.
+	#[structopt(long, value_name = "bool", require_equals = true)]
+	pub hide_unusable: Option<Option<bool>>,
+	/// Enables the desktop notifications.
+	#[structopt(long, value_name = "bool", require_equals = true)]
+	pub notify: Option<Option<bool>>,
+	/// Redacts the names and email addresses.
+	#[structopt(long, value_name = "bool", require_equals = true)]
+	pub redact: Option<Option<bool>>,
+	/// Caches the key listing for faster startup.
+	#[structopt(long, value_name = "bool", require_equals = true)]
+	pub cache: Option<Option<bool>>,
+	/// Uses a temporary keyring that is removed on exit.
+	#[structopt(long)]
+	pub ephemeral: bool,
+	/// Uses a temporary keyring with generated demo keys.
 	#[structopt(long)]
-	pub splash: bool,
+	pub demo: bool,
+	/// Disables restoring the previous session on startup.
+	#[structopt(long)]
+	pub no_restore: bool,
+	/// Prints the fingerprint of the picked key.
+	#[structopt(long, conflicts_with = "select")]
+	pub picker: bool,
+	/// Enables the debug messages in the log file.
+	#[structopt(short, long)]
+	pub verbose: bool,
+	/// Sets the configuration file.
+	#[structopt(long, value_name = "path", env, parse(from_str = Args::parse_dir))]
+	pub config: Option<String>,
+	/// Sets the file to write the logs to.
+	#[structopt(long, value_name = "path", env, parse(from_str = Args::parse_dir))]
+	pub log_file: Option<String>,
 	/// Sets the GnuPG home directory.
 	#[structopt(long, value_name = "dir", env = "GNUPGHOME", parse(from_str = Args::parse_dir))]
 	pub homedir: Option<String>,
 	/// Sets the output directory.
 	#[structopt(short, long, value_name = "dir", env, parse(from_str = Args::parse_dir))]
 	pub outdir: Option<String>,
+	/// Sets the template of the exported file names.
+	#[structopt(long, value_name = "template", env)]
+	pub export_name: Option<String>,
+	/// Sets the path of the control socket for running commands.
+	#[structopt(long, value_name = "path", env, parse(from_str = Args::parse_dir))]
+	pub socket: Option<String>,
+	/// Sets the path of the gpg binary.
+	#[structopt(long, value_name = "path", env, parse(from_str = Args::parse_dir))]
+	pub gpg_binary: Option<String>,
+	/// Sets the mechanisms for locating the keys by email address.
+	#[structopt(long, value_name = "mechanisms", env)]
+	pub auto_key_locate: Option<String>,
 	/// Sets the default key to sign with.
 	#[structopt(short, long, value_name = "key", env)]
 	pub default_key: Option<String>,
@@ -53,10 +110,134 @@ pub struct Args {
 	#[structopt(
 		long,
 		value_name = "option",
-		possible_values = &["key_id", "key_fpr", "user_id", "row1", "row2"],
+		possible_values = &["key_id", "key_fpr", "user_id", "ssh_key", "row1", "row2"],
 		env
 	)]
 	pub select: Option<Selection>,
+	/// Sets the confirmation level for deleting secret keys.
+	#[structopt(
+		long,
+		value_name = "level",
+		possible_values = &["simple", "typed", "strict"],
+		env
+	)]
+	pub confirm: Option<ConfirmLevel>,
+	/// Sets the number of days for warning about the expiring keys.
+	#[structopt(long, value_name = "days", env)]
+	pub expiry_warn: Option<u32>,
+	/// Sets the format of the absolute dates.
+	#[structopt(long, value_name = "format", env)]
+	pub date_format: Option<String>,
+	/// Sets the template of the status line.
+	#[structopt(long, value_name = "template", env)]
+	pub statusline: Option<String>,
+	/// Layout of the options menu (from the configuration file).
+	#[structopt(skip)]
+	pub menu: MenuLayout,
+	/// Templates for copying the key information (from the configuration file).
+	#[structopt(skip)]
+	pub copy_templates: HashMap<String, String>,
+	/// Shell commands to run after the actions (from the configuration file).
+	#[structopt(skip)]
+	pub hooks: HashMap<String, String>,
+	/// Sets the fields to show in the table columns.
+	#[structopt(long, value_name = "layout", env = "TABLE_COLUMNS")]
+	pub columns: Option<ColumnLayout>,
+	/// Sets the clipboard backend.
+	#[structopt(
+		long,
+		value_name = "backend",
+		possible_values = &["auto", "x11", "x11-bin", "wayland"],
+		env
+	)]
+	pub clipboard: Option<ClipboardBackend>,
+	/// Sets the timeout for clearing the copied secret keys.
+	#[structopt(long, value_name = "secs", env)]
+	pub clipboard_timeout: Option<u64>,
+	/// Sets the action to take when the keyring is changed externally.
+	#[structopt(
+		long,
+		value_name = "mode",
+		possible_values = &["off", "notify", "reload"],
+		env
+	)]
+	pub watch: Option<WatchMode>,
+	/// Imports the keys from the given file ("-" for stdin) on startup.
+	#[structopt(long, value_name = "path", number_of_values = 1)]
+	pub import: Vec<String>,
+	/// Selects the key that matches the given query on startup.
+	#[structopt(long, value_name = "query")]
+	pub focus: Option<String>,
+	/// Runs the given command on startup.
+	#[structopt(long, value_name = "command")]
+	pub command: Option<String>,
+	/// Subcommand to run without starting the terminal interface.
+	#[structopt(subcommand)]
+	pub subcommand: Option<SubCommand>,
+}
+
+/// Subcommands for scripting.
+///
+/// They print their output to stdout without
+/// starting the terminal interface.
+#[derive(Debug, StructOpt)]
+pub enum SubCommand {
+	/// Lists the keys (type, fingerprint, validity, expiration, user ID).
+	///
+	/// The JSON format also includes the subkeys and all user IDs.
+	ListKeys {
+		/// Sets the type of the keys.
+		#[structopt(
+			short = "t",
+			long = "type",
+			value_name = "type",
+			default_value = "pub",
+			possible_values = &["pub", "sec"],
+			parse(try_from_str = Args::parse_key_type)
+		)]
+		key_type: KeyType,
+		/// Sets the output format.
+		#[structopt(
+			short = "f",
+			long = "format",
+			value_name = "format",
+			default_value = "plain",
+			possible_values = &["plain", "json"]
+		)]
+		format: OutputFormat,
+		/// Patterns for filtering the keys.
+		patterns: Vec<String>,
+	},
+	/// Exports the keys.
+	Export {
+		/// Sets the type of the keys.
+		#[structopt(
+			short = "t",
+			long = "type",
+			value_name = "type",
+			default_value = "pub",
+			possible_values = &["pub", "sec"],
+			parse(try_from_str = Args::parse_key_type)
+		)]
+		key_type: KeyType,
+		/// Sets the destination of the exported keys.
+		#[structopt(
+			long = "to",
+			value_name = "destination",
+			default_value = "stdout",
+			possible_values = &["stdout", "file"],
+			parse(try_from_str = Args::parse_export_destination)
+		)]
+		destination: ExportDestination,
+		/// Patterns for selecting the keys (e.g. key ID).
+		#[structopt(required = true)]
+		patterns: Vec<String>,
+	},
+	/// Locates the key of an email address.
+	Locate {
+		/// Email address to locate the key for.
+		email: String,
+	},
 }
 
 impl Args {
@@ -66,10 +247,31 @@ impl Args {
 	/// input string into contents of the path returned by [`home_dir`].
 	///
 	/// [`home_dir`]: dirs_next::home_dir
-	fn parse_dir(dir: &str) -> String {
+	pub(crate) fn parse_dir(dir: &str) -> String {
 		shellexpand::tilde(dir).to_string()
 	}
 
+	/// Returns the value of the given flag.
+	///
+	/// Flags that are given without a value (e.g. `--armor`) are enabled.
+	pub fn get_flag(flag: Option<Option<bool>>) -> bool {
+		flag.map(|v| v.unwrap_or(true)).unwrap_or_default()
+	}
+
+	/// Custom string parser for key types.
+	fn parse_key_type(key_type: &str) -> Result<KeyType, String> {
+		KeyType::from_str(key_type)
+			.map_err(|_| format!("invalid key type: {}", key_type))
+	}
+
+	/// Custom string parser for export destinations.
+	fn parse_export_destination(
+		destination: &str,
+	) -> Result<ExportDestination, String> {
+		ExportDestination::from_str(destination)
+			.map_err(|_| format!("invalid destination: {}", destination))
+	}
+
 	/// Parses the command-line arguments.
 	///
 	/// See [`StructOpt::from_args`].
@@ -0,0 +1,182 @@
+//! Headless command-line interface for scripting.
+
+use crate::app::export::ExportDestination;
+use crate::args::SubCommand;
+use crate::gpg::context::GpgContext;
+use crate::gpg::key::GpgKey;
+use crate::gpg::key::KeyData;
+use anyhow::Result;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Write;
+use std::str::FromStr;
+
+/// Output format of the subcommands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+	/// Tab-separated plain text.
+	Plain,
+	/// JSON.
+	Json,
+}
+
+impl Display for OutputFormat {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}", format!("{:?}", self).to_lowercase())
+	}
+}
+
+impl FromStr for OutputFormat {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"plain" => Ok(Self::Plain),
+			"json" => Ok(Self::Json),
+			_ => Err(format!("invalid output format: {}", s)),
+		}
+	}
+}
+
+/// Returns the summary of the given key for listing.
+///
+/// Fields are separated by tab characters.
+fn get_key_summary(key_type: &str, key: &GpgKey) -> String {
+	format!(
+		"{}\t{}\t{}\t{}\t{}",
+		key_type,
+		key.get_fingerprint(),
+		key.get_validity(),
+		key.get_expiration_time()
+			.map(|time| time.format("%F").to_string())
+			.unwrap_or_else(|| String::from("-")),
+		key.get_user_id()
+	)
+}
+
+/// Runs the given subcommand and writes its output.
+pub fn run<W: Write>(
+	subcommand: &SubCommand,
+	gpgme: &mut GpgContext,
+	output: &mut W,
+) -> Result<()> {
+	match subcommand {
+		SubCommand::ListKeys {
+			key_type,
+			format,
+			patterns,
+		} => {
+			let patterns = if patterns.is_empty() {
+				None
+			} else {
+				Some(patterns.clone())
+			};
+			let keys = gpgme.get_keys(*key_type, patterns)?;
+			match format {
+				OutputFormat::Plain => {
+					for key in keys {
+						writeln!(
+							output,
+							"{}",
+							get_key_summary(&key_type.to_string(), &key)
+						)?;
+					}
+				}
+				OutputFormat::Json => {
+					serde_json::to_writer_pretty(
+						&mut *output,
+						&keys
+							.iter()
+							.map(|key| key.get_data(false))
+							.collect::<Vec<KeyData>>(),
+					)?;
+					writeln!(output)?;
+				}
+			}
+		}
+		SubCommand::Export {
+			key_type,
+			destination: ExportDestination::File,
+			patterns,
+		} => {
+			for path in gpgme.export_keys(*key_type, Some(patterns.clone()))? {
+				writeln!(output, "{}", path)?;
+			}
+		}
+		SubCommand::Export {
+			key_type, patterns, ..
+		} => {
+			output.write_all(
+				&gpgme.get_exported_keys(*key_type, Some(patterns.clone()))?,
+			)?;
+		}
+		SubCommand::Locate { email } => {
+			let key = gpgme.locate_key(email.to_string())?;
+			writeln!(output, "{}", get_key_summary("pub", &key))?;
+		}
+	}
+	output.flush()?;
+	Ok(())
+}
+
+#[cfg(feature = "gpg-tests")]
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::args::Args;
+	use crate::gpg::config::GpgConfig;
+	use crate::gpg::key::KeyType;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_output_format() {
+		assert_eq!(Ok(OutputFormat::Json), OutputFormat::from_str("json"));
+		assert_eq!("plain", OutputFormat::Plain.to_string());
+		assert!(OutputFormat::from_str("xml").is_err());
+	}
+	#[test]
+	fn test_cli() -> Result<()> {
+		let args = Args::default();
+		let config = GpgConfig::new(&args)?;
+		let mut context = GpgContext::new(config)?;
+		let keys = context.get_keys(KeyType::Public, None)?;
+		let mut output = Vec::new();
+		run(
+			&SubCommand::ListKeys {
+				key_type: KeyType::Public,
+				format: OutputFormat::Plain,
+				patterns: Vec::new(),
+			},
+			&mut context,
+			&mut output,
+		)?;
+		let output = String::from_utf8(output)?;
+		assert_eq!(keys.len(), output.lines().count());
+		assert!(output.contains(&keys[0].get_fingerprint()));
+		let mut output = Vec::new();
+		run(
+			&SubCommand::ListKeys {
+				key_type: KeyType::Public,
+				format: OutputFormat::Json,
+				patterns: Vec::new(),
+			},
+			&mut context,
+			&mut output,
+		)?;
+		let output = String::from_utf8(output)?;
+		assert!(output.trim_start().starts_with('['));
+		assert!(output.contains(&format!(
+			"\"fingerprint\": \"{}\"",
+			keys[0].get_fingerprint()
+		)));
+		let mut output = Vec::new();
+		run(
+			&SubCommand::Export {
+				key_type: KeyType::Public,
+				destination: ExportDestination::Stdout,
+				patterns: vec![keys[0].get_fingerprint()],
+			},
+			&mut context,
+			&mut output,
+		)?;
+		assert!(!output.is_empty());
+		Ok(())
+	}
+}
@@ -0,0 +1,271 @@
+//! Configuration file parser.
+
+use crate::app::clipboard::ClipboardBackend;
+use crate::app::menu::MenuLayout;
+use crate::app::prompt::ConfirmLevel;
+use crate::app::watch::WatchMode;
+use crate::args::Args;
+use crate::gpg::key::ColumnLayout;
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+
+/// Application configuration.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+	/// General configuration.
+	#[serde(default)]
+	pub general: GeneralConfig,
+	/// GnuPG configuration.
+	#[serde(default)]
+	pub gpg: GnuPGConfig,
+	/// Options menu configuration.
+	#[serde(default)]
+	pub menu: MenuLayout,
+	/// Copy templates.
+	#[serde(default)]
+	pub copy: HashMap<String, String>,
+	/// Post-action hooks.
+	#[serde(default)]
+	pub hooks: HashMap<String, String>,
+}
+
+/// General configuration.
+#[derive(Debug, Default, Deserialize)]
+pub struct GeneralConfig {
+	/// [`Args::splash`]
+	pub splash: Option<bool>,
+	/// [`Args::hide_unusable`]
+	pub hide_unusable: Option<bool>,
+	/// [`Args::notify`]
+	pub notify: Option<bool>,
+	/// [`Args::redact`]
+	pub redact: Option<bool>,
+	/// [`Args::cache`]
+	pub cache: Option<bool>,
+	/// [`Args::expiry_warn`]
+	pub expiry_warn: Option<u32>,
+	/// [`Args::date_format`]
+	pub date_format: Option<String>,
+	/// [`Args::statusline`]
+	pub statusline: Option<String>,
+	/// [`Args::columns`]
+	pub columns: Option<String>,
+	/// [`Args::confirm`]
+	pub confirm: Option<String>,
+	/// [`Args::clipboard`]
+	pub clipboard: Option<String>,
+	/// [`Args::clipboard_timeout`]
+	pub clipboard_timeout: Option<u64>,
+	/// [`Args::socket`]
+	pub socket: Option<String>,
+	/// [`Args::watch`]
+	pub watch: Option<String>,
+}
+
+/// GnuPG configuration.
+#[derive(Debug, Default, Deserialize)]
+pub struct GnuPGConfig {
+	/// [`Args::armor`]
+	pub armor: Option<bool>,
+	/// [`Args::honor_keyserver_url`]
+	pub honor_keyserver_url: Option<bool>,
+	/// [`Args::homedir`]
+	pub homedir: Option<String>,
+	/// [`Args::outdir`]
+	pub outdir: Option<String>,
+	/// [`Args::export_name`]
+	pub export_name: Option<String>,
+	/// [`Args::gpg_binary`]
+	pub gpg_binary: Option<String>,
+	/// [`Args::default_key`]
+	pub default_key: Option<String>,
+	/// [`Args::auto_key_locate`]
+	pub auto_key_locate: Option<String>,
+}
+
+impl Config {
+	/// Returns the default location of the configuration file.
+	///
+	/// It is `$XDG_CONFIG_HOME/gpg-tui/gpg-tui.toml` on Linux and
+	/// `None` is returned if the file does not exist.
+	pub fn get_default_location() -> Option<String> {
+		let path = dirs_next::config_dir()?
+			.join(env!("CARGO_PKG_NAME"))
+			.join(concat!(env!("CARGO_PKG_NAME"), ".toml"));
+		if path.exists() {
+			Some(path.to_string_lossy().to_string())
+		} else {
+			None
+		}
+	}
+
+	/// Parses the given configuration file.
+	pub fn parse_config(file: &str) -> Result<Self> {
+		Ok(toml::from_str(&fs::read_to_string(file)?)?)
+	}
+
+	/// Updates the arguments that are not given on the command-line.
+	pub fn update_args(self, mut args: Args) -> Args {
+		let GeneralConfig {
+			splash,
+			hide_unusable,
+			notify,
+			redact,
+			cache,
+			expiry_warn,
+			date_format,
+			statusline,
+			columns,
+			confirm,
+			clipboard,
+			clipboard_timeout,
+			socket,
+			watch,
+		} = self.general;
+		let GnuPGConfig {
+			armor,
+			honor_keyserver_url,
+			homedir,
+			outdir,
+			export_name,
+			gpg_binary,
+			default_key,
+			auto_key_locate,
+		} = self.gpg;
+		args.splash = args.splash.or(splash.map(Some));
+		args.hide_unusable = args.hide_unusable.or(hide_unusable.map(Some));
+		args.notify = args.notify.or(notify.map(Some));
+		args.redact = args.redact.or(redact.map(Some));
+		args.cache = args.cache.or(cache.map(Some));
+		args.expiry_warn = args.expiry_warn.or(expiry_warn);
+		args.date_format = args.date_format.or(date_format);
+		args.statusline = args.statusline.or(statusline);
+		args.columns = args
+			.columns
+			.or_else(|| columns.and_then(|v| ColumnLayout::from_str(&v).ok()));
+		args.confirm = args
+			.confirm
+			.or_else(|| confirm.and_then(|v| ConfirmLevel::from_str(&v).ok()));
+		args.clipboard = args.clipboard.or_else(|| {
+			clipboard.and_then(|v| ClipboardBackend::from_str(&v).ok())
+		});
+		args.clipboard_timeout = args.clipboard_timeout.or(clipboard_timeout);
+		args.socket =
+			args.socket.or_else(|| socket.map(|v| Args::parse_dir(&v)));
+		args.watch = args
+			.watch
+			.or_else(|| watch.and_then(|v| WatchMode::from_str(&v).ok()));
+		args.armor = args.armor.or(armor.map(Some));
+		args.honor_keyserver_url =
+			args.honor_keyserver_url.or(honor_keyserver_url.map(Some));
+		args.homedir = args
+			.homedir
+			.or_else(|| homedir.map(|v| Args::parse_dir(&v)));
+		args.outdir =
+			args.outdir.or_else(|| outdir.map(|v| Args::parse_dir(&v)));
+		args.export_name = args.export_name.or(export_name);
+		args.gpg_binary = args
+			.gpg_binary
+			.or_else(|| gpg_binary.map(|v| Args::parse_dir(&v)));
+		args.default_key = args.default_key.or(default_key);
+		args.auto_key_locate = args.auto_key_locate.or(auto_key_locate);
+		args.menu = self.menu;
+		args.copy_templates = self.copy;
+		args.hooks = self.hooks;
+		args
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::path::PathBuf;
+	#[test]
+	fn test_config() -> Result<()> {
+		let config = Config::parse_config(
+			&PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+				.join("config")
+				.join(concat!(env!("CARGO_PKG_NAME"), ".toml"))
+				.to_string_lossy(),
+		)?;
+		assert_eq!(Some(false), config.general.hide_unusable);
+		assert_eq!(None, config.gpg.homedir);
+		let config: Config = toml::from_str(
+			r#"
+			[general]
+			hide_unusable = true
+			notify = true
+			redact = true
+			cache = true
+			expiry_warn = 7
+			date_format = "%d/%m/%Y"
+			statusline = "{tab} {selection}/{total}"
+			columns = "flags,id|uid"
+			confirm = "strict"
+			clipboard = "wayland"
+			clipboard_timeout = 10
+			socket = "/tmp/gpg-tui.sock"
+			watch = "reload"
+			[gpg]
+			honor_keyserver_url = true
+			outdir = "/tmp"
+			export_name = "{keyid}_{date}"
+			default_key = "0x0"
+			auto_key_locate = "wkd,keyserver"
+			[menu]
+			remove = ["send key"]
+			[copy]
+			signature = "{uid} ({fpr})"
+			[hooks]
+			post-import = "sync.sh {fingerprints}"
+			"#,
+		)?;
+		let args = config.update_args(Args {
+			default_key: Some(String::from("0x1")),
+			notify: Some(Some(false)),
+			armor: Some(None),
+			..Args::default()
+		});
+		assert!(Args::get_flag(args.hide_unusable));
+		assert!(!Args::get_flag(args.notify));
+		assert!(Args::get_flag(args.redact));
+		assert!(Args::get_flag(args.cache));
+		assert_eq!(Some(7), args.expiry_warn);
+		assert_eq!(Some(String::from("%d/%m/%Y")), args.date_format);
+		assert_eq!(
+			Some(String::from("{tab} {selection}/{total}")),
+			args.statusline
+		);
+		assert_eq!(
+			Some(String::from("flags,id|uid")),
+			args.columns.map(|v| v.to_string())
+		);
+		assert_eq!(Some(ConfirmLevel::Strict), args.confirm);
+		assert_eq!(Some(ClipboardBackend::Wayland), args.clipboard);
+		assert_eq!(Some(10), args.clipboard_timeout);
+		assert_eq!(Some(String::from("/tmp/gpg-tui.sock")), args.socket);
+		assert_eq!(Some(WatchMode::Reload), args.watch);
+		assert!(Args::get_flag(args.armor));
+		assert!(Args::get_flag(args.honor_keyserver_url));
+		assert!(!Args::get_flag(args.splash));
+		assert_eq!(Some(String::from("/tmp")), args.outdir);
+		assert_eq!(Some(String::from("{keyid}_{date}")), args.export_name);
+		assert_eq!(Some(String::from("0x1")), args.default_key);
+		assert_eq!(Some(String::from("wkd,keyserver")), args.auto_key_locate);
+		assert_eq!(vec![String::from("send key")], args.menu.remove);
+		assert!(args.menu.add.is_empty());
+		assert_eq!(
+			Some(&String::from("{uid} ({fpr})")),
+			args.copy_templates.get("signature")
+		);
+		assert_eq!(
+			Some(&String::from("sync.sh {fingerprints}")),
+			args.hooks.get("post-import")
+		);
+		Ok(())
+	}
+}
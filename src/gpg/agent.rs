@@ -0,0 +1,233 @@
+use anyhow::{anyhow, Result};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command as OsCommand;
+
+/// Name of the file that lists the keys to use for SSH.
+const SSHCONTROL_FILE: &str = "sshcontrol";
+
+/// Name of the configuration file of gpg-agent.
+const AGENT_CONFIG_FILE: &str = "gpg-agent.conf";
+
+/// Option of gpg-agent for enabling the SSH support.
+const SSH_SUPPORT_OPTION: &str = "enable-ssh-support";
+
+/// Components that are shown in the agent information.
+const COMPONENTS: &[&str] = &["gpg", "gpg-agent", "dirmngr", "scdaemon"];
+
+/// Daemons that can be managed via `gpgconf`.
+pub const DAEMONS: &[&str] = &["gpg-agent", "dirmngr", "scdaemon", "all"];
+
+/// Actions that can be run on the daemons via `gpgconf`.
+pub const DAEMON_ACTIONS: &[&str] = &["reload", "kill", "launch"];
+
+/// Configuration files (name, file name, daemon to reload).
+pub const CONFIG_FILES: &[(&str, &str, Option<&str>)] = &[
+	("gpg", "gpg.conf", None),
+	("dirmngr", "dirmngr.conf", Some("dirmngr")),
+	("agent", "gpg-agent.conf", Some("gpg-agent")),
+];
+
+/// Information about a GnuPG component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+	/// Name of the component.
+	pub name: String,
+	/// Path of the executable.
+	pub path: String,
+	/// Version of the component.
+	pub version: Option<String>,
+	/// Is the daemon running? (`None` if not known)
+	pub running: Option<bool>,
+}
+
+/// Returns the configured lines of the given file content.
+///
+/// Empty lines and comments are skipped.
+fn get_entries(content: &str) -> impl Iterator<Item = &str> {
+	content
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Returns the keygrips that are listed in the given `sshcontrol` content.
+///
+/// Disabled entries (prefixed with `!`) are skipped.
+pub fn parse_sshcontrol(content: &str) -> Vec<String> {
+	get_entries(content)
+		.filter(|line| !line.starts_with('!'))
+		.filter_map(|line| line.split_whitespace().next())
+		.map(|keygrip| keygrip.to_uppercase())
+		.collect()
+}
+
+/// Returns the keygrips that are exposed to the SSH agent.
+pub fn get_ssh_keygrips(home_dir: &Path) -> Result<Vec<String>> {
+	let path = home_dir.join(SSHCONTROL_FILE);
+	if path.exists() {
+		Ok(parse_sshcontrol(&fs::read_to_string(path)?))
+	} else {
+		Ok(Vec::new())
+	}
+}
+
+/// Appends the given keygrips to `sshcontrol` file.
+///
+/// Returns the number of the keygrips that are added.
+pub fn add_ssh_keygrips(home_dir: &Path, keygrips: &[String]) -> Result<usize> {
+	let existing_keygrips = get_ssh_keygrips(home_dir)?;
+	let keygrips = keygrips
+		.iter()
+		.filter(|keygrip| !existing_keygrips.contains(&keygrip.to_uppercase()))
+		.collect::<Vec<&String>>();
+	if !keygrips.is_empty() {
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(home_dir.join(SSHCONTROL_FILE))?;
+		for keygrip in &keygrips {
+			writeln!(file, "{}", keygrip)?;
+		}
+	}
+	Ok(keygrips.len())
+}
+
+/// Returns `true` if `enable-ssh-support` is set in the given
+/// content of the gpg-agent configuration.
+pub fn parse_ssh_support(content: &str) -> bool {
+	get_entries(content).any(|line| line == SSH_SUPPORT_OPTION)
+}
+
+/// Returns `true` if the SSH support of gpg-agent is enabled.
+pub fn is_ssh_support_enabled(home_dir: &Path) -> bool {
+	fs::read_to_string(home_dir.join(AGENT_CONFIG_FILE))
+		.map(|content| parse_ssh_support(&content))
+		.unwrap_or(false)
+}
+
+/// Returns the names and paths of the components that are
+/// listed in the given output of `gpgconf --list-components`.
+pub fn parse_components(output: &str) -> Vec<(String, String)> {
+	output
+		.lines()
+		.filter_map(|line| {
+			let values = line.split(':').collect::<Vec<&str>>();
+			match values.as_slice() {
+				[name, _, path, ..] => Some((
+					name.to_string(),
+					path.replace("%3a", ":").replace("%25", "%"),
+				)),
+				_ => None,
+			}
+		})
+		.collect()
+}
+
+/// Runs the given command and returns its output if it succeeds.
+pub fn get_output(command: &mut OsCommand) -> Result<String> {
+	let output = command
+		.output()
+		.map_err(|e| anyhow!("cannot run command: {:?}", e))?;
+	if output.status.success() {
+		Ok(String::from_utf8(output.stdout)?)
+	} else {
+		Err(anyhow!(
+			"{}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		))
+	}
+}
+
+/// Returns `true` if the given daemon is running.
+///
+/// It is checked via connecting without starting the daemon.
+fn is_running(home_dir: &Path, daemon: &str) -> Option<bool> {
+	let mut command = OsCommand::new("gpg-connect-agent");
+	command.arg("--homedir").arg(home_dir).arg("--no-autostart");
+	match daemon {
+		"gpg-agent" => {}
+		"dirmngr" => {
+			command.arg("--dirmngr");
+		}
+		_ => return None,
+	}
+	command
+		.arg("/bye")
+		.output()
+		.ok()
+		.map(|output| output.status.success() && output.stderr.is_empty())
+}
+
+/// Returns information about the GnuPG components.
+pub fn get_components(home_dir: &Path) -> Result<Vec<Component>> {
+	Ok(parse_components(&get_output(
+		OsCommand::new("gpgconf").arg("--list-components"),
+	)?)
+	.into_iter()
+	.filter(|(name, _)| COMPONENTS.contains(&name.as_str()))
+	.map(|(name, path)| Component {
+		version: get_output(OsCommand::new(&path).arg("--version"))
+			.ok()
+			.and_then(|output| {
+				output
+					.lines()
+					.next()
+					.and_then(|line| line.split_whitespace().last())
+					.map(String::from)
+			}),
+		running: is_running(home_dir, &name),
+		name,
+		path,
+	})
+	.collect())
+}
+
+/// Runs the given action (e.g. `reload`) on the daemon via `gpgconf`.
+pub fn run_gpgconf(home_dir: &Path, action: &str, daemon: &str) -> Result<()> {
+	get_output(
+		OsCommand::new("gpgconf")
+			.arg("--homedir")
+			.arg(home_dir)
+			.arg(format!("--{}", action))
+			.arg(daemon),
+	)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn test_gpg_agent() -> Result<()> {
+		assert_eq!(
+			vec![String::from("ABCD"), String::from("EF01")],
+			parse_sshcontrol("# comment\nabcd 0\n\n!1234\n EF01 600 confirm")
+		);
+		assert!(parse_ssh_support("# x\n enable-ssh-support\n"));
+		assert!(!parse_ssh_support("#enable-ssh-support"));
+		assert_eq!(
+			vec![
+				(String::from("gpg"), String::from("/usr/bin/gpg")),
+				(String::from("x"), String::from("C:\\gnupg\\x.exe")),
+			],
+			parse_components(
+				"gpg:OpenPGP:/usr/bin/gpg\nx:X:C%3a\\gnupg\\x.exe\ninvalid"
+			)
+		);
+		let home_dir = env::temp_dir().join("gpg-tui-agent-test");
+		let _ = fs::remove_dir_all(&home_dir);
+		fs::create_dir_all(&home_dir)?;
+		assert!(get_ssh_keygrips(&home_dir)?.is_empty());
+		let keygrips = vec![String::from("ABCD"), String::from("EF01")];
+		assert_eq!(2, add_ssh_keygrips(&home_dir, &keygrips)?);
+		assert_eq!(0, add_ssh_keygrips(&home_dir, &keygrips[..1])?);
+		assert_eq!(keygrips, get_ssh_keygrips(&home_dir)?);
+		assert!(!is_ssh_support_enabled(&home_dir));
+		fs::remove_dir_all(home_dir)?;
+		Ok(())
+	}
+}
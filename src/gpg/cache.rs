@@ -0,0 +1,216 @@
+use crate::gpg::handler;
+use crate::gpg::key::{GpgKey, KeyData, KeyType};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Name of the cache file.
+const CACHE_FILE: &str = "keys.json";
+
+/// Snapshot of the keyring for rendering the keys on startup.
+///
+/// It is shown until the live key listing is loaded.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyCache {
+	/// Home directory of the keyring.
+	pub home_dir: PathBuf,
+	/// Public keys.
+	pub public: Vec<KeyData>,
+	/// Secret keys.
+	pub secret: Vec<KeyData>,
+}
+
+impl KeyCache {
+	/// Constructs a new instance of `KeyCache` from the given keys.
+	pub fn new(
+		home_dir: &Path,
+		keys: &HashMap<KeyType, Vec<Arc<GpgKey>>>,
+	) -> Self {
+		let get_data = |key_type| {
+			keys.get(&key_type)
+				.map(|keys| {
+					keys.iter().map(|key| key.get_data(false)).collect()
+				})
+				.unwrap_or_default()
+		};
+		Self {
+			home_dir: home_dir.to_path_buf(),
+			public: get_data(KeyType::Public),
+			secret: get_data(KeyType::Secret),
+		}
+	}
+
+	/// Returns the path of the cache file.
+	pub fn get_path() -> Result<PathBuf> {
+		dirs_next::cache_dir()
+			.map(|path| path.join(env!("CARGO_PKG_NAME")).join(CACHE_FILE))
+			.ok_or_else(|| anyhow!("failed to find the cache directory"))
+	}
+
+	/// Loads the snapshot of the given home directory.
+	pub fn load(home_dir: &Path) -> Result<Self> {
+		let cache: Self =
+			serde_json::from_reader(File::open(Self::get_path()?)?)?;
+		if cache.home_dir == home_dir {
+			Ok(cache)
+		} else {
+			Err(anyhow!("cache belongs to {:?}", cache.home_dir))
+		}
+	}
+
+	/// Saves the snapshot to the cache file.
+	pub fn save(&self) -> Result<()> {
+		let path = Self::get_path()?;
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		serde_json::to_writer(File::create(&path)?, &self)?;
+		Ok(())
+	}
+
+	/// Returns the keys of the given type.
+	pub fn get_keys(&self, key_type: KeyType) -> &[KeyData] {
+		match key_type {
+			KeyType::Public => &self.public,
+			KeyType::Secret => &self.secret,
+		}
+	}
+
+	/// Returns the rows of the keys table for the given key type.
+	///
+	/// Each row consists of the primary key and user ID.
+	pub fn get_rows(
+		&self,
+		key_type: KeyType,
+		redact: bool,
+	) -> Vec<[String; 2]> {
+		self.get_keys(key_type)
+			.iter()
+			.map(|key| {
+				[
+					key.subkeys
+						.first()
+						.map(|subkey| {
+							format!(
+								"[{}]{} {}/{}",
+								handler::get_subkey_flags_from_names(
+									&subkey.flags
+								),
+								if subkey.card_serial.is_some() {
+									handler::CARD_BADGE
+								} else {
+									""
+								},
+								subkey.algorithm,
+								subkey.fingerprint
+							)
+						})
+						.unwrap_or_else(|| key.fingerprint.to_string()),
+					key.user_ids
+						.first()
+						.map(|user| {
+							format!(
+								"[{}] {}",
+								user.validity,
+								if redact {
+									handler::redact_user_id(&user.uid)
+								} else {
+									user.uid.to_string()
+								}
+							)
+						})
+						.unwrap_or_else(|| String::from("[?]")),
+				]
+			})
+			.collect()
+	}
+
+	/// Returns the number of added, removed and changed keys
+	/// in the given snapshot compared to this one.
+	pub fn get_changes(&self, other: &Self) -> (usize, usize, usize) {
+		let (mut added, mut removed, mut changed) = (0, 0, 0);
+		for key_type in &[KeyType::Public, KeyType::Secret] {
+			let (keys, other_keys) =
+				(self.get_keys(*key_type), other.get_keys(*key_type));
+			let find = |keys: &[KeyData], fingerprint: &str| {
+				keys.iter()
+					.find(|key| key.fingerprint == fingerprint)
+					.cloned()
+			};
+			for key in other_keys {
+				match find(keys, &key.fingerprint) {
+					Some(cached_key) if cached_key != *key => changed += 1,
+					Some(_) => {}
+					None => added += 1,
+				}
+			}
+			removed += keys
+				.iter()
+				.filter(|key| find(other_keys, &key.fingerprint).is_none())
+				.count();
+		}
+		(added, removed, changed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::gpg::key::{KeyValidity, SubkeyData, UserIdData};
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_cache() {
+		let key = KeyData {
+			fingerprint: String::from("ABCD"),
+			validity: KeyValidity::Full,
+			owner_trust: KeyValidity::Unknown,
+			subkeys: vec![SubkeyData {
+				fingerprint: String::from("ABCD"),
+				algorithm: String::from("ed25519"),
+				flags: vec![String::from("sign"), String::from("certify")],
+				created: None,
+				expires: None,
+				card_serial: Some(String::from("1")),
+			}],
+			user_ids: vec![UserIdData {
+				uid: String::from("Alice <alice@test>"),
+				name: String::from("Alice"),
+				email: String::from("alice@test"),
+				validity: KeyValidity::Ultimate,
+			}],
+		};
+		let cache = KeyCache {
+			home_dir: PathBuf::from("/tmp"),
+			public: vec![key.clone()],
+			secret: Vec::new(),
+		};
+		assert_eq!(
+			vec![[
+				String::from("[sc--]> ed25519/ABCD"),
+				String::from("[ultimate] Alice <alice@test>"),
+			]],
+			cache.get_rows(KeyType::Public, false)
+		);
+		assert!(cache.get_rows(KeyType::Secret, false).is_empty());
+		assert_eq!((0, 0, 0), cache.get_changes(&cache));
+		let other = KeyCache {
+			public: vec![
+				KeyData {
+					validity: KeyValidity::Revoked,
+					..key.clone()
+				},
+				KeyData {
+					fingerprint: String::from("EFGH"),
+					..key.clone()
+				},
+			],
+			secret: Vec::new(),
+			..cache.clone()
+		};
+		assert_eq!((1, 0, 1), cache.get_changes(&other));
+		assert_eq!((0, 1, 1), other.get_changes(&cache));
+	}
+}
@@ -0,0 +1,301 @@
+use crate::gpg::agent;
+use anyhow::{anyhow, Result as AnyhowResult};
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command as OsCommand;
+use std::str::FromStr;
+
+/// Key slot of an OpenPGP card.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CardSlot {
+	/// Signature key slot.
+	Signature,
+	/// Encryption key slot.
+	Encryption,
+	/// Authentication key slot.
+	Authentication,
+}
+
+impl Display for CardSlot {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Signature => "signature",
+				Self::Encryption => "encryption",
+				Self::Authentication => "authentication",
+			}
+		)
+	}
+}
+
+impl FromStr for CardSlot {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"signature" | "sig" | "1" => Ok(Self::Signature),
+			"encryption" | "enc" | "2" => Ok(Self::Encryption),
+			"authentication" | "auth" | "3" => Ok(Self::Authentication),
+			_ => Err(()),
+		}
+	}
+}
+
+impl CardSlot {
+	/// Returns the number of the slot in `keytocard` menu.
+	pub fn get_number(&self) -> u8 {
+		match self {
+			Self::Signature => 1,
+			Self::Encryption => 2,
+			Self::Authentication => 3,
+		}
+	}
+}
+
+/// PIN of an OpenPGP card.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CardPin {
+	/// User PIN.
+	Pin,
+	/// Admin PIN.
+	AdminPin,
+}
+
+impl Display for CardPin {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Pin => "PIN",
+				Self::AdminPin => "Admin PIN",
+			}
+		)
+	}
+}
+
+impl FromStr for CardPin {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"pin" | "user-pin" => Ok(Self::Pin),
+			"admin-pin" | "admin" => Ok(Self::AdminPin),
+			_ => Err(()),
+		}
+	}
+}
+
+impl CardPin {
+	/// Returns the identifier of the PIN for scdaemon.
+	fn get_chv(&self) -> &str {
+		match self {
+			Self::Pin => "OPENPGP.1",
+			Self::AdminPin => "OPENPGP.3",
+		}
+	}
+
+	/// Returns the remaining retries of the PIN from the given
+	/// `CHV-STATUS` attribute (e.g. `S CHV-STATUS +1+127+127+127+3+0+3`).
+	pub fn parse_retries(&self, output: &str) -> Option<u8> {
+		let values = output
+			.lines()
+			.find_map(|line| line.strip_prefix("S CHV-STATUS "))?
+			.split('+')
+			.filter(|v| !v.is_empty())
+			.collect::<Vec<&str>>();
+		values
+			.get(match self {
+				Self::Pin => 4,
+				Self::AdminPin => 6,
+			})
+			.and_then(|v| v.parse().ok())
+	}
+
+	/// Returns the remaining retries of the PIN from the card.
+	pub fn get_retries(&self, home_dir: &Path) -> AnyhowResult<u8> {
+		let output = run_scd(home_dir, "GETATTR CHV-STATUS")?;
+		self.parse_retries(&output)
+			.ok_or_else(|| anyhow!("cannot read the PIN status"))
+	}
+
+	/// Changes the PIN via scdaemon.
+	///
+	/// Current and new PINs are asked via pinentry.
+	pub fn change(&self, home_dir: &Path) -> AnyhowResult<()> {
+		run_scd(home_dir, &format!("PASSWD {}", self.get_chv()))?;
+		Ok(())
+	}
+}
+
+/// Returns `true` if a card is present.
+pub fn is_present(home_dir: &Path) -> bool {
+	run_scd(home_dir, "SERIALNO").is_ok()
+}
+
+/// Sends the given command to scdaemon via `gpg-connect-agent`.
+///
+/// The error that is returned by the agent (e.g. `ERR ... Bad PIN`)
+/// is converted to an error.
+fn run_scd(home_dir: &Path, command: &str) -> AnyhowResult<String> {
+	let output = agent::get_output(
+		OsCommand::new("gpg-connect-agent")
+			.arg("--homedir")
+			.arg(home_dir)
+			.arg(format!("SCD {}", command))
+			.arg("/bye"),
+	)?;
+	match output.lines().find_map(|line| line.strip_prefix("ERR ")) {
+		Some(error) => Err(anyhow!(
+			"{}",
+			error.split_once(' ').map(|(_, e)| e).unwrap_or(error)
+		)),
+		None => Ok(output),
+	}
+}
+
+/// Interactor for moving a (sub)key to the card via `keytocard`.
+///
+/// Changes are saved at the end which means that the secret
+/// key on the disk is replaced by a stub that points to the card.
+#[derive(Debug)]
+pub struct KeyToCard {
+	/// Index of the subkey (0 for the primary key).
+	subkey: usize,
+	/// Slot to store the key.
+	slot: CardSlot,
+	/// Is the subkey selected?
+	selected: bool,
+	/// Is the `keytocard` command sent?
+	moved: bool,
+	/// Is the slot answered?
+	stored: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl KeyToCard {
+	/// Constructs a new instance of `KeyToCard`.
+	pub fn new(subkey: usize, slot: CardSlot) -> Self {
+		Self {
+			subkey,
+			slot,
+			selected: subkey == 0,
+			moved: false,
+			stored: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	///
+	/// [`CONFLICT`] is returned if the slot is not empty,
+	/// [`WRONG_KEY_USAGE`] if the key cannot be stored in the slot
+	/// and [`CARD_NOT_PRESENT`] if the card is not accessible.
+	///
+	/// [`CONFLICT`]: Error::CONFLICT
+	/// [`WRONG_KEY_USAGE`]: Error::WRONG_KEY_USAGE
+	/// [`CARD_NOT_PRESENT`]: Error::CARD_NOT_PRESENT
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.selected => {
+				self.selected = true;
+				Ok(format!("key {}", self.subkey))
+			}
+			"keyedit.prompt" if !self.moved => {
+				self.moved = true;
+				Ok(String::from("keytocard"))
+			}
+			"keyedit.prompt" if self.stored && !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"keyedit.prompt" if !self.stored => Err(Error::CARD_NOT_PRESENT),
+			"cardedit.genkeys.storekeytype" if !self.stored => {
+				self.stored = true;
+				Ok(self.slot.get_number().to_string())
+			}
+			"cardedit.genkeys.storekeytype" => Err(Error::WRONG_KEY_USAGE),
+			"cardedit.genkeys.replace_key" => Err(Error::CONFLICT),
+			"keyedit.save.okay" => Ok(String::from("y")),
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for KeyToCard {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_card() {
+		assert_eq!(Ok(CardSlot::Encryption), CardSlot::from_str("enc"));
+		assert_eq!(Ok(CardSlot::Authentication), CardSlot::from_str("3"));
+		assert_eq!(Err(()), CardSlot::from_str("x"));
+		assert_eq!("signature", CardSlot::Signature.to_string());
+		assert_eq!(Ok(CardPin::AdminPin), CardPin::from_str("admin-pin"));
+		assert_eq!("Admin PIN", CardPin::AdminPin.to_string());
+		let status = "S CHV-STATUS +1+127+127+127+3+0+2\nOK";
+		assert_eq!(Some(3), CardPin::Pin.parse_retries(status));
+		assert_eq!(Some(2), CardPin::AdminPin.parse_retries(status));
+		assert_eq!(None, CardPin::Pin.parse_retries("ERR 100663404"));
+		let mut interactor = KeyToCard::new(2, CardSlot::Encryption);
+		for (prompt, response) in &[
+			("keyedit.prompt", "key 2"),
+			("keyedit.prompt", "keytocard"),
+			("cardedit.genkeys.storekeytype", "2"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+		let mut interactor = KeyToCard::new(0, CardSlot::Signature);
+		assert_eq!(
+			Ok(String::from("keytocard")),
+			interactor.get_response("keyedit.prompt")
+		);
+		assert_eq!(
+			Err(Error::CARD_NOT_PRESENT),
+			interactor.get_response("keyedit.prompt")
+		);
+		assert_eq!(
+			Ok(String::from("1")),
+			interactor.get_response("cardedit.genkeys.storekeytype")
+		);
+		assert_eq!(
+			Err(Error::WRONG_KEY_USAGE),
+			interactor.get_response("cardedit.genkeys.storekeytype")
+		);
+		assert_eq!(
+			Err(Error::CONFLICT),
+			interactor.get_response("cardedit.genkeys.replace_key")
+		);
+	}
+}
@@ -0,0 +1,191 @@
+use crate::gpg::handler;
+use chrono::{DateTime, Utc};
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::io::Write;
+
+/// Certification (user ID signature) that is made by a key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Certification {
+	/// ID of the certified key.
+	pub key_id: String,
+	/// Fingerprint of the certified key.
+	pub fingerprint: String,
+	/// Certified user ID.
+	pub user_id: String,
+	/// ID of the signing key.
+	pub signer_id: String,
+	/// Creation time of the certification.
+	pub created: Option<DateTime<Utc>>,
+	/// Expiration time of the certification.
+	pub expires: Option<DateTime<Utc>>,
+	/// Whether if the certification is exportable.
+	pub exportable: bool,
+	/// Whether if the certification is revoked.
+	pub revoked: bool,
+}
+
+impl Certification {
+	/// Returns the description of the certification
+	/// with the dates in the given format.
+	pub fn get_info(&self, date_format: &str, redact: bool) -> String {
+		format!(
+			"{} {} ({}){}{}{}",
+			self.key_id,
+			if redact {
+				handler::redact_user_id(&self.user_id)
+			} else {
+				self.user_id.to_string()
+			},
+			self.created
+				.map(|date| date.format(date_format).to_string())
+				.unwrap_or_else(|| String::from("[?]")),
+			self.expires
+				.map(|date| format!(" ─> ({})", date.format(date_format)))
+				.unwrap_or_default(),
+			if self.exportable { "" } else { " [local]" },
+			if self.revoked { " [revoked]" } else { "" },
+		)
+	}
+}
+
+/// Interactor for revoking the certifications via `revsig`.
+///
+/// `gpg` asks about each certification that is made by one of the
+/// secret keys in the keyring, so the answers are expected to be
+/// given in the same order (user IDs and then their signatures).
+#[derive(Debug)]
+pub struct RevokeSig {
+	/// Answers for the certifications (`true` to revoke).
+	answers: Vec<bool>,
+	/// Number of the answered certifications.
+	answered: usize,
+	/// Is the `revsig` command sent?
+	sent: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl RevokeSig {
+	/// Constructs a new instance of `RevokeSig`.
+	pub fn new(answers: Vec<bool>) -> Self {
+		Self {
+			answers,
+			answered: 0,
+			sent: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	///
+	/// [`NOT_FOUND`] is returned if `gpg` asks about
+	/// more certifications than expected.
+	///
+	/// [`NOT_FOUND`]: Error::NOT_FOUND
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		let yes_or_no = |v: bool| String::from(if v { "y" } else { "n" });
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from("revsig"))
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"ask_revoke_sig.one" => {
+				let answer = self
+					.answers
+					.get(self.answered)
+					.copied()
+					.ok_or(Error::NOT_FOUND)?;
+				self.answered += 1;
+				Ok(yes_or_no(answer))
+			}
+			"ask_revoke_sig.expired" => Ok(yes_or_no(
+				self.answers.get(self.answered).copied().unwrap_or_default(),
+			)),
+			"ask_revocation_reason.code" => Ok(String::from("0")),
+			"ask_revocation_reason.text" => Ok(String::new()),
+			"ask_revoke_sig.okay"
+			| "ask_revocation_reason.okay"
+			| "keyedit.save.okay" => Ok(String::from("y")),
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for RevokeSig {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_cert() {
+		let mut cert = Certification {
+			key_id: String::from("0x1234"),
+			fingerprint: String::from("ABCD1234"),
+			user_id: String::from("test <test@test>"),
+			signer_id: String::from("0x5678"),
+			created: Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)),
+			expires: None,
+			exportable: true,
+			revoked: false,
+		};
+		assert_eq!(
+			"0x1234 test <test@test> (2021-01-01)",
+			cert.get_info("%F", false)
+		);
+		cert.expires = Some(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0));
+		cert.exportable = false;
+		cert.revoked = true;
+		assert_eq!(
+			"0x1234 test <test@test> (2021) ─> (2022) [local] [revoked]",
+			cert.get_info("%Y", false)
+		);
+		let mut interactor = RevokeSig::new(vec![false, true]);
+		for (prompt, response) in &[
+			("keyedit.prompt", "revsig"),
+			("ask_revoke_sig.one", "n"),
+			("ask_revoke_sig.expired", "y"),
+			("ask_revoke_sig.one", "y"),
+			("ask_revoke_sig.okay", "y"),
+			("ask_revocation_reason.code", "0"),
+			("ask_revocation_reason.text", ""),
+			("ask_revocation_reason.okay", "y"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::NOT_FOUND),
+			interactor.get_response("ask_revoke_sig.one")
+		);
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+	}
+}
@@ -0,0 +1,92 @@
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::io::Write;
+
+/// Interactor for cleaning the key via `clean` (or `minimize`).
+///
+/// `clean` removes the unusable user IDs and the signatures that
+/// are expired or made by the keys that are not in the keyring.
+/// `minimize` removes all signatures except the latest self-signatures.
+#[derive(Debug)]
+pub struct CleanKey {
+	/// Is the key minimized?
+	minimize: bool,
+	/// Is the `clean`/`minimize` command sent?
+	sent: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl CleanKey {
+	/// Constructs a new instance of `CleanKey`.
+	pub fn new(minimize: bool) -> Self {
+		Self {
+			minimize,
+			sent: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from(if self.minimize {
+					"minimize"
+				} else {
+					"clean"
+				}))
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"keyedit.save.okay" => Ok(String::from("y")),
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for CleanKey {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_clean() {
+		for (minimize, command) in &[(false, "clean"), (true, "minimize")] {
+			let mut interactor = CleanKey::new(*minimize);
+			for (prompt, response) in &[
+				("keyedit.prompt", *command),
+				("keyedit.prompt", "save"),
+				("keyedit.save.okay", "y"),
+			] {
+				assert_eq!(
+					Ok(response.to_string()),
+					interactor.get_response(prompt)
+				);
+			}
+			assert_eq!(
+				Err(Error::UNEXPECTED),
+				interactor.get_response("keyedit.prompt")
+			);
+		}
+	}
+}
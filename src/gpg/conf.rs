@@ -0,0 +1,119 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+/// Name of the GnuPG configuration file.
+const GPG_CONF: &str = "gpg.conf";
+
+/// Option of the default key.
+pub const DEFAULT_KEY_OPTION: &str = "default-key";
+
+/// Reads `gpg.conf` in the given home directory.
+///
+/// Returns an empty string if the file does not exist.
+pub fn read_gpg_conf(home_dir: &Path) -> Result<String> {
+	let path = home_dir.join(GPG_CONF);
+	if path.exists() {
+		Ok(fs::read_to_string(path)?)
+	} else {
+		Ok(String::new())
+	}
+}
+
+/// Writes `gpg.conf` in the given home directory.
+pub fn write_gpg_conf(home_dir: &Path, config: &str) -> Result<()> {
+	fs::write(home_dir.join(GPG_CONF), config)?;
+	Ok(())
+}
+
+/// Returns the name and the value of the option in the given line.
+fn parse_option(line: &str) -> Option<(&str, &str)> {
+	let line = line.trim();
+	if line.is_empty() || line.starts_with('#') {
+		return None;
+	}
+	match line.split_once(char::is_whitespace) {
+		Some((name, value)) => Some((name, value.trim())),
+		None => Some((line, "")),
+	}
+}
+
+/// Returns the value of the given option.
+///
+/// The last occurrence is used if the option is given more than once.
+pub fn get_option(config: &str, name: &str) -> Option<String> {
+	config
+		.lines()
+		.rev()
+		.filter_map(parse_option)
+		.find(|(option, _)| *option == name)
+		.map(|(_, value)| value.to_string())
+}
+
+/// Returns the configuration with the given option replaced
+/// (or appended) and the other lines kept as is.
+///
+/// The option is removed if no value is given.
+pub fn set_option(config: &str, name: &str, value: Option<&str>) -> String {
+	let definition = value.map(|value| format!("{} {}", name, value));
+	let mut lines = Vec::new();
+	let mut replaced = definition.is_none();
+	for line in config.lines() {
+		if parse_option(line).map(|(option, _)| option) == Some(name) {
+			if !replaced {
+				lines.extend(definition.as_deref());
+				replaced = true;
+			}
+		} else {
+			lines.push(line);
+		}
+	}
+	if !replaced {
+		lines.extend(definition.as_deref());
+	}
+	let mut config = lines.join("\n");
+	if !config.is_empty() {
+		config.push('\n');
+	}
+	config
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_conf() {
+		let config = "# default-key 0x0
+default-key 0xABC
+keyserver hkps://keys.openpgp.org
+default-key 0xDEF
+no-greeting";
+		assert_eq!(
+			Some(String::from("0xDEF")),
+			get_option(config, "default-key")
+		);
+		assert_eq!(Some(String::new()), get_option(config, "no-greeting"));
+		assert_eq!(None, get_option(config, "default"));
+		assert_eq!(
+			"# default-key 0x0
+default-key 0x123
+keyserver hkps://keys.openpgp.org
+no-greeting
+",
+			set_option(config, "default-key", Some("0x123"))
+		);
+		assert_eq!(
+			"armor\ndefault-key 0x123\n",
+			set_option("armor", "default-key", Some("0x123"))
+		);
+		assert_eq!(
+			"# default-key 0x0
+keyserver hkps://keys.openpgp.org
+no-greeting
+",
+			set_option(config, "default-key", None)
+		);
+		assert_eq!("", set_option("", "default-key", None));
+	}
+}
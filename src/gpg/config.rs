@@ -1,7 +1,14 @@
 use crate::args::Args;
+use crate::gpg::agent;
+use crate::gpg::conf;
+use crate::gpg::locate::{self, LocateMechanism};
 use anyhow::{anyhow, Result};
 use gpgme::{Gpgme, Protocol};
+use std::env;
+use std::fs::{self, DirBuilder};
 use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Configuration manager for GPGME.
 #[derive(Clone, Debug)]
@@ -11,17 +18,32 @@ pub struct GpgConfig {
 	/// Flag for using ASCII armored output.
 	pub armor: bool,
 	/// Default key for signing operations.
+	///
+	/// `default-key` option in `gpg.conf` is used if it is not given.
 	pub default_key: Option<String>,
+	/// Flag for refreshing the keys from their preferred keyservers.
+	pub honor_keyserver_url: bool,
+	/// Expiration time of the key signatures.
+	pub cert_expire: Option<String>,
 	/// Home directory.
 	pub home_dir: PathBuf,
 	/// Output directory.
 	pub output_dir: PathBuf,
+	/// Template of the exported file names.
+	pub export_name: Option<String>,
+	/// Path of the gpg binary.
+	pub gpg_binary: String,
+	/// Mechanisms for locating the keys (in order).
+	pub auto_key_locate: Vec<LocateMechanism>,
 }
 
 impl GpgConfig {
 	/// Constructs a new instance of `GpgConfig`.
 	pub fn new(args: &Args) -> Result<Self> {
 		let gpgme = gpgme::init();
+		if let Some(gpg_binary) = &args.gpg_binary {
+			gpgme.set_engine_path(Protocol::OpenPgp, gpg_binary)?;
+		}
 		let home_dir = PathBuf::from(if let Some(home_dir) = &args.homedir {
 			gpgme.set_engine_home_dir(Protocol::OpenPgp, home_dir)?;
 			home_dir
@@ -34,12 +56,32 @@ impl GpgConfig {
 		if let Some(output) = &args.outdir {
 			output_dir = PathBuf::from(output);
 		}
+		let gpg_binary = gpgme
+			.engine_info()?
+			.get(Protocol::OpenPgp)
+			.and_then(|engine| engine.path().ok().map(String::from))
+			.unwrap_or_else(|| String::from("gpg"));
+		let auto_key_locate = match &args.auto_key_locate {
+			Some(value) => locate::parse_mechanisms(value).map_err(|e| {
+				anyhow!("invalid mechanisms for locating keys: {}", e)
+			})?,
+			None => locate::DEFAULT_MECHANISMS.to_vec(),
+		};
 		Ok(Self {
 			inner: gpgme,
-			armor: args.armor,
-			default_key: args.default_key.as_ref().cloned(),
+			armor: Args::get_flag(args.armor),
+			default_key: args.default_key.as_ref().cloned().or_else(|| {
+				conf::read_gpg_conf(&home_dir).ok().and_then(|v| {
+					conf::get_option(&v, conf::DEFAULT_KEY_OPTION)
+				})
+			}),
+			honor_keyserver_url: Args::get_flag(args.honor_keyserver_url),
+			cert_expire: None,
 			home_dir,
 			output_dir,
+			export_name: args.export_name.as_ref().cloned(),
+			gpg_binary,
+			auto_key_locate,
 		})
 	}
 
@@ -89,6 +131,40 @@ impl GpgConfig {
 	}
 }
 
+/// Temporary GnuPG home directory for the ephemeral mode.
+///
+/// The running daemons are stopped and the directory
+/// is removed when it is dropped.
+#[derive(Debug)]
+pub struct TempHomeDir {
+	/// Path of the directory.
+	pub path: PathBuf,
+}
+
+impl TempHomeDir {
+	/// Creates a new temporary home directory.
+	pub fn new() -> Result<Self> {
+		let path = env::temp_dir().join(format!(
+			"{}-{}-{}",
+			env!("CARGO_PKG_NAME"),
+			process::id(),
+			SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis()
+		));
+		let mut builder = DirBuilder::new();
+		#[cfg(unix)]
+		std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+		builder.recursive(true).create(&path)?;
+		Ok(Self { path })
+	}
+}
+
+impl Drop for TempHomeDir {
+	fn drop(&mut self) {
+		let _ = agent::run_gpgconf(&self.path, "kill", "all");
+		let _ = fs::remove_dir_all(&self.path);
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -100,4 +176,13 @@ mod tests {
 		config.check_gpgme_version(GPGME_REQUIRED_VERSION);
 		Ok(())
 	}
+	#[test]
+	fn test_temp_home_dir() -> Result<()> {
+		let home_dir = TempHomeDir::new()?;
+		let path = home_dir.path.clone();
+		assert!(path.is_dir());
+		drop(home_dir);
+		assert!(!path.exists());
+		Ok(())
+	}
 }
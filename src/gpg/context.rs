@@ -1,14 +1,78 @@
-use crate::gpg::config::GpgConfig;
-use crate::gpg::key::{GpgKey, KeyType};
+use crate::gpg::card::{CardSlot, KeyToCard};
+use crate::gpg::cert::RevokeSig;
+use crate::gpg::clean::CleanKey;
+use crate::gpg::conf;
+use crate::gpg::config::{GpgConfig, TempHomeDir};
+use crate::gpg::demo::UserIdGenerator;
+use crate::gpg::edit::{
+	ChangePassphrase, OwnerTrust, RevokeSubkey, SetOwnerTrust,
+};
+use crate::gpg::expire::SetExpire;
+use crate::gpg::group::{self, RecipientGroup};
+use crate::gpg::handler;
+use crate::gpg::import::ImportSummary;
+use crate::gpg::key::{GpgKey, KeyData, KeyType};
+use crate::gpg::keyserver::SetKeyserver;
+use crate::gpg::prefs::{self, KeyPreferences, SetPrefs};
+use crate::gpg::revoker::AddRevoker;
 use anyhow::{anyhow, Result};
+use chrono::{Local, Utc};
+use crossterm::tty::IsTty;
 use gpgme::context::Keys;
 use gpgme::{
-	Context, Data, ExportMode, Key, KeyListMode, PinentryMode, Protocol,
+	Context, CreateKeyFlags, Data, Error as GpgmeError, ExportMode, Key,
+	KeyListMode, PinentryMode, ProgressInfo, Protocol, TofuPolicy,
 };
 use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
 use std::fs::{self, File};
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{self, Read, Write};
+use std::panic::RefUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::process::Command as OsCommand;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Template of the exported file names that is used by default.
+pub const DEFAULT_EXPORT_NAME: &str = "{type}_{keyid}";
+
+/// Files of the keyring that are copied for previewing an import.
+const KEYRING_FILES: &[&str] = &["pubring.kbx", "pubring.gpg", "trustdb.gpg"];
+
+/// Path for reading the keys from the standard input.
+pub const STDIN_PATH: &str = "-";
+
+/// Function for reporting the progress of operations.
+///
+/// It is called with the description of the current step,
+/// the current value and the total value (zero if unknown).
+type ProgressFn = dyn Fn(&str, u64, u64) + Send + Sync + RefUnwindSafe;
+
+/// Callback for reporting the progress of operations.
+#[derive(Clone)]
+pub struct ProgressCallback(pub Arc<ProgressFn>);
+
+impl Debug for ProgressCallback {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		f.write_str("ProgressCallback")
+	}
+}
+
+/// Returns the mode for listing the keys.
+///
+/// Signatures, TOFU information and secret key flags are only
+/// listed in the detailed mode since it is considerably slower.
+fn get_key_list_mode(detailed: bool) -> KeyListMode {
+	if detailed {
+		KeyListMode::LOCAL
+			| KeyListMode::SIGS
+			| KeyListMode::SIG_NOTATIONS
+			| KeyListMode::WITH_TOFU
+			| KeyListMode::WITH_SECRET
+	} else {
+		KeyListMode::LOCAL
+	}
+}
 
 /// A context for cryptographic operations.
 #[derive(Debug)]
@@ -17,21 +81,22 @@ pub struct GpgContext {
 	inner: Context,
 	/// GPGME configuration manager.
 	pub config: GpgConfig,
+	/// Progress reporter of the operations.
+	progress: Option<ProgressCallback>,
 }
 
 impl GpgContext {
 	/// Constructs a new instance of `GpgContext`.
 	pub fn new(config: GpgConfig) -> Result<Self> {
 		let mut context = Context::from_protocol(Protocol::OpenPgp)?;
-		context.set_key_list_mode(
-			KeyListMode::LOCAL | KeyListMode::SIGS | KeyListMode::SIG_NOTATIONS,
-		)?;
+		context.set_key_list_mode(get_key_list_mode(false))?;
 		context.set_armor(config.armor);
 		context.set_offline(false);
 		context.set_pinentry_mode(PinentryMode::Ask)?;
 		Ok(Self {
 			inner: context,
 			config,
+			progress: None,
 		})
 	}
 
@@ -40,24 +105,75 @@ impl GpgContext {
 		self.inner.set_armor(self.config.armor);
 	}
 
+	/// Sets the callback for reporting the progress of
+	/// import, export and key generation operations.
+	pub fn set_progress_callback(&mut self, callback: ProgressCallback) {
+		self.progress = Some(callback);
+	}
+
+	/// Runs the given operation on the GPGME context
+	/// while reporting its progress (if a callback is set).
+	fn with_progress<R>(
+		&mut self,
+		operation: impl FnOnce(&mut Context) -> R,
+	) -> R {
+		match self.progress.clone() {
+			Some(ProgressCallback(callback)) => {
+				self.inner.with_progress_reporter(
+					move |info: ProgressInfo<'_>| {
+						callback(
+							info.what().unwrap_or_default(),
+							info.current.max(0) as u64,
+							info.total.max(0) as u64,
+						)
+					},
+					operation,
+				)
+			}
+			None => operation(&mut self.inner),
+		}
+	}
+
+	/// Runs the given operation while listing the keys
+	/// in the detailed mode (see [`get_key_list_mode`]).
+	fn with_details<R>(
+		&mut self,
+		operation: impl FnOnce(&mut Self) -> Result<R>,
+	) -> Result<R> {
+		self.inner.set_key_list_mode(get_key_list_mode(true))?;
+		let result = operation(self);
+		self.inner.set_key_list_mode(get_key_list_mode(false))?;
+		result
+	}
+
 	/// Returns the configured file path.
 	///
-	/// [`output_dir`] is used for output directory.
+	/// [`output_dir`] is used for output directory and the file name
+	/// is built from [`export_name`] template (or [`DEFAULT_EXPORT_NAME`]).
 	///
 	/// [`output_dir`]: GpgConfig::output_dir
+	/// [`export_name`]: GpgConfig::export_name
 	pub fn get_output_file(
 		&self,
 		key_type: KeyType,
 		patterns: Vec<String>,
 	) -> Result<PathBuf> {
+		let key_id = if patterns.len() == 1 {
+			&patterns[0]
+		} else {
+			"out"
+		};
+		let file_name = self
+			.config
+			.export_name
+			.as_deref()
+			.unwrap_or(DEFAULT_EXPORT_NAME)
+			.replace("{type}", &key_type.to_string())
+			.replace("{keyid}", key_id)
+			.replace("{date}", &Local::today().format("%Y-%m-%d").to_string());
 		let path = self.config.output_dir.join(format!(
-			"{}_{}.{}",
-			key_type,
-			if patterns.len() == 1 {
-				&patterns[0]
-			} else {
-				"out"
-			},
+			"{}.{}",
+			file_name,
 			if self.config.armor { "asc" } else { "pgp" }
 		));
 		if !path.exists() {
@@ -109,31 +225,124 @@ impl GpgContext {
 			.collect())
 	}
 
+	/// Returns the fingerprints of all public/secret keys.
+	pub fn get_fingerprints(
+		&mut self,
+		key_type: KeyType,
+	) -> Result<Vec<String>> {
+		Ok(self
+			.get_keys_iter(key_type, None)?
+			.filter_map(|key| key.ok())
+			.filter_map(|key| key.fingerprint().ok().map(String::from))
+			.collect())
+	}
+
+	/// Returns a list of all public/secret keys while reporting
+	/// the number of the listed keys via the given function.
+	pub fn get_keys_with_progress(
+		&mut self,
+		key_type: KeyType,
+		progress: impl Fn(usize),
+	) -> Result<Vec<GpgKey>> {
+		let mut keys = Vec::new();
+		for key in self.get_keys_iter(key_type, None)?.filter_map(|v| v.ok()) {
+			keys.push(GpgKey::from(key));
+			progress(keys.len());
+		}
+		Ok(keys)
+	}
+
+	/// Returns a list of all public/secret keys matching one or more
+	/// of the specified patterns along with their details.
+	///
+	/// See [`GpgKey::has_details`].
+	pub fn get_detailed_keys(
+		&mut self,
+		key_type: KeyType,
+		patterns: Option<Vec<String>>,
+	) -> Result<Vec<GpgKey>> {
+		self.with_details(|context| context.get_keys(key_type, patterns))
+	}
+
 	/// Returns the all available keys and their types in a HashMap.
-	pub fn get_all_keys(&mut self) -> Result<HashMap<KeyType, Vec<GpgKey>>> {
+	///
+	/// Only the keys that match one or more of the specified
+	/// patterns (e.g. fingerprints of the changed keys) are listed.
+	pub fn get_all_keys(
+		&mut self,
+		patterns: Option<Vec<String>>,
+	) -> Result<HashMap<KeyType, Vec<GpgKey>>> {
 		let mut keys = HashMap::new();
-		keys.insert(KeyType::Public, self.get_keys(KeyType::Public, None)?);
-		keys.insert(KeyType::Secret, self.get_keys(KeyType::Secret, None)?);
+		keys.insert(
+			KeyType::Public,
+			self.get_keys(KeyType::Public, patterns.clone())?,
+		);
+		keys.insert(KeyType::Secret, self.get_keys(KeyType::Secret, patterns)?);
 		Ok(keys)
 	}
 
 	/// Adds the given keys to the keyring.
+	///
+	/// If `read_from_file` is set, keys are read from the given paths
+	/// ([`STDIN_PATH`] for stdin) unless they are ASCII armored blocks.
+	/// Returns the summary of the changes.
 	pub fn import_keys(
 		&mut self,
 		keys: Vec<String>,
 		read_from_file: bool,
-	) -> Result<u32> {
-		let mut imported_keys = 0;
+	) -> Result<ImportSummary> {
+		let mut summary = ImportSummary::default();
 		for key in keys {
-			if read_from_file {
+			let result = if read_from_file && key == STDIN_PATH {
+				if io::stdin().is_tty() {
+					return Err(anyhow!("stdin is a terminal"));
+				}
+				let mut input = Vec::new();
+				io::stdin().read_to_end(&mut input)?;
+				self.with_progress(|ctx| ctx.import(&input))?
+			} else if read_from_file && !handler::is_armored_block(&key) {
 				let input = File::open(key)?;
 				let mut data = Data::from_seekable_stream(input)?;
-				imported_keys += self.inner.import(&mut data)?.imported();
+				self.with_progress(|ctx| ctx.import(&mut data))?
 			} else {
-				imported_keys += self.inner.import(key)?.imported();
+				self.with_progress(|ctx| ctx.import(key))?
+			};
+			summary.add(&result);
+		}
+		log::info!("imported {} key(s)", summary.new_keys);
+		Ok(summary)
+	}
+
+	/// Imports the keys into a temporary copy of the public keyring
+	/// and returns the summary of the changes without applying them.
+	///
+	/// Keys are read from the given paths unless they are ASCII armored
+	/// blocks. Secret keys are not compared with the existing ones.
+	pub fn preview_import(&self, keys: Vec<String>) -> Result<ImportSummary> {
+		let home_dir = TempHomeDir::new()?;
+		for file in KEYRING_FILES {
+			let path = self.config.home_dir.join(file);
+			if path.exists() {
+				fs::copy(path, home_dir.path.join(file))?;
 			}
 		}
-		Ok(imported_keys)
+		let mut context = Context::from_protocol(Protocol::OpenPgp)?;
+		context
+			.set_engine_home_dir(home_dir.path.to_string_lossy().as_ref())?;
+		context.set_offline(true);
+		let mut summary = ImportSummary::default();
+		for key in keys {
+			let result = if key == STDIN_PATH {
+				return Err(anyhow!("cannot preview the keys from stdin"));
+			} else if handler::is_armored_block(&key) {
+				context.import(key)?
+			} else {
+				let mut data = Data::from_seekable_stream(File::open(key)?)?;
+				context.import(&mut data)?
+			};
+			summary.add(&result);
+		}
+		Ok(summary)
 	}
 
 	/// Returns the exported public/secret keys
@@ -148,15 +357,17 @@ impl GpgContext {
 			.get_keys_iter(key_type, patterns)?
 			.filter_map(|key| key.ok())
 			.collect::<Vec<Key>>();
-		self.inner.export_keys(
-			&keys,
-			if key_type == KeyType::Secret {
-				ExportMode::SECRET
-			} else {
-				ExportMode::empty()
-			},
-			&mut output,
-		)?;
+		self.with_progress(|ctx| {
+			ctx.export_keys(
+				&keys,
+				if key_type == KeyType::Secret {
+					ExportMode::SECRET
+				} else {
+					ExportMode::empty()
+				},
+				&mut output,
+			)
+		})?;
 		if output.is_empty() {
 			Err(anyhow!("nothing exported"))
 		} else {
@@ -165,10 +376,37 @@ impl GpgContext {
 	}
 
 	/// Exports keys and saves them to the specified/default path.
+	///
+	/// Each key is saved to a separate file if the [`export_name`]
+	/// template contains the key ID. Returns the paths of the files.
+	///
+	/// [`export_name`]: GpgConfig::export_name
 	pub fn export_keys(
 		&mut self,
 		key_type: KeyType,
 		patterns: Option<Vec<String>>,
+	) -> Result<Vec<String>> {
+		let split = self
+			.config
+			.export_name
+			.as_ref()
+			.is_some_and(|template| template.contains("{keyid}"));
+		match patterns {
+			Some(patterns) if split && patterns.len() > 1 => patterns
+				.into_iter()
+				.map(|pattern| {
+					self.export_key_file(key_type, Some(vec![pattern]))
+				})
+				.collect(),
+			patterns => Ok(vec![self.export_key_file(key_type, patterns)?]),
+		}
+	}
+
+	/// Exports the keys that match the patterns to a single file.
+	fn export_key_file(
+		&mut self,
+		key_type: KeyType,
+		patterns: Option<Vec<String>>,
 	) -> Result<String> {
 		let output = self.get_exported_keys(key_type, patterns.clone())?;
 		let path =
@@ -177,6 +415,336 @@ impl GpgContext {
 		Ok(path.to_string_lossy().to_string())
 	}
 
+	/// Exports the information about the keys in JSON format
+	/// and saves it to the specified/default path.
+	pub fn export_keys_json(
+		&mut self,
+		key_type: KeyType,
+		patterns: Option<Vec<String>>,
+		redact: bool,
+	) -> Result<String> {
+		let keys = self
+			.get_keys(key_type, patterns.clone())?
+			.iter()
+			.map(|key| key.get_data(redact))
+			.collect::<Vec<KeyData>>();
+		let path = self
+			.get_output_file(key_type, patterns.unwrap_or_default())?
+			.with_extension("json");
+		serde_json::to_writer_pretty(File::create(&path)?, &keys)?;
+		Ok(path.to_string_lossy().to_string())
+	}
+
+	/// Returns the authentication subkey of the given key
+	/// in OpenSSH public key format.
+	///
+	/// It uses `gpg --export-ssh-key` since GPGME does not support it.
+	pub fn get_ssh_key(&self, key_id: &str) -> Result<String> {
+		let output = OsCommand::new(&self.config.gpg_binary)
+			.arg("--homedir")
+			.arg(self.config.home_dir.as_os_str())
+			.arg("--export-ssh-key")
+			.arg(key_id)
+			.output()
+			.map_err(|e| anyhow!("cannot run gpg: {:?}", e))?;
+		if output.status.success() {
+			Ok(String::from_utf8(output.stdout)?.trim().to_string())
+		} else {
+			Err(anyhow!(
+				"{}",
+				String::from_utf8_lossy(&output.stderr)
+					.lines()
+					.last()
+					.unwrap_or("unknown error")
+					.trim_start_matches("gpg: ")
+			))
+		}
+	}
+
+	/// Returns the preferences (e.g. algorithms and keyserver)
+	/// of the given keys by their fingerprints.
+	///
+	/// It uses `gpg --list-sigs` since GPGME does not provide
+	/// the signature subpackets.
+	pub fn get_key_preferences(
+		&self,
+		fingerprints: &[String],
+	) -> Result<HashMap<String, KeyPreferences>> {
+		let output = OsCommand::new(&self.config.gpg_binary)
+			.arg("--homedir")
+			.arg(self.config.home_dir.as_os_str())
+			.arg("--batch")
+			.arg("--with-colons")
+			.arg("--fixed-list-mode")
+			.arg("--list-options")
+			.arg(format!(
+				"show-sig-subpackets={}",
+				prefs::PREFERENCE_SUBPACKETS
+					.iter()
+					.map(u8::to_string)
+					.collect::<Vec<String>>()
+					.join(",")
+			))
+			.arg("--list-sigs")
+			.args(fingerprints)
+			.output()
+			.map_err(|e| anyhow!("cannot run gpg: {:?}", e))?;
+		Ok(prefs::parse_key_preferences(&String::from_utf8_lossy(
+			&output.stdout,
+		)))
+	}
+
+	/// Sets the preferred keyserver of the key via `keyserver`.
+	pub fn set_preferred_keyserver(
+		&mut self,
+		key_id: String,
+		url: &str,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner
+			.interact(&key, SetKeyserver::new(url), Vec::new())?;
+		Ok(())
+	}
+
+	/// Adds the given key as a designated revoker of the key
+	/// via `addrevoker`.
+	///
+	/// Returns the fingerprint of the revoker.
+	pub fn add_revoker(
+		&mut self,
+		key_id: String,
+		revoker_id: String,
+	) -> Result<String> {
+		let revoker = self.get_key(KeyType::Public, revoker_id)?;
+		let fingerprint = revoker
+			.fingerprint()
+			.map_err(|_| anyhow!("revoker has no fingerprint"))?
+			.to_string();
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		if key.fingerprint_raw() == revoker.fingerprint_raw() {
+			return Err(anyhow!("key cannot be its own revoker"));
+		}
+		self.inner
+			.interact(&key, AddRevoker::new(&fingerprint), Vec::new())?;
+		Ok(fingerprint)
+	}
+
+	/// Sets the expiration time of the key (and its subkeys
+	/// if they are included) via `expire`.
+	///
+	/// The expiration time is given as a date or `0` for no expiration.
+	pub fn set_expire(
+		&mut self,
+		key_id: String,
+		expiry: &str,
+		subkeys: bool,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner.interact(
+			&key,
+			SetExpire::new(expiry, subkeys),
+			Vec::new(),
+		)?;
+		Ok(())
+	}
+
+	/// Returns the number of the user ID signatures of the key.
+	fn get_signature_count(&mut self, key_id: &str) -> Result<usize> {
+		self.with_details(|gpgme| {
+			Ok(gpgme
+				.get_key(KeyType::Public, key_id.to_string())?
+				.user_ids()
+				.map(|user| user.signatures().count())
+				.sum())
+		})
+	}
+
+	/// Cleans the key via `clean` (or `minimize` if set).
+	///
+	/// Returns the number of the removed signatures.
+	pub fn clean_key(
+		&mut self,
+		key_id: String,
+		minimize: bool,
+	) -> Result<usize> {
+		let signatures = self.get_signature_count(&key_id)?;
+		let key = self.get_key(KeyType::Public, key_id.to_string())?;
+		self.inner
+			.interact(&key, CleanKey::new(minimize), Vec::new())?;
+		Ok(signatures.saturating_sub(self.get_signature_count(&key_id)?))
+	}
+
+	/// Sets the algorithm preferences of the key via `setpref`.
+	pub fn set_key_preferences(
+		&mut self,
+		key_id: String,
+		preferences: &str,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner
+			.interact(&key, SetPrefs::new(preferences), Vec::new())?;
+		Ok(())
+	}
+
+	/// Exports the given key in OpenSSH public key format
+	/// and saves it to the output directory.
+	pub fn export_ssh_key(&self, key_id: &str) -> Result<String> {
+		let ssh_key = self.get_ssh_key(key_id)?;
+		let path = self.config.output_dir.join(format!("ssh_{}.pub", key_id));
+		fs::create_dir_all(path.parent().expect("path has no parent"))?;
+		writeln!(File::create(&path)?, "{}", ssh_key)?;
+		Ok(path.to_string_lossy().to_string())
+	}
+
+	/// Exports the given public key to the given trash directory
+	/// before it is deleted.
+	///
+	/// Returns the path of the exported file.
+	pub fn export_to_trash(
+		&mut self,
+		trash_dir: &Path,
+		key_id: String,
+	) -> Result<String> {
+		let output = self.get_exported_keys(
+			KeyType::Public,
+			Some(vec![key_id.to_string()]),
+		)?;
+		let path = trash_dir.join(format!(
+			"{}_{}_{}.{}",
+			KeyType::Public,
+			key_id,
+			Utc::now().timestamp(),
+			if self.config.armor { "asc" } else { "pgp" }
+		));
+		File::create(&path)?.write_all(&output)?;
+		Ok(path.to_string_lossy().to_string())
+	}
+
+	/// Exports the secret keys encrypted with a passphrase
+	/// and saves them to the output directory.
+	///
+	/// Returns the path of the encrypted file.
+	pub fn export_keys_symmetric(
+		&mut self,
+		patterns: Option<Vec<String>>,
+	) -> Result<String> {
+		let input =
+			self.get_exported_keys(KeyType::Secret, patterns.clone())?;
+		let mut output = Vec::new();
+		self.with_progress(|ctx| ctx.encrypt_symmetric(&input, &mut output))?;
+		let mut path = self
+			.get_output_file(KeyType::Secret, patterns.unwrap_or_default())?
+			.into_os_string();
+		path.push(".gpg");
+		File::create(&path)?.write_all(&output)?;
+		Ok(path.to_string_lossy().to_string())
+	}
+
+	/// Encrypts the given file for the recipients
+	/// and saves it to the output directory.
+	///
+	/// Names of the recipient groups are expanded to their members.
+	///
+	/// Returns the path of the encrypted file.
+	pub fn encrypt_file(
+		&mut self,
+		file: &str,
+		recipients: Vec<String>,
+	) -> Result<String> {
+		let recipients = group::expand_groups(&self.get_groups()?, recipients);
+		let keys = self
+			.get_keys_iter(KeyType::Public, Some(recipients))?
+			.filter_map(|key| key.ok())
+			.filter(|key| key.can_encrypt())
+			.collect::<Vec<Key>>();
+		if keys.is_empty() {
+			return Err(anyhow!("no valid recipients"));
+		}
+		let input = fs::read(file)?;
+		let mut output = Vec::new();
+		self.with_progress(|ctx| ctx.encrypt(&keys, &input, &mut output))?;
+		let path = self.config.output_dir.join(format!(
+			"{}.{}",
+			Path::new(file)
+				.file_name()
+				.map(|v| v.to_string_lossy().to_string())
+				.unwrap_or_else(|| String::from("out")),
+			if self.config.armor { "asc" } else { "gpg" }
+		));
+		fs::create_dir_all(path.parent().expect("path has no parent"))?;
+		File::create(&path)?.write_all(&output)?;
+		Ok(path.to_string_lossy().to_string())
+	}
+
+	/// Returns the recipient groups that are defined in `gpg.conf`.
+	pub fn get_groups(&self) -> Result<Vec<RecipientGroup>> {
+		Ok(group::get_groups(&conf::read_gpg_conf(
+			&self.config.home_dir,
+		)?))
+	}
+
+	/// Defines the recipient group in `gpg.conf`.
+	///
+	/// The existing definition is replaced and the group
+	/// is removed if no members are given.
+	pub fn set_group(&self, name: &str, members: &[String]) -> Result<()> {
+		let config = conf::read_gpg_conf(&self.config.home_dir)?;
+		conf::write_gpg_conf(
+			&self.config.home_dir,
+			&group::set_group(&config, name, members),
+		)
+	}
+
+	/// Sets the default key in `gpg.conf`.
+	pub fn set_default_key(&mut self, key_id: &str) -> Result<()> {
+		let config = conf::read_gpg_conf(&self.config.home_dir)?;
+		conf::write_gpg_conf(
+			&self.config.home_dir,
+			&conf::set_option(&config, conf::DEFAULT_KEY_OPTION, Some(key_id)),
+		)?;
+		self.config.default_key = Some(key_id.to_string());
+		Ok(())
+	}
+
+	/// Locates the key of the given email address.
+	///
+	/// The local keyring is searched first and the key is
+	/// retrieved via the configured methods (e.g. WKD) if not found.
+	pub fn locate_key(&mut self, email: String) -> Result<GpgKey> {
+		let key_list_mode = self.inner.key_list_mode();
+		let key = self.inner.locate_key(email.as_str());
+		log::debug!(
+			"locating key of {}: {:?}",
+			email,
+			key.as_ref().map(|k| k.id())
+		);
+		self.inner.set_key_list_mode(key_list_mode)?;
+		Ok(GpgKey::from(
+			key.map_err(|e| anyhow!("key not found: {}", e))?,
+		))
+	}
+
+	/// Generates the given number of keys with fake user IDs.
+	///
+	/// Keys are not protected with a passphrase and every
+	/// other key expires in a year for showing the expiry dates.
+	pub fn generate_demo_keys(&mut self, count: usize) -> Result<usize> {
+		let user_ids = UserIdGenerator::new(count as u64).generate(count);
+		for (i, user_id) in user_ids.iter().enumerate() {
+			self.inner.create_key_with_flags(
+				user_id.as_str(),
+				"future-default",
+				if i % 2 == 0 {
+					None
+				} else {
+					Some(SystemTime::now() + Duration::from_secs(31_536_000))
+				},
+				CreateKeyFlags::NOPASSWD,
+			)?;
+		}
+		Ok(user_ids.len())
+	}
+
 	/// Sends the given key to the default keyserver.
 	pub fn send_key(&mut self, key_id: String) -> Result<String> {
 		let keys = self
@@ -186,13 +754,198 @@ impl GpgContext {
 		if let Some(key) = &keys.first() {
 			self.inner
 				.export_keys_extern(vec![*key], ExportMode::EXTERN)
-				.map_err(|e| anyhow!("failed to send key(s): {:?}", e))?;
+				.map_err(|e| {
+					log::error!(
+						"failed to send {:?}: {:?} (source: {:?})",
+						key.id(),
+						e,
+						e.source()
+					);
+					anyhow::Error::new(e)
+						.context(format!("failed to send key(s): {:?}", e))
+				})?;
 			Ok(key.id().unwrap_or_default().to_string())
 		} else {
 			Err(anyhow!("key not found"))
 		}
 	}
 
+	/// Moves the given subkey (0 for the primary key)
+	/// of the secret key to the specified slot of the card.
+	///
+	/// The secret key on the disk is replaced by a stub.
+	pub fn move_to_card(
+		&mut self,
+		key_id: String,
+		subkey: usize,
+		slot: CardSlot,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner
+			.interact(&key, KeyToCard::new(subkey, slot), Vec::new())
+			.map_err(|e| {
+				if e.code() == GpgmeError::CONFLICT.code() {
+					anyhow!("{} slot of the card is not empty", slot)
+				} else if e.code() == GpgmeError::WRONG_KEY_USAGE.code() {
+					anyhow!("key cannot be stored in the {} slot", slot)
+				} else {
+					anyhow::Error::new(e)
+				}
+			})
+	}
+
+	/// Revokes the certifications that are made by the
+	/// signing key on the specified key via `revsig`.
+	///
+	/// Returns the number of revoked certifications.
+	pub fn revoke_sig(
+		&mut self,
+		key_id: String,
+		signing_key: String,
+	) -> Result<usize> {
+		let key = self
+			.with_details(|context| context.get_key(KeyType::Public, key_id))?;
+		let signer = GpgKey::from(self.get_key(KeyType::Secret, signing_key)?);
+		let signer_id = signer.get_id().replacen("0x", "", 1);
+		let secret_ids = self
+			.get_keys(KeyType::Secret, None)?
+			.iter()
+			.map(|key| key.get_id().replacen("0x", "", 1))
+			.collect::<Vec<String>>();
+		let answers = GpgKey::from(key.clone())
+			.get_certification_signers()
+			.into_iter()
+			.filter(|id| secret_ids.contains(id))
+			.map(|id| id == signer_id)
+			.collect::<Vec<bool>>();
+		let count = answers.iter().filter(|v| **v).count();
+		if count == 0 {
+			return Err(anyhow!(
+				"no certifications found by {}",
+				signer.get_id()
+			));
+		}
+		self.inner
+			.interact(&key, RevokeSig::new(answers), Vec::new())?;
+		Ok(count)
+	}
+
+	/// Sets the TOFU policy of the key.
+	pub fn set_tofu_policy(
+		&mut self,
+		key_id: String,
+		policy: TofuPolicy,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Public, key_id)?;
+		self.inner.change_key_tofu_policy(&key, policy)?;
+		Ok(())
+	}
+
+	/// Adds a user ID to the key.
+	pub fn add_user_id(&mut self, key_id: String, user_id: &str) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner.add_uid(&key, user_id)?;
+		Ok(())
+	}
+
+	/// Revokes the user ID of the key.
+	pub fn revoke_user_id(
+		&mut self,
+		key_id: String,
+		user_id: &str,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner.revoke_uid(&key, user_id)?;
+		Ok(())
+	}
+
+	/// Sets the user ID as the primary user ID of the key.
+	pub fn set_primary_user_id(
+		&mut self,
+		key_id: String,
+		user_id: &str,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner
+			.set_uid_flag(&key, user_id, "primary", None::<&str>)?;
+		Ok(())
+	}
+
+	/// Generates a new key pair that expires after the given duration.
+	///
+	/// Returns the fingerprint of the generated key.
+	pub fn generate_key(
+		&mut self,
+		user_id: &str,
+		algo: &str,
+		flags: CreateKeyFlags,
+		expires: Option<Duration>,
+	) -> Result<String> {
+		// GPGME takes the expiration time as seconds from now.
+		let result = self.with_progress(|ctx| {
+			ctx.create_key_with_flags(
+				user_id,
+				algo,
+				expires.map(|duration| UNIX_EPOCH + duration),
+				flags,
+			)
+		})?;
+		Ok(result.fingerprint().unwrap_or_default().to_string())
+	}
+
+	/// Adds a subkey to the key that expires after the given duration.
+	///
+	/// Returns the fingerprint of the generated subkey.
+	pub fn add_subkey(
+		&mut self,
+		key_id: String,
+		algo: &str,
+		flags: CreateKeyFlags,
+		expires: Option<Duration>,
+	) -> Result<String> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		// GPGME takes the expiration time as seconds from now.
+		let result = self.inner.create_subkey_with_flags(
+			&key,
+			algo,
+			expires.map(|duration| UNIX_EPOCH + duration),
+			flags,
+		)?;
+		Ok(result.fingerprint().unwrap_or_default().to_string())
+	}
+
+	/// Changes the passphrase of the key via `passwd`.
+	pub fn change_passphrase(&mut self, key_id: String) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner
+			.interact(&key, ChangePassphrase::default(), Vec::new())?;
+		Ok(())
+	}
+
+	/// Revokes the given subkey (1-based) of the key via `revkey`.
+	pub fn revoke_subkey(
+		&mut self,
+		key_id: String,
+		subkey: usize,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Secret, key_id)?;
+		self.inner
+			.interact(&key, RevokeSubkey::new(subkey), Vec::new())?;
+		Ok(())
+	}
+
+	/// Sets the owner trust of the key via `trust`.
+	pub fn set_owner_trust(
+		&mut self,
+		key_id: String,
+		trust: OwnerTrust,
+	) -> Result<()> {
+		let key = self.get_key(KeyType::Public, key_id)?;
+		self.inner
+			.interact(&key, SetOwnerTrust::new(trust), Vec::new())?;
+		Ok(())
+	}
+
 	/// Deletes the specified public/secret key.
 	///
 	/// Searches the keyring for finding the specified
@@ -202,6 +955,7 @@ impl GpgContext {
 		key_type: KeyType,
 		key_id: String,
 	) -> Result<()> {
+		log::debug!("deleting {} key: {}", key_type, key_id);
 		match self.get_key(key_type, key_id) {
 			Ok(key) => match key_type {
 				KeyType::Public => {
@@ -243,7 +997,7 @@ mod tests {
 		context.config.armor = true;
 		context.apply_config();
 		assert_eq!(true, context.config.armor);
-		let keys = context.get_all_keys()?;
+		let keys = context.get_all_keys(None)?;
 		let key_count = keys.get(&KeyType::Public).unwrap().len();
 		assert!(context
 			.get_key(
@@ -253,13 +1007,30 @@ mod tests {
 			.is_ok());
 		let key_id = keys.get(&KeyType::Public).unwrap()[1].get_id();
 		assert!(context.get_key(KeyType::Public, key_id.clone()).is_ok());
+		assert!(!keys.get(&KeyType::Public).unwrap()[1].has_details());
+		assert!(context
+			.get_detailed_keys(KeyType::Public, Some(vec![key_id.clone()]))?
+			.iter()
+			.all(GpgKey::has_details));
 		assert_eq!(
 			context.config.output_dir.join(String::from("sec_0x0.asc")),
 			context
 				.get_output_file(KeyType::Secret, vec![String::from("0x0")])
 				.unwrap()
 		);
-		let output_file = context.export_keys(KeyType::Public, None)?;
+		context.config.export_name = Some(String::from("{keyid}-{type}"));
+		assert_eq!(
+			context.config.output_dir.join(String::from("0x0-sec.asc")),
+			context
+				.get_output_file(KeyType::Secret, vec![String::from("0x0")])
+				.unwrap()
+		);
+		context.config.export_name = None;
+		let output_file = context.export_keys(KeyType::Public, None)?.remove(0);
+		let trash_dir = TempHomeDir::new()?;
+		let trash_file =
+			context.export_to_trash(&trash_dir.path, key_id.clone())?;
+		assert!(trash_file.contains(&key_id));
 		context.delete_key(KeyType::Public, key_id)?;
 		assert_eq!(
 			key_count - 1,
@@ -269,6 +1040,7 @@ mod tests {
 			1,
 			context
 				.import_keys(vec![output_file.clone()], true)
+				.map(|summary| summary.new_keys)
 				.unwrap_or_default()
 		);
 		assert_eq!(
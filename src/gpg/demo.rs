@@ -0,0 +1,86 @@
+/// Number of keys that are generated in demo mode.
+pub const DEMO_KEY_COUNT: usize = 8;
+
+/// First names of the demo users.
+const FIRST_NAMES: &[&str] = &[
+	"Alice", "Bob", "Carol", "Dave", "Erin", "Frank", "Grace", "Heidi", "Ivan",
+	"Judy", "Mallory", "Niaj", "Olivia", "Peggy", "Rupert", "Sybil", "Trent",
+	"Victor", "Walter", "Zoe",
+];
+
+/// Last names of the demo users.
+const LAST_NAMES: &[&str] = &[
+	"Anderson", "Baker", "Clarke", "Dawson", "Ellis", "Fischer", "Garcia",
+	"Hughes", "Ito", "Jensen", "Kowalski", "Larsen", "Moreau", "Novak",
+	"Okafor", "Petrov", "Quinn", "Rossi", "Silva", "Tanaka",
+];
+
+/// Domains of the demo email addresses.
+const DOMAINS: &[&str] = &["example.org", "example.com", "example.net"];
+
+/// Generator of the fake user IDs for demo mode.
+///
+/// It is a linear congruential generator so that the
+/// same seed always produces the same user IDs.
+#[derive(Clone, Debug)]
+pub struct UserIdGenerator {
+	/// Current state of the generator.
+	state: u64,
+}
+
+impl UserIdGenerator {
+	/// Constructs a new instance of `UserIdGenerator`.
+	pub fn new(seed: u64) -> Self {
+		Self { state: seed }
+	}
+
+	/// Returns the next pseudo-random index that is less than `len`.
+	fn next_index(&mut self, len: usize) -> usize {
+		self.state = self
+			.state
+			.wrapping_mul(6364136223846793005)
+			.wrapping_add(1442695040888963407);
+		((self.state >> 33) % len as u64) as usize
+	}
+
+	/// Returns the given number of unique user IDs.
+	pub fn generate(&mut self, count: usize) -> Vec<String> {
+		let count = count.min(FIRST_NAMES.len() * LAST_NAMES.len());
+		let mut user_ids = Vec::new();
+		while user_ids.len() < count {
+			let first_name = FIRST_NAMES[self.next_index(FIRST_NAMES.len())];
+			let last_name = LAST_NAMES[self.next_index(LAST_NAMES.len())];
+			let domain = DOMAINS[self.next_index(DOMAINS.len())];
+			let user_id = format!(
+				"{} {} <{}.{}@{}>",
+				first_name,
+				last_name,
+				first_name.to_lowercase(),
+				last_name.to_lowercase(),
+				domain
+			);
+			if !user_ids.contains(&user_id) {
+				user_ids.push(user_id);
+			}
+		}
+		user_ids
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::{assert_eq, assert_ne};
+	#[test]
+	fn test_gpg_demo() {
+		let user_ids = UserIdGenerator::new(1).generate(DEMO_KEY_COUNT);
+		assert_eq!(DEMO_KEY_COUNT, user_ids.len());
+		assert_eq!(user_ids, UserIdGenerator::new(1).generate(DEMO_KEY_COUNT));
+		assert_ne!(user_ids, UserIdGenerator::new(2).generate(DEMO_KEY_COUNT));
+		for user_id in user_ids {
+			assert!(user_id.contains(" <") && user_id.ends_with('>'));
+			assert!(DOMAINS.iter().any(|domain| user_id.contains(domain)));
+		}
+		assert_eq!(50, UserIdGenerator::new(1).generate(50).len());
+	}
+}
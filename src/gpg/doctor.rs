@@ -0,0 +1,204 @@
+use crate::gpg::key::{GpgKey, KeyType};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+/// Kind of a keyring issue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IssueKind {
+	/// User ID is used by more than one key.
+	DuplicateUserId,
+	/// Secret key does not have a matching public key.
+	MissingPublicKey,
+	/// Public key claims a secret key that is not listed.
+	MissingSecretKey,
+	/// Card stub of an expired or revoked subkey.
+	StaleCardStub,
+}
+
+impl Display for IssueKind {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::DuplicateUserId => "duplicate",
+				Self::MissingPublicKey => "no public",
+				Self::MissingSecretKey => "no secret",
+				Self::StaleCardStub => "stale stub",
+			}
+		)
+	}
+}
+
+/// Issue that is found in the keyring.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyringIssue {
+	/// Kind of the issue.
+	pub kind: IssueKind,
+	/// Type of the offending key.
+	pub key_type: KeyType,
+	/// Fingerprint of the offending key.
+	pub fingerprint: String,
+	/// Description of the issue.
+	pub description: String,
+}
+
+impl Display for KeyringIssue {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "[{}] {}", self.kind, self.description)
+	}
+}
+
+impl KeyringIssue {
+	/// Checks the given keys for the issues.
+	///
+	/// * duplicate user IDs across different keys
+	/// * secret keys without matching public keys (and vice versa)
+	/// * card stubs of the expired/revoked subkeys
+	pub fn check(
+		public_keys: &[Arc<GpgKey>],
+		secret_keys: &[Arc<GpgKey>],
+	) -> Vec<Self> {
+		let mut issues = Vec::new();
+		for (fingerprint, user_id, other_keys) in find_duplicate_user_ids(
+			public_keys
+				.iter()
+				.map(|key| (key.get_fingerprint(), key.get_user_ids())),
+		) {
+			issues.push(Self {
+				kind: IssueKind::DuplicateUserId,
+				key_type: KeyType::Public,
+				description: format!(
+					"{} is also used by {}",
+					user_id,
+					other_keys
+						.iter()
+						.map(|v| format!(
+							"0x{}",
+							&v[v.len().saturating_sub(16)..]
+						))
+						.collect::<Vec<String>>()
+						.join(", ")
+				),
+				fingerprint,
+			});
+		}
+		let has_key = |keys: &[Arc<GpgKey>], key: &GpgKey| {
+			keys.iter()
+				.any(|k| k.get_fingerprint() == key.get_fingerprint())
+		};
+		for key in secret_keys {
+			if !has_key(public_keys, key) {
+				issues.push(Self {
+					kind: IssueKind::MissingPublicKey,
+					key_type: KeyType::Secret,
+					fingerprint: key.get_fingerprint(),
+					description: format!(
+						"{} {} has no public key",
+						key.get_id(),
+						key.get_user_id()
+					),
+				});
+			}
+			for subkey_id in key.get_stale_card_stubs() {
+				issues.push(Self {
+					kind: IssueKind::StaleCardStub,
+					key_type: KeyType::Secret,
+					fingerprint: key.get_fingerprint(),
+					description: format!(
+						"{} of {} is expired/revoked",
+						subkey_id,
+						key.get_id()
+					),
+				});
+			}
+		}
+		for key in public_keys {
+			if key.has_secret() && !has_key(secret_keys, key) {
+				issues.push(Self {
+					kind: IssueKind::MissingSecretKey,
+					key_type: KeyType::Public,
+					fingerprint: key.get_fingerprint(),
+					description: format!(
+						"{} {} has no listed secret key",
+						key.get_id(),
+						key.get_user_id()
+					),
+				});
+			}
+		}
+		issues
+	}
+}
+
+/// Returns the user IDs that are used by more than one key.
+///
+/// Each item consists of the key (fingerprint), the user ID
+/// and the other keys that use the same user ID.
+fn find_duplicate_user_ids<I: IntoIterator<Item = (String, Vec<String>)>>(
+	keys: I,
+) -> Vec<(String, String, Vec<String>)> {
+	let keys = keys.into_iter().collect::<Vec<(String, Vec<String>)>>();
+	let mut duplicates = Vec::new();
+	for (fingerprint, user_ids) in &keys {
+		for user_id in user_ids {
+			let other_keys = keys
+				.iter()
+				.filter(|(other, other_user_ids)| {
+					other != fingerprint
+						&& other_user_ids.iter().any(|other_user_id| {
+							other_user_id.to_lowercase()
+								== user_id.to_lowercase()
+						})
+				})
+				.map(|(other, _)| other.to_string())
+				.collect::<Vec<String>>();
+			if !other_keys.is_empty() {
+				duplicates.push((
+					fingerprint.to_string(),
+					user_id.to_string(),
+					other_keys,
+				));
+			}
+		}
+	}
+	duplicates
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_doctor() {
+		assert_eq!(
+			vec![
+				(
+					String::from("A"),
+					String::from("x <x@test>"),
+					vec![String::from("C")]
+				),
+				(
+					String::from("C"),
+					String::from("X <x@test>"),
+					vec![String::from("A")]
+				),
+			],
+			find_duplicate_user_ids(vec![
+				(String::from("A"), vec![String::from("x <x@test>")]),
+				(String::from("B"), vec![String::from("y <y@test>")]),
+				(String::from("C"), vec![String::from("X <x@test>")]),
+			])
+		);
+		assert_eq!(
+			"[stale stub] 0x1 of 0x2 is expired/revoked",
+			KeyringIssue {
+				kind: IssueKind::StaleCardStub,
+				key_type: KeyType::Secret,
+				fingerprint: String::new(),
+				description: String::from("0x1 of 0x2 is expired/revoked"),
+			}
+			.to_string()
+		);
+	}
+}
@@ -0,0 +1,320 @@
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{CreateKeyFlags, Error, InteractionStatus};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Write;
+use std::str::FromStr;
+
+/// Returns the flags for creating a subkey with the given usage.
+///
+/// Default usage of the algorithm is used for `default`.
+pub fn get_subkey_flags(usage: &str) -> Option<CreateKeyFlags> {
+	match usage {
+		"default" => Some(CreateKeyFlags::empty()),
+		"sign" | "sig" => Some(CreateKeyFlags::SIGN),
+		"encrypt" | "encr" | "enc" => Some(CreateKeyFlags::ENCR),
+		"auth" => Some(CreateKeyFlags::AUTH),
+		_ => None,
+	}
+}
+
+/// Owner trust levels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OwnerTrust {
+	/// I don't know or won't say.
+	Unknown,
+	/// I do NOT trust.
+	Never,
+	/// I trust marginally.
+	Marginal,
+	/// I trust fully.
+	Full,
+	/// I trust ultimately.
+	Ultimate,
+}
+
+impl Display for OwnerTrust {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Unknown => "unknown",
+				Self::Never => "never",
+				Self::Marginal => "marginal",
+				Self::Full => "full",
+				Self::Ultimate => "ultimate",
+			}
+		)
+	}
+}
+
+impl FromStr for OwnerTrust {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"unknown" | "undefined" | "1" => Ok(Self::Unknown),
+			"never" | "2" => Ok(Self::Never),
+			"marginal" | "3" => Ok(Self::Marginal),
+			"full" | "4" => Ok(Self::Full),
+			"ultimate" | "5" => Ok(Self::Ultimate),
+			_ => Err(()),
+		}
+	}
+}
+
+impl OwnerTrust {
+	/// All of the trust levels.
+	pub const ALL: &'static [Self] = &[
+		Self::Unknown,
+		Self::Never,
+		Self::Marginal,
+		Self::Full,
+		Self::Ultimate,
+	];
+
+	/// Returns the number of the level in `trust` menu.
+	pub fn get_number(&self) -> u8 {
+		match self {
+			Self::Unknown => 1,
+			Self::Never => 2,
+			Self::Marginal => 3,
+			Self::Full => 4,
+			Self::Ultimate => 5,
+		}
+	}
+}
+
+/// Interactor for setting the owner trust via `trust`.
+#[derive(Debug)]
+pub struct SetOwnerTrust {
+	/// Trust level.
+	trust: OwnerTrust,
+	/// Is the `trust` command sent?
+	sent: bool,
+	/// Is the `quit` command sent?
+	quit: bool,
+}
+
+impl SetOwnerTrust {
+	/// Constructs a new instance of `SetOwnerTrust`.
+	pub fn new(trust: OwnerTrust) -> Self {
+		Self {
+			trust,
+			sent: false,
+			quit: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from("trust"))
+			}
+			"keyedit.prompt" if !self.quit => {
+				self.quit = true;
+				Ok(String::from("quit"))
+			}
+			"edit_ownertrust.value" => Ok(self.trust.get_number().to_string()),
+			"edit_ownertrust.set_ultimate.okay" => Ok(String::from("y")),
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for SetOwnerTrust {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+/// Interactor for changing the passphrase via `passwd`.
+///
+/// The passphrases are asked by the pinentry.
+#[derive(Debug, Default)]
+pub struct ChangePassphrase {
+	/// Is the `passwd` command sent?
+	sent: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl ChangePassphrase {
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from("passwd"))
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"keyedit.save.okay" => Ok(String::from("y")),
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for ChangePassphrase {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+/// Interactor for revoking a subkey via `revkey`.
+///
+/// No reason is specified for the revocation.
+#[derive(Debug)]
+pub struct RevokeSubkey {
+	/// Index of the subkey.
+	subkey: usize,
+	/// Is the subkey selected?
+	selected: bool,
+	/// Is the `revkey` command sent?
+	sent: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl RevokeSubkey {
+	/// Constructs a new instance of `RevokeSubkey`.
+	pub fn new(subkey: usize) -> Self {
+		Self {
+			subkey,
+			selected: false,
+			sent: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.selected => {
+				self.selected = true;
+				Ok(format!("key {}", self.subkey))
+			}
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from("revkey"))
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"ask_revocation_reason.code" => Ok(String::from("0")),
+			"ask_revocation_reason.text" => Ok(String::new()),
+			"keyedit.revoke.subkey.okay"
+			| "ask_revocation_reason.okay"
+			| "keyedit.save.okay" => Ok(String::from("y")),
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for RevokeSubkey {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_edit() {
+		let mut interactor = SetOwnerTrust::new(OwnerTrust::Ultimate);
+		for (prompt, response) in &[
+			("keyedit.prompt", "trust"),
+			("edit_ownertrust.value", "5"),
+			("edit_ownertrust.set_ultimate.okay", "y"),
+			("keyedit.prompt", "quit"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+		let mut interactor = ChangePassphrase::default();
+		for (prompt, response) in &[
+			("keyedit.prompt", "passwd"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+		let mut interactor = RevokeSubkey::new(2);
+		for (prompt, response) in &[
+			("keyedit.prompt", "key 2"),
+			("keyedit.prompt", "revkey"),
+			("keyedit.revoke.subkey.okay", "y"),
+			("ask_revocation_reason.code", "0"),
+			("ask_revocation_reason.text", ""),
+			("ask_revocation_reason.okay", "y"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+		for trust in OwnerTrust::ALL {
+			assert_eq!(Ok(*trust), OwnerTrust::from_str(&trust.to_string()));
+		}
+		assert_eq!(Ok(OwnerTrust::Marginal), OwnerTrust::from_str("3"));
+		assert!(OwnerTrust::from_str("xyz").is_err());
+		assert_eq!(Some(CreateKeyFlags::ENCR), get_subkey_flags("encr"));
+		assert_eq!(None, get_subkey_flags("xyz"));
+	}
+}
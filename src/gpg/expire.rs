@@ -0,0 +1,103 @@
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::collections::VecDeque;
+use std::io::Write;
+
+/// Interactor for setting the expiration time via `expire`.
+///
+/// Subkeys are selected with `key *` if they are included.
+#[derive(Debug)]
+pub struct SetExpire {
+	/// Expiration time (`0` for no expiration).
+	expiry: String,
+	/// Remaining commands to send.
+	commands: VecDeque<&'static str>,
+}
+
+impl SetExpire {
+	/// Constructs a new instance of `SetExpire`.
+	pub fn new(expiry: &str, subkeys: bool) -> Self {
+		let mut commands = VecDeque::from(vec!["expire"]);
+		if subkeys {
+			commands.extend(&["key *", "expire"]);
+		}
+		commands.push_back("save");
+		Self {
+			expiry: expiry.to_string(),
+			commands,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" => self
+				.commands
+				.pop_front()
+				.map(String::from)
+				.ok_or(Error::UNEXPECTED),
+			"keygen.valid" => Ok(self.expiry.to_string()),
+			"keyedit.expire_multiple_subkeys.okay" | "keyedit.save.okay" => {
+				Ok(String::from("y"))
+			}
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for SetExpire {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_expire() {
+		let mut interactor = SetExpire::new("2023-01-01", true);
+		for (prompt, response) in &[
+			("keyedit.prompt", "expire"),
+			("keygen.valid", "2023-01-01"),
+			("keyedit.prompt", "key *"),
+			("keyedit.prompt", "expire"),
+			("keyedit.expire_multiple_subkeys.okay", "y"),
+			("keygen.valid", "2023-01-01"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+		let mut interactor = SetExpire::new("0", false);
+		for (prompt, response) in &[
+			("keyedit.prompt", "expire"),
+			("keygen.valid", "0"),
+			("keyedit.prompt", "save"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+	}
+}
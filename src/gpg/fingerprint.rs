@@ -0,0 +1,648 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+/// PGP word list for the bytes in even positions (two syllables).
+const EVEN_WORDS: [&str; 256] = [
+	"aardvark",
+	"absurd",
+	"accrue",
+	"acme",
+	"adrift",
+	"adult",
+	"afflict",
+	"ahead",
+	"aimless",
+	"Algol",
+	"allow",
+	"alone",
+	"ammo",
+	"ancient",
+	"apple",
+	"artist",
+	"assume",
+	"Athens",
+	"atlas",
+	"Aztec",
+	"baboon",
+	"backfield",
+	"backward",
+	"banjo",
+	"beaming",
+	"bedlamp",
+	"beehive",
+	"beeswax",
+	"befriend",
+	"Belfast",
+	"berserk",
+	"billiard",
+	"bison",
+	"blackjack",
+	"blockade",
+	"blowtorch",
+	"bluebird",
+	"bombast",
+	"bookshelf",
+	"brackish",
+	"breadline",
+	"breakup",
+	"brickyard",
+	"briefcase",
+	"Burbank",
+	"button",
+	"buzzard",
+	"cement",
+	"chairlift",
+	"chatter",
+	"checkup",
+	"chisel",
+	"choking",
+	"chopper",
+	"Christmas",
+	"clamshell",
+	"classic",
+	"classroom",
+	"cleanup",
+	"clockwork",
+	"cobra",
+	"commence",
+	"concert",
+	"cowbell",
+	"crackdown",
+	"cranky",
+	"crowfoot",
+	"crucial",
+	"crumpled",
+	"crusade",
+	"cubic",
+	"dashboard",
+	"deadbolt",
+	"deckhand",
+	"dogsled",
+	"dragnet",
+	"drainage",
+	"dreadful",
+	"drifter",
+	"dropper",
+	"drumbeat",
+	"drunken",
+	"Dupont",
+	"dwelling",
+	"eating",
+	"edict",
+	"egghead",
+	"eightball",
+	"endorse",
+	"endow",
+	"enlist",
+	"erase",
+	"escape",
+	"exceed",
+	"eyeglass",
+	"eyetooth",
+	"facial",
+	"fallout",
+	"flagpole",
+	"flatfoot",
+	"flytrap",
+	"fracture",
+	"framework",
+	"freedom",
+	"frighten",
+	"gazelle",
+	"Geiger",
+	"glitter",
+	"glucose",
+	"goggles",
+	"goldfish",
+	"gremlin",
+	"guidance",
+	"hamlet",
+	"highchair",
+	"hockey",
+	"indoors",
+	"indulge",
+	"inverse",
+	"involve",
+	"island",
+	"jawbone",
+	"keyboard",
+	"kickoff",
+	"kiwi",
+	"klaxon",
+	"locale",
+	"lockup",
+	"merit",
+	"minnow",
+	"miser",
+	"Mohawk",
+	"mural",
+	"music",
+	"necklace",
+	"Neptune",
+	"newborn",
+	"nightbird",
+	"Oakland",
+	"obtuse",
+	"offload",
+	"optic",
+	"orca",
+	"payday",
+	"peachy",
+	"pheasant",
+	"physique",
+	"playhouse",
+	"Pluto",
+	"preclude",
+	"prefer",
+	"preshrunk",
+	"printer",
+	"prowler",
+	"pupil",
+	"puppy",
+	"python",
+	"quadrant",
+	"quiver",
+	"quota",
+	"ragtime",
+	"ratchet",
+	"rebirth",
+	"reform",
+	"regain",
+	"reindeer",
+	"rematch",
+	"repay",
+	"retouch",
+	"revenge",
+	"reward",
+	"rhythm",
+	"ribcage",
+	"ringbolt",
+	"robust",
+	"rocker",
+	"ruffled",
+	"sailboat",
+	"sawdust",
+	"scallion",
+	"scenic",
+	"scorecard",
+	"Scotland",
+	"seabird",
+	"select",
+	"sentence",
+	"shadow",
+	"shamrock",
+	"showgirl",
+	"skullcap",
+	"skydive",
+	"slingshot",
+	"slowdown",
+	"snapline",
+	"snapshot",
+	"snowcap",
+	"snowslide",
+	"solo",
+	"southward",
+	"soybean",
+	"spaniel",
+	"spearhead",
+	"spellbind",
+	"spheroid",
+	"spigot",
+	"spindle",
+	"spyglass",
+	"stagehand",
+	"stagnate",
+	"stairway",
+	"standard",
+	"stapler",
+	"steamship",
+	"sterling",
+	"stockman",
+	"stopwatch",
+	"stormy",
+	"sugar",
+	"surmount",
+	"suspense",
+	"sweatband",
+	"swelter",
+	"tactics",
+	"talon",
+	"tapeworm",
+	"tempest",
+	"tiger",
+	"tissue",
+	"tonic",
+	"topmost",
+	"tracker",
+	"transit",
+	"trauma",
+	"treadmill",
+	"Trojan",
+	"trouble",
+	"tumor",
+	"tunnel",
+	"tycoon",
+	"uncut",
+	"unearth",
+	"unwind",
+	"uproot",
+	"upset",
+	"upshot",
+	"vapor",
+	"village",
+	"virus",
+	"Vulcan",
+	"waffle",
+	"wallet",
+	"watchword",
+	"wayside",
+	"willow",
+	"woodlark",
+	"Zulu",
+];
+
+/// PGP word list for the bytes in odd positions (three syllables).
+const ODD_WORDS: [&str; 256] = [
+	"adroitness",
+	"adviser",
+	"aftermath",
+	"aggregate",
+	"alkali",
+	"almighty",
+	"amulet",
+	"amusement",
+	"antenna",
+	"applicant",
+	"Apollo",
+	"armistice",
+	"article",
+	"asteroid",
+	"Atlantic",
+	"atmosphere",
+	"autopsy",
+	"Babylon",
+	"backwater",
+	"barbecue",
+	"belowground",
+	"bifocals",
+	"bodyguard",
+	"bookseller",
+	"borderline",
+	"bottomless",
+	"Bradbury",
+	"bravado",
+	"Brazilian",
+	"breakaway",
+	"Burlington",
+	"businessman",
+	"butterfat",
+	"Camelot",
+	"candidate",
+	"cannonball",
+	"Capricorn",
+	"caravan",
+	"caretaker",
+	"celebrate",
+	"cellulose",
+	"certify",
+	"chambermaid",
+	"Cherokee",
+	"Chicago",
+	"clergyman",
+	"coherence",
+	"combustion",
+	"commando",
+	"company",
+	"component",
+	"concurrent",
+	"confidence",
+	"conformist",
+	"congregate",
+	"consensus",
+	"consulting",
+	"corporate",
+	"corrosion",
+	"councilman",
+	"crossover",
+	"crucifix",
+	"cumbersome",
+	"customer",
+	"Dakota",
+	"decadence",
+	"December",
+	"decimal",
+	"designing",
+	"detector",
+	"detergent",
+	"determine",
+	"dictator",
+	"dinosaur",
+	"direction",
+	"disable",
+	"disbelief",
+	"disruptive",
+	"distortion",
+	"document",
+	"embezzle",
+	"enchanting",
+	"enrollment",
+	"enterprise",
+	"equation",
+	"equipment",
+	"escapade",
+	"Eskimo",
+	"everyday",
+	"examine",
+	"existence",
+	"exodus",
+	"fascinate",
+	"filament",
+	"finicky",
+	"forever",
+	"fortitude",
+	"frequency",
+	"gadgetry",
+	"Galveston",
+	"getaway",
+	"glossary",
+	"gossamer",
+	"graduate",
+	"gravity",
+	"guitarist",
+	"hamburger",
+	"Hamilton",
+	"handiwork",
+	"hazardous",
+	"headwaters",
+	"hemisphere",
+	"hesitate",
+	"hideaway",
+	"holiness",
+	"hurricane",
+	"hydraulic",
+	"impartial",
+	"impetus",
+	"inception",
+	"indigo",
+	"inertia",
+	"infancy",
+	"inferno",
+	"informant",
+	"insincere",
+	"insurgent",
+	"integrate",
+	"intention",
+	"inventive",
+	"Istanbul",
+	"Jamaica",
+	"Jupiter",
+	"leprosy",
+	"letterhead",
+	"liberty",
+	"maritime",
+	"matchmaker",
+	"maverick",
+	"Medusa",
+	"megaton",
+	"microscope",
+	"microwave",
+	"midsummer",
+	"millionaire",
+	"miracle",
+	"misnomer",
+	"molasses",
+	"molecule",
+	"Montana",
+	"monument",
+	"mosquito",
+	"narrative",
+	"nebula",
+	"newsletter",
+	"Norwegian",
+	"October",
+	"Ohio",
+	"onlooker",
+	"opulent",
+	"Orlando",
+	"outfielder",
+	"Pacific",
+	"pandemic",
+	"Pandora",
+	"paperweight",
+	"paragon",
+	"paragraph",
+	"paramount",
+	"passenger",
+	"pedigree",
+	"Pegasus",
+	"penetrate",
+	"perceptive",
+	"performance",
+	"pharmacy",
+	"phonetic",
+	"photograph",
+	"pioneer",
+	"pocketful",
+	"politeness",
+	"positive",
+	"potato",
+	"processor",
+	"provincial",
+	"proximate",
+	"puberty",
+	"publisher",
+	"pyramid",
+	"quantity",
+	"racketeer",
+	"rebellion",
+	"recipe",
+	"recover",
+	"repellent",
+	"replica",
+	"reproduce",
+	"resistor",
+	"responsive",
+	"retraction",
+	"retrieval",
+	"retrospect",
+	"revenue",
+	"revival",
+	"revolver",
+	"sandalwood",
+	"sardonic",
+	"Saturday",
+	"savagery",
+	"scavenger",
+	"sensation",
+	"sociable",
+	"souvenir",
+	"specialist",
+	"speculate",
+	"stethoscope",
+	"stupendous",
+	"supportive",
+	"surrender",
+	"suspicious",
+	"sympathy",
+	"tambourine",
+	"telephone",
+	"therapist",
+	"tobacco",
+	"tolerance",
+	"tomorrow",
+	"torpedo",
+	"tradition",
+	"travesty",
+	"trombonist",
+	"truncated",
+	"typewriter",
+	"ultimate",
+	"undaunted",
+	"underfoot",
+	"unicorn",
+	"unify",
+	"universe",
+	"unravel",
+	"upcoming",
+	"vacancy",
+	"vagabond",
+	"vertigo",
+	"Virginia",
+	"visitor",
+	"vocalist",
+	"voyager",
+	"warranty",
+	"Waterloo",
+	"whimsical",
+	"Wichita",
+	"Wilmington",
+	"Wyoming",
+	"yesteryear",
+	"Yucatan",
+];
+
+/// NATO phonetic alphabet for the hexadecimal digits.
+const NATO_ALPHABET: [&str; 16] = [
+	"Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight",
+	"Nine", "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot",
+];
+
+/// Format of the fingerprint for verifying it by humans.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FingerprintFormat {
+	/// Groups of 4 characters separated by space.
+	Groups,
+	/// PGP word list (biometric words).
+	Words,
+	/// NATO phonetic alphabet.
+	Nato,
+}
+
+impl Display for FingerprintFormat {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Groups => "groups",
+				Self::Words => "words",
+				Self::Nato => "NATO",
+			}
+		)
+	}
+}
+
+impl FromStr for FingerprintFormat {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"groups" | "group" => Ok(Self::Groups),
+			"words" | "word" | "pgp" => Ok(Self::Words),
+			"nato" | "phonetic" => Ok(Self::Nato),
+			_ => Err(()),
+		}
+	}
+}
+
+impl FingerprintFormat {
+	/// Returns the formatted groups (4 characters) of the fingerprint.
+	pub fn get_groups(&self, fingerprint: &str) -> Vec<String> {
+		let digits = fingerprint
+			.chars()
+			.filter_map(|c| c.to_digit(16))
+			.map(|v| v as usize)
+			.collect::<Vec<usize>>();
+		digits
+			.chunks(4)
+			.enumerate()
+			.map(|(i, group)| match self {
+				Self::Groups => {
+					group.iter().map(|v| format!("{:X}", v)).collect::<String>()
+				}
+				Self::Words => group
+					.chunks(2)
+					.enumerate()
+					.map(|(j, byte)| {
+						let value = byte.iter().fold(0, |acc, v| acc * 16 + v);
+						if (i * 2 + j) % 2 == 0 {
+							EVEN_WORDS[value]
+						} else {
+							ODD_WORDS[value]
+						}
+					})
+					.collect::<Vec<&str>>()
+					.join(" "),
+				Self::Nato => group
+					.iter()
+					.map(|v| NATO_ALPHABET[*v])
+					.collect::<Vec<&str>>()
+					.join(" "),
+			})
+			.collect()
+	}
+
+	/// Returns the fingerprint in this format.
+	pub fn format(&self, fingerprint: &str) -> String {
+		self.get_groups(fingerprint).join(match self {
+			Self::Groups | Self::Words => " ",
+			Self::Nato => ", ",
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_fingerprint() {
+		let fingerprint = "E9D0B5C7F0A1D43E6F3B2A1C0D9E8F7A6B5C4D3E";
+		assert_eq!(
+			"E9D0 B5C7 F0A1 D43E 6F3B 2A1C 0D9E 8F7A 6B5C 4D3E",
+			FingerprintFormat::Groups.format(fingerprint)
+		);
+		assert_eq!(
+			vec!["treadmill savagery", "scorecard retraction"],
+			FingerprintFormat::Words.get_groups(&fingerprint[..8])
+		);
+		assert_eq!(
+			"aardvark adroitness Zulu Yucatan",
+			FingerprintFormat::Words.format("0000 FFFF")
+		);
+		assert_eq!(
+			"Echo Nine Delta Zero, Bravo Five Charlie Seven",
+			FingerprintFormat::Nato.format(&fingerprint[..8])
+		);
+		for format in &[
+			FingerprintFormat::Groups,
+			FingerprintFormat::Words,
+			FingerprintFormat::Nato,
+		] {
+			assert_eq!(
+				*format,
+				FingerprintFormat::from_str(&format.to_string()).unwrap()
+			);
+			assert_eq!(10, format.get_groups(fingerprint).len());
+		}
+	}
+}
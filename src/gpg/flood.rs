@@ -0,0 +1,210 @@
+use crate::gpg::handler;
+use std::fs;
+
+/// Number of signatures that a key can have before
+/// it is considered as flooded.
+pub const SIGNATURE_LIMIT: usize = 1000;
+
+/// Tag of the signature packets.
+const SIGNATURE_PACKET: u8 = 2;
+
+/// Tag of the secret key packets.
+const SECRET_KEY_PACKET: u8 = 5;
+
+/// Tag of the public key packets.
+const PUBLIC_KEY_PACKET: u8 = 6;
+
+/// Tag of the user ID packets.
+const USER_ID_PACKET: u8 = 13;
+
+/// Key in the imported data along with its number of signatures.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeySignatures {
+	/// Primary user ID of the key.
+	pub user_id: String,
+	/// Number of the signatures (including the self-signatures).
+	pub signatures: usize,
+}
+
+/// Returns the header length, body length and tag of the packet
+/// at the start of the given data.
+///
+/// Returns `None` for the invalid and partial length packets.
+fn parse_packet_header(data: &[u8]) -> Option<(usize, usize, u8)> {
+	let header = *data.first()?;
+	if header & 0x80 == 0 {
+		return None;
+	}
+	let read_length = |bytes: &[u8]| {
+		bytes
+			.iter()
+			.fold(0, |length, byte| (length << 8) | *byte as usize)
+	};
+	if header & 0x40 != 0 {
+		let tag = header & 0x3f;
+		match *data.get(1)? {
+			length @ 0..=191 => Some((2, length as usize, tag)),
+			length @ 192..=223 => Some((
+				3,
+				((length as usize - 192) << 8) + *data.get(2)? as usize + 192,
+				tag,
+			)),
+			255 => Some((6, read_length(data.get(2..6)?), tag)),
+			_ => None,
+		}
+	} else {
+		let tag = (header >> 2) & 0x0f;
+		match header & 0x03 {
+			0 => Some((2, read_length(data.get(1..2)?), tag)),
+			1 => Some((3, read_length(data.get(1..3)?), tag)),
+			2 => Some((5, read_length(data.get(1..5)?), tag)),
+			_ => Some((1, data.len() - 1, tag)),
+		}
+	}
+}
+
+/// Counts the signatures of each key in the given OpenPGP packets.
+///
+/// Parsing stops at the first invalid packet.
+pub fn count_signatures(data: &[u8]) -> Vec<KeySignatures> {
+	let mut keys: Vec<KeySignatures> = Vec::new();
+	let mut offset = 0;
+	while let Some((header_length, length, tag)) =
+		parse_packet_header(&data[offset..])
+	{
+		let body = match data
+			.get(offset + header_length..offset + header_length + length)
+		{
+			Some(body) => body,
+			None => break,
+		};
+		match (tag, keys.last_mut()) {
+			(PUBLIC_KEY_PACKET, _) | (SECRET_KEY_PACKET, _) => {
+				keys.push(KeySignatures::default())
+			}
+			(USER_ID_PACKET, Some(key)) if key.user_id.is_empty() => {
+				key.user_id = String::from_utf8_lossy(body).to_string()
+			}
+			(SIGNATURE_PACKET, Some(key)) => key.signatures += 1,
+			_ => {}
+		}
+		offset += header_length + length;
+	}
+	keys
+}
+
+/// Decodes the given base64 data.
+///
+/// Invalid characters (e.g. padding and line breaks) are skipped.
+fn decode_base64(input: &str) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	let mut buffer = 0u32;
+	let mut bits = 0;
+	for c in input.bytes() {
+		let value = match c {
+			b'A'..=b'Z' => c - b'A',
+			b'a'..=b'z' => c - b'a' + 26,
+			b'0'..=b'9' => c - b'0' + 52,
+			b'+' => 62,
+			b'/' => 63,
+			_ => continue,
+		};
+		buffer = (buffer << 6) | u32::from(value);
+		bits += 6;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	bytes
+}
+
+/// Decodes the ASCII armored blocks in the given text.
+///
+/// Armor headers and checksums are skipped.
+pub fn decode_armor(text: &str) -> Vec<u8> {
+	let mut data = Vec::new();
+	let mut body = String::new();
+	let mut in_headers = false;
+	let mut in_body = false;
+	for line in text.lines().map(str::trim) {
+		if line.starts_with("-----BEGIN PGP") {
+			in_headers = true;
+		} else if in_headers {
+			in_headers = !line.is_empty();
+			in_body = !in_headers;
+		} else if in_body
+			&& (line.starts_with('=') || line.starts_with("-----"))
+		{
+			data.extend(decode_base64(&body));
+			body.clear();
+			in_body = false;
+		} else if in_body {
+			body.push_str(line);
+		}
+	}
+	data
+}
+
+/// Returns the keys that have more signatures than the given limit
+/// in the given files (or ASCII armored blocks).
+///
+/// Files that cannot be read are skipped.
+pub fn get_flooded_keys(keys: &[String], limit: usize) -> Vec<KeySignatures> {
+	keys.iter()
+		.filter_map(|key| {
+			if handler::is_armored_block(key) {
+				Some(decode_armor(key))
+			} else {
+				let data = fs::read(key).ok()?;
+				Some(if data.starts_with(b"-----BEGIN PGP") {
+					decode_armor(&String::from_utf8_lossy(&data))
+				} else {
+					data
+				})
+			}
+		})
+		.flat_map(|data| count_signatures(&data))
+		.filter(|key| key.signatures > limit)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_flood() {
+		let data = [
+			198, 1, 4, 205, 5, 97, 108, 105, 99, 101, 136, 1, 0, 136, 1, 0,
+			136, 1, 0, 153, 0, 1, 4, 136, 1, 0,
+		];
+		let keys = vec![
+			KeySignatures {
+				user_id: String::from("alice"),
+				signatures: 3,
+			},
+			KeySignatures {
+				user_id: String::new(),
+				signatures: 1,
+			},
+		];
+		assert_eq!(keys, count_signatures(&data));
+		assert_eq!(vec![keys[0].clone()], count_signatures(&data[..19]));
+		assert_eq!(Vec::<KeySignatures>::new(), count_signatures(&[0x00]));
+		let armor = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+Comment: test
+
+xgEEzQVhbGljZYgBAIgB
+AIgBAJkAAQSIAQA=
+=ABCD
+-----END PGP PUBLIC KEY BLOCK-----";
+		assert_eq!(data.to_vec(), decode_armor(armor));
+		assert_eq!(
+			vec![keys[0].clone()],
+			get_flooded_keys(&[armor.to_string()], 2)
+		);
+		assert!(get_flooded_keys(&[armor.to_string()], 3).is_empty());
+	}
+}
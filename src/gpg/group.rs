@@ -0,0 +1,159 @@
+/// Keyword of the group definitions in `gpg.conf`.
+const GROUP_KEYWORD: &str = "group";
+
+/// Named set of recipients (`group` definition in `gpg.conf`).
+///
+/// Encrypting to the name of the group is the same as
+/// encrypting to each of its members.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipientGroup {
+	/// Name of the group.
+	pub name: String,
+	/// Members of the group (e.g. key IDs or email addresses).
+	pub members: Vec<String>,
+}
+
+impl RecipientGroup {
+	/// Parses the group definition from the given line.
+	///
+	/// e.g. `group team = 0x0E7C3E21 alice@example.org`
+	fn parse(line: &str) -> Option<Self> {
+		let line = line.trim();
+		let definition = line.strip_prefix(GROUP_KEYWORD)?;
+		if !definition.starts_with(char::is_whitespace) {
+			return None;
+		}
+		let (name, members) = definition.split_once('=')?;
+		let name = name.trim();
+		if name.is_empty() {
+			return None;
+		}
+		Some(Self {
+			name: name.to_string(),
+			members: members.split_whitespace().map(String::from).collect(),
+		})
+	}
+}
+
+/// Returns the groups that are defined in the given configuration.
+///
+/// Members of the groups that are defined more than once are merged.
+pub fn get_groups(config: &str) -> Vec<RecipientGroup> {
+	let mut groups: Vec<RecipientGroup> = Vec::new();
+	for group in config.lines().filter_map(RecipientGroup::parse) {
+		match groups.iter_mut().find(|v| v.name == group.name) {
+			Some(existing) => existing.members.extend(group.members),
+			None => groups.push(group),
+		}
+	}
+	groups
+}
+
+/// Returns the configuration with the definition of the given group
+/// replaced (or appended) and the other lines kept as is.
+///
+/// The group is removed if no members are given.
+pub fn set_group(config: &str, name: &str, members: &[String]) -> String {
+	let definition =
+		format!("{} {} = {}", GROUP_KEYWORD, name, members.join(" "));
+	let mut lines = Vec::new();
+	let mut replaced = members.is_empty();
+	for line in config.lines() {
+		if RecipientGroup::parse(line).map(|v| v.name == name) == Some(true) {
+			if !replaced {
+				lines.push(definition.as_str());
+				replaced = true;
+			}
+		} else {
+			lines.push(line);
+		}
+	}
+	if !replaced {
+		lines.push(definition.as_str());
+	}
+	let mut config = lines.join("\n");
+	if !config.is_empty() {
+		config.push('\n');
+	}
+	config
+}
+
+/// Returns the recipients with the group names replaced by their members.
+pub fn expand_groups(
+	groups: &[RecipientGroup],
+	recipients: Vec<String>,
+) -> Vec<String> {
+	recipients
+		.into_iter()
+		.flat_map(|recipient| {
+			match groups.iter().find(|group| group.name == recipient) {
+				Some(group) => group.members.clone(),
+				None => vec![recipient],
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_group() {
+		let config = "# groups
+group team = 0xABC alice@example.org
+groups = x
+group ops=0xDEF
+group team = bob@example.org
+keyserver hkps://keys.openpgp.org";
+		let groups = get_groups(config);
+		assert_eq!(
+			vec![
+				RecipientGroup {
+					name: String::from("team"),
+					members: vec![
+						String::from("0xABC"),
+						String::from("alice@example.org"),
+						String::from("bob@example.org"),
+					],
+				},
+				RecipientGroup {
+					name: String::from("ops"),
+					members: vec![String::from("0xDEF")],
+				},
+			],
+			groups
+		);
+		assert_eq!(
+			vec!["0xABC", "alice@example.org", "bob@example.org", "0x123"],
+			expand_groups(
+				&groups,
+				vec![String::from("team"), String::from("0x123")]
+			)
+		);
+		assert_eq!(
+			"# groups
+group team = 0xGHI
+groups = x
+group ops=0xDEF
+keyserver hkps://keys.openpgp.org
+",
+			set_group(config, "team", &[String::from("0xGHI")])
+		);
+		assert_eq!(
+			"# groups
+group team = 0xABC alice@example.org
+groups = x
+group team = bob@example.org
+keyserver hkps://keys.openpgp.org
+group dev = 0x1 0x2
+",
+			set_group(
+				&set_group(config, "ops", &[]),
+				"dev",
+				&[String::from("0x1"), String::from("0x2")]
+			)
+		);
+		assert_eq!("", set_group("", "dev", &[]));
+	}
+}
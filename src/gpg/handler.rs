@@ -1,5 +1,307 @@
-use chrono::{DateTime, Utc};
-use gpgme::{Subkey, UserIdSignature};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, Utc};
+use gpgme::{
+	Error as GpgmeError, SignatureNotation, Subkey, TofuInfo, TofuPolicy,
+	UserIdSignature, Validity,
+};
+use std::time::SystemTime;
+
+/// Beginning of the ASCII armored key blocks.
+pub const ARMOR_HEADER: &str = "-----BEGIN PGP";
+
+/// Ending of the ASCII armored key blocks.
+pub const ARMOR_FOOTER: &str = "-----END PGP";
+
+/// Flag of a subkey (symbol, description, check).
+type SubkeyFlag = (&'static str, &'static str, fn(&Subkey<'_>) -> bool);
+
+/// Flag of a signature (symbol, description, check).
+type SignatureFlag =
+	(&'static str, &'static str, fn(&UserIdSignature<'_>) -> bool);
+
+/// Flag of a signature notation (symbol, description, check).
+type NotationFlag = (
+	&'static str,
+	&'static str,
+	fn(&SignatureNotation<'_>) -> bool,
+);
+
+/// Badge of the subkeys that are stored on a smartcard.
+pub const CARD_BADGE: &str = ">";
+
+/// Capabilities of the subkeys.
+const SUBKEY_CAPABILITIES: &[SubkeyFlag] = &[
+	("s", "sign", |subkey| subkey.can_sign()),
+	("c", "certify", |subkey| subkey.can_certify()),
+	("e", "encrypt", |subkey| subkey.can_encrypt()),
+	("a", "authenticate", |subkey| subkey.can_authenticate()),
+];
+
+/// States of the subkeys.
+const SUBKEY_STATES: &[SubkeyFlag] = &[
+	("exp", "expired", |subkey| subkey.is_expired()),
+	("rev", "revoked", |subkey| subkey.is_revoked()),
+	("d", "disabled", |subkey| subkey.is_disabled()),
+	("i", "invalid", |subkey| subkey.is_invalid()),
+	("q", "qualified", |subkey| subkey.is_qualified()),
+];
+
+/// States of the signatures.
+const SIGNATURE_STATES: &[SignatureFlag] = &[
+	("exp", "expired", |signature| signature.is_expired()),
+	("rev", "revoked", |signature| signature.is_revocation()),
+	("i", "invalid", |signature| signature.is_invalid()),
+	("!x", "non-exportable", |signature| {
+		!signature.is_exportable()
+	}),
+];
+
+/// Flags of the signature notations.
+const NOTATION_FLAGS: &[NotationFlag] = &[
+	("!", "the notation data is critical", |notation| {
+		notation.is_critical()
+	}),
+	(
+		"h",
+		"the notation data is in human readable form",
+		|notation| notation.is_human_readable(),
+	),
+];
+
+/// Certification levels of the signatures.
+const CERT_LEVELS: &[(u64, &str)] = &[
+	(0x10, "no indication"),
+	(0x11, "personal belief but no verification"),
+	(0x12, "casual verification"),
+	(0x13, "extensive verification"),
+];
+
+/// Validities of the user IDs.
+const VALIDITIES: &[(Validity, &str)] = &[
+	(Validity::Undefined, "undefined"),
+	(Validity::Never, "never"),
+	(Validity::Marginal, "marginal"),
+	(Validity::Full, "full"),
+	(Validity::Ultimate, "ultimate"),
+	(Validity::Unknown, "unknown"),
+];
+
+/// TOFU policies that can be set for the keys.
+pub const TOFU_POLICIES: &[(TofuPolicy, &str)] = &[
+	(TofuPolicy::Auto, "auto"),
+	(TofuPolicy::Good, "good"),
+	(TofuPolicy::Unknown, "unknown"),
+	(TofuPolicy::Bad, "bad"),
+	(TofuPolicy::Ask, "ask"),
+];
+
+/// Validities of the TOFU bindings (i.e. the amount of history).
+const TOFU_VALIDITIES: &[(u32, &str)] = &[
+	(0, "conflict"),
+	(1, "no history"),
+	(2, "little history"),
+	(3, "enough history"),
+	(4, "lots of history"),
+];
+
+/// Units for formatting the relative times (name, short name, seconds).
+const TIME_UNITS: &[(&str, &str, i64)] = &[
+	("year", "y", 365 * 24 * 60 * 60),
+	("month", "mo", 30 * 24 * 60 * 60),
+	("day", "d", 24 * 60 * 60),
+	("hour", "h", 60 * 60),
+	("minute", "m", 60),
+];
+
+/// Suggested remedies for the errors (pattern, remedy).
+///
+/// Patterns are matched against the lowercased error messages.
+const ERROR_REMEDIES: &[(&str, &str)] = &[
+	(
+		"no keyserver",
+		"set a keyserver in dirmngr.conf (:config edit dirmngr)",
+	),
+	(
+		"server indicated a failure",
+		"the keyserver might be unavailable, try again later or use another one",
+	),
+	(
+		"no data",
+		"check if the key exists and the input contains OpenPGP data",
+	),
+	(
+		"connection refused",
+		"check the network connection and the keyserver address",
+	),
+	(
+		"unreachable",
+		"check the network connection and the keyserver address",
+	),
+	(
+		"no route to host",
+		"check the network connection and the keyserver address",
+	),
+	(
+		"unknown host",
+		"check the network connection and the keyserver address",
+	),
+	("timeout", "check the network connection and try again"),
+	("timed out", "check the network connection and try again"),
+	("no dirmngr", "launch dirmngr (:agent launch dirmngr)"),
+	("no agent", "launch gpg-agent (:agent launch gpg-agent)"),
+	(
+		"no pinentry",
+		"install a pinentry program or set pinentry-program in gpg-agent.conf",
+	),
+	("bad passphrase", "check the passphrase of the key"),
+	(
+		"cancel",
+		"the operation is cancelled by the user or pinentry, try again",
+	),
+	("no public key", "import the public key first (:receive <key>)"),
+	("no secret key", "select a key with the secret part in the keyring"),
+	("unusable", "the key might be expired or revoked, check its validity"),
+	("ambiguous name", "use the fingerprint of the key instead of its name"),
+	("no such file", "check the given path and the output directory"),
+	(
+		"permission denied",
+		"check the permissions of the GnuPG home and output directories",
+	),
+	(
+		"stdin is a terminal",
+		"pipe the keys to gpg-tui (e.g. cat key.asc | gpg-tui --import -)",
+	),
+];
+
+/// Checks if the given string is a valid date format.
+///
+/// See [`chrono::format::strftime`] for the supported specifiers.
+pub fn is_valid_date_format(format: &str) -> bool {
+	!StrftimeItems::new(format).any(|item| item == Item::Error)
+}
+
+/// Returns the given duration as a relative time.
+///
+/// e.g. "in 12 days" or "3 years ago" ("in 12d" or "3y ago" if truncated)
+pub fn get_relative_time(duration: Duration, truncate: bool) -> String {
+	let seconds = duration.num_seconds();
+	let (name, short_name, unit) = TIME_UNITS
+		.iter()
+		.find(|(_, _, unit)| seconds.abs() >= *unit)
+		.copied()
+		.unwrap_or(("minute", "m", 60));
+	let value = seconds.abs() / unit;
+	let time = if value == 0 {
+		return String::from("now");
+	} else if truncate {
+		format!("{}{}", value, short_name)
+	} else {
+		format!("{} {}{}", value, name, if value == 1 { "" } else { "s" })
+	};
+	if seconds.is_negative() {
+		format!("{} ago", time)
+	} else {
+		format!("in {}", time)
+	}
+}
+
+/// Formats the given time with the given format.
+///
+/// Relative time is returned if the format is not specified.
+fn format_time(
+	time: SystemTime,
+	format: Option<&str>,
+	truncate: bool,
+) -> String {
+	let time = DateTime::<Utc>::from(time);
+	match format {
+		Some(format) => time.format(format).to_string(),
+		None => get_relative_time(time - Utc::now(), truncate),
+	}
+}
+
+/// Masks the given word by keeping only its first character.
+///
+/// Punctuation around the word (e.g. brackets) is kept as is.
+fn mask_word(word: &str) -> String {
+	let start = word.find(char::is_alphanumeric).unwrap_or(word.len());
+	let end = word
+		.rfind(char::is_alphanumeric)
+		.map(|i| i + word[i..].chars().next().map_or(1, char::len_utf8))
+		.unwrap_or(start);
+	let value = &word[start..end];
+	let masked = if let Some((local, domain)) = value.split_once('@') {
+		let mut labels = domain.split('.').collect::<Vec<&str>>();
+		let tld = if labels.len() > 1 { labels.pop() } else { None };
+		format!(
+			"{}@{}{}",
+			mask_word(local),
+			labels
+				.iter()
+				.map(|label| mask_word(label))
+				.collect::<Vec<String>>()
+				.join("."),
+			tld.map(|tld| format!(".{}", tld)).unwrap_or_default()
+		)
+	} else {
+		match value.chars().next() {
+			Some(c) => format!("{}***", c),
+			None => String::new(),
+		}
+	};
+	format!("{}{}{}", &word[..start], masked, &word[end..])
+}
+
+/// Redacts the names and email addresses in the given user ID.
+///
+/// e.g. `Alice Doe <alice@example.org>` -> `A*** D*** <a***@e***.org>`
+pub fn redact_user_id(user_id: &str) -> String {
+	user_id
+		.split(' ')
+		.map(mask_word)
+		.collect::<Vec<String>>()
+		.join(" ")
+}
+
+/// Returns the details of the given error.
+///
+/// It consists of the causes in the error chain
+/// and the GPGME error code and source (if any).
+pub fn get_error_details(error: &anyhow::Error) -> Vec<String> {
+	let mut details = error
+		.chain()
+		.skip(1)
+		.map(|cause| format!("caused by: {}", cause))
+		.collect::<Vec<String>>();
+	if let Some(gpgme_error) =
+		error.chain().find_map(|e| e.downcast_ref::<GpgmeError>())
+	{
+		details.push(format!(
+			"gpgme error code: {} ({})",
+			gpgme_error.code(),
+			gpgme_error.description()
+		));
+		details.push(format!(
+			"gpgme error source: {}",
+			gpgme_error.source().unwrap_or("?")
+		));
+	}
+	details
+}
+
+/// Returns the suggested remedies for the given error messages.
+pub fn get_error_remedies(messages: &[String]) -> Vec<&'static str> {
+	let messages = messages.join("\n").to_lowercase();
+	ERROR_REMEDIES
+		.iter()
+		.filter(|(pattern, _)| messages.contains(pattern))
+		.fold(Vec::new(), |mut remedies, (_, remedy)| {
+			if !remedies.contains(remedy) {
+				remedies.push(*remedy);
+			}
+			remedies
+		})
+}
 
 /// Returns the flags of the given subkey.
 ///
@@ -8,13 +310,147 @@ use gpgme::{Subkey, UserIdSignature};
 /// * `E`: encrypt
 /// * `A`: authenticate
 pub fn get_subkey_flags(subkey: Subkey) -> String {
-	format!(
-		"{}{}{}{}",
-		if subkey.can_sign() { "s" } else { "-" },
-		if subkey.can_certify() { "c" } else { "-" },
-		if subkey.can_encrypt() { "e" } else { "-" },
-		if subkey.can_authenticate() { "a" } else { "-" },
-	)
+	SUBKEY_CAPABILITIES
+		.iter()
+		.map(|(symbol, _, check)| if check(&subkey) { symbol } else { "-" })
+		.collect()
+}
+
+/// Returns the badge of the given subkey if it is stored on a smartcard.
+///
+/// It follows the convention of `gpg` (e.g. `ssb>`) and is
+/// appended to the capabilities of the subkey (e.g. `[--e-]>`).
+pub fn get_card_badge(subkey: Subkey) -> &'static str {
+	if subkey.is_card_key() {
+		CARD_BADGE
+	} else {
+		""
+	}
+}
+
+/// Returns `true` if the given text is an ASCII armored key block.
+///
+/// Other armored blocks (e.g. messages and signatures) are not matched.
+pub fn is_armored_block(text: &str) -> bool {
+	matches!(text.trim_start().lines().next(), Some(line)
+		if line.starts_with(ARMOR_HEADER)
+			&& line.trim_end().ends_with("KEY BLOCK-----"))
+}
+
+/// Returns the descriptions of the capabilities and
+/// states of the given subkey (e.g. `["sign", "expired"]`).
+pub fn get_subkey_flag_names(subkey: Subkey) -> Vec<&'static str> {
+	SUBKEY_CAPABILITIES
+		.iter()
+		.chain(SUBKEY_STATES)
+		.filter(|(_, _, check)| check(&subkey))
+		.map(|(_, description, _)| *description)
+		.collect()
+}
+
+/// Returns the capability flags (e.g. `sc--`) from their names.
+pub fn get_subkey_flags_from_names(names: &[String]) -> String {
+	SUBKEY_CAPABILITIES
+		.iter()
+		.map(|(symbol, description, _)| {
+			if names.iter().any(|name| name == description) {
+				symbol
+			} else {
+				"-"
+			}
+		})
+		.collect()
+}
+
+/// Returns the flag of the given signature notation.
+pub fn get_notation_flag(notation: &SignatureNotation) -> &'static str {
+	NOTATION_FLAGS
+		.iter()
+		.find(|(_, _, check)| check(notation))
+		.map(|(symbol, _, _)| *symbol)
+		.unwrap_or("?")
+}
+
+/// Returns `true` if the given text is a valid notation for signing.
+///
+/// Notations are in `name@domain=value` format and the name can be
+/// prefixed with `!` for marking the notation as critical.
+pub fn is_valid_notation(notation: &str) -> bool {
+	match notation.trim_start_matches('!').split_once('=') {
+		Some((name, value)) => {
+			!value.is_empty()
+				&& matches!(name.split_once('@'), Some((local, domain))
+					if !local.is_empty() && !domain.is_empty())
+				&& !name.contains(|c: char| c.is_whitespace() || c == '!')
+		}
+		None => false,
+	}
+}
+
+/// Returns the given flags in brackets if their check passes.
+fn get_flags<T, F: Fn(&T) -> bool>(
+	value: &T,
+	flags: &[(&'static str, &'static str, F)],
+) -> String {
+	flags
+		.iter()
+		.filter(|(_, _, check)| check(value))
+		.map(|(symbol, _, _)| format!(" [{}]", symbol))
+		.collect()
+}
+
+/// Returns the symbols (in brackets) and descriptions of the given flags.
+fn describe_flags<F>(
+	flags: &[(&'static str, &'static str, F)],
+) -> Vec<(String, &'static str)> {
+	flags
+		.iter()
+		.map(|(symbol, description, _)| (format!("[{}]", symbol), *description))
+		.collect()
+}
+
+/// Returns the explanation of the flags that are
+/// shown in the key information.
+///
+/// It consists of the titles of the sections
+/// along with the flags and their descriptions.
+pub fn get_flag_legend() -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+	let mut capabilities = describe_flags(SUBKEY_CAPABILITIES);
+	capabilities.push((String::from("[-]"), "not capable"));
+	vec![
+		("Key capabilities (e.g. [sc--])", capabilities),
+		("Key states", describe_flags(SUBKEY_STATES)),
+		(
+			"Key storage",
+			vec![
+				(
+					format!("[....]{}", CARD_BADGE),
+					"stored on a smartcard (local stub)",
+				),
+				(String::from("[card:..]"), "serial number of the card"),
+			],
+		),
+		(
+			"User validity",
+			VALIDITIES
+				.iter()
+				.map(|(validity, description)| {
+					(format!("[{}]", validity), *description)
+				})
+				.collect(),
+		),
+		(
+			"Certification levels",
+			CERT_LEVELS
+				.iter()
+				.map(|(level, description)| {
+					(format!("[{:x}]", level), *description)
+				})
+				.collect(),
+		),
+		("Signature states", describe_flags(SIGNATURE_STATES)),
+		("Notation flags", describe_flags(NOTATION_FLAGS)),
+	]
 }
 
 /// Returns time information about the given subkey.
@@ -22,26 +458,42 @@ pub fn get_subkey_flags(subkey: Subkey) -> String {
 /// * creation time
 /// * expiration time
 /// * is the key expired/revoked/disabled/invalid/qualified?
-pub fn get_subkey_time(subkey: Subkey, format: &str) -> String {
+///
+/// Times are shown as relative (e.g. "expires in 12 days")
+/// if the format is not specified.
+pub fn get_subkey_time(
+	subkey: Subkey,
+	format: Option<&str>,
+	truncate: bool,
+) -> String {
+	let relative = format.is_none() && !truncate;
 	format!(
-		"({}){}{}{}{}{}{}",
+		"({}){}{}",
 		if let Some(date) = subkey.creation_time() {
-			DateTime::<Utc>::from(date).format(format).to_string()
+			format!(
+				"{}{}",
+				if relative { "created " } else { "" },
+				format_time(date, format, truncate)
+			)
 		} else {
 			String::from("[?]")
 		},
 		if let Some(date) = subkey.expiration_time() {
-			DateTime::<Utc>::from(date)
-				.format(&format!(" ─> ({})", format))
-				.to_string()
+			format!(
+				" ─> ({}{})",
+				if !relative {
+					""
+				} else if subkey.is_expired() {
+					"expired "
+				} else {
+					"expires "
+				},
+				format_time(date, format, truncate)
+			)
 		} else {
 			String::new()
 		},
-		if subkey.is_expired() { " [exp]" } else { "" },
-		if subkey.is_revoked() { " [rev]" } else { "" },
-		if subkey.is_disabled() { " [d]" } else { "" },
-		if subkey.is_invalid() { " [i]" } else { "" },
-		if subkey.is_qualified() { " [q]" } else { "" }
+		get_flags(&subkey, SUBKEY_STATES)
 	)
 }
 
@@ -52,7 +504,7 @@ pub fn get_subkey_time(subkey: Subkey, format: &str) -> String {
 /// * is the signature expired/revoked/invalid/non-exportable?
 pub fn get_signature_time(signature: UserIdSignature, format: &str) -> String {
 	format!(
-		"({}){}{}{}{}{}",
+		"({}){}{}",
 		if let Some(date) = signature.creation_time() {
 			DateTime::<Utc>::from(date).format(format).to_string()
 		} else {
@@ -65,17 +517,146 @@ pub fn get_signature_time(signature: UserIdSignature, format: &str) -> String {
 		} else {
 			String::new()
 		},
-		if signature.is_expired() { " [exp]" } else { "" },
-		if signature.is_revocation() {
-			" [rev]"
-		} else {
-			""
-		},
-		if signature.is_invalid() { " [i]" } else { "" },
-		if !signature.is_exportable() {
-			" [!x]"
-		} else {
-			""
+		get_flags(&signature, SIGNATURE_STATES)
+	)
+}
+
+/// Returns the name of the given TOFU policy.
+pub fn get_tofu_policy_name(policy: TofuPolicy) -> &'static str {
+	TOFU_POLICIES
+		.iter()
+		.find(|(p, _)| *p == policy)
+		.map(|(_, name)| *name)
+		.unwrap_or("none")
+}
+
+/// Returns the TOFU policy with the given name.
+pub fn parse_tofu_policy(name: &str) -> Option<TofuPolicy> {
+	TOFU_POLICIES
+		.iter()
+		.find(|(_, n)| *n == name)
+		.map(|(policy, _)| *policy)
+}
+
+/// Returns the TOFU statistics of a user ID.
+///
+/// * policy of the binding
+/// * number of the verified signatures and encrypted messages
+/// * time of the first and last usage
+/// * is the binding in conflict?
+pub fn get_tofu_info(info: TofuInfo, format: &str) -> String {
+	let first = [info.first_signed(), info.first_encrypted()]
+		.iter()
+		.flatten()
+		.min()
+		.copied();
+	let last = [info.last_signed(), info.last_encrypted()]
+		.iter()
+		.flatten()
+		.max()
+		.copied();
+	format!(
+		"tofu: [{}] {} signature{}, {} encryption{}{}{}",
+		get_tofu_policy_name(info.policy()),
+		info.signature_count(),
+		if info.signature_count() == 1 { "" } else { "s" },
+		info.encrypted_count(),
+		if info.encrypted_count() == 1 { "" } else { "s" },
+		match (first, last) {
+			(Some(first), Some(last)) => format!(
+				" ({} ─> {})",
+				format_time(first, Some(format), false),
+				format_time(last, Some(format), false)
+			),
+			_ => String::new(),
 		},
+		match TOFU_VALIDITIES.iter().find(|(v, _)| *v == info.validity()) {
+			Some((0, description)) => format!(" [{}]", description),
+			Some((_, description)) => format!(" ({})", description),
+			None => String::new(),
+		}
 	)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_handler() {
+		assert_eq!(
+			"A*** D*** (w***) <a***@e***.org>",
+			redact_user_id("Alice Doe (work) <alice@example.org>")
+		);
+		assert_eq!(
+			"a***@m***.e***.co",
+			redact_user_id("alice.doe@mail.example.co")
+		);
+		assert_eq!("[?] Ö***", redact_user_id("[?] Ömer"));
+		let legend = get_flag_legend();
+		assert_eq!(7, legend.len());
+		assert!(legend.iter().all(|(_, flags)| !flags.is_empty()));
+		assert!(legend[1].1.contains(&(String::from("[exp]"), "expired")));
+		assert_eq!(String::from("[....]>"), legend[2].1[0].0);
+		assert_eq!(
+			vec!["[s]", "[c]", "[e]", "[a]", "[-]"],
+			legend[0]
+				.1
+				.iter()
+				.map(|(symbol, _)| symbol.as_str())
+				.collect::<Vec<&str>>()
+		);
+		assert!(is_armored_block("\n-----BEGIN PGP PUBLIC KEY BLOCK-----"));
+		assert!(!is_armored_block("key.asc"));
+		assert!(!is_armored_block("-----BEGIN PGP MESSAGE-----"));
+		assert!(is_valid_notation("policy@example.org=https://example.org"));
+		assert!(is_valid_notation("!level@example.org=2"));
+		assert!(!is_valid_notation("policy=x"));
+		assert!(!is_valid_notation("policy@example.org="));
+		assert!(!is_valid_notation("@example.org=x"));
+		assert!(is_valid_date_format("%Y-%m-%d %H:%M"));
+		assert!(!is_valid_date_format("%Q"));
+		assert_eq!(
+			"in 12 days",
+			get_relative_time(Duration::days(12) + Duration::hours(1), false)
+		);
+		assert_eq!("in 1 day", get_relative_time(Duration::days(1), false));
+		assert_eq!("3y ago", get_relative_time(Duration::days(-1100), true));
+		assert_eq!(
+			"2 months ago",
+			get_relative_time(Duration::days(-65), false)
+		);
+		assert_eq!("in 5m", get_relative_time(Duration::minutes(5), true));
+		assert_eq!("now", get_relative_time(Duration::seconds(30), false));
+		let error = anyhow::anyhow!("No route to host").context("send error");
+		let details = get_error_details(&error);
+		assert_eq!(vec!["caused by: No route to host"], details);
+		assert_eq!(
+			vec!["check the network connection and the keyserver address"],
+			get_error_remedies(&details)
+		);
+		assert_eq!(
+			vec![
+				"set a keyserver in dirmngr.conf (:config edit dirmngr)",
+				"check if the key exists and the input contains OpenPGP data",
+			],
+			get_error_remedies(&[
+				String::from("gpg: No keyserver available"),
+				String::from("gpg: keyserver receive failed: No data"),
+			])
+		);
+		assert!(get_error_remedies(&[String::from("?")]).is_empty());
+		assert_eq!(Some(TofuPolicy::Bad), parse_tofu_policy("bad"));
+		assert_eq!(None, parse_tofu_policy("none"));
+		assert_eq!("ask", get_tofu_policy_name(TofuPolicy::Ask));
+		assert_eq!("none", get_tofu_policy_name(TofuPolicy::None));
+		assert_eq!(
+			"s--a",
+			get_subkey_flags_from_names(&[
+				String::from("sign"),
+				String::from("authenticate"),
+				String::from("expired"),
+			])
+		);
+	}
+}
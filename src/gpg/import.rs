@@ -0,0 +1,162 @@
+use gpgme::{ImportFlags, ImportResult};
+
+/// Summary of the changes that importing keys makes to the keyring.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportSummary {
+	/// Number of the keys in the imported data.
+	pub considered: u32,
+	/// Number of the new keys.
+	pub new_keys: u32,
+	/// Number of the keys that are not changed.
+	pub unchanged: u32,
+	/// Number of the new user IDs.
+	pub new_user_ids: u32,
+	/// Number of the new subkeys.
+	pub new_subkeys: u32,
+	/// Number of the new signatures.
+	pub new_signatures: u32,
+	/// Number of the new revocations.
+	pub new_revocations: u32,
+	/// Fingerprints and the import status of the keys.
+	pub keys: Vec<(String, ImportFlags)>,
+}
+
+impl ImportSummary {
+	/// Adds the counts of the given import result to the summary.
+	pub fn add(&mut self, result: &ImportResult) {
+		self.considered += result.considered();
+		self.new_keys += result.imported();
+		self.unchanged += result.unchanged();
+		self.new_user_ids += result.new_user_ids();
+		self.new_subkeys += result.new_subkeys();
+		self.new_signatures += result.new_signatures();
+		self.new_revocations += result.new_revocations();
+		for import in result.imports() {
+			let fingerprint = match import.fingerprint() {
+				Ok(fingerprint) if import.result().is_ok() => fingerprint,
+				_ => continue,
+			};
+			match self.keys.iter_mut().find(|(fpr, _)| fpr == fingerprint) {
+				Some((_, status)) => *status |= import.status(),
+				None => {
+					self.keys.push((fingerprint.to_string(), import.status()))
+				}
+			}
+		}
+	}
+
+	/// Returns the fingerprints of the imported keys.
+	pub fn get_fingerprints(&self) -> Vec<String> {
+		self.keys
+			.iter()
+			.map(|(fingerprint, _)| fingerprint.to_string())
+			.collect()
+	}
+
+	/// Returns `true` if the import does not change the keyring.
+	pub fn is_empty(&self) -> bool {
+		self.get_totals()
+			.iter()
+			.skip(2)
+			.all(|(_, value)| *value == 0)
+	}
+
+	/// Returns the totals as (name, value) pairs.
+	pub fn get_totals(&self) -> Vec<(&str, u32)> {
+		vec![
+			("keys", self.considered),
+			("unchanged keys", self.unchanged),
+			("new keys", self.new_keys),
+			("new user IDs", self.new_user_ids),
+			("new subkeys", self.new_subkeys),
+			("new signatures", self.new_signatures),
+			("new revocations", self.new_revocations),
+		]
+	}
+
+	/// Returns the lines that show the totals and the changes of each key.
+	pub fn get_lines(&self) -> Vec<String> {
+		let mut lines = self
+			.get_totals()
+			.iter()
+			.map(|(name, value)| format!("{}: {}", name, value))
+			.collect::<Vec<String>>();
+		if !self.keys.is_empty() {
+			lines.push(String::new());
+		}
+		for (fingerprint, status) in &self.keys {
+			lines.push(format!("{}: {}", fingerprint, get_changes(*status)));
+		}
+		lines
+	}
+}
+
+/// Returns the description of the changes in the given import status.
+fn get_changes(status: ImportFlags) -> String {
+	let changes = [
+		(ImportFlags::NEW, "new key"),
+		(ImportFlags::UID, "new user IDs"),
+		(ImportFlags::SIG, "new signatures"),
+		(ImportFlags::SUBKEY, "new subkeys"),
+		(ImportFlags::SECRET, "secret key"),
+	]
+	.iter()
+	.filter(|(flag, _)| status.contains(*flag))
+	.map(|(_, change)| *change)
+	.collect::<Vec<&str>>();
+	if changes.is_empty() {
+		String::from("unchanged")
+	} else {
+		changes.join(", ")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_import() {
+		let summary = ImportSummary {
+			considered: 2,
+			unchanged: 2,
+			..ImportSummary::default()
+		};
+		assert!(summary.is_empty());
+		let summary = ImportSummary {
+			new_signatures: 3,
+			..summary
+		};
+		assert!(!summary.is_empty());
+		assert_eq!(
+			vec![
+				("keys", 2),
+				("unchanged keys", 2),
+				("new keys", 0),
+				("new user IDs", 0),
+				("new subkeys", 0),
+				("new signatures", 3),
+				("new revocations", 0),
+			],
+			summary.get_totals()
+		);
+		let summary = ImportSummary {
+			keys: vec![
+				(String::from("ABC"), ImportFlags::NEW | ImportFlags::SECRET),
+				(String::from("DEF"), ImportFlags::SIG),
+				(String::from("GHI"), ImportFlags::empty()),
+			],
+			..summary
+		};
+		assert_eq!(vec!["ABC", "DEF", "GHI"], summary.get_fingerprints());
+		assert_eq!(
+			vec![
+				"",
+				"ABC: new key, secret key",
+				"DEF: new signatures",
+				"GHI: unchanged"
+			],
+			summary.get_lines()[7..].to_vec()
+		);
+	}
+}
@@ -1,7 +1,15 @@
+use crate::gpg::card::CardSlot;
+use crate::gpg::cert::Certification;
 use crate::gpg::handler;
-use gpgme::{Key, SignatureNotation, Subkey, UserId, UserIdSignature};
+use chrono::{DateTime, Utc};
+use gpgme::{
+	Key, KeyListMode, SignatureNotation, Subkey, UserId, UserIdSignature,
+	Validity,
+};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Type of the key.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -77,21 +85,292 @@ impl KeyDetail {
 	}
 }
 
+/// Validity of a key.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyValidity {
+	/// Key is revoked.
+	Revoked,
+	/// Key is expired.
+	Expired,
+	/// Key is disabled.
+	Disabled,
+	/// Key is invalid.
+	Invalid,
+	/// Validity is not known.
+	Unknown,
+	/// Validity is not defined.
+	Undefined,
+	/// Key is never valid (not trusted).
+	Never,
+	/// Key is marginally valid.
+	Marginal,
+	/// Key is fully valid.
+	Full,
+	/// Key is ultimately valid.
+	Ultimate,
+}
+
+impl Display for KeyValidity {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}", format!("{:?}", self).to_lowercase())
+	}
+}
+
+impl FromStr for KeyValidity {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"revoked" => Ok(Self::Revoked),
+			"expired" => Ok(Self::Expired),
+			"disabled" => Ok(Self::Disabled),
+			"invalid" => Ok(Self::Invalid),
+			"unknown" => Ok(Self::Unknown),
+			"undefined" => Ok(Self::Undefined),
+			"never" => Ok(Self::Never),
+			"marginal" => Ok(Self::Marginal),
+			"full" => Ok(Self::Full),
+			"ultimate" => Ok(Self::Ultimate),
+			_ => Err(()),
+		}
+	}
+}
+
+impl From<Validity> for KeyValidity {
+	fn from(validity: Validity) -> Self {
+		match validity {
+			Validity::Undefined => Self::Undefined,
+			Validity::Never => Self::Never,
+			Validity::Marginal => Self::Marginal,
+			Validity::Full => Self::Full,
+			Validity::Ultimate => Self::Ultimate,
+			_ => Self::Unknown,
+		}
+	}
+}
+
+/// Field of the key information.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyField {
+	/// Capability flags of the subkeys (e.g. `[sc--]`).
+	Flags,
+	/// Public key algorithm of the subkeys.
+	Algorithm,
+	/// ID or fingerprint of the subkeys.
+	Id,
+	/// Creation and expiration time of the subkeys.
+	Expiry,
+	/// Validity of the user IDs.
+	Trust,
+	/// User IDs of the key.
+	UserId,
+	/// Validity and owner trust of the key.
+	Validity,
+}
+
+impl Display for KeyField {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Flags => "flags",
+				Self::Algorithm => "algo",
+				Self::Id => "id",
+				Self::Expiry => "expiry",
+				Self::Trust => "trust",
+				Self::UserId => "uid",
+				Self::Validity => "validity",
+			}
+		)
+	}
+}
+
+impl FromStr for KeyField {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"flags" => Ok(Self::Flags),
+			"algo" => Ok(Self::Algorithm),
+			"id" => Ok(Self::Id),
+			"expiry" => Ok(Self::Expiry),
+			"trust" => Ok(Self::Trust),
+			"uid" => Ok(Self::UserId),
+			"validity" => Ok(Self::Validity),
+			_ => Err(format!("unknown field: {}", s)),
+		}
+	}
+}
+
+impl KeyField {
+	/// Checks if the field belongs to the subkeys.
+	fn is_subkey_field(&self) -> bool {
+		matches!(
+			self,
+			Self::Flags | Self::Algorithm | Self::Id | Self::Expiry
+		)
+	}
+
+	/// Checks if the field belongs to the users.
+	fn is_user_field(&self) -> bool {
+		matches!(self, Self::Trust | Self::UserId)
+	}
+}
+
+/// Fields to show in the columns of the keys table.
+///
+/// Columns are separated by `|` and the fields of a column
+/// are separated by `,` (e.g. `flags,algo,id,expiry|trust,uid`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnLayout {
+	/// Fields of the first column.
+	pub first: Vec<KeyField>,
+	/// Fields of the second column.
+	pub second: Vec<KeyField>,
+}
+
+impl Default for ColumnLayout {
+	fn default() -> Self {
+		Self {
+			first: vec![
+				KeyField::Flags,
+				KeyField::Algorithm,
+				KeyField::Id,
+				KeyField::Expiry,
+			],
+			second: vec![KeyField::Trust, KeyField::UserId],
+		}
+	}
+}
+
+impl Display for ColumnLayout {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		let join = |fields: &[KeyField]| {
+			fields
+				.iter()
+				.map(|field| field.to_string())
+				.collect::<Vec<String>>()
+				.join(",")
+		};
+		write!(f, "{}|{}", join(&self.first), join(&self.second))
+	}
+}
+
+impl FromStr for ColumnLayout {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let columns = s
+			.split('|')
+			.map(|column| {
+				column
+					.split(',')
+					.filter(|field| !field.trim().is_empty())
+					.map(KeyField::from_str)
+					.collect::<Result<Vec<KeyField>, String>>()
+			})
+			.collect::<Result<Vec<Vec<KeyField>>, String>>()?;
+		match columns.as_slice() {
+			[first, second] if !first.is_empty() && !second.is_empty() => {
+				Ok(Self {
+					first: first.to_vec(),
+					second: second.to_vec(),
+				})
+			}
+			_ => Err(String::from("expected two columns of fields")),
+		}
+	}
+}
+
+/// Serializable information about a subkey.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubkeyData {
+	/// Fingerprint of the subkey.
+	pub fingerprint: String,
+	/// Public key algorithm of the subkey.
+	pub algorithm: String,
+	/// Capabilities and states of the subkey.
+	pub flags: Vec<String>,
+	/// Creation time in RFC 3339 format.
+	pub created: Option<String>,
+	/// Expiration time in RFC 3339 format.
+	pub expires: Option<String>,
+	/// Serial number of the card that stores the subkey.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub card_serial: Option<String>,
+}
+
+/// Serializable information about a user ID.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserIdData {
+	/// User ID.
+	pub uid: String,
+	/// Name of the user.
+	pub name: String,
+	/// Email address of the user.
+	pub email: String,
+	/// Validity of the user ID.
+	pub validity: KeyValidity,
+}
+
+/// Serializable information about a key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyData {
+	/// Fingerprint of the primary key.
+	pub fingerprint: String,
+	/// Validity of the key.
+	pub validity: KeyValidity,
+	/// Owner trust of the key.
+	pub owner_trust: KeyValidity,
+	/// Subkeys (including the primary key).
+	pub subkeys: Vec<SubkeyData>,
+	/// User IDs of the key.
+	pub user_ids: Vec<UserIdData>,
+}
+
+/// Lowercase values of a key for searching.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchIndex {
+	/// Names of the users.
+	pub names: Vec<String>,
+	/// Email addresses of the users.
+	pub emails: Vec<String>,
+	/// Fingerprint of the key.
+	pub fingerprint: String,
+	/// Joined contents of the table row.
+	pub row: String,
+}
+
+impl From<&GpgKey> for SearchIndex {
+	fn from(key: &GpgKey) -> Self {
+		let lowercase = |values: Vec<String>| {
+			values.iter().map(|v| v.to_lowercase()).collect()
+		};
+		Self {
+			names: lowercase(key.get_user_names()),
+			emails: lowercase(key.get_user_emails()),
+			fingerprint: key.get_fingerprint().to_lowercase(),
+			row: key.get_row_text().to_lowercase(),
+		}
+	}
+}
+
 /// Representation of a key.
 #[derive(Clone, Debug)]
 pub struct GpgKey {
 	/// GPGME Key type.
 	inner: Key,
-	/// Level of detail to show about key information.
-	pub detail: KeyDetail,
+	/// Search index of the key.
+	search_index: Arc<SearchIndex>,
 }
 
 impl From<Key> for GpgKey {
 	fn from(key: Key) -> Self {
-		Self {
+		let mut key = Self {
 			inner: key,
-			detail: KeyDetail::Minimum,
-		}
+			search_index: Arc::default(),
+		};
+		key.search_index = Arc::new(SearchIndex::from(&key));
+		key
 	}
 }
 
@@ -120,46 +399,465 @@ impl GpgKey {
 		}
 	}
 
+	/// Returns the names of the users.
+	pub fn get_user_names(&self) -> Vec<String> {
+		self.inner
+			.user_ids()
+			.filter_map(|user| user.name().ok())
+			.map(String::from)
+			.collect()
+	}
+
+	/// Returns the email addresses of the users.
+	pub fn get_user_emails(&self) -> Vec<String> {
+		self.inner
+			.user_ids()
+			.filter_map(|user| user.email().ok())
+			.map(String::from)
+			.collect()
+	}
+
+	/// Returns the search index of the key.
+	///
+	/// It is built once for speeding up the search.
+	pub fn get_search_index(&self) -> &SearchIndex {
+		&self.search_index
+	}
+
+	/// Returns the contents of the table row for searching.
+	///
+	/// All the fields are included in the standard detail level
+	/// along with the key ID.
+	pub fn get_row_text(&self) -> String {
+		let layout = ColumnLayout::default();
+		let fields = [vec![KeyField::Validity], layout.first, layout.second];
+		let mut row = vec![self.get_id()];
+		row.extend(self.get_info(
+			&fields.concat(),
+			KeyDetail::Standard,
+			"%F",
+			false,
+			false,
+		));
+		row.join("\n")
+	}
+
+	/// Returns the user IDs of the key.
+	pub fn get_user_ids(&self) -> Vec<String> {
+		self.inner
+			.user_ids()
+			.filter_map(|user| user.id().ok())
+			.map(String::from)
+			.collect()
+	}
+
+	/// Returns `true` if the secret key is available.
+	pub fn has_secret(&self) -> bool {
+		self.inner.has_secret()
+	}
+
+	/// Returns the IDs of the subkeys that are stored on a smartcard
+	/// but no longer usable (i.e. expired or revoked).
+	pub fn get_stale_card_stubs(&self) -> Vec<String> {
+		self.inner
+			.subkeys()
+			.filter(|subkey| {
+				subkey.is_card_key()
+					&& (subkey.is_expired() || subkey.is_revoked())
+			})
+			.map(|subkey| format!("0x{}", subkey.id().unwrap_or("[?]")))
+			.collect()
+	}
+
+	/// Returns `true` if the key is expired.
+	pub fn is_expired(&self) -> bool {
+		self.inner.is_expired()
+	}
+
+	/// Returns the creation time of the primary key.
+	pub fn get_creation_time(&self) -> Option<DateTime<Utc>> {
+		self.inner
+			.primary_key()?
+			.creation_time()
+			.map(DateTime::<Utc>::from)
+	}
+
+	/// Returns the algorithm of the primary key (e.g. `rsa4096`).
+	pub fn get_algorithm(&self) -> String {
+		self.inner
+			.primary_key()
+			.and_then(|key| key.algorithm_name().ok())
+			.unwrap_or_else(|| String::from("[?]"))
+	}
+
+	/// Returns the expiration time of the primary key.
+	pub fn get_expiration_time(&self) -> Option<DateTime<Utc>> {
+		self.inner
+			.primary_key()?
+			.expiration_time()
+			.map(DateTime::<Utc>::from)
+	}
+
+	/// Returns the number of days left until the key expires.
+	///
+	/// Returns `None` if the key does not expire.
+	pub fn get_days_until_expiry(&self) -> Option<i64> {
+		self.get_expiration_time()
+			.map(|time| (time - Utc::now()).num_days())
+	}
+
+	/// Returns the validity of the key.
+	///
+	/// Revoked, expired, disabled and invalid keys take precedence
+	/// over the validity of the primary user ID.
+	pub fn get_validity(&self) -> KeyValidity {
+		if self.inner.is_revoked() {
+			KeyValidity::Revoked
+		} else if self.inner.is_expired() {
+			KeyValidity::Expired
+		} else if self.inner.is_disabled() {
+			KeyValidity::Disabled
+		} else if self.inner.is_invalid() {
+			KeyValidity::Invalid
+		} else {
+			self.inner
+				.user_ids()
+				.next()
+				.map(|user| KeyValidity::from(user.validity()))
+				.unwrap_or(KeyValidity::Unknown)
+		}
+	}
+
+	/// Returns the owner trust of the key.
+	pub fn get_owner_trust(&self) -> KeyValidity {
+		KeyValidity::from(self.inner.owner_trust())
+	}
+
+	/// Returns `true` if the key is revoked.
+	pub fn is_revoked(&self) -> bool {
+		self.inner.is_revoked()
+	}
+
+	/// Returns `true` if the key is invalid.
+	pub fn is_invalid(&self) -> bool {
+		self.inner.is_invalid()
+	}
+
+	/// Returns `true` if the key is expired, revoked or invalid.
+	pub fn is_unusable(&self) -> bool {
+		self.is_expired() || self.is_revoked() || self.is_invalid()
+	}
+
+	/// Returns `true` if the key can be used for encryption.
+	pub fn can_encrypt(&self) -> bool {
+		self.inner.can_encrypt()
+	}
+
+	/// Returns `true` if the key can be used for signing.
+	pub fn can_sign(&self) -> bool {
+		self.inner.can_sign()
+	}
+
+	/// Returns `true` if the key can be used for authentication.
+	pub fn can_authenticate(&self) -> bool {
+		self.inner.can_authenticate()
+	}
+
+	/// Returns the keygrips of the usable authentication subkeys.
+	pub fn get_auth_keygrips(&self) -> Vec<String> {
+		self.inner
+			.subkeys()
+			.filter(|subkey| {
+				subkey.can_authenticate()
+					&& !subkey.is_expired()
+					&& !subkey.is_revoked()
+			})
+			.filter_map(|subkey| subkey.keygrip().ok().map(String::from))
+			.collect()
+	}
+
+	/// Returns the fingerprints of the subkeys (primary key first).
+	pub fn get_subkey_fingerprints(&self) -> Vec<String> {
+		self.inner
+			.subkeys()
+			.map(|subkey| subkey.fingerprint().unwrap_or("[?]").to_string())
+			.collect()
+	}
+
+	/// Returns the keygrips of the subkeys (primary key first).
+	pub fn get_subkey_keygrips(&self) -> Vec<String> {
+		self.inner
+			.subkeys()
+			.map(|subkey| subkey.keygrip().unwrap_or("[?]").to_string())
+			.collect()
+	}
+
+	/// Returns the subkeys (0 for the primary key) that can be moved
+	/// to the card along with the matching slots.
+	pub fn get_card_candidates(&self) -> Vec<(usize, CardSlot)> {
+		self.inner
+			.subkeys()
+			.enumerate()
+			.filter(|(_, subkey)| {
+				subkey.is_secret()
+					&& !subkey.is_card_key()
+					&& !subkey.is_expired()
+					&& !subkey.is_revoked()
+			})
+			.filter_map(|(i, subkey)| {
+				if subkey.can_sign() {
+					Some((i, CardSlot::Signature))
+				} else if subkey.can_encrypt() {
+					Some((i, CardSlot::Encryption))
+				} else if subkey.can_authenticate() {
+					Some((i, CardSlot::Authentication))
+				} else {
+					None
+				}
+			})
+			.collect()
+	}
+
+	/// Returns `true` if the key can be used for certification.
+	pub fn can_certify(&self) -> bool {
+		self.inner.can_certify()
+	}
+
+	/// Returns the serializable information about the key.
+	///
+	/// Names and email addresses are masked if `redact` is set.
+	pub fn get_data(&self, redact: bool) -> KeyData {
+		let format_time = |time: Option<std::time::SystemTime>| {
+			time.map(|time| DateTime::<Utc>::from(time).to_rfc3339())
+		};
+		let redact_value = |value: Result<&str, _>| {
+			let value = value.unwrap_or_default();
+			if redact && !value.is_empty() {
+				handler::redact_user_id(value)
+			} else {
+				value.to_string()
+			}
+		};
+		KeyData {
+			fingerprint: self.get_fingerprint(),
+			validity: self.get_validity(),
+			owner_trust: self.get_owner_trust(),
+			subkeys: self
+				.inner
+				.subkeys()
+				.map(|subkey| SubkeyData {
+					fingerprint: subkey
+						.fingerprint()
+						.unwrap_or("[?]")
+						.to_string(),
+					algorithm: subkey
+						.algorithm_name()
+						.unwrap_or_else(|_| String::from("[?]")),
+					flags: handler::get_subkey_flag_names(subkey)
+						.into_iter()
+						.map(String::from)
+						.collect(),
+					created: format_time(subkey.creation_time()),
+					expires: format_time(subkey.expiration_time()),
+					card_serial: subkey
+						.card_serial_number()
+						.ok()
+						.map(String::from),
+				})
+				.collect(),
+			user_ids: self
+				.inner
+				.user_ids()
+				.map(|user| UserIdData {
+					uid: redact_value(user.id()),
+					name: redact_value(user.name()),
+					email: redact_value(user.email()),
+					validity: KeyValidity::from(user.validity()),
+				})
+				.collect(),
+		}
+	}
+
+	/// Returns information about the given fields of the key
+	/// in the given detail level.
+	///
+	/// Validity of the key comes first (if given), then the
+	/// information about the subkeys and the users.
+	/// Names and email addresses are masked if `redact` is set.
+	pub fn get_info(
+		&self,
+		fields: &[KeyField],
+		detail: KeyDetail,
+		date_format: &str,
+		truncate: bool,
+		redact: bool,
+	) -> Vec<String> {
+		let mut info = Vec::new();
+		if fields.contains(&KeyField::Validity) {
+			info.push(format!(
+				"[{}] trust: {}",
+				self.get_validity(),
+				self.get_owner_trust()
+			));
+		}
+		if fields.iter().any(|field| field.is_subkey_field()) {
+			info.extend(self.get_subkey_fields(
+				fields,
+				detail,
+				date_format,
+				truncate,
+			));
+		}
+		if fields.iter().any(|field| field.is_user_field()) {
+			info.extend(self.get_user_fields(
+				fields,
+				detail,
+				date_format,
+				truncate,
+				redact,
+			));
+		}
+		info
+	}
+
 	/// Returns information about the subkeys.
-	pub fn get_subkey_info(&self, truncate: bool) -> Vec<String> {
+	///
+	/// Times are shown as relative in the standard detail level
+	/// and formatted with the given date format in the full detail level.
+	pub fn get_subkey_info(
+		&self,
+		detail: KeyDetail,
+		date_format: &str,
+		truncate: bool,
+	) -> Vec<String> {
+		self.get_subkey_fields(
+			&ColumnLayout::default().first,
+			detail,
+			date_format,
+			truncate,
+		)
+	}
+
+	/// Returns the given fields of the subkeys.
+	fn get_subkey_fields(
+		&self,
+		fields: &[KeyField],
+		detail: KeyDetail,
+		date_format: &str,
+		truncate: bool,
+	) -> Vec<String> {
 		let mut key_info = Vec::new();
 		let subkeys = self.inner.subkeys().collect::<Vec<Subkey>>();
 		for (i, subkey) in subkeys.iter().enumerate() {
-			key_info.push(format!(
-				"[{}] {}/{}",
-				handler::get_subkey_flags(*subkey),
-				subkey
-					.algorithm_name()
-					.unwrap_or_else(|_| { String::from("[?]") }),
-				if truncate {
-					subkey.id()
-				} else {
-					subkey.fingerprint()
+			let mut line = String::new();
+			let mut previous_field = None;
+			for field in fields {
+				let value = match field {
+					KeyField::Flags => {
+						format!(
+							"[{}]{}",
+							handler::get_subkey_flags(*subkey),
+							handler::get_card_badge(*subkey)
+						)
+					}
+					KeyField::Algorithm => subkey
+						.algorithm_name()
+						.unwrap_or_else(|_| String::from("[?]")),
+					KeyField::Id => if truncate {
+						subkey.id()
+					} else {
+						subkey.fingerprint()
+					}
+					.unwrap_or("[?]")
+					.to_string(),
+					_ => continue,
+				};
+				if !line.is_empty() {
+					line.push(
+						if previous_field == Some(KeyField::Algorithm)
+							&& field == &KeyField::Id
+						{
+							'/'
+						} else {
+							' '
+						},
+					);
 				}
-				.unwrap_or("[?]"),
-			));
-			if self.detail == KeyDetail::Minimum {
+				line.push_str(&value);
+				previous_field = Some(*field);
+			}
+			if !line.is_empty() {
+				key_info.push(line.clone());
+			}
+			if detail == KeyDetail::Minimum {
 				break;
 			}
+			if !fields.contains(&KeyField::Expiry) {
+				continue;
+			}
 			key_info.push(format!(
-				"{}      └─{}",
-				if i != subkeys.len() - 1 { "|" } else { " " },
+				"{}{}{}",
+				if line.is_empty() {
+					""
+				} else if i != subkeys.len() - 1 {
+					"|      └─"
+				} else {
+					"       └─"
+				},
 				handler::get_subkey_time(
 					*subkey,
-					if truncate { "%Y" } else { "%F" }
-				)
+					match detail {
+						KeyDetail::Full if truncate => Some("%Y"),
+						KeyDetail::Full => Some(date_format),
+						_ => None,
+					},
+					truncate
+				),
+				match subkey.card_serial_number() {
+					Ok(serial) if detail == KeyDetail::Full => {
+						format!(" [card:{}]", serial)
+					}
+					_ => String::new(),
+				}
 			));
 		}
 		key_info
 	}
 
 	/// Returns information about the users of the key.
-	pub fn get_user_info(&self, truncate: bool) -> Vec<String> {
+	///
+	/// Signature times are formatted with the given date format.
+	/// Names and email addresses are masked if `redact` is set.
+	pub fn get_user_info(
+		&self,
+		detail: KeyDetail,
+		date_format: &str,
+		truncate: bool,
+		redact: bool,
+	) -> Vec<String> {
+		self.get_user_fields(
+			&ColumnLayout::default().second,
+			detail,
+			date_format,
+			truncate,
+			redact,
+		)
+	}
+
+	/// Returns the given fields of the users.
+	fn get_user_fields(
+		&self,
+		fields: &[KeyField],
+		detail: KeyDetail,
+		date_format: &str,
+		truncate: bool,
+		redact: bool,
+	) -> Vec<String> {
 		let mut user_info = Vec::new();
 		let user_ids = self.inner.user_ids().collect::<Vec<UserId>>();
 		for (i, user) in user_ids.iter().enumerate() {
 			user_info.push(format!(
-				"{}[{}] {}",
+				"{}{}",
 				if i == 0 {
 					""
 				} else if i == user_ids.len() - 1 {
@@ -167,70 +865,181 @@ impl GpgKey {
 				} else {
 					" ├─"
 				},
-				user.validity(),
-				if truncate { user.email() } else { user.id() }
-					.unwrap_or("[?]")
+				fields
+					.iter()
+					.filter_map(|field| match field {
+						KeyField::Trust =>
+							Some(format!("[{}]", user.validity())),
+						KeyField::UserId => {
+							let user_id =
+								if truncate { user.email() } else { user.id() }
+									.unwrap_or("[?]");
+							Some(if redact {
+								handler::redact_user_id(user_id)
+							} else {
+								user_id.to_string()
+							})
+						}
+						_ => None,
+					})
+					.collect::<Vec<String>>()
+					.join(" ")
 			));
-			if self.detail == KeyDetail::Minimum {
+			if detail == KeyDetail::Minimum {
 				break;
 			}
-			if self.detail == KeyDetail::Full {
+			if detail == KeyDetail::Full {
+				if let Some(info) = user.tofu_info() {
+					user_info.push(format!(
+						" {}  {}{}",
+						Self::get_user_padding(user_ids.len(), i),
+						if user.signatures().next().is_some() {
+							"├─"
+						} else {
+							"└─"
+						},
+						handler::get_tofu_info(
+							info,
+							if truncate { "%Y" } else { date_format }
+						)
+					));
+				}
 				user_info.extend(self.get_user_signatures(
 					user,
 					user_ids.len(),
 					i,
+					date_format,
 					truncate,
+					redact,
 				));
 			}
 		}
 		user_info
 	}
 
+	/// Returns the padding of the lines under an user.
+	fn get_user_padding(user_count: usize, user_index: usize) -> &'static str {
+		if user_count == 1 {
+			" "
+		} else if user_index == user_count - 1 {
+			"    "
+		} else if user_index == 0 {
+			"│"
+		} else {
+			"│   "
+		}
+	}
+
+	/// Returns `true` if the key is listed with its details.
+	///
+	/// Signatures and TOFU information of the user IDs are only
+	/// available for the keys that are listed in the detailed mode.
+	pub fn has_details(&self) -> bool {
+		self.inner.key_list_mode().contains(KeyListMode::SIGS)
+	}
+
+	/// Replaces the underlying key with the given listing of it.
+	pub fn set_inner(&mut self, key: &GpgKey) {
+		self.inner = key.inner.clone();
+		self.search_index = Arc::clone(&key.search_index);
+	}
+
+	/// Returns the IDs of the keys that certified the users of the key.
+	///
+	/// Self signatures, revocations and invalid/expired
+	/// signatures are not taken into account.
+	pub fn get_certifiers(&self) -> Vec<String> {
+		let mut certifiers = Vec::new();
+		for sig in self.inner.user_ids().flat_map(|user| user.signatures()) {
+			if sig.signer_key_id() == self.inner.id()
+				|| sig.is_revocation()
+				|| sig.is_invalid()
+				|| sig.is_expired()
+			{
+				continue;
+			}
+			if let Ok(key_id) = sig.signer_key_id() {
+				let key_id = format!("0x{}", key_id);
+				if !certifiers.contains(&key_id) {
+					certifiers.push(key_id);
+				}
+			}
+		}
+		certifiers
+	}
+
+	/// Returns the certifications that are made by the given key (ID).
+	///
+	/// Self signatures are not included and the certifications
+	/// that have a matching revocation are marked as revoked.
+	pub fn get_certifications_by(&self, signer_id: &str) -> Vec<Certification> {
+		let mut certifications = Vec::new();
+		if self.inner.id() == Ok(signer_id) {
+			return certifications;
+		}
+		for user in self.inner.user_ids() {
+			let signatures = user
+				.signatures()
+				.filter(|sig| sig.signer_key_id() == Ok(signer_id))
+				.collect::<Vec<UserIdSignature>>();
+			let revoked = signatures.iter().any(|sig| sig.is_revocation());
+			for sig in signatures.iter().filter(|sig| !sig.is_revocation()) {
+				certifications.push(Certification {
+					key_id: self.get_id(),
+					fingerprint: self.get_fingerprint(),
+					user_id: user.id().unwrap_or("[?]").to_string(),
+					signer_id: format!("0x{}", signer_id),
+					created: sig.creation_time().map(DateTime::<Utc>::from),
+					expires: sig.expiration_time().map(DateTime::<Utc>::from),
+					exportable: sig.is_exportable(),
+					revoked,
+				});
+			}
+		}
+		certifications
+	}
+
+	/// Returns the IDs of the keys that made the certifications
+	/// (including the self signatures) in the order of user IDs.
+	///
+	/// Revocations are not included.
+	pub fn get_certification_signers(&self) -> Vec<String> {
+		self.inner
+			.user_ids()
+			.flat_map(|user| user.signatures())
+			.filter(|sig| !sig.is_revocation())
+			.map(|sig| sig.signer_key_id().unwrap_or("[?]").to_string())
+			.collect()
+	}
+
+	/// Returns `true` if the key has TOFU information.
+	pub fn has_tofu_info(&self) -> bool {
+		self.inner.user_ids().any(|user| user.tofu_info().is_some())
+	}
+
 	/// Returns the signature information of an user.
 	fn get_user_signatures(
 		&self,
 		user: &UserId,
 		user_count: usize,
 		user_index: usize,
+		date_format: &str,
 		truncate: bool,
+		redact: bool,
 	) -> Vec<String> {
 		let mut user_signatures = Vec::new();
 		let signatures = user.signatures().collect::<Vec<UserIdSignature>>();
 		for (i, sig) in signatures.iter().enumerate() {
-			let padding = if user_count == 1 {
-				" "
-			} else if user_index == user_count - 1 {
-				"    "
-			} else if user_index == 0 {
-				"│"
-			} else {
-				"│   "
-			};
+			let padding = Self::get_user_padding(user_count, user_index);
 			user_signatures.push(format!(
-				" {}  {}[{:x}] {} {}",
+				" {}  {}{}",
 				padding,
 				if i == signatures.len() - 1 {
 					"└─"
 				} else {
 					"├─"
 				},
-				sig.cert_class(),
-				if sig.signer_key_id() == self.inner.id() {
-					String::from("selfsig")
-				} else if truncate {
-					sig.signer_key_id().unwrap_or("[?]").to_string()
-				} else {
-					let user_id = sig.signer_user_id().unwrap_or("[-]");
-					format!(
-						"{} {}",
-						sig.signer_key_id().unwrap_or("[?]"),
-						if user_id.is_empty() { "[?]" } else { user_id }
-					)
-				},
-				handler::get_signature_time(
-					*sig,
-					if truncate { "%Y" } else { "%F" }
-				)
+				self.get_signature_label(sig, date_format, truncate, redact)
 			));
 			let notations = sig.notations().collect::<Vec<SignatureNotation>>();
 			if !notations.is_empty() {
@@ -243,6 +1052,99 @@ impl GpgKey {
 		user_signatures
 	}
 
+	/// Returns the class, signer and time of the given signature.
+	fn get_signature_label(
+		&self,
+		sig: &UserIdSignature,
+		date_format: &str,
+		truncate: bool,
+		redact: bool,
+	) -> String {
+		format!(
+			"[{:x}] {} {}",
+			sig.cert_class(),
+			if sig.signer_key_id() == self.inner.id() {
+				String::from("selfsig")
+			} else if truncate {
+				sig.signer_key_id().unwrap_or("[?]").to_string()
+			} else {
+				let user_id = match sig.signer_user_id() {
+					Ok("") => String::from("[?]"),
+					Ok(user_id) if redact => handler::redact_user_id(user_id),
+					Ok(user_id) => user_id.to_string(),
+					Err(_) => String::from("[-]"),
+				};
+				format!("{} {}", sig.signer_key_id().unwrap_or("[?]"), user_id)
+			},
+			handler::get_signature_time(
+				*sig,
+				if truncate { "%Y" } else { date_format }
+			)
+		)
+	}
+
+	/// Returns the labels of the subkeys (primary key first).
+	///
+	/// Each label contains the flags, algorithm, fingerprint
+	/// and times of the subkey formatted with the given date format.
+	pub fn get_subkey_labels(&self, date_format: &str) -> Vec<String> {
+		self.inner
+			.subkeys()
+			.map(|subkey| {
+				format!(
+					"[{}]{} {}/{} {}{}",
+					handler::get_subkey_flags(subkey),
+					handler::get_card_badge(subkey),
+					subkey
+						.algorithm_name()
+						.unwrap_or_else(|_| String::from("[?]")),
+					subkey.fingerprint().unwrap_or("[?]"),
+					handler::get_subkey_time(subkey, Some(date_format), false),
+					match subkey.card_serial_number() {
+						Ok(serial) => format!(" [card:{}]", serial),
+						Err(_) => String::new(),
+					}
+				)
+			})
+			.collect()
+	}
+
+	/// Returns the labels of the user IDs along with
+	/// the labels of their TOFU information and signatures.
+	///
+	/// Names and email addresses are masked if `redact` is set.
+	pub fn get_user_labels(
+		&self,
+		date_format: &str,
+		redact: bool,
+	) -> Vec<(String, Vec<String>)> {
+		self.inner
+			.user_ids()
+			.map(|user| {
+				let user_id = user.id().unwrap_or("[?]");
+				let mut children = Vec::new();
+				if let Some(info) = user.tofu_info() {
+					children.push(handler::get_tofu_info(info, date_format));
+				}
+				children.extend(user.signatures().map(|sig| {
+					self.get_signature_label(&sig, date_format, false, redact)
+				}));
+				(
+					format!(
+						"[{}] {}",
+						user.validity(),
+						if redact {
+							handler::redact_user_id(user_id)
+						} else {
+							user_id.to_string()
+						}
+					),
+					children,
+				)
+			})
+			.collect()
+	}
+
 	/// Returns the notations of the given signature.
 	fn get_signature_notations(
 		&self,
@@ -261,13 +1163,7 @@ impl GpgKey {
 					} else {
 						"├─"
 					},
-					if notation.is_critical() {
-						"!"
-					} else if notation.is_human_readable() {
-						"h"
-					} else {
-						"?"
-					},
+					handler::get_notation_flag(notation),
 					notation.name().unwrap_or("?"),
 					notation.value().unwrap_or("?"),
 				)
@@ -290,26 +1186,72 @@ mod tests {
 		let args = Args::default();
 		let config = GpgConfig::new(&args)?;
 		let mut context = GpgContext::new(config)?;
-		let mut keys = context.get_keys(KeyType::Public, None)?;
-		let key = &mut keys[0];
-		key.detail.increase();
-		assert_eq!(KeyDetail::Standard, key.detail);
-		assert_eq!(Ok(key.detail), KeyDetail::from_str("standard"));
-		key.detail.increase();
-		assert_eq!(KeyDetail::Full, key.detail);
-		assert_eq!("full", key.detail.to_string());
+		let keys = context.get_keys(KeyType::Public, None)?;
+		let key = &keys[0];
+		let mut detail = KeyDetail::Minimum;
+		detail.increase();
+		assert_eq!(KeyDetail::Standard, detail);
+		assert_eq!(Ok(detail), KeyDetail::from_str("standard"));
+		detail.increase();
+		assert_eq!(KeyDetail::Full, detail);
+		assert_eq!("full", detail.to_string());
 		assert!(key
-			.get_subkey_info(true)
+			.get_subkey_info(detail, "%F", true)
 			.join("\n")
 			.contains(&key.get_id().replace("0x", "")));
 		assert!(key
-			.get_subkey_info(false)
+			.get_subkey_info(detail, "%F", false)
 			.join("\n")
 			.contains(&key.get_fingerprint()));
 		assert!(key
-			.get_user_info(false)
+			.get_user_info(detail, "%F", false, false)
 			.join("\n")
 			.contains(&key.get_user_id()));
+		assert!(!key
+			.get_user_info(detail, "%F", false, true)
+			.join("\n")
+			.contains(&key.get_user_emails()[0]));
+		assert_eq!(
+			1,
+			key.get_user_info(KeyDetail::Minimum, "%F", false, false)
+				.len()
+		);
+		let layout = ColumnLayout::from_str("uid,id|expiry")
+			.map_err(|e| anyhow::anyhow!(e))?;
+		assert_eq!("uid,id|expiry", layout.to_string());
+		assert!(ColumnLayout::from_str("id").is_err());
+		assert!(ColumnLayout::from_str("id|xyz").is_err());
+		let info = key.get_info(&layout.first, detail, "%F", false, false);
+		assert!(info[0].contains(&key.get_fingerprint()));
+		assert!(info.join("\n").contains(&key.get_user_id()));
+		assert_eq!(
+			key.get_subkey_info(detail, "%F", false),
+			key.get_info(
+				&ColumnLayout::default().first,
+				detail,
+				"%F",
+				false,
+				false
+			)
+		);
+		assert!(key
+			.get_info(&[KeyField::Validity], detail, "%F", false, false)
+			.join("\n")
+			.contains(&format!("[{}]", key.get_validity())));
+		assert_eq!(
+			Ok(key.get_owner_trust()),
+			KeyValidity::from_str(&key.get_owner_trust().to_string())
+		);
+		assert!(!key.is_invalid());
+		let index = key.get_search_index();
+		assert_eq!(key.get_row_text().to_lowercase(), index.row);
+		assert!(index.row.contains(&key.get_fingerprint().to_lowercase()));
+		assert!(key.get_user_id().contains(&key.get_user_emails()[0]));
+		let data = key.get_data(false);
+		assert_eq!(key.get_fingerprint(), data.fingerprint);
+		assert_eq!(key.get_user_id(), data.user_ids[0].uid);
+		assert!(data.subkeys[0].flags.contains(&String::from("certify")));
+		assert_ne!(key.get_user_id(), key.get_data(true).user_ids[0].uid);
 		Ok(())
 	}
 }
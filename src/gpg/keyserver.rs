@@ -0,0 +1,92 @@
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::io::Write;
+
+/// Value for removing the preferred keyserver.
+pub const NO_KEYSERVER: &str = "none";
+
+/// Interactor for setting the preferred keyserver via `keyserver`.
+///
+/// It is set for all user IDs of the key.
+#[derive(Debug)]
+pub struct SetKeyserver {
+	/// URL of the keyserver ("none" for removing it).
+	url: String,
+	/// Is the `keyserver` command sent?
+	sent: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl SetKeyserver {
+	/// Constructs a new instance of `SetKeyserver`.
+	pub fn new(url: &str) -> Self {
+		Self {
+			url: url.to_string(),
+			sent: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from("keyserver"))
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"keyedit.add.keyserver" => Ok(self.url.to_string()),
+			"keyedit.confirm_keyserver" | "keyedit.save.okay" => {
+				Ok(String::from("y"))
+			}
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for SetKeyserver {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_keyserver() {
+		let mut interactor = SetKeyserver::new("hkps://keys.example.org");
+		for (prompt, response) in &[
+			("keyedit.prompt", "keyserver"),
+			("keyedit.add.keyserver", "hkps://keys.example.org"),
+			("keyedit.confirm_keyserver", "y"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+	}
+}
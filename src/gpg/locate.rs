@@ -0,0 +1,249 @@
+use crate::gpg::config::{GpgConfig, TempHomeDir};
+use anyhow::{anyhow, Result};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs;
+use std::path::Path;
+use std::process::{Command as OsCommand, ExitStatus};
+use std::str::FromStr;
+
+/// Configuration file of dirmngr (for the keyserver settings).
+const DIRMNGR_CONF: &str = "dirmngr.conf";
+
+/// Mechanisms that are tried in order by default.
+pub const DEFAULT_MECHANISMS: &[LocateMechanism] = &[
+	LocateMechanism::Local,
+	LocateMechanism::Wkd,
+	LocateMechanism::Dane,
+	LocateMechanism::Keyserver,
+];
+
+/// Mechanisms for locating a key by email address.
+///
+/// They correspond to the `--auto-key-locate` values of gpg.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LocateMechanism {
+	/// Local keyring.
+	Local,
+	/// Web Key Directory.
+	Wkd,
+	/// DANE (OPENPGPKEY records in DNS).
+	Dane,
+	/// CERT records in DNS.
+	Cert,
+	/// LDAP server of the domain.
+	Ldap,
+	/// Configured keyserver.
+	Keyserver,
+}
+
+impl Display for LocateMechanism {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Local => "local",
+				Self::Wkd => "wkd",
+				Self::Dane => "dane",
+				Self::Cert => "cert",
+				Self::Ldap => "ldap",
+				Self::Keyserver => "keyserver",
+			}
+		)
+	}
+}
+
+impl FromStr for LocateMechanism {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"local" => Ok(Self::Local),
+			"wkd" => Ok(Self::Wkd),
+			"dane" => Ok(Self::Dane),
+			"cert" => Ok(Self::Cert),
+			"ldap" => Ok(Self::Ldap),
+			"keyserver" => Ok(Self::Keyserver),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Parses the given mechanisms (separated by comma or whitespace).
+pub fn parse_mechanisms(value: &str) -> Result<Vec<LocateMechanism>> {
+	let mechanisms = value
+		.split(|c: char| c == ',' || c.is_whitespace())
+		.filter(|v| !v.is_empty())
+		.map(|v| {
+			LocateMechanism::from_str(&v.to_lowercase())
+				.map_err(|_| anyhow!("unknown mechanism: {}", v))
+		})
+		.collect::<Result<Vec<LocateMechanism>>>()?;
+	if mechanisms.is_empty() {
+		Err(anyhow!("no mechanisms are given"))
+	} else {
+		Ok(mechanisms)
+	}
+}
+
+/// Key that is found by one of the mechanisms.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocatedKey {
+	/// Mechanism that found the key.
+	pub mechanism: LocateMechanism,
+	/// Fingerprint of the key.
+	pub fingerprint: String,
+	/// Primary user ID of the key.
+	pub user_id: String,
+	/// ASCII armored key (if it is not in the keyring).
+	pub data: Option<String>,
+}
+
+impl LocatedKey {
+	/// Parses the first key in the colon listing of gpg (`--with-colons`).
+	pub fn from_colons(
+		output: &str,
+		mechanism: LocateMechanism,
+	) -> Option<Self> {
+		let mut key: Option<Self> = None;
+		for fields in output
+			.lines()
+			.map(|line| line.split(':').collect::<Vec<&str>>())
+		{
+			let value = fields.get(9).copied().unwrap_or_default();
+			match (fields.first().copied(), key.as_mut()) {
+				(Some("pub"), None) => {
+					key = Some(Self {
+						mechanism,
+						fingerprint: String::new(),
+						user_id: String::new(),
+						data: None,
+					})
+				}
+				(Some("pub"), Some(_)) => break,
+				(Some("fpr"), Some(key)) if key.fingerprint.is_empty() => {
+					key.fingerprint = value.to_string()
+				}
+				(Some("uid"), Some(key)) if key.user_id.is_empty() => {
+					key.user_id = value.replace("\\x3a", ":")
+				}
+				_ => {}
+			}
+		}
+		key.filter(|key| !key.fingerprint.is_empty())
+	}
+}
+
+/// Locates the key of the email address via the given mechanism.
+///
+/// External keys are retrieved into a temporary keyring and returned
+/// in armored format without importing them. The given function runs
+/// the gpg command and it returns `None` if the command is cancelled.
+pub fn locate_key<F>(
+	config: &GpgConfig,
+	email: &str,
+	mechanism: LocateMechanism,
+	run: F,
+) -> Result<Option<LocatedKey>>
+where
+	F: FnOnce(OsCommand) -> Result<Option<(ExitStatus, String)>>,
+{
+	let pattern = format!("<{}>", email);
+	let list_key = |home_dir: &Path| -> Result<LocatedKey> {
+		let output = OsCommand::new(&config.gpg_binary)
+			.arg("--homedir")
+			.arg(home_dir)
+			.args(["--batch", "--with-colons", "--list-keys"])
+			.arg(&pattern)
+			.output()?;
+		LocatedKey::from_colons(
+			&String::from_utf8_lossy(&output.stdout),
+			mechanism,
+		)
+		.ok_or_else(|| anyhow!("key not found"))
+	};
+	if mechanism == LocateMechanism::Local {
+		return list_key(&config.home_dir).map(Some);
+	}
+	let temp_dir = TempHomeDir::new()?;
+	let dirmngr_conf = config.home_dir.join(DIRMNGR_CONF);
+	if dirmngr_conf.exists() {
+		fs::copy(&dirmngr_conf, temp_dir.path.join(DIRMNGR_CONF))?;
+	}
+	let mut os_command = OsCommand::new(&config.gpg_binary);
+	os_command
+		.arg("--homedir")
+		.arg(&temp_dir.path)
+		.arg("--batch")
+		.arg("--auto-key-locate")
+		.arg(format!("clear,nodefault,{}", mechanism))
+		.arg("--locate-external-keys")
+		.arg(email);
+	match run(os_command)? {
+		Some((status, _)) if status.success() => {
+			let mut key = list_key(&temp_dir.path)?;
+			let output = OsCommand::new(&config.gpg_binary)
+				.arg("--homedir")
+				.arg(&temp_dir.path)
+				.args(["--batch", "--armor", "--export"])
+				.arg(&key.fingerprint)
+				.output()?;
+			key.data =
+				Some(String::from_utf8_lossy(&output.stdout).to_string());
+			Ok(Some(key))
+		}
+		Some((_, stderr)) => Err(anyhow!(
+			"{}",
+			stderr.lines().last().unwrap_or("key not found")
+		)),
+		None => Ok(None),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_locate() {
+		assert_eq!(
+			Some(vec![
+				LocateMechanism::Wkd,
+				LocateMechanism::Keyserver,
+				LocateMechanism::Local
+			]),
+			parse_mechanisms("wkd,keyserver LOCAL").ok()
+		);
+		assert!(parse_mechanisms("wkd,xyz").is_err());
+		assert!(parse_mechanisms(" ,").is_err());
+		for mechanism in DEFAULT_MECHANISMS {
+			assert_eq!(
+				Ok(*mechanism),
+				LocateMechanism::from_str(&mechanism.to_string())
+			);
+		}
+		let output = "tru::1:1640995200:0:3:1:5
+pub:-:255:22:84C7E8B2F0A4B8B8:1640995200:::-:::scSC::::::23::0:
+fpr:::::::::A1B2C3D4E5F6A1B2C3D4E5F684C7E8B2F0A4B8B8:
+uid:-::::1640995200::ABCD::Alice <alice@example.org>::::::::::0:
+uid:-::::1640995200::EF01::Alice\\x3a Work <alice@example.com>::::::::::0:
+sub:-:255:18:1E2D3C4B5A697887:1640995200::::::e::::::23:
+fpr:::::::::B1B2C3D4E5F6A1B2C3D4E5F61E2D3C4B5A697887:
+pub:-:255:22:0000000000000000:1640995200:::-:::scSC::::::23::0:
+";
+		assert_eq!(
+			Some(LocatedKey {
+				mechanism: LocateMechanism::Wkd,
+				fingerprint: String::from(
+					"A1B2C3D4E5F6A1B2C3D4E5F684C7E8B2F0A4B8B8"
+				),
+				user_id: String::from("Alice <alice@example.org>"),
+				data: None,
+			}),
+			LocatedKey::from_colons(output, LocateMechanism::Wkd)
+		);
+		assert_eq!(
+			None,
+			LocatedKey::from_colons("tru::1", LocateMechanism::Local)
+		);
+	}
+}
@@ -17,3 +17,63 @@ pub mod key;
 
 /// Handler methods.
 pub mod handler;
+
+/// Helpers for gpg-agent.
+pub mod agent;
+
+/// Smartcard operations.
+pub mod card;
+
+/// Web of trust.
+pub mod wot;
+
+/// Keyring statistics.
+pub mod stats;
+
+/// Keyring checks.
+pub mod doctor;
+
+/// Certifications made by a key.
+pub mod cert;
+
+/// Preferred keyservers.
+pub mod keyserver;
+
+/// Key preferences.
+pub mod prefs;
+
+/// Designated revokers.
+pub mod revoker;
+
+/// Expiration times.
+pub mod expire;
+
+/// Key cleaning.
+pub mod clean;
+
+/// Key editing.
+pub mod edit;
+
+/// Cache of the key listing.
+pub mod cache;
+
+/// Import summary.
+pub mod import;
+
+/// Certificate flooding detection.
+pub mod flood;
+
+/// Key discovery by email address.
+pub mod locate;
+
+/// GnuPG configuration file.
+pub mod conf;
+
+/// Recipient groups.
+pub mod group;
+
+/// Demo keys.
+pub mod demo;
+
+/// Fingerprint formats.
+pub mod fingerprint;
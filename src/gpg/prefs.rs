@@ -0,0 +1,340 @@
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::collections::HashMap;
+use std::io::Write;
+use std::mem;
+
+/// Signature subpacket type of the preferred symmetric algorithms.
+const CIPHER_SUBPACKET: u8 = 11;
+
+/// Signature subpacket type of the preferred hash algorithms.
+const HASH_SUBPACKET: u8 = 21;
+
+/// Signature subpacket type of the preferred compression algorithms.
+const COMPRESSION_SUBPACKET: u8 = 22;
+
+/// Signature subpacket type of the preferred keyserver.
+const KEYSERVER_SUBPACKET: u8 = 24;
+
+/// Signature subpackets that are parsed for the preferences.
+pub const PREFERENCE_SUBPACKETS: &[u8] = &[
+	CIPHER_SUBPACKET,
+	HASH_SUBPACKET,
+	COMPRESSION_SUBPACKET,
+	KEYSERVER_SUBPACKET,
+];
+
+/// Symmetric algorithms by their IDs.
+const CIPHERS: &[(u8, &str)] = &[
+	(1, "IDEA"),
+	(2, "3DES"),
+	(3, "CAST5"),
+	(4, "BLOWFISH"),
+	(7, "AES"),
+	(8, "AES192"),
+	(9, "AES256"),
+	(10, "TWOFISH"),
+	(11, "CAMELLIA128"),
+	(12, "CAMELLIA192"),
+	(13, "CAMELLIA256"),
+];
+
+/// Hash algorithms by their IDs.
+const HASHES: &[(u8, &str)] = &[
+	(1, "MD5"),
+	(2, "SHA1"),
+	(3, "RIPEMD160"),
+	(8, "SHA256"),
+	(9, "SHA384"),
+	(10, "SHA512"),
+	(11, "SHA224"),
+];
+
+/// Compression algorithms by their IDs.
+const COMPRESSIONS: &[(u8, &str)] =
+	&[(0, "Uncompressed"), (1, "ZIP"), (2, "ZLIB"), (3, "BZIP2")];
+
+/// Presets of the preference lists (name, preferences).
+///
+/// Empty preferences are replaced by the default preferences of `gpg`.
+pub const PREFERENCE_PRESETS: &[(&str, &str)] = &[
+	(
+		"modern",
+		"AES256 AES192 AES SHA512 SHA384 SHA256 ZLIB BZIP2 ZIP Uncompressed",
+	),
+	(
+		"compatible",
+		"AES256 AES192 AES CAMELLIA256 3DES SHA512 SHA384 SHA256 SHA224 SHA1 \
+		 ZLIB BZIP2 ZIP Uncompressed",
+	),
+	("default", ""),
+];
+
+/// Preferences of a key that are stored in its self-signatures.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyPreferences {
+	/// Preferred symmetric algorithms.
+	pub ciphers: Vec<u8>,
+	/// Preferred hash algorithms.
+	pub hashes: Vec<u8>,
+	/// Preferred compression algorithms.
+	pub compressions: Vec<u8>,
+	/// Preferred keyserver.
+	pub keyserver: Option<String>,
+	/// Fingerprints of the designated revokers.
+	pub revokers: Vec<String>,
+}
+
+impl KeyPreferences {
+	/// Returns `true` if any algorithms are preferred.
+	pub fn has_algorithms(&self) -> bool {
+		!(self.ciphers.is_empty()
+			&& self.hashes.is_empty()
+			&& self.compressions.is_empty())
+	}
+
+	/// Returns the names of the preferred algorithms
+	/// (cipher, hash and compression).
+	pub fn get_algorithm_names(&self) -> [String; 3] {
+		[
+			get_names(&self.ciphers, CIPHERS, "S"),
+			get_names(&self.hashes, HASHES, "H"),
+			get_names(&self.compressions, COMPRESSIONS, "Z"),
+		]
+	}
+
+	/// Returns the preferred algorithms in a single line.
+	pub fn get_algorithm_summary(&self) -> String {
+		self.get_algorithm_names().join(", ")
+	}
+}
+
+/// Returns the names of the given algorithms.
+///
+/// Unknown algorithms are shown with their IDs (e.g. `S100`).
+fn get_names(ids: &[u8], names: &[(u8, &str)], prefix: &str) -> String {
+	ids.iter()
+		.map(|id| match names.iter().find(|(v, _)| v == id) {
+			Some((_, name)) => name.to_string(),
+			None => format!("{}{}", prefix, id),
+		})
+		.collect::<Vec<String>>()
+		.join(" ")
+}
+
+/// Decodes the percent-escaped value of a colon listing field.
+fn decode_field(value: &str) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	let mut input = value.bytes();
+	while let Some(byte) = input.next() {
+		if byte == b'%' {
+			let hex = input.by_ref().take(2).collect::<Vec<u8>>();
+			match std::str::from_utf8(&hex)
+				.ok()
+				.and_then(|v| u8::from_str_radix(v, 16).ok())
+			{
+				Some(decoded) => bytes.push(decoded),
+				None => {
+					bytes.push(byte);
+					bytes.extend(hex);
+				}
+			}
+		} else {
+			bytes.push(byte);
+		}
+	}
+	bytes
+}
+
+/// Parses the preferences of the keys from the output of
+/// `gpg --with-colons --list-options show-sig-subpackets --list-sigs`.
+///
+/// Only the self-signatures are taken into account and the
+/// last one wins if there are more than one.
+pub fn parse_key_preferences(output: &str) -> HashMap<String, KeyPreferences> {
+	let mut preferences: HashMap<String, KeyPreferences> = HashMap::new();
+	let mut key_id = "";
+	let mut fingerprint = None;
+	let mut revokers = Vec::new();
+	let mut self_signature = false;
+	for line in output.lines() {
+		let fields = line.split(':').collect::<Vec<&str>>();
+		match fields.as_slice() {
+			["pub", _, _, _, id, ..] => {
+				key_id = *id;
+				fingerprint = None;
+			}
+			["rvk", _, _, _, _, _, _, _, _, revoker, ..] => {
+				revokers.push(revoker.to_string());
+			}
+			["fpr", .., fpr, _] if fingerprint.is_none() => {
+				if !revokers.is_empty() {
+					preferences.entry(fpr.to_string()).or_default().revokers =
+						mem::take(&mut revokers);
+				}
+				fingerprint = Some(fpr.to_string());
+			}
+			["sig", _, _, _, issuer, ..] => self_signature = *issuer == key_id,
+			["spk", subpacket, _, _, value, ..] if self_signature => {
+				if let (Some(fingerprint), Ok(subpacket)) =
+					(&fingerprint, subpacket.parse::<u8>())
+				{
+					let entry =
+						preferences.entry(fingerprint.to_string()).or_default();
+					let value = decode_field(value);
+					match subpacket {
+						CIPHER_SUBPACKET => entry.ciphers = value,
+						HASH_SUBPACKET => entry.hashes = value,
+						COMPRESSION_SUBPACKET => entry.compressions = value,
+						KEYSERVER_SUBPACKET => {
+							entry.keyserver = Some(
+								String::from_utf8_lossy(&value).to_string(),
+							)
+						}
+						_ => {}
+					}
+				}
+			}
+			_ => {}
+		}
+		if let Some(record) = fields.first() {
+			if *record != "spk" && *record != "sig" {
+				self_signature = false;
+			}
+		}
+	}
+	preferences
+}
+
+/// Interactor for setting the algorithm preferences via `setpref`.
+///
+/// They are set for all user IDs of the key.
+#[derive(Debug)]
+pub struct SetPrefs {
+	/// Preference list (empty for the defaults).
+	preferences: String,
+	/// Is the `setpref` command sent?
+	sent: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl SetPrefs {
+	/// Constructs a new instance of `SetPrefs`.
+	pub fn new(preferences: &str) -> Self {
+		Self {
+			preferences: preferences.to_string(),
+			sent: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(format!("setpref {}", self.preferences)
+					.trim_end()
+					.to_string())
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"keyedit.setpref.okay" | "keyedit.save.okay" => {
+				Ok(String::from("y"))
+			}
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for SetPrefs {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_prefs() {
+		let output = "tru::1:1640995200:0:3:1:5
+pub:u:255:22:ABCD1234ABCD1234:1640995200:::u:::scESC:::::ed25519:::0:
+rvk:::22::::::FFFFEEEEDDDDCCCCBBBBAAAA9999000099990000:80:
+fpr:::::::::0123456789ABCDEF0123ABCD1234ABCD1234:
+uid:u::::1640995200::HASH::Alice <alice@example.org>::::::::::0:
+sig:::22:ABCD1234ABCD1234:1640995200::::Alice <alice@example.org>:13x:::::8:
+spk:11:0:4:%09%08%07%64:
+spk:21:0:3:%0a%09%08:
+spk:22:0:3:%02%03%01:
+spk:24:1:21:hkps%3A//keys.example.org:
+sig:::22:1111222233334444:1640995200::::Bob <bob@example.org>:10x:::::8:
+spk:24:1:23:hkps%3A//keys.example.com:
+sub:u:255:18:5555666677778888:1640995200::::::e:::::cv25519::
+fpr:::::::::AAAABBBBCCCCDDDDEEEEFFFF5555666677778888:
+pub:-:255:22:9999000099990000:1640995200:::-:::scESC:::::ed25519:::0:
+fpr:::::::::FFFFEEEEDDDDCCCCBBBBAAAA9999000099990000:
+uid:-::::1640995200::HASH::Bob <bob@example.org>::::::::::0:
+sig:::22:9999000099990000:1640995200::::Bob <bob@example.org>:13x:::::8:";
+		let preferences = parse_key_preferences(output);
+		assert_eq!(1, preferences.len());
+		let preferences = preferences
+			.get("0123456789ABCDEF0123ABCD1234ABCD1234")
+			.cloned()
+			.unwrap_or_default();
+		assert_eq!(
+			Some(String::from("hkps://keys.example.org")),
+			preferences.keyserver
+		);
+		assert!(preferences.has_algorithms());
+		assert_eq!(
+			vec![String::from("FFFFEEEEDDDDCCCCBBBBAAAA9999000099990000")],
+			preferences.revokers
+		);
+		assert_eq!(
+			"AES256 AES192 AES S100, SHA512 SHA384 SHA256, ZLIB BZIP2 ZIP",
+			preferences.get_algorithm_summary()
+		);
+		assert!(!KeyPreferences::default().has_algorithms());
+		assert_eq!(b"100%".to_vec(), decode_field("100%"));
+		let mut interactor = SetPrefs::new(PREFERENCE_PRESETS[0].1);
+		for (prompt, response) in &[
+			(
+				"keyedit.prompt",
+				"setpref AES256 AES192 AES SHA512 SHA384 SHA256 \
+				 ZLIB BZIP2 ZIP Uncompressed",
+			),
+			("keyedit.setpref.okay", "y"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+		assert_eq!(
+			Ok(String::from("setpref")),
+			SetPrefs::new("").get_response("keyedit.prompt")
+		);
+	}
+}
@@ -0,0 +1,107 @@
+#[allow(deprecated)]
+use gpgme::Interactor;
+use gpgme::{Error, InteractionStatus};
+use std::io::Write;
+
+/// Interactor for adding a designated revoker via `addrevoker`.
+///
+/// The revoker can revoke the key on behalf of its owner and
+/// it cannot be removed once the key is saved.
+#[derive(Debug)]
+pub struct AddRevoker {
+	/// Fingerprint of the revoker key.
+	fingerprint: String,
+	/// Is the `addrevoker` command sent?
+	sent: bool,
+	/// Is the revoker given?
+	answered: bool,
+	/// Is the `save` command sent?
+	saved: bool,
+}
+
+impl AddRevoker {
+	/// Constructs a new instance of `AddRevoker`.
+	pub fn new(fingerprint: &str) -> Self {
+		Self {
+			fingerprint: fingerprint.to_string(),
+			sent: false,
+			answered: false,
+			saved: false,
+		}
+	}
+
+	/// Returns the response for the given prompt.
+	///
+	/// [`INV_VALUE`] is returned if `gpg` asks for the revoker
+	/// again (e.g. the key is not found or not usable).
+	///
+	/// [`INV_VALUE`]: Error::INV_VALUE
+	fn get_response(&mut self, prompt: &str) -> Result<String, Error> {
+		match prompt {
+			"keyedit.prompt" if !self.sent => {
+				self.sent = true;
+				Ok(String::from("addrevoker"))
+			}
+			"keyedit.prompt" if !self.saved => {
+				self.saved = true;
+				Ok(String::from("save"))
+			}
+			"keyedit.add_revoker" if !self.answered => {
+				self.answered = true;
+				Ok(self.fingerprint.to_string())
+			}
+			"keyedit.add_revoker" => Err(Error::INV_VALUE),
+			"keyedit.add_revoker.okay" | "keyedit.save.okay" => {
+				Ok(String::from("y"))
+			}
+			_ => Err(Error::UNEXPECTED),
+		}
+	}
+}
+
+#[allow(deprecated)]
+impl Interactor for AddRevoker {
+	fn interact<W: Write>(
+		&mut self,
+		status: InteractionStatus<'_>,
+		out: Option<W>,
+	) -> Result<(), Error> {
+		if let Some(mut out) = out {
+			let response =
+				self.get_response(status.args().unwrap_or_default())?;
+			writeln!(out, "{}", response)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_revoker() {
+		let fingerprint = "FFFFEEEEDDDDCCCCBBBBAAAA9999000099990000";
+		let mut interactor = AddRevoker::new(fingerprint);
+		for (prompt, response) in &[
+			("keyedit.prompt", "addrevoker"),
+			("keyedit.add_revoker", fingerprint),
+			("keyedit.add_revoker.okay", "y"),
+			("keyedit.prompt", "save"),
+			("keyedit.save.okay", "y"),
+		] {
+			assert_eq!(
+				Ok(response.to_string()),
+				interactor.get_response(prompt)
+			);
+		}
+		assert_eq!(
+			Err(Error::INV_VALUE),
+			interactor.get_response("keyedit.add_revoker")
+		);
+		assert_eq!(
+			Err(Error::UNEXPECTED),
+			interactor.get_response("keyedit.prompt")
+		);
+	}
+}
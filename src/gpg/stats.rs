@@ -0,0 +1,117 @@
+use crate::gpg::key::GpgKey;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Statistics about the keyring.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyringStats {
+	/// Number of the public keys.
+	pub public: usize,
+	/// Number of the secret keys.
+	pub secret: usize,
+	/// Number of the expired keys.
+	pub expired: usize,
+	/// Number of the revoked keys.
+	pub revoked: usize,
+	/// Number of the keys that will expire soon.
+	pub expiring: usize,
+	/// Average age of the keys in days.
+	pub average_age: Option<i64>,
+	/// Number of the keys by their algorithms (in descending order).
+	pub algorithms: Vec<(String, u64)>,
+}
+
+impl KeyringStats {
+	/// Constructs a new instance of `KeyringStats`.
+	///
+	/// Keys that will expire within `expiry_days`
+	/// are counted as expiring.
+	pub fn new(
+		public_keys: &[Arc<GpgKey>],
+		secret_keys: &[Arc<GpgKey>],
+		expiry_days: u32,
+	) -> Self {
+		let mut stats = Self {
+			public: public_keys.len(),
+			secret: secret_keys.len(),
+			..Self::default()
+		};
+		let mut ages = Vec::new();
+		for key in public_keys {
+			if key.is_revoked() {
+				stats.revoked += 1;
+			} else if key.is_expired() {
+				stats.expired += 1;
+			} else if matches!(key.get_days_until_expiry(), Some(days)
+				if days < i64::from(expiry_days))
+			{
+				stats.expiring += 1;
+			}
+			if let Some(time) = key.get_creation_time() {
+				ages.push((Utc::now() - time).num_days());
+			}
+			let algorithm = key.get_algorithm();
+			match stats.algorithms.iter_mut().find(|(a, _)| *a == algorithm) {
+				Some((_, count)) => *count += 1,
+				None => stats.algorithms.push((algorithm, 1)),
+			}
+		}
+		if !ages.is_empty() {
+			stats.average_age =
+				Some(ages.iter().sum::<i64>() / ages.len() as i64);
+		}
+		stats
+			.algorithms
+			.sort_by(|(a1, c1), (a2, c2)| c2.cmp(c1).then(a1.cmp(a2)));
+		stats
+	}
+
+	/// Returns the totals as (name, value) pairs.
+	pub fn get_totals(&self) -> Vec<(&str, String)> {
+		vec![
+			("public keys", self.public.to_string()),
+			("secret keys", self.secret.to_string()),
+			("expired", self.expired.to_string()),
+			("revoked", self.revoked.to_string()),
+			("expiring soon", self.expiring.to_string()),
+			(
+				"average age",
+				match self.average_age {
+					Some(days) if days >= 365 => {
+						format!("{} years {} days", days / 365, days % 365)
+					}
+					Some(days) => format!("{} days", days),
+					None => String::from("-"),
+				},
+			),
+		]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_stats() {
+		let stats = KeyringStats::new(&[], &[], 30);
+		assert_eq!(KeyringStats::default(), stats);
+		let stats = KeyringStats {
+			public: 3,
+			secret: 1,
+			average_age: Some(400),
+			..stats
+		};
+		assert_eq!(
+			vec![
+				("public keys", String::from("3")),
+				("secret keys", String::from("1")),
+				("expired", String::from("0")),
+				("revoked", String::from("0")),
+				("expiring soon", String::from("0")),
+				("average age", String::from("1 years 35 days")),
+			],
+			stats.get_totals()
+		);
+	}
+}
@@ -0,0 +1,187 @@
+use crate::gpg::key::GpgKey;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Maximum depth of the certification paths.
+pub const MAX_DEPTH: usize = 5;
+
+/// Maximum number of the certification paths to show.
+const MAX_PATHS: usize = 10;
+
+/// Graph of the local certifications between the keys.
+#[derive(Debug, Default)]
+pub struct TrustGraph {
+	/// Keys that are certified by the key (by their IDs).
+	edges: HashMap<String, Vec<String>>,
+}
+
+impl<'a> From<&'a [Arc<GpgKey>]> for TrustGraph {
+	fn from(keys: &'a [Arc<GpgKey>]) -> Self {
+		Self::new(keys.iter().map(|key| (key.get_id(), key.get_certifiers())))
+	}
+}
+
+impl TrustGraph {
+	/// Constructs a new instance of `TrustGraph`
+	/// from the keys along with their certifiers.
+	pub fn new<I: IntoIterator<Item = (String, Vec<String>)>>(
+		certifications: I,
+	) -> Self {
+		let mut edges = HashMap::<String, Vec<String>>::new();
+		for (key_id, certifiers) in certifications {
+			for certifier in certifiers {
+				edges.entry(certifier).or_default().push(key_id.to_string());
+			}
+		}
+		Self { edges }
+	}
+
+	/// Returns the shortest certification paths between the given keys.
+	///
+	/// Paths are searched breadth-first and the ones that are
+	/// longer than `max_depth` certifications are not considered.
+	pub fn find_paths(
+		&self,
+		from: &str,
+		to: &str,
+		max_depth: usize,
+	) -> Vec<Vec<String>> {
+		let mut depths = HashMap::new();
+		let mut parents = HashMap::<&str, Vec<&str>>::new();
+		let mut queue = VecDeque::new();
+		depths.insert(from, 0);
+		queue.push_back(from);
+		while let Some(key_id) = queue.pop_front() {
+			let depth = depths[key_id];
+			if key_id == to || depth >= max_depth {
+				continue;
+			}
+			for certified in self.edges.get(key_id).into_iter().flatten() {
+				match depths.get(certified.as_str()) {
+					Some(d) if *d == depth + 1 => {
+						parents.entry(certified).or_default().push(key_id);
+					}
+					Some(_) => {}
+					None => {
+						depths.insert(certified, depth + 1);
+						parents.entry(certified).or_default().push(key_id);
+						queue.push_back(certified);
+					}
+				}
+			}
+		}
+		let mut paths = Vec::new();
+		if depths.contains_key(to) {
+			Self::collect_paths(&parents, from, vec![to], &mut paths);
+		}
+		paths
+	}
+
+	/// Collects the paths by following the parents of the last key.
+	fn collect_paths(
+		parents: &HashMap<&str, Vec<&str>>,
+		from: &str,
+		path: Vec<&str>,
+		paths: &mut Vec<Vec<String>>,
+	) {
+		if paths.len() >= MAX_PATHS {
+			return;
+		}
+		let last = path[path.len() - 1];
+		if last == from {
+			paths.push(path.iter().rev().map(|v| v.to_string()).collect());
+			return;
+		}
+		for parent in parents.get(last).into_iter().flatten() {
+			let mut path = path.clone();
+			path.push(parent);
+			Self::collect_paths(parents, from, path, paths);
+		}
+	}
+}
+
+/// Returns the given paths as an indented tree.
+///
+/// Paths are expected to start from the same key and
+/// keys are described with the given function.
+pub fn get_path_tree<F: Fn(&str) -> String>(
+	paths: &[Vec<String>],
+	describe: F,
+) -> Vec<String> {
+	let mut tree = Vec::new();
+	if let Some(root) = paths.first().and_then(|path| path.first()) {
+		tree.push(describe(root));
+		add_tree_level(paths, 1, "", &describe, &mut tree);
+	}
+	tree
+}
+
+/// Adds the keys at the given depth of the paths to the tree.
+fn add_tree_level<F: Fn(&str) -> String>(
+	paths: &[Vec<String>],
+	depth: usize,
+	prefix: &str,
+	describe: &F,
+	tree: &mut Vec<String>,
+) {
+	let mut children = Vec::<(&str, Vec<Vec<String>>)>::new();
+	for path in paths.iter().filter(|path| path.len() > depth) {
+		match children.iter_mut().find(|(id, _)| *id == path[depth]) {
+			Some((_, child_paths)) => child_paths.push(path.to_vec()),
+			None => children.push((&path[depth], vec![path.to_vec()])),
+		}
+	}
+	for (i, (key_id, child_paths)) in children.iter().enumerate() {
+		let last = i == children.len() - 1;
+		tree.push(format!(
+			"{}{}{}",
+			prefix,
+			if last { "└─" } else { "├─" },
+			describe(key_id)
+		));
+		add_tree_level(
+			child_paths,
+			depth + 1,
+			&format!("{}{}", prefix, if last { "  " } else { "│ " }),
+			describe,
+			tree,
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_gpg_wot() {
+		let graph = TrustGraph::new(vec![
+			(String::from("a"), vec![String::from("me")]),
+			(String::from("b"), vec![String::from("me")]),
+			(
+				String::from("c"),
+				vec![String::from("a"), String::from("b")],
+			),
+			(String::from("d"), vec![String::from("c")]),
+			(
+				String::from("x"),
+				vec![String::from("d"), String::from("me")],
+			),
+		]);
+		let paths = graph.find_paths("me", "d", MAX_DEPTH);
+		assert_eq!(
+			vec![vec!["me", "a", "c", "d"], vec!["me", "b", "c", "d"]],
+			paths
+		);
+		assert!(graph.find_paths("me", "d", 2).is_empty());
+		assert!(graph.find_paths("d", "a", MAX_DEPTH).is_empty());
+		assert_eq!(
+			vec![vec!["me", "x"]],
+			graph.find_paths("me", "x", MAX_DEPTH)
+		);
+		assert_eq!(
+			vec!["ME", "├─A", "│ └─C", "│   └─D", "└─B", "  └─C", "    └─D"],
+			get_path_tree(&paths, |v| v.to_uppercase())
+		);
+	}
+}
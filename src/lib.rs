@@ -3,7 +3,10 @@
 
 pub mod app;
 pub mod args;
+pub mod cli;
+pub mod config;
 pub mod gpg;
+pub mod logger;
 pub mod term;
 pub mod widget;
 
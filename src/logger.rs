@@ -0,0 +1,123 @@
+//! File logger for diagnostics.
+
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+
+/// Logger that appends the records to a file.
+///
+/// Records are written in [logfmt](https://brandur.org/logfmt)
+/// format for making them easy to filter and parse.
+#[derive(Debug)]
+pub struct FileLogger {
+	/// Log file.
+	file: Mutex<File>,
+	/// Maximum level of the records to write.
+	level: LevelFilter,
+}
+
+impl FileLogger {
+	/// Constructs a new instance of `FileLogger`.
+	pub fn new(path: &str, level: LevelFilter) -> Result<Self> {
+		Ok(Self {
+			file: Mutex::new(
+				OpenOptions::new().create(true).append(true).open(path)?,
+			),
+			level,
+		})
+	}
+
+	/// Initializes the logger for the given file.
+	///
+	/// Debug records are also written if `verbose` is set.
+	pub fn init(path: &str, verbose: bool) -> Result<()> {
+		let level = if verbose {
+			LevelFilter::Debug
+		} else {
+			LevelFilter::Info
+		};
+		log::set_boxed_logger(Box::new(Self::new(path, level)?))
+			.map_err(|e| anyhow!("failed to set logger: {}", e))?;
+		log::set_max_level(level);
+		Ok(())
+	}
+
+	/// Returns the formatted line for the given record.
+	fn format(record: &Record) -> String {
+		format!(
+			"time={} level={} target={} msg={:?}",
+			Local::now().to_rfc3339(),
+			record.level(),
+			record.target(),
+			record.args().to_string(),
+		)
+	}
+}
+
+impl Log for FileLogger {
+	fn enabled(&self, metadata: &Metadata) -> bool {
+		metadata.level() <= self.level
+			&& metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+	}
+
+	fn log(&self, record: &Record) {
+		if self.enabled(record.metadata()) {
+			if let Ok(mut file) = self.file.lock() {
+				let _ = writeln!(file, "{}", Self::format(record));
+			}
+		}
+	}
+
+	fn flush(&self) {
+		if let Ok(mut file) = self.file.lock() {
+			let _ = file.flush();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use log::Level;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	use std::fs;
+	#[test]
+	fn test_logger() -> Result<()> {
+		let path = env::temp_dir().join(format!(
+			"{}-{}.log",
+			env!("CARGO_PKG_NAME"),
+			std::process::id()
+		));
+		let logger =
+			FileLogger::new(&path.to_string_lossy(), LevelFilter::Info)?;
+		for (level, message) in
+			&[(Level::Debug, "hidden"), (Level::Error, "failed: \"x\"")]
+		{
+			logger.log(
+				&Record::builder()
+					.args(format_args!("{}", message))
+					.level(*level)
+					.target("gpg_tui::app")
+					.build(),
+			);
+		}
+		logger.log(
+			&Record::builder()
+				.args(format_args!("other"))
+				.level(Level::Error)
+				.target("gpgme")
+				.build(),
+		);
+		let output = fs::read_to_string(&path)?;
+		fs::remove_file(path)?;
+		assert_eq!(1, output.lines().count());
+		assert!(output.ends_with(
+			"level=ERROR target=gpg_tui::app msg=\"failed: \\\"x\\\"\"\n"
+		));
+		Ok(())
+	}
+}
@@ -1,49 +1,189 @@
 use anyhow::Result;
+use gpg_tui::app::command::Command;
 use gpg_tui::app::handler;
 use gpg_tui::app::launcher::App;
+use gpg_tui::app::prompt::OutputType;
+use gpg_tui::app::selection::Selection;
+use gpg_tui::app::session::Session;
 use gpg_tui::args::Args;
-use gpg_tui::gpg::config::GpgConfig;
+use gpg_tui::cli;
+use gpg_tui::config::Config;
+use gpg_tui::gpg::config::{GpgConfig, TempHomeDir};
 use gpg_tui::gpg::context::GpgContext;
+use gpg_tui::gpg::demo::DEMO_KEY_COUNT;
+use gpg_tui::logger::FileLogger;
 use gpg_tui::term::event::{Event, EventHandler};
+#[cfg(unix)]
+use gpg_tui::term::socket::ControlSocket;
 use gpg_tui::term::tui::Tui;
 use gpg_tui::GPGME_REQUIRED_VERSION;
-use std::io;
+use std::env;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
 use tui::backend::CrosstermBackend;
 use tui::Terminal;
 
 fn main() -> Result<()> {
 	// Parse command-line arguments.
-	let args = Args::parse();
+	let mut args = Args::parse();
+	// Parse the configuration file.
+	if let Some(config_file) =
+		args.config.clone().or_else(Config::get_default_location)
+	{
+		args = Config::parse_config(&config_file)?.update_args(args);
+	}
+	// Initialize the logger.
+	if let Some(log_file) = &args.log_file {
+		FileLogger::init(log_file, args.verbose)?;
+		log::info!(
+			"starting {} v{}",
+			env!("CARGO_PKG_NAME"),
+			env!("CARGO_PKG_VERSION")
+		);
+	}
+	// Select the key fingerprint in picker mode.
+	if args.picker {
+		args.select = Some(Selection::KeyFingerprint);
+	}
+	// Use a temporary home directory in ephemeral/demo mode.
+	if args.demo {
+		args.ephemeral = true;
+	}
+	let temp_home_dir = if args.ephemeral {
+		let home_dir = TempHomeDir::new()?;
+		args.homedir = Some(home_dir.path.to_string_lossy().to_string());
+		if args.outdir.is_none() {
+			args.outdir =
+				Some(env::current_dir()?.to_string_lossy().to_string());
+		}
+		Some(home_dir)
+	} else {
+		None
+	};
 	// Initialize GPGME library.
-	let config = GpgConfig::new(&args).unwrap();
+	let config = GpgConfig::new(&args)?;
 	config.check_gpgme_version(GPGME_REQUIRED_VERSION);
 	let mut gpgme = GpgContext::new(config)?;
+	// Generate the keys to show in demo mode.
+	if args.demo {
+		gpgme.generate_demo_keys(DEMO_KEY_COUNT)?;
+	}
+	// Import the given keys before launching.
+	let imported_keys = if args.import.is_empty() {
+		None
+	} else {
+		Some(gpgme.import_keys(args.import.clone(), true)?.new_keys)
+	};
+	// Run the subcommand without starting the user interface.
+	if let Some(subcommand) = &args.subcommand {
+		return cli::run(subcommand, &mut gpgme, &mut io::stdout());
+	}
 	// Create an application for rendering.
 	let mut app = App::new(&mut gpgme, &args)?;
+	if let Some(key_count) = imported_keys {
+		app.prompt.set_output((
+			OutputType::Success,
+			format!("{} key(s) imported", key_count),
+		));
+	}
 	// Initialize the text-based user interface.
-	let backend = CrosstermBackend::new(io::stdout());
+	let output: Box<dyn Write> = if args.picker {
+		Box::new(io::stderr())
+	} else {
+		Box::new(io::stdout())
+	};
+	let backend = CrosstermBackend::new(output);
 	let terminal = Terminal::new(backend)?;
 	let events = EventHandler::new(args.tick_rate);
 	let mut tui = Tui::new(terminal, events);
+	tui.use_stderr = args.picker;
+	tui.set_panic_hook();
+	// Listen on the control socket for the commands.
+	#[cfg(unix)]
+	let control_socket = args
+		.socket
+		.as_ref()
+		.map(|path| {
+			ControlSocket::new(Path::new(path), tui.events.get_sender())
+		})
+		.transpose()?;
 	tui.init()?;
+	// Load the previous session unless the key is selected on startup.
+	let persist_session = !args.no_restore
+		&& !args.ephemeral
+		&& args.select.is_none()
+		&& args.focus.is_none();
+	let mut session = if persist_session {
+		Session::load(&app.gpgme.config.home_dir)
+			.map_err(|e| log::debug!("session is not loaded: {}", e))
+			.ok()
+	} else {
+		None
+	};
+	// Parse the command to run on startup.
+	let mut startup_command = args.command.as_ref().and_then(|command| {
+		Command::from_str(command)
+			.map_err(|_| {
+				app.prompt.set_output((
+					OutputType::Failure,
+					format!("invalid command: {}", command),
+				))
+			})
+			.ok()
+	});
 	// Start the main loop.
 	while app.state.running {
 		// Render the user interface.
 		tui.draw(&mut app)?;
+		// Run the startup command after the splash screen
+		// and the keys are loaded.
+		if !app.state.show_splash && app.key_loader.is_none() {
+			if let Some(session) = session.take() {
+				app.restore_session(session)?;
+			}
+			if let Some(command) = startup_command.take() {
+				handler::handle_command_execution(command, &mut tui, &mut app)?;
+				continue;
+			}
+		}
 		// Handle events.
 		match tui.events.next()? {
 			Event::Key(key_event) => {
 				handler::handle_events(key_event, &mut tui, &mut app)?
 			}
-			Event::Tick => app.tick(),
+			Event::Tick => app.tick()?,
+			Event::Terminate(signal) => {
+				log::warn!("received signal: {}", signal);
+				app.state.running = false;
+			}
+			Event::Command(command) => {
+				handler::handle_command_execution(command, &mut tui, &mut app)?
+			}
 			_ => {}
 		}
 	}
+	// Save the session for the next launch.
+	if persist_session {
+		if let Err(e) = app.get_session().save() {
+			log::error!("failed to save the session: {}", e);
+		}
+	}
+	// Stop listening on the control socket.
+	#[cfg(unix)]
+	drop(control_socket);
 	// Exit the user interface.
 	tui.exit()?;
 	// Print the exit message if any.
-	if let Some(message) = app.state.exit_message {
+	if let Some(message) = &app.state.exit_message {
 		println!("{}", message);
+	} else if args.picker {
+		// Exit with failure if no key is picked.
+		drop(app);
+		drop(temp_home_dir);
+		process::exit(1);
 	}
 	Ok(())
 }
@@ -1,5 +1,11 @@
+use crate::app::command::Command;
 use anyhow::Result;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
+#[cfg(unix)]
+use signal_hook::{
+	consts::signal::{SIGHUP, SIGINT, SIGTERM},
+	iterator::Signals,
+};
 use std::sync::mpsc;
 use std::sync::{
 	atomic::{AtomicBool, Ordering},
@@ -9,11 +15,13 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 /// Representation of terminal events
-/// ([`Crossterm events`] + [`Tick`]).
+/// ([`Crossterm events`] + [`Tick`] + [`Terminate`] + [`Command`]).
 ///
 /// [`Crossterm events`]: crossterm::event::Event
 /// [`Tick`]: Event::Tick
-#[derive(Clone, Copy, Debug)]
+/// [`Terminate`]: Event::Terminate
+/// [`Command`]: Event::Command
+#[derive(Clone, Debug)]
 pub enum Event {
 	/// Key press.
 	Key(KeyEvent),
@@ -23,6 +31,10 @@ pub enum Event {
 	Resize(u16, u16),
 	/// Terminal tick.
 	Tick,
+	/// Termination signal (e.g. `SIGTERM`).
+	Terminate(i32),
+	/// Command from another application (e.g. via the control socket).
+	Command(Command),
 }
 
 /// Basic event handler for terminal [`events`].
@@ -83,6 +95,17 @@ impl EventHandler {
 				}
 			})
 		};
+		#[cfg(unix)]
+		if let Ok(mut signals) = Signals::new([SIGINT, SIGTERM, SIGHUP]) {
+			let sender = sender.clone();
+			thread::spawn(move || {
+				for signal in signals.forever() {
+					if sender.send(Event::Terminate(signal)).is_err() {
+						break;
+					}
+				}
+			});
+		}
 		Self {
 			sender,
 			receiver,
@@ -91,6 +114,11 @@ impl EventHandler {
 		}
 	}
 
+	/// Returns a sender for sending events from other sources.
+	pub fn get_sender(&self) -> mpsc::Sender<Event> {
+		self.sender.clone()
+	}
+
 	/// Receive the next event from handler.
 	///
 	/// > This function will always block the current thread if
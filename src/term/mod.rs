@@ -5,3 +5,7 @@ pub mod event;
 
 /// Terminal user interface.
 pub mod tui;
+
+/// Control socket.
+#[cfg(unix)]
+pub mod socket;
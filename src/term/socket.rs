@@ -0,0 +1,117 @@
+use crate::app::command::Command;
+use crate::term::event::Event;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::thread;
+
+/// Control socket for running commands from other applications.
+///
+/// Each line that is written to the socket is parsed as a command
+/// (e.g. `select <fpr>`, `import <path>` or `refresh`) and sent to
+/// the application as an event. The socket replies with `ok` or
+/// `invalid command` for each line.
+#[derive(Debug)]
+pub struct ControlSocket {
+	/// Path of the socket.
+	path: PathBuf,
+}
+
+impl ControlSocket {
+	/// Binds the socket to the given path and starts listening.
+	///
+	/// Fails if another instance is already listening on the path.
+	pub fn new(path: &Path, sender: mpsc::Sender<Event>) -> Result<Self> {
+		if UnixStream::connect(path).is_ok() {
+			return Err(anyhow!("socket is already in use: {:?}", path));
+		} else if path.exists() {
+			fs::remove_file(path)?;
+		}
+		let listener = UnixListener::bind(path)?;
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				match stream {
+					Ok(stream) => {
+						let sender = sender.clone();
+						thread::spawn(move || handle_client(stream, sender));
+					}
+					Err(e) => {
+						log::error!("socket error: {}", e);
+						break;
+					}
+				}
+			}
+		});
+		log::info!("listening on {:?}", path);
+		Ok(Self {
+			path: path.to_path_buf(),
+		})
+	}
+}
+
+impl Drop for ControlSocket {
+	fn drop(&mut self) {
+		let _ = fs::remove_file(&self.path);
+	}
+}
+
+/// Reads the commands from the client and sends them as events.
+fn handle_client(stream: UnixStream, sender: mpsc::Sender<Event>) {
+	let mut writer = match stream.try_clone() {
+		Ok(writer) => writer,
+		Err(e) => {
+			log::error!("failed to handle the socket client: {}", e);
+			return;
+		}
+	};
+	for line in BufReader::new(stream).lines().map_while(Result::ok) {
+		if line.trim().is_empty() {
+			continue;
+		}
+		let reply = match Command::from_str(line.trim()) {
+			Ok(command) => {
+				log::debug!("received command from socket: {:?}", command);
+				if sender.send(Event::Command(command)).is_err() {
+					break;
+				}
+				"ok"
+			}
+			Err(_) => "invalid command",
+		};
+		if writeln!(writer, "{}", reply).is_err() {
+			break;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn test_term_socket() -> Result<()> {
+		let path = env::temp_dir()
+			.join(format!("{}-test.sock", env!("CARGO_PKG_NAME")));
+		let (sender, receiver) = mpsc::channel();
+		let socket = ControlSocket::new(&path, sender.clone())?;
+		assert!(ControlSocket::new(&path, sender).is_err());
+		let mut stream = UnixStream::connect(&path)?;
+		writeln!(stream, "refresh\nxyz")?;
+		let mut reader = BufReader::new(stream);
+		let mut reply = String::new();
+		reader.read_line(&mut reply)?;
+		assert_eq!("ok\n", reply);
+		reply.clear();
+		reader.read_line(&mut reply)?;
+		assert_eq!("invalid command\n", reply);
+		assert!(matches!(receiver.recv()?, Event::Command(Command::Refresh)));
+		drop(socket);
+		assert!(!path.exists());
+		Ok(())
+	}
+}
@@ -2,9 +2,11 @@ use crate::app::launcher::App;
 use crate::app::renderer;
 use crate::term::event::EventHandler;
 use anyhow::{Context, Result};
+use crossterm::cursor::Show;
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
-use std::io;
+use std::io::{self, Write};
+use std::panic;
 use std::sync::atomic::Ordering;
 use tui::backend::Backend;
 use tui::Terminal;
@@ -24,6 +26,8 @@ pub struct Tui<B: Backend> {
 	pub events: EventHandler,
 	/// Is the interface paused?
 	pub paused: bool,
+	/// Is the interface drawn on stderr instead of stdout?
+	pub use_stderr: bool,
 }
 
 impl<B: Backend> Tui<B> {
@@ -33,16 +37,57 @@ impl<B: Backend> Tui<B> {
 			terminal,
 			events,
 			paused: false,
+			use_stderr: false,
 		}
 	}
 
+	/// Returns the output stream that the interface is drawn on.
+	fn get_output(&self) -> Box<dyn Write> {
+		Self::get_output_stream(self.use_stderr)
+	}
+
+	/// Returns the standard output or standard error stream.
+	fn get_output_stream(use_stderr: bool) -> Box<dyn Write> {
+		if use_stderr {
+			Box::new(io::stderr())
+		} else {
+			Box::new(io::stdout())
+		}
+	}
+
+	/// Restores the terminal properties.
+	///
+	/// It leaves the alternate screen, disables the raw mode
+	/// and shows the cursor without requiring an instance of `Tui`.
+	pub fn reset(use_stderr: bool) -> Result<()> {
+		terminal::disable_raw_mode()?;
+		crossterm::execute!(
+			Self::get_output_stream(use_stderr),
+			LeaveAlternateScreen,
+			DisableMouseCapture,
+			Show
+		)?;
+		Ok(())
+	}
+
+	/// Sets a panic hook that restores the terminal
+	/// before printing the panic message.
+	pub fn set_panic_hook(&self) {
+		let use_stderr = self.use_stderr;
+		let panic_hook = panic::take_hook();
+		panic::set_hook(Box::new(move |panic| {
+			let _ = Self::reset(use_stderr);
+			panic_hook(panic);
+		}));
+	}
+
 	/// Initializes the terminal interface.
 	///
 	/// It enables the raw mode and sets terminal properties.
 	pub fn init(&mut self) -> Result<()> {
 		terminal::enable_raw_mode()?;
 		crossterm::execute!(
-			io::stdout(),
+			self.get_output(),
 			EnterAlternateScreen,
 			EnableMouseCapture
 		)?;
@@ -72,12 +117,12 @@ impl<B: Backend> Tui<B> {
 
 	/// Enables the mouse capture.
 	pub fn enable_mouse_capture(&mut self) -> Result<()> {
-		Ok(crossterm::execute!(io::stdout(), EnableMouseCapture)?)
+		Ok(crossterm::execute!(self.get_output(), EnableMouseCapture)?)
 	}
 
 	/// Disables the mouse capture.
 	pub fn disable_mouse_capture(&mut self) -> Result<()> {
-		Ok(crossterm::execute!(io::stdout(), DisableMouseCapture)?)
+		Ok(crossterm::execute!(self.get_output(), DisableMouseCapture)?)
 	}
 
 	/// [`Draw`] the terminal interface by [`rendering`] the widgets.
@@ -95,12 +140,7 @@ impl<B: Backend> Tui<B> {
 	///
 	/// It disables the raw mode and reverts back the terminal properties.
 	pub fn exit(&mut self) -> Result<()> {
-		terminal::disable_raw_mode()?;
-		crossterm::execute!(
-			io::stdout(),
-			LeaveAlternateScreen,
-			DisableMouseCapture
-		)?;
+		Self::reset(self.use_stderr)?;
 		self.terminal.show_cursor()?;
 		Ok(())
 	}
@@ -0,0 +1,158 @@
+use crate::widget::input::Input;
+use tui::widgets::ListState;
+
+/// Item of the checkbox list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckItem<T> {
+	/// Label to show and search.
+	pub label: String,
+	/// Value of the item.
+	pub value: T,
+	/// Is the item checked?
+	pub checked: bool,
+}
+
+/// Searchable list of checkboxes.
+///
+/// Only the items that match the [`query`] are listed
+/// while the checked items are kept regardless of the query.
+///
+/// [`query`]: CheckList::query
+#[derive(Debug)]
+pub struct CheckList<T> {
+	/// List items.
+	pub items: Vec<CheckItem<T>>,
+	/// Search query.
+	pub query: Input,
+	/// Indices of the items that match the query.
+	pub visible: Vec<usize>,
+	/// State that can be modified by TUI.
+	pub state: ListState,
+}
+
+impl<T> CheckList<T> {
+	/// Constructs a new instance of `CheckList`.
+	pub fn new(items: Vec<(String, T)>) -> Self {
+		let mut list = Self {
+			items: items
+				.into_iter()
+				.map(|(label, value)| CheckItem {
+					label,
+					value,
+					checked: false,
+				})
+				.collect(),
+			query: Input::default(),
+			visible: Vec::new(),
+			state: ListState::default(),
+		};
+		list.filter();
+		list
+	}
+
+	/// Updates the visible items based on the query.
+	pub fn filter(&mut self) {
+		let query = self.query.value.to_lowercase();
+		self.visible = self
+			.items
+			.iter()
+			.enumerate()
+			.filter(|(_, item)| {
+				item.checked || item.label.to_lowercase().contains(&query)
+			})
+			.map(|(i, _)| i)
+			.collect();
+		self.state.select(if self.visible.is_empty() {
+			None
+		} else {
+			Some(0)
+		});
+	}
+
+	/// Returns the visible items.
+	pub fn get_visible(&self) -> Vec<&CheckItem<T>> {
+		self.visible.iter().map(|i| &self.items[*i]).collect()
+	}
+
+	/// Returns the selected item.
+	pub fn selected(&self) -> Option<&CheckItem<T>> {
+		self.items.get(*self.visible.get(self.state.selected()?)?)
+	}
+
+	/// Selects the next item.
+	pub fn next(&mut self) {
+		if let Some(i) = self.state.selected() {
+			self.state.select(Some((i + 1) % self.visible.len()));
+		}
+	}
+
+	/// Selects the previous item.
+	pub fn previous(&mut self) {
+		if let Some(i) = self.state.selected() {
+			self.state.select(Some(
+				i.checked_sub(1).unwrap_or(self.visible.len() - 1),
+			));
+		}
+	}
+
+	/// Toggles the selected item.
+	pub fn toggle(&mut self) {
+		if let Some(i) = self
+			.state
+			.selected()
+			.and_then(|i| self.visible.get(i).copied())
+		{
+			self.items[i].checked = !self.items[i].checked;
+		}
+	}
+
+	/// Returns the values of the checked items.
+	pub fn get_checked(&self) -> Vec<&T> {
+		self.items
+			.iter()
+			.filter(|item| item.checked)
+			.map(|item| &item.value)
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_checklist() {
+		let mut list = CheckList::new(vec![
+			(String::from("Alice <alice@example.org>"), 1),
+			(String::from("Bob <bob@example.org>"), 2),
+			(String::from("Carol <carol@example.com>"), 3),
+		]);
+		assert_eq!(3, list.get_visible().len());
+		list.next();
+		list.toggle();
+		assert_eq!(vec![&2], list.get_checked());
+		list.query.set_value("EXAMPLE.COM");
+		list.filter();
+		assert_eq!(
+			vec![2, 3],
+			list.get_visible()
+				.iter()
+				.map(|item| item.value)
+				.collect::<Vec<i32>>()
+		);
+		list.previous();
+		assert_eq!(Some(3), list.selected().map(|item| item.value));
+		list.toggle();
+		assert_eq!(vec![&2, &3], list.get_checked());
+		list.query.set_value("xyz");
+		list.filter();
+		list.query.clear();
+		list.filter();
+		assert_eq!(3, list.get_visible().len());
+		let mut list = CheckList::<i32>::new(Vec::new());
+		list.next();
+		list.previous();
+		list.toggle();
+		assert_eq!(None, list.selected());
+	}
+}
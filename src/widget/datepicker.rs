@@ -0,0 +1,225 @@
+use crate::widget::input::Input;
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Format of the absolute dates.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Value of the expiration time that means no expiration.
+pub const NEVER: &str = "never";
+
+/// Returns the number of days in the given month.
+fn get_days_in_month(year: i32, month: u32) -> u32 {
+	let (next_year, next_month) = if month == 12 {
+		(year + 1, 1)
+	} else {
+		(year, month + 1)
+	};
+	NaiveDate::from_ymd(next_year, next_month, 1).pred().day()
+}
+
+/// Adds the given number of months to the date.
+///
+/// The day is clamped to the last day of the resulting month.
+fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+	let month = date.year() * 12 + date.month0() as i32 + months;
+	let (year, month) = (month.div_euclid(12), month.rem_euclid(12) as u32 + 1);
+	NaiveDate::from_ymd(
+		year,
+		month,
+		date.day().min(get_days_in_month(year, month)),
+	)
+}
+
+/// Parses the given expiration time.
+///
+/// Accepts absolute dates (`YYYY-MM-DD`), durations relative
+/// to today (e.g. `10d`, `2w`, `6m`, `1y`) and `never` (or `0`).
+/// Returns `None` if the expiration time is `never`.
+pub fn parse_expiry(
+	value: &str,
+	today: NaiveDate,
+) -> Result<Option<NaiveDate>> {
+	let value = value.trim().to_lowercase();
+	if value == NEVER || value == "0" {
+		return Ok(None);
+	}
+	let date = match NaiveDate::parse_from_str(&value, DATE_FORMAT) {
+		Ok(date) => date,
+		Err(_) => {
+			let (index, unit) = value.char_indices().last().unwrap_or_default();
+			let count = value[..index]
+				.parse::<u16>()
+				.map_err(|_| anyhow!("invalid expiration time: {}", value))?;
+			match unit {
+				'd' => today + Duration::days(count.into()),
+				'w' => today + Duration::weeks(count.into()),
+				'm' => add_months(today, count.into()),
+				'y' => add_months(today, i32::from(count) * 12),
+				_ => return Err(anyhow!("invalid expiration time: {}", value)),
+			}
+		}
+	};
+	if date <= today {
+		Err(anyhow!("expiration date must be in the future"))
+	} else if date.year() > 2105 {
+		Err(anyhow!("expiration date is too far in the future"))
+	} else {
+		Ok(Some(date))
+	}
+}
+
+/// Calendar for selecting an expiration date.
+///
+/// Dates can be selected on the calendar or typed into
+/// the [`input`] as an absolute date or a relative duration.
+/// Only the dates after today are selectable.
+///
+/// [`input`]: DatePicker::input
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatePicker {
+	/// Current date.
+	pub today: NaiveDate,
+	/// Selected date.
+	pub date: NaiveDate,
+	/// Is the "never expire" option selected?
+	pub never: bool,
+	/// Typed expiration time.
+	pub input: Input,
+}
+
+impl DatePicker {
+	/// Constructs a new instance of `DatePicker`.
+	///
+	/// One year from today is selected by default.
+	pub fn new(today: NaiveDate) -> Self {
+		Self {
+			today,
+			date: add_months(today, 12),
+			never: false,
+			input: Input::default(),
+		}
+	}
+
+	/// Sets the selected date.
+	fn set_date(&mut self, date: NaiveDate) {
+		self.date = date.max(self.today.succ());
+		self.never = false;
+	}
+
+	/// Moves the selection by the given number of days.
+	pub fn move_days(&mut self, days: i64) {
+		self.set_date(self.date + Duration::days(days));
+	}
+
+	/// Moves the selection by the given number of months.
+	pub fn move_months(&mut self, months: i32) {
+		self.set_date(add_months(self.date, months));
+	}
+
+	/// Toggles the "never expire" option.
+	pub fn toggle_never(&mut self) {
+		self.never = !self.never;
+	}
+
+	/// Selects the typed expiration time.
+	///
+	/// The input is cleared if the value is valid.
+	pub fn apply_input(&mut self) -> Result<()> {
+		match parse_expiry(&self.input.value, self.today)? {
+			Some(date) => self.set_date(date),
+			None => self.never = true,
+		}
+		self.input.clear();
+		Ok(())
+	}
+
+	/// Returns the selected expiration time.
+	pub fn get_value(&self) -> String {
+		if self.never {
+			String::from(NEVER)
+		} else {
+			self.date.format(DATE_FORMAT).to_string()
+		}
+	}
+
+	/// Returns the title of the selected month.
+	pub fn get_title(&self) -> String {
+		self.date.format("%B %Y").to_string()
+	}
+
+	/// Returns the days of the selected month as weeks
+	/// (starting from Monday).
+	pub fn get_weeks(&self) -> Vec<[Option<u32>; 7]> {
+		let first_day = self.date.with_day(1).unwrap_or(self.date);
+		let offset = first_day.weekday().num_days_from_monday() as usize;
+		let days = get_days_in_month(self.date.year(), self.date.month());
+		let mut weeks = Vec::new();
+		let mut week = [None; 7];
+		for day in 1..=days {
+			let index = (offset + day as usize - 1) % 7;
+			week[index] = Some(day);
+			if index == 6 || day == days {
+				weeks.push(week);
+				week = [None; 7];
+			}
+		}
+		weeks
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_datepicker() -> Result<()> {
+		let today = NaiveDate::from_ymd(2024, 1, 31);
+		assert_eq!(None, parse_expiry("never", today)?);
+		assert_eq!(None, parse_expiry("0", today)?);
+		assert_eq!(
+			Some(NaiveDate::from_ymd(2024, 2, 29)),
+			parse_expiry("1m", today)?
+		);
+		assert_eq!(
+			Some(NaiveDate::from_ymd(2024, 2, 14)),
+			parse_expiry("2W", today)?
+		);
+		assert_eq!(
+			Some(NaiveDate::from_ymd(2026, 1, 31)),
+			parse_expiry("2y", today)?
+		);
+		assert_eq!(
+			Some(NaiveDate::from_ymd(2025, 6, 1)),
+			parse_expiry("2025-06-01", today)?
+		);
+		assert!(parse_expiry("2024-01-31", today).is_err());
+		assert!(parse_expiry("2200-01-01", today).is_err());
+		assert!(parse_expiry("1x", today).is_err());
+		assert!(parse_expiry("y", today).is_err());
+		assert!(parse_expiry("", today).is_err());
+		let mut picker = DatePicker::new(today);
+		assert_eq!("2025-01-31", picker.get_value());
+		assert_eq!("January 2025", picker.get_title());
+		picker.move_months(1);
+		assert_eq!("2025-02-28", picker.get_value());
+		picker.move_days(1);
+		assert_eq!("2025-03-01", picker.get_value());
+		let weeks = picker.get_weeks();
+		assert_eq!(6, weeks.len());
+		assert_eq!([None, None, None, None, None, Some(1), Some(2)], weeks[0]);
+		assert_eq!([Some(31), None, None, None, None, None, None], weeks[5]);
+		picker.move_months(-24);
+		assert_eq!("2024-02-01", picker.get_value());
+		picker.toggle_never();
+		assert_eq!(NEVER, picker.get_value());
+		picker.input.set_value("10d");
+		picker.apply_input()?;
+		assert_eq!("2024-02-10", picker.get_value());
+		assert!(picker.input.is_empty());
+		picker.input.set_value("yesterday");
+		assert!(picker.apply_input().is_err());
+		assert_eq!("yesterday", picker.input.value);
+		Ok(())
+	}
+}
@@ -0,0 +1,97 @@
+use tui::text::Text;
+
+/// Modal dialog with a title, body and buttons.
+///
+/// Each button is associated with an action (e.g. a command)
+/// that is returned when the button is pressed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dialog<T> {
+	/// Title of the dialog.
+	pub title: String,
+	/// Body text of the dialog.
+	pub body: Text<'static>,
+	/// Labels and actions of the buttons.
+	pub buttons: Vec<(String, T)>,
+	/// Index of the selected button.
+	pub selected: usize,
+}
+
+impl<T> Dialog<T> {
+	/// Constructs a new instance of `Dialog`.
+	pub fn new<S: Into<String>, B: Into<Text<'static>>>(
+		title: S,
+		body: B,
+		buttons: Vec<(String, T)>,
+	) -> Self {
+		Self {
+			title: title.into(),
+			body: body.into(),
+			buttons,
+			selected: 0,
+		}
+	}
+
+	/// Selects the next button.
+	pub fn next(&mut self) {
+		if !self.buttons.is_empty() {
+			self.selected = (self.selected + 1) % self.buttons.len();
+		}
+	}
+
+	/// Selects the previous button.
+	pub fn previous(&mut self) {
+		if !self.buttons.is_empty() {
+			self.selected = self
+				.selected
+				.checked_sub(1)
+				.unwrap_or(self.buttons.len() - 1);
+		}
+	}
+
+	/// Returns the action of the selected button.
+	pub fn selected(&self) -> Option<&T> {
+		self.buttons.get(self.selected).map(|(_, action)| action)
+	}
+
+	/// Returns the action of the button that has a label
+	/// starting with the given character (case-insensitive).
+	pub fn get_shortcut(&self, c: char) -> Option<&T> {
+		self.buttons
+			.iter()
+			.find(|(label, _)| {
+				label.chars().next().map(|v| v.to_ascii_lowercase())
+					== Some(c.to_ascii_lowercase())
+			})
+			.map(|(_, action)| action)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_dialog() {
+		let mut dialog = Dialog::new(
+			"Confirm",
+			String::from("Do you want to continue?"),
+			vec![(String::from("yes"), 1), (String::from("no"), 2)],
+		);
+		assert_eq!("Confirm", dialog.title);
+		assert_eq!(1, dialog.body.height());
+		assert_eq!(Some(&1), dialog.selected());
+		dialog.next();
+		assert_eq!(Some(&2), dialog.selected());
+		dialog.next();
+		assert_eq!(Some(&1), dialog.selected());
+		dialog.previous();
+		assert_eq!(Some(&2), dialog.selected());
+		assert_eq!(Some(&1), dialog.get_shortcut('Y'));
+		assert_eq!(Some(&2), dialog.get_shortcut('n'));
+		assert_eq!(None, dialog.get_shortcut('x'));
+		let mut dialog = Dialog::<u8>::new("Empty", String::new(), Vec::new());
+		dialog.next();
+		dialog.previous();
+		assert_eq!(None, dialog.selected());
+	}
+}
@@ -0,0 +1,231 @@
+use crate::widget::list::StatefulList;
+use anyhow::Result;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Entry of the file picker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileEntry {
+	/// Path of the entry.
+	pub path: PathBuf,
+	/// Name to show.
+	pub name: String,
+	/// Is the entry a directory?
+	pub is_dir: bool,
+}
+
+impl FileEntry {
+	/// Constructs a new instance of `FileEntry`.
+	fn new<S: Into<String>>(path: PathBuf, name: S, is_dir: bool) -> Self {
+		Self {
+			path,
+			name: name.into(),
+			is_dir,
+		}
+	}
+
+	/// Checks if the entry is hidden.
+	fn is_hidden(&self) -> bool {
+		self.name.starts_with('.')
+	}
+
+	/// Checks if the entry has one of the given extensions.
+	fn has_extension(&self, extensions: &[String]) -> bool {
+		match self.path.extension().and_then(|v| v.to_str()) {
+			Some(extension) => {
+				extensions.iter().any(|v| v.eq_ignore_ascii_case(extension))
+			}
+			None => false,
+		}
+	}
+}
+
+/// File picker with directory navigation.
+///
+/// Files can be filtered by their extensions and the
+/// hidden files/directories are shown only if enabled.
+/// If only directories are selectable, the current
+/// directory is listed as the first entry (`.`).
+#[derive(Debug)]
+pub struct FilePicker {
+	/// Current directory.
+	pub dir: PathBuf,
+	/// Entries of the current directory.
+	pub entries: StatefulList<FileEntry>,
+	/// Are the hidden entries showing?
+	pub show_hidden: bool,
+	/// Extensions to filter the files.
+	pub extensions: Vec<String>,
+	/// Is the extension filter enabled?
+	pub filter: bool,
+	/// Are only the directories selectable?
+	pub dirs_only: bool,
+}
+
+impl FilePicker {
+	/// Constructs a new instance of `FilePicker`.
+	pub fn new(
+		dir: &Path,
+		extensions: Vec<String>,
+		dirs_only: bool,
+	) -> Result<Self> {
+		let mut picker = Self {
+			dir: dir.canonicalize()?,
+			entries: StatefulList::with_items(Vec::new()),
+			show_hidden: false,
+			filter: !extensions.is_empty(),
+			extensions,
+			dirs_only,
+		};
+		picker.refresh()?;
+		Ok(picker)
+	}
+
+	/// Reads the entries of the current directory.
+	pub fn refresh(&mut self) -> Result<()> {
+		let mut entries = Vec::new();
+		for entry in fs::read_dir(&self.dir)? {
+			let entry = entry?;
+			let path = entry.path();
+			let entry = FileEntry::new(
+				path.to_path_buf(),
+				entry.file_name().to_string_lossy(),
+				path.is_dir(),
+			);
+			if (!entry.is_hidden() || self.show_hidden)
+				&& (entry.is_dir
+					|| (!self.dirs_only
+						&& (!self.filter
+							|| entry.has_extension(&self.extensions))))
+			{
+				entries.push(entry);
+			}
+		}
+		entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+			(true, false) => Ordering::Less,
+			(false, true) => Ordering::Greater,
+			_ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+		});
+		if let Some(parent) = self.dir.parent() {
+			entries.insert(0, FileEntry::new(parent.to_path_buf(), "..", true));
+		}
+		if self.dirs_only {
+			entries
+				.insert(0, FileEntry::new(self.dir.to_path_buf(), ".", true));
+		}
+		self.entries = StatefulList::with_items(entries);
+		self.entries.state.select(Some(0));
+		Ok(())
+	}
+
+	/// Changes the current directory.
+	fn change_dir(&mut self, dir: PathBuf) -> Result<()> {
+		let previous_dir = self.dir.to_path_buf();
+		self.dir = dir;
+		if let Err(e) = self.refresh() {
+			self.dir = previous_dir;
+			return Err(e);
+		}
+		Ok(())
+	}
+
+	/// Opens the selected entry.
+	///
+	/// Returns the path if a file (or the current directory,
+	/// if only directories are selectable) is selected.
+	pub fn open(&mut self) -> Result<Option<PathBuf>> {
+		match self.entries.selected().cloned() {
+			Some(entry) if self.dirs_only && entry.name == "." => {
+				Ok(Some(entry.path))
+			}
+			Some(entry) if entry.is_dir => {
+				self.change_dir(entry.path)?;
+				Ok(None)
+			}
+			Some(entry) => Ok(Some(entry.path)),
+			None => Ok(None),
+		}
+	}
+
+	/// Goes to the parent directory.
+	pub fn parent(&mut self) -> Result<()> {
+		match self.dir.parent() {
+			Some(parent) => self.change_dir(parent.to_path_buf()),
+			None => Ok(()),
+		}
+	}
+
+	/// Toggles the visibility of the hidden entries.
+	pub fn toggle_hidden(&mut self) -> Result<()> {
+		self.show_hidden = !self.show_hidden;
+		self.refresh()
+	}
+
+	/// Toggles the extension filter.
+	pub fn toggle_filter(&mut self) -> Result<()> {
+		self.filter = !self.filter && !self.extensions.is_empty();
+		self.refresh()
+	}
+
+	/// Returns the title to show.
+	pub fn get_title(&self) -> String {
+		format!(
+			"{}{}{}",
+			self.dir.to_string_lossy(),
+			if self.filter {
+				format!(" [*.{}]", self.extensions.join(", *."))
+			} else {
+				String::new()
+			},
+			if self.show_hidden { " [hidden]" } else { "" }
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn test_widget_filepicker() -> Result<()> {
+		let dir = env::temp_dir().join("gpg-tui-filepicker");
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		fs::create_dir_all(dir.join("keys"))?;
+		fs::create_dir_all(dir.join(".hidden"))?;
+		fs::write(dir.join("key.asc"), "")?;
+		fs::write(dir.join("notes.txt"), "")?;
+		let mut picker =
+			FilePicker::new(&dir, vec![String::from("asc")], false)?;
+		let names = |picker: &FilePicker| {
+			picker
+				.entries
+				.items
+				.iter()
+				.map(|entry| entry.name.to_string())
+				.collect::<Vec<String>>()
+		};
+		assert_eq!(vec!["..", "keys", "key.asc"], names(&picker));
+		assert!(picker.get_title().ends_with("[*.asc]"));
+		picker.toggle_filter()?;
+		picker.toggle_hidden()?;
+		assert_eq!(
+			vec!["..", ".hidden", "keys", "key.asc", "notes.txt"],
+			names(&picker)
+		);
+		picker.entries.state.select(Some(2));
+		assert_eq!(None, picker.open()?);
+		assert_eq!(dir.join("keys").canonicalize()?, picker.dir);
+		picker.parent()?;
+		picker.entries.state.select(Some(3));
+		assert_eq!(Some(dir.canonicalize()?.join("key.asc")), picker.open()?);
+		let mut picker = FilePicker::new(&dir, Vec::new(), true)?;
+		assert_eq!(vec![".", "..", "keys"], names(&picker));
+		assert_eq!(Some(dir.canonicalize()?), picker.open()?);
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,240 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use unicode_width::UnicodeWidthStr;
+
+/// Character that is shown instead of the masked characters.
+pub const MASK_CHAR: char = '*';
+
+/// Text input with cursor support.
+///
+/// The value can contain multiple lines and it can be
+/// masked for hiding the sensitive input (e.g. passphrases).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Input {
+	/// Value of the input.
+	pub value: String,
+	/// Position of the cursor (in characters).
+	pub cursor: usize,
+	/// Is the value masked?
+	pub masked: bool,
+}
+
+impl Display for Input {
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+		write!(f, "{}", self.get_display(&self.value))
+	}
+}
+
+impl Input {
+	/// Constructs a new instance of `Input`.
+	pub fn new<S: Into<String>>(value: S) -> Self {
+		let mut input = Self::default();
+		input.set_value(value);
+		input
+	}
+
+	/// Constructs a new masked instance of `Input`.
+	pub fn masked() -> Self {
+		Self {
+			masked: true,
+			..Self::default()
+		}
+	}
+
+	/// Sets the value and moves the cursor to the end.
+	pub fn set_value<S: Into<String>>(&mut self, value: S) {
+		self.value = value.into();
+		self.cursor = self.value.chars().count();
+	}
+
+	/// Clears the value.
+	pub fn clear(&mut self) {
+		self.value.clear();
+		self.cursor = 0;
+	}
+
+	/// Checks if the value is empty.
+	pub fn is_empty(&self) -> bool {
+		self.value.is_empty()
+	}
+
+	/// Returns the byte index of the given character position.
+	fn get_byte_index(&self, position: usize) -> usize {
+		self.value
+			.char_indices()
+			.nth(position)
+			.map(|(i, _)| i)
+			.unwrap_or_else(|| self.value.len())
+	}
+
+	/// Returns the start and end positions (in characters)
+	/// of the line that contains the cursor.
+	fn get_line_bounds(&self) -> (usize, usize) {
+		let chars = self.value.chars().collect::<Vec<char>>();
+		let start = chars[..self.cursor]
+			.iter()
+			.rposition(|c| *c == '\n')
+			.map(|i| i + 1)
+			.unwrap_or_default();
+		let end = chars[self.cursor..]
+			.iter()
+			.position(|c| *c == '\n')
+			.map(|i| self.cursor + i)
+			.unwrap_or_else(|| chars.len());
+		(start, end)
+	}
+
+	/// Inserts a character at the cursor position.
+	pub fn insert(&mut self, c: char) {
+		let index = self.get_byte_index(self.cursor);
+		self.value.insert(index, c);
+		self.cursor += 1;
+	}
+
+	/// Inserts a string at the cursor position.
+	pub fn insert_str(&mut self, s: &str) {
+		let index = self.get_byte_index(self.cursor);
+		self.value.insert_str(index, s);
+		self.cursor += s.chars().count();
+	}
+
+	/// Deletes the character before the cursor.
+	pub fn delete_previous(&mut self) -> Option<char> {
+		if self.cursor == 0 {
+			return None;
+		}
+		self.cursor -= 1;
+		Some(self.value.remove(self.get_byte_index(self.cursor)))
+	}
+
+	/// Deletes the character at the cursor position.
+	pub fn delete_next(&mut self) -> Option<char> {
+		if self.cursor < self.value.chars().count() {
+			Some(self.value.remove(self.get_byte_index(self.cursor)))
+		} else {
+			None
+		}
+	}
+
+	/// Moves the cursor to the left.
+	pub fn move_left(&mut self) {
+		self.cursor = self.cursor.saturating_sub(1);
+	}
+
+	/// Moves the cursor to the right.
+	pub fn move_right(&mut self) {
+		if self.cursor < self.value.chars().count() {
+			self.cursor += 1;
+		}
+	}
+
+	/// Moves the cursor to the start of the current line.
+	pub fn move_home(&mut self) {
+		self.cursor = self.get_line_bounds().0;
+	}
+
+	/// Moves the cursor to the end of the current line.
+	pub fn move_end(&mut self) {
+		self.cursor = self.get_line_bounds().1;
+	}
+
+	/// Moves the cursor to the previous line.
+	///
+	/// Returns `false` if the cursor is already on the first line.
+	pub fn move_up(&mut self) -> bool {
+		let (start, _) = self.get_line_bounds();
+		if start == 0 {
+			return false;
+		}
+		let column = self.cursor - start;
+		self.cursor = start - 1;
+		let (start, end) = self.get_line_bounds();
+		self.cursor = end.min(start + column);
+		true
+	}
+
+	/// Moves the cursor to the next line.
+	///
+	/// Returns `false` if the cursor is already on the last line.
+	pub fn move_down(&mut self) -> bool {
+		let (start, end) = self.get_line_bounds();
+		if end == self.value.chars().count() {
+			return false;
+		}
+		let column = self.cursor - start;
+		self.cursor = end + 1;
+		let (start, end) = self.get_line_bounds();
+		self.cursor = end.min(start + column);
+		true
+	}
+
+	/// Returns the text to display for the given value.
+	///
+	/// Masked characters are replaced with [`MASK_CHAR`]
+	/// while the line breaks are preserved.
+	fn get_display(&self, value: &str) -> String {
+		if self.masked {
+			value
+				.chars()
+				.map(|c| if c == '\n' { c } else { MASK_CHAR })
+				.collect()
+		} else {
+			value.to_string()
+		}
+	}
+
+	/// Returns the lines to display.
+	pub fn get_lines(&self) -> Vec<String> {
+		self.to_string().split('\n').map(String::from).collect()
+	}
+
+	/// Returns the position of the cursor on the display
+	/// as row and column (in terminal cells).
+	pub fn get_cursor_position(&self) -> (usize, usize) {
+		let before =
+			self.get_display(&self.value[..self.get_byte_index(self.cursor)]);
+		let mut lines = before.split('\n');
+		let current = lines.next_back().unwrap_or_default();
+		(lines.count(), current.width())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_input() {
+		let mut input = Input::new("tst");
+		assert_eq!(3, input.cursor);
+		input.move_left();
+		input.move_left();
+		input.insert('e');
+		assert_eq!("test", input.value);
+		assert_eq!((0, 2), input.get_cursor_position());
+		input.move_end();
+		input.insert_str("\nxyz");
+		assert_eq!(vec!["test", "xyz"], input.get_lines());
+		assert_eq!((1, 3), input.get_cursor_position());
+		input.move_home();
+		assert_eq!(Some('\n'), input.delete_previous());
+		assert_eq!("testxyz", input.value);
+		assert_eq!(Some('x'), input.delete_next());
+		input.insert('\n');
+		input.move_right();
+		assert!(input.move_up());
+		assert_eq!(1, input.cursor);
+		assert!(!input.move_up());
+		assert!(input.move_down());
+		assert_eq!(6, input.cursor);
+		assert!(!input.move_down());
+		input.move_end();
+		assert_eq!(None, input.delete_next());
+		input.clear();
+		assert!(input.is_empty());
+		assert_eq!(None, input.delete_previous());
+		let mut input = Input::masked();
+		input.insert_str("pass\nwörd");
+		assert_eq!("****\n****", input.to_string());
+		assert_eq!((1, 4), input.get_cursor_position());
+	}
+}
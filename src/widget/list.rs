@@ -1,3 +1,4 @@
+use std::cmp;
 use tui::widgets::ListState;
 
 /// List widget with TUI controlled states.
@@ -7,12 +8,18 @@ pub struct StatefulList<T> {
 	pub items: Vec<T>,
 	/// State that can be modified by TUI.
 	pub state: ListState,
+	/// Number of items that fit in the rendered area.
+	pub page_size: usize,
 }
 
 impl<T> StatefulList<T> {
 	/// Constructs a new instance of `StatefulList`.
 	pub fn new(items: Vec<T>, state: ListState) -> StatefulList<T> {
-		Self { items, state }
+		Self {
+			items,
+			state,
+			page_size: 1,
+		}
 	}
 
 	/// Construct a new `StatefulList` with given items.
@@ -54,6 +61,45 @@ impl<T> StatefulList<T> {
 		};
 		self.state.select(Some(i));
 	}
+
+	/// Selects the item that is `amount` rows below the selected item.
+	///
+	/// A single step wraps around like [`next`] while larger
+	/// steps stop at the last item.
+	///
+	/// [`next`]: StatefulList::next
+	pub fn next_by(&mut self, amount: usize) {
+		if amount <= 1 {
+			self.next();
+		} else {
+			let i = self
+				.state
+				.selected()
+				.unwrap_or_default()
+				.saturating_add(amount);
+			self.state
+				.select(Some(cmp::min(i, self.items.len().saturating_sub(1))));
+		}
+	}
+
+	/// Selects the item that is `amount` rows above the selected item.
+	///
+	/// A single step wraps around like [`previous`] while larger
+	/// steps stop at the first item.
+	///
+	/// [`previous`]: StatefulList::previous
+	pub fn previous_by(&mut self, amount: usize) {
+		if amount <= 1 {
+			self.previous();
+		} else {
+			self.state.select(Some(
+				self.state
+					.selected()
+					.unwrap_or_default()
+					.saturating_sub(amount),
+			));
+		}
+	}
 }
 
 #[cfg(test)]
@@ -70,5 +116,9 @@ mod tests {
 		assert_eq!(Some(2), list.state.selected());
 		list.previous();
 		assert_eq!(Some(1), list.state.selected());
+		list.next_by(3);
+		assert_eq!(Some(2), list.state.selected());
+		list.previous_by(3);
+		assert_eq!(Some(0), list.state.selected());
 	}
 }
@@ -6,6 +6,30 @@ pub mod table;
 /// List widget with state support.
 pub mod list;
 
+/// Stack of timed notifications.
+pub mod toast;
+
+/// Modal dialog with buttons.
+pub mod dialog;
+
+/// Text input with cursor and masking support.
+pub mod input;
+
+/// File picker with directory navigation.
+pub mod filepicker;
+
+/// Searchable list of checkboxes.
+pub mod checklist;
+
+/// Calendar for selecting expiration dates.
+pub mod datepicker;
+
+/// Vertical scrollbar.
+pub mod scrollbar;
+
+/// Collapsible tree widget with state support.
+pub mod tree;
+
 /// Row item with limited width/height and scrolling properties.
 pub mod row;
 
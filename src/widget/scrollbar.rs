@@ -0,0 +1,104 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::widgets::Widget;
+
+/// Symbol of the scrollbar thumb.
+const THUMB_SYMBOL: &str = "█";
+
+/// Vertical scrollbar.
+///
+/// The thumb shows the position of the selected item
+/// and its size is proportional to the visible items.
+/// Nothing is rendered if all of the items are visible.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Scrollbar {
+	/// Position of the selected item.
+	pub position: usize,
+	/// Total number of items.
+	pub length: usize,
+	/// Number of the visible items.
+	pub viewport: usize,
+	/// Style of the thumb.
+	pub style: Style,
+}
+
+impl Scrollbar {
+	/// Constructs a new instance of `Scrollbar`.
+	pub fn new(position: usize, length: usize, viewport: usize) -> Self {
+		Self {
+			position,
+			length,
+			viewport,
+			style: Style::default(),
+		}
+	}
+
+	/// Sets the style of the thumb.
+	pub fn style(mut self, style: Style) -> Self {
+		self.style = style;
+		self
+	}
+
+	/// Returns the offset and the size of the thumb
+	/// for the given height of the track.
+	pub fn get_thumb(&self, height: u16) -> Option<(u16, u16)> {
+		if height == 0 || self.length <= self.viewport {
+			return None;
+		}
+		let height = usize::from(height);
+		let size = (height * self.viewport / self.length).max(1).min(height);
+		let offset = (height - size) * self.position.min(self.length - 1)
+			/ (self.length - 1);
+		Some((offset as u16, size as u16))
+	}
+
+	/// Returns the area of the track on the right border of the given area.
+	pub fn get_track(area: Rect) -> Rect {
+		Rect::new(
+			area.x + area.width.saturating_sub(1),
+			area.y + 1,
+			area.width.min(1),
+			area.height.saturating_sub(2),
+		)
+	}
+}
+
+impl Widget for Scrollbar {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		if let Some((offset, size)) = self.get_thumb(area.height) {
+			for y in area.y + offset..area.y + offset + size {
+				buf.set_string(area.x, y, THUMB_SYMBOL, self.style);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_scrollbar() {
+		assert_eq!(None, Scrollbar::new(0, 5, 10).get_thumb(10));
+		assert_eq!(None, Scrollbar::new(0, 50, 10).get_thumb(0));
+		assert_eq!(Some((0, 2)), Scrollbar::new(0, 50, 10).get_thumb(10));
+		assert_eq!(Some((4, 2)), Scrollbar::new(25, 50, 10).get_thumb(10));
+		assert_eq!(Some((8, 2)), Scrollbar::new(49, 50, 10).get_thumb(10));
+		assert_eq!(Some((9, 1)), Scrollbar::new(999, 1000, 1).get_thumb(10));
+		assert_eq!(
+			Rect::new(9, 1, 1, 8),
+			Scrollbar::get_track(Rect::new(0, 0, 10, 10))
+		);
+		let mut buffer = Buffer::empty(Rect::new(0, 0, 1, 4));
+		Scrollbar::new(7, 8, 4).render(buffer.area, &mut buffer);
+		assert_eq!(
+			vec![" ", " ", THUMB_SYMBOL, THUMB_SYMBOL],
+			buffer
+				.content
+				.iter()
+				.map(|cell| cell.symbol.as_str())
+				.collect::<Vec<&str>>()
+		);
+	}
+}
@@ -1,4 +1,5 @@
 use crate::widget::row::{ScrollAmount, ScrollDirection};
+use std::cmp;
 use tui::widgets::TableState as TuiState;
 
 /// Table size mode.
@@ -43,6 +44,10 @@ pub struct TableState {
 	pub size: TableSize,
 	/// Threshold value (width) for minimizing.
 	pub minimize_threshold: u16,
+	/// Number of items that fit in the rendered area.
+	pub page_size: usize,
+	/// Start of the selection (visual mode).
+	pub anchor: Option<usize>,
 }
 
 impl Default for TableState {
@@ -52,6 +57,38 @@ impl Default for TableState {
 			scroll: ScrollAmount::default(),
 			size: TableSize::Normal,
 			minimize_threshold: 90,
+			page_size: 1,
+			anchor: None,
+		}
+	}
+}
+
+/// Patches the given items with their updated versions.
+///
+/// Changed items are replaced with the updated item that has the
+/// same ID or removed if there is no such item. Updated items that
+/// do not exist yet are appended.
+pub fn patch_items<T: Clone, F: Fn(&T) -> String>(
+	items: &mut Vec<T>,
+	changed: &[String],
+	updated: &[T],
+	get_id: F,
+) {
+	let find_updated =
+		|id: &str| updated.iter().find(|item| get_id(item) == id);
+	items.retain(|item| {
+		let id = get_id(item);
+		!changed.contains(&id) || find_updated(&id).is_some()
+	});
+	for item in items.iter_mut() {
+		if let Some(updated_item) = find_updated(&get_id(item)) {
+			*item = updated_item.clone();
+		}
+	}
+	for updated_item in updated {
+		let id = get_id(updated_item);
+		if !items.iter().any(|item| get_id(item) == id) {
+			items.push(updated_item.clone());
 		}
 	}
 }
@@ -65,6 +102,8 @@ pub struct StatefulTable<T: Clone> {
 	pub items: Vec<T>,
 	/// Table state.
 	pub state: TableState,
+	/// Whether if the items are changed and need to be filtered again.
+	pub dirty: bool,
 }
 
 impl<T: Clone> StatefulTable<T> {
@@ -75,6 +114,7 @@ impl<T: Clone> StatefulTable<T> {
 			default_items: items.clone(),
 			items,
 			state,
+			dirty: true,
 		}
 	}
 
@@ -88,6 +128,70 @@ impl<T: Clone> StatefulTable<T> {
 		self.items.get(self.state.tui.selected()?)
 	}
 
+	/// Patches the items in place (see [`patch_items`]).
+	///
+	/// The selected item is kept selected if it still exists,
+	/// otherwise the nearest item is selected.
+	pub fn patch_items<F: Fn(&T) -> String>(
+		&mut self,
+		changed: &[String],
+		updated: &[T],
+		get_id: F,
+	) {
+		let selected = self.selected().map(&get_id);
+		patch_items(&mut self.default_items, changed, updated, &get_id);
+		patch_items(&mut self.items, changed, updated, &get_id);
+		self.dirty = true;
+		let index = selected
+			.and_then(|id| {
+				self.items.iter().position(|item| get_id(item) == id)
+			})
+			.or_else(|| {
+				self.state
+					.tui
+					.selected()
+					.map(|i| cmp::min(i, self.items.len().saturating_sub(1)))
+			});
+		self.state.tui.select(index);
+	}
+
+	/// Places the selection anchor on the selected item
+	/// or removes it.
+	pub fn set_anchor(&mut self, enabled: bool) {
+		self.state.anchor = if enabled {
+			self.state.tui.selected()
+		} else {
+			None
+		};
+	}
+
+	/// Returns the indices of the selected items.
+	///
+	/// If the [`anchor`] is set, all the items between
+	/// the anchor and the selected item are included.
+	///
+	/// [`anchor`]: TableState::anchor
+	pub fn selected_indices(&self) -> Vec<usize> {
+		match (self.state.anchor, self.state.tui.selected()) {
+			(Some(anchor), Some(selected)) => (cmp::min(anchor, selected)
+				..=cmp::max(anchor, selected))
+				.filter(|i| *i < self.items.len())
+				.collect(),
+			(None, Some(selected)) if selected < self.items.len() => {
+				vec![selected]
+			}
+			_ => Vec::new(),
+		}
+	}
+
+	/// Returns the selected items.
+	pub fn selected_items(&self) -> Vec<&T> {
+		self.selected_indices()
+			.into_iter()
+			.filter_map(|i| self.items.get(i))
+			.collect()
+	}
+
 	/// Selects the next item.
 	pub fn next(&mut self) {
 		let i = match self.state.tui.selected() {
@@ -120,6 +224,50 @@ impl<T: Clone> StatefulTable<T> {
 		self.reset_scroll();
 	}
 
+	/// Selects the item that is `amount` rows below the selected item.
+	///
+	/// A single step wraps around like [`next`] while larger
+	/// steps stop at the last item.
+	///
+	/// [`next`]: StatefulTable::next
+	pub fn next_by(&mut self, amount: usize) {
+		if amount <= 1 {
+			self.next();
+		} else {
+			let i = self
+				.state
+				.tui
+				.selected()
+				.unwrap_or_default()
+				.saturating_add(amount);
+			self.state
+				.tui
+				.select(Some(cmp::min(i, self.items.len().saturating_sub(1))));
+			self.reset_scroll();
+		}
+	}
+
+	/// Selects the item that is `amount` rows above the selected item.
+	///
+	/// A single step wraps around like [`previous`] while larger
+	/// steps stop at the first item.
+	///
+	/// [`previous`]: StatefulTable::previous
+	pub fn previous_by(&mut self, amount: usize) {
+		if amount <= 1 {
+			self.previous();
+		} else {
+			self.state.tui.select(Some(
+				self.state
+					.tui
+					.selected()
+					.unwrap_or_default()
+					.saturating_sub(amount),
+			));
+			self.reset_scroll();
+		}
+	}
+
 	/// Sets the scrolling state of the table row
 	/// depending on the given direction and offset.
 	pub fn scroll_row(&mut self, direction: ScrollDirection) {
@@ -160,10 +308,17 @@ impl<T: Clone> StatefulTable<T> {
 		}
 	}
 
+	/// Restores the default items for filtering them again.
+	pub fn reset_items(&mut self) {
+		self.items = self.default_items.clone();
+		self.dirty = true;
+	}
+
 	/// Resets the items state.
 	pub fn reset_state(&mut self) {
-		self.items = self.default_items.clone();
+		self.reset_items();
 		self.state.tui.select(Some(0));
+		self.state.anchor = None;
 	}
 
 	/// Resets the scroll state.
@@ -186,6 +341,13 @@ mod tests {
 		assert_eq!(Some(2), table.state.tui.selected());
 		table.previous();
 		assert_eq!(Some(1), table.state.tui.selected());
+		table.next_by(5);
+		assert_eq!(Some(2), table.state.tui.selected());
+		table.previous_by(5);
+		assert_eq!(Some(0), table.state.tui.selected());
+		table.previous_by(1);
+		assert_eq!(Some(2), table.state.tui.selected());
+		table.state.tui.select(Some(1));
 		table.reset_scroll();
 		assert_eq!(
 			"ScrollAmount { vertical: 0, horizontal: 0 }",
@@ -203,12 +365,36 @@ mod tests {
 			"ScrollAmount { vertical: 2, horizontal: 1 }",
 			&format!("{:?}", table.state.scroll)
 		);
+		assert_eq!(vec![&"data2"], table.selected_items());
+		table.next();
+		table.set_anchor(true);
+		assert_eq!(Some(2), table.state.anchor);
+		table.previous_by(2);
+		assert_eq!(vec![0, 1, 2], table.selected_indices());
+		assert_eq!(vec![&"data1", &"data2", &"data3"], table.selected_items());
+		table.dirty = false;
 		table.reset_state();
+		assert!(table.dirty);
+		assert_eq!(None, table.state.anchor);
 		assert_eq!(Some(0), table.state.tui.selected());
 		assert_eq!(table.default_items, table.items);
 		assert_eq!(TableSize::Normal, table.state.size);
 		table.state.size = TableSize::Minimized;
 		table.state.size.set_minimized(false);
 		assert_eq!(TableSize::Compact, table.state.size.next());
+		let get_id = |v: &&str| v[..1].to_string();
+		let mut table = StatefulTable::with_items(vec!["a1", "b1", "c1"]);
+		table.state.tui.select(Some(2));
+		table.patch_items(
+			&[String::from("b"), String::from("c")],
+			&["b2", "d2"],
+			get_id,
+		);
+		assert_eq!(vec!["a1", "b2", "d2"], table.items);
+		assert_eq!(table.default_items, table.items);
+		assert_eq!(Some(&"d2"), table.selected());
+		table.state.tui.select(Some(1));
+		table.patch_items(&[String::from("a")], &[], get_id);
+		assert_eq!(Some(&"b2"), table.selected());
 	}
 }
@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Default number of notifications to keep in the history.
+const DEFAULT_CAPACITY: usize = 50;
+
+/// Timed notification.
+#[derive(Clone, Debug)]
+pub struct Toast<T> {
+	/// Content of the notification.
+	pub content: T,
+	/// Time that the notification is created.
+	pub clock: Instant,
+}
+
+/// Stack of timed notifications (toasts).
+///
+/// Recent notifications are kept as a history
+/// up to the specified capacity.
+#[derive(Clone, Debug)]
+pub struct ToastStack<T> {
+	/// Notifications (oldest first).
+	pub toasts: VecDeque<Toast<T>>,
+	/// Maximum number of notifications to keep.
+	pub capacity: usize,
+}
+
+impl<T> Default for ToastStack<T> {
+	fn default() -> Self {
+		Self::with_capacity(DEFAULT_CAPACITY)
+	}
+}
+
+impl<T> ToastStack<T> {
+	/// Constructs a new instance of `ToastStack`.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			toasts: VecDeque::with_capacity(capacity),
+			capacity,
+		}
+	}
+
+	/// Pushes a new notification to the stack.
+	///
+	/// The oldest notification is dropped if the capacity is exceeded.
+	pub fn push(&mut self, content: T) {
+		self.toasts.push_back(Toast {
+			content,
+			clock: Instant::now(),
+		});
+		while self.toasts.len() > self.capacity {
+			self.toasts.pop_front();
+		}
+	}
+
+	/// Returns the notifications that are younger than
+	/// the given duration in milliseconds (oldest first).
+	pub fn get_active(&self, duration: u128) -> Vec<&Toast<T>> {
+		self.toasts
+			.iter()
+			.filter(|toast| toast.clock.elapsed().as_millis() < duration)
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_toast() {
+		let mut toasts = ToastStack::with_capacity(2);
+		toasts.push("a");
+		toasts.push("b");
+		toasts.push("c");
+		assert_eq!(
+			vec!["b", "c"],
+			toasts
+				.toasts
+				.iter()
+				.map(|toast| toast.content)
+				.collect::<Vec<&str>>()
+		);
+		assert_eq!(2, toasts.get_active(1000).len());
+		assert!(toasts.get_active(0).is_empty());
+		assert_eq!(DEFAULT_CAPACITY, ToastStack::<()>::default().capacity);
+	}
+}
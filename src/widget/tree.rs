@@ -0,0 +1,226 @@
+use tui::widgets::ListState;
+
+/// Node of the tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeNode {
+	/// Label of the node.
+	pub label: String,
+	/// Child nodes.
+	pub children: Vec<TreeNode>,
+	/// Are the child nodes showing?
+	pub expanded: bool,
+}
+
+impl TreeNode {
+	/// Constructs a new instance of `TreeNode`.
+	pub fn new<S: Into<String>>(label: S, children: Vec<TreeNode>) -> Self {
+		Self {
+			label: label.into(),
+			children,
+			expanded: false,
+		}
+	}
+
+	/// Sets the expanded state of the node.
+	pub fn expanded(mut self, expanded: bool) -> Self {
+		self.expanded = expanded;
+		self
+	}
+
+	/// Sets the expanded state of the node and its descendants.
+	fn set_expanded_all(&mut self, expanded: bool) {
+		self.expanded = expanded;
+		for child in self.children.iter_mut() {
+			child.set_expanded_all(expanded);
+		}
+	}
+}
+
+/// Collapsible tree with TUI controlled states.
+///
+/// Only the nodes under the expanded nodes are visible
+/// and the selection is tracked by their visible index.
+#[derive(Debug, Default)]
+pub struct Tree {
+	/// Root nodes.
+	pub nodes: Vec<TreeNode>,
+	/// State that can be modified by TUI.
+	pub state: ListState,
+}
+
+impl Tree {
+	/// Constructs a new instance of `Tree`.
+	pub fn new(nodes: Vec<TreeNode>) -> Self {
+		let mut state = ListState::default();
+		if !nodes.is_empty() {
+			state.select(Some(0));
+		}
+		Self { nodes, state }
+	}
+
+	/// Returns the paths (indices from the root) of the visible nodes.
+	fn get_visible_paths(&self) -> Vec<Vec<usize>> {
+		fn visit(
+			nodes: &[TreeNode],
+			path: &[usize],
+			paths: &mut Vec<Vec<usize>>,
+		) {
+			for (i, node) in nodes.iter().enumerate() {
+				let mut node_path = path.to_vec();
+				node_path.push(i);
+				paths.push(node_path.clone());
+				if node.expanded {
+					visit(&node.children, &node_path, paths);
+				}
+			}
+		}
+		let mut paths = Vec::new();
+		visit(&self.nodes, &[], &mut paths);
+		paths
+	}
+
+	/// Returns the node at the given path.
+	fn get_node_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+		let (first, rest) = path.split_first()?;
+		let mut node = self.nodes.get_mut(*first)?;
+		for i in rest {
+			node = node.children.get_mut(*i)?;
+		}
+		Some(node)
+	}
+
+	/// Returns the lines of the visible nodes.
+	///
+	/// Nodes are indented by their depth and the nodes with
+	/// children are prefixed with their expanded state.
+	pub fn get_lines(&self) -> Vec<String> {
+		self.get_visible_paths()
+			.into_iter()
+			.map(|path| {
+				let mut nodes = &self.nodes;
+				let mut node = &nodes[path[0]];
+				for i in &path[1..] {
+					nodes = &node.children;
+					node = &nodes[*i];
+				}
+				format!(
+					"{}{}{}",
+					"  ".repeat(path.len() - 1),
+					if node.children.is_empty() {
+						"  "
+					} else if node.expanded {
+						"▾ "
+					} else {
+						"▸ "
+					},
+					node.label
+				)
+			})
+			.collect()
+	}
+
+	/// Selects the next visible node.
+	pub fn next(&mut self) {
+		let count = self.get_visible_paths().len();
+		if let Some(i) = self.state.selected() {
+			self.state
+				.select(Some(if i + 1 >= count { i } else { i + 1 }));
+		}
+	}
+
+	/// Selects the previous visible node.
+	pub fn previous(&mut self) {
+		if let Some(i) = self.state.selected() {
+			self.state.select(Some(i.saturating_sub(1)));
+		}
+	}
+
+	/// Sets the expanded state of the selected node.
+	///
+	/// Collapsing a node that has no visible children
+	/// selects its parent instead.
+	pub fn set_expanded(&mut self, expanded: bool) {
+		let paths = self.get_visible_paths();
+		let path = match self.state.selected().and_then(|i| paths.get(i)) {
+			Some(path) => path.to_vec(),
+			None => return,
+		};
+		if let Some(node) = self.get_node_mut(&path) {
+			if expanded || (node.expanded && !node.children.is_empty()) {
+				node.expanded = expanded;
+				return;
+			}
+		}
+		if path.len() > 1 {
+			let parent = &path[..path.len() - 1];
+			self.state
+				.select(paths.iter().position(|v| v.as_slice() == parent));
+		}
+	}
+
+	/// Toggles the expanded state of the selected node.
+	pub fn toggle(&mut self) {
+		let paths = self.get_visible_paths();
+		if let Some(path) = self.state.selected().and_then(|i| paths.get(i)) {
+			if let Some(node) = self.get_node_mut(path) {
+				node.expanded = !node.expanded;
+			}
+		}
+	}
+
+	/// Expands/collapses all of the nodes.
+	///
+	/// The selection is reset to the first node.
+	pub fn set_expanded_all(&mut self, expanded: bool) {
+		for node in self.nodes.iter_mut() {
+			node.set_expanded_all(expanded);
+		}
+		if !self.nodes.is_empty() {
+			self.state.select(Some(0));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_widget_tree() {
+		let mut tree = Tree::new(vec![TreeNode::new(
+			"key",
+			vec![
+				TreeNode::new("subkey", Vec::new()),
+				TreeNode::new("user", vec![TreeNode::new("sig", Vec::new())]),
+			],
+		)
+		.expanded(true)]);
+		assert_eq!(vec!["▾ key", "    subkey", "  ▸ user"], tree.get_lines());
+		tree.next();
+		tree.next();
+		tree.next();
+		assert_eq!(Some(2), tree.state.selected());
+		tree.set_expanded(true);
+		assert_eq!(
+			vec!["▾ key", "    subkey", "  ▾ user", "      sig"],
+			tree.get_lines()
+		);
+		tree.next();
+		tree.set_expanded(false);
+		assert_eq!(Some(2), tree.state.selected());
+		tree.set_expanded(false);
+		assert_eq!(3, tree.get_lines().len());
+		tree.previous();
+		tree.previous();
+		tree.toggle();
+		assert_eq!(vec!["▸ key"], tree.get_lines());
+		tree.set_expanded_all(true);
+		assert_eq!(4, tree.get_lines().len());
+		tree.set_expanded_all(false);
+		assert_eq!(1, tree.get_lines().len());
+		let mut tree = Tree::new(Vec::new());
+		tree.next();
+		tree.set_expanded(true);
+		assert_eq!(None, tree.state.selected());
+	}
+}